@@ -12,15 +12,17 @@ use crate::compute::types::{
     AcceptTaskInput, ApproveTaskInput, ComputeMutationResponse, CreateAndFundTaskInput,
     DisputeTaskInput, RegisterNodeInput, SubmitResultInput, VerifyNodeInput,
 };
+use crate::wallet::evm::{gas, nonce};
 use crate::wallet::evm::private_key::map_security_error;
 use crate::wallet::evm::transaction::{load_signing_secret, wallet_from_signing_secret};
 use crate::wallet::security::types::SignerOperation;
+use crate::wallet::transaction_types::EvmFeeSpeed;
 use crate::DB;
 use chrono::Utc;
 use ethers::abi::{decode as abi_decode, encode, ParamType, Token};
 use ethers::prelude::*;
 use ethers::providers::{Http, Middleware, Provider};
-use ethers::types::{Address, BlockId, BlockNumber, Bytes, TransactionReceipt, TransactionRequest, U256};
+use ethers::types::{Address, Bytes, TransactionReceipt, TransactionRequest, U256};
 use sha2::{Digest, Sha256};
 use std::str::FromStr;
 use uuid::Uuid;
@@ -181,7 +183,8 @@ async fn sign_persist_and_broadcast(
     let existing_step =
         with_db(|conn| db::load_mutation_step(conn, mutation_id, step_name))?;
 
-    let (tx_hash_str, raw_rlp_hex, skip_broadcast) = if let Some(existing) = existing_step {
+    // A fresh step holds its nonce reservation until it is broadcast.
+    let (tx_hash_str, raw_rlp_hex, skip_broadcast, _reservation) = if let Some(existing) = existing_step {
         // Resume path: if tx is already mined, skip broadcast entirely.
         // Rebroadcasting a mined tx produces "nonce too low" / "already known" from the
         // node — which would incorrectly fail the step.
@@ -225,37 +228,39 @@ async fn sign_persist_and_broadcast(
             }
         }
 
-        (tx_hash, raw_hex, false)
+        (tx_hash, raw_hex, false, None)
     } else {
         // Sign and persist
         let address = wallet.address();
-        let nonce = provider
-            .get_transaction_count(address, Some(BlockId::Number(BlockNumber::Pending)))
+        let reservation = nonce::reserve_nonce(provider, address, chain_id)
             .await
             .map_err(|e| format!("get_nonce: {}", e))?;
+        let nonce = reservation.nonce;
 
-        let gas_price = provider
-            .get_gas_price()
+        // Type-2 on EIP-1559 chains, legacy elsewhere
+        let fee_params = gas::fee_market(provider)
             .await
-            .map_err(|e| format!("get_gas_price: {}", e))?;
-
-        let tx = TransactionRequest::new()
-            .from(address)
-            .to(to)
-            .nonce(nonce)
-            .gas_price(gas_price)
-            .value(value)
-            .data(calldata.clone())
-            .chain_id(chain_id);
+            .map_err(|e| format!("fee_market: {}", e))?
+            .params(EvmFeeSpeed::Normal);
+
+        let mut tx = fee_params.apply(
+            TransactionRequest::new()
+                .from(address)
+                .to(to)
+                .nonce(nonce)
+                .value(value)
+                .data(calldata.clone())
+                .chain_id(chain_id),
+        );
 
         let gas = provider
-            .estimate_gas(&tx.clone().into(), None)
+            .estimate_gas(&tx, None)
             .await
             .unwrap_or(U256::from(300_000u64));
-        let tx = tx.gas(gas);
+        tx.set_gas(gas);
 
         let signed = wallet
-            .sign_transaction(&tx.clone().into())
+            .sign_transaction(&tx)
             .await
             .map_err(|e| format!("sign_tx: {}", e))?;
 
@@ -299,7 +304,7 @@ async fn sign_persist_and_broadcast(
 
         // Persist BEFORE broadcast — crash safe
         with_db(|conn| db::upsert_mutation_step(conn, &step_row))?;
-        (tx_hash_str, raw_hex, false)
+        (tx_hash_str, raw_hex, false, Some(reservation))
     };
 
     if skip_broadcast {
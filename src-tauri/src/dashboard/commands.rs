@@ -1,4 +1,6 @@
 use crate::dashboard::valuation::build_portfolio_valuation_snapshot;
+use crate::wallet::bitcoin::amount::sats_to_btc;
+use crate::wallet::evm::config::is_testnet_chain_id;
 use crate::wallet::state::freshness::classify_age;
 use crate::wallet::state::types::{FreshnessMetadata, FreshnessStatus};
 use crate::wallet::sync::engine;
@@ -158,12 +160,10 @@ fn format_dashboard_stats_without_snapshot(
 ) -> Result<DashboardStats, String> {
     let valuation_status = valuation_snapshot.valuation_status();
     let total_balance_usd = valuation_snapshot.priced_total_usd;
-    let total_btc_balance: f64 = db
-        .get_bitcoin_wallets()
-        .map_err(|e| e.to_string())?
-        .into_iter()
-        .map(|wallet| wallet.balance)
-        .sum();
+    let total_btc_balance = sats_to_btc(
+        db.get_mainnet_bitcoin_balance_sats()
+            .map_err(|e| e.to_string())?,
+    );
     let btc_price_state = crate::wallet::evm::price_manager::get_cached_price_state("BTC");
     let btc_price_unavailable = total_btc_balance > 0.0 && btc_price_state.price_usd.is_none();
     let has_priced_subtotal = total_balance_usd > 0.0;
@@ -340,7 +340,7 @@ pub fn get_unified_recent_transactions() -> Result<Vec<UnifiedTransaction>, Stri
     let evm_txs = db.get_all_evm_transactions().map_err(|e| e.to_string())?;
     let evm_unified: Vec<UnifiedTransaction> = evm_txs
         .into_iter()
-        .filter(|tx| tx.chain_id != 11155111) // Filter out Sepolia
+        .filter(|tx| !is_testnet_chain_id(tx.chain_id)) // Filter out testnets
         .map(|tx| UnifiedTransaction {
             id: tx.id,
             r#type: "evm".to_string(),
@@ -383,8 +383,10 @@ pub fn get_unified_assets() -> Result<Vec<UnifiedAsset>, String> {
     let mut result = Vec::new();
 
     // 1. BTC Wallets
-    let btc_wallets = db.get_bitcoin_wallets().map_err(|e| e.to_string())?;
-    let total_btc_balance: f64 = btc_wallets.iter().map(|w| w.balance).sum();
+    let total_btc_balance = sats_to_btc(
+        db.get_mainnet_bitcoin_balance_sats()
+            .map_err(|e| e.to_string())?,
+    );
 
     if total_btc_balance > 0.0 {
         let btc_price_state = crate::wallet::evm::price_manager::get_cached_price_state("BTC");
@@ -420,7 +422,7 @@ pub fn get_unified_assets() -> Result<Vec<UnifiedAsset>, String> {
                 balance: balance_float,
                 usd_value: usd_value.unwrap_or(0.0),
                 chain,
-                is_testnet: chain_id == 11155111,
+                is_testnet: is_testnet_chain_id(chain_id),
             });
         }
     }
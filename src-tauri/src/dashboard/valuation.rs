@@ -1,11 +1,11 @@
 use crate::db::Database;
+use crate::wallet::bitcoin::amount::sats_to_btc;
+use crate::wallet::evm::config::is_testnet_chain_id;
 use crate::wallet::evm::price_manager;
 use crate::wallet::state::types::PriceStatus;
 use crate::wallet::types::ValuationStatus;
 use std::collections::HashMap;
 
-const SEPOLIA_CHAIN_ID: u64 = 11155111;
-
 #[derive(Debug, Clone)]
 pub struct AllocationBucket {
     pub name: String,
@@ -40,14 +40,16 @@ pub fn build_portfolio_valuation_snapshot(
     db: &Database,
 ) -> Result<PortfolioValuationSnapshot, String> {
     let btc_price_state = price_manager::get_cached_price_state("BTC");
-    let btc_wallets = db.get_bitcoin_wallets().map_err(|e| e.to_string())?;
     let evm_wallets = db.get_evm_wallets().map_err(|e| e.to_string())?;
 
     let mut priced_total_usd = 0.0;
     let mut unpriced_asset_count = 0;
     let mut priced_allocations = Vec::new();
 
-    let total_btc: f64 = btc_wallets.iter().map(|wallet| wallet.balance).sum();
+    let total_btc = sats_to_btc(
+        db.get_mainnet_bitcoin_balance_sats()
+            .map_err(|e| e.to_string())?,
+    );
     if total_btc > 0.0 {
         match (btc_price_state.status, btc_price_state.price_usd) {
             (PriceStatus::Unavailable, _) | (_, None) => {
@@ -89,7 +91,7 @@ pub fn build_portfolio_valuation_snapshot(
             valuation_status,
         ) in assets
         {
-            if chain_id == SEPOLIA_CHAIN_ID || balance_float <= 0.0 {
+            if is_testnet_chain_id(chain_id) || balance_float <= 0.0 {
                 continue;
             }
 
@@ -177,6 +179,7 @@ pub fn build_portfolio_valuation_snapshot(
 mod tests {
     use super::build_portfolio_valuation_snapshot;
     use crate::db::Database;
+    use crate::wallet::bitcoin::config::BitcoinNetwork;
     use crate::wallet::security::secret_envelope::{StoredSecret, SECRET_FORMAT_PLAINTEXT_V0};
 
     #[test]
@@ -234,4 +237,34 @@ mod tests {
         assert_eq!(snapshot.allocations.len(), 2);
         assert_eq!(snapshot.allocations[1].symbol, "UNPRICED");
     }
+
+    #[test]
+    fn test_network_bitcoin_is_left_out_of_valuation() {
+        let db = Database::new(":memory:").unwrap();
+        for (address, network, balance_sats) in [
+            ("tb1qtestnet", BitcoinNetwork::Testnet, 100_000_000),
+            ("tb1qsignet", BitcoinNetwork::Signet, 50_000_000),
+            ("bcrt1qregtest", BitcoinNetwork::Regtest, 25_000_000),
+        ] {
+            let wallet = db
+                .insert_bitcoin_watch_only_wallet(address.to_string(), address.to_string())
+                .unwrap();
+            db.set_bitcoin_wallet_network(&wallet.id, network).unwrap();
+            db.update_bitcoin_wallet_balance(&wallet.id, balance_sats)
+                .unwrap();
+        }
+
+        assert_eq!(db.get_mainnet_bitcoin_balance_sats().unwrap(), 0);
+        let snapshot = build_portfolio_valuation_snapshot(&db).unwrap();
+        assert_eq!(snapshot.unpriced_asset_count, 0);
+        assert!(snapshot.allocations.is_empty());
+
+        let mainnet = db
+            .insert_bitcoin_watch_only_wallet("Main".to_string(), "bc1qmainnet".to_string())
+            .unwrap();
+        db.update_bitcoin_wallet_balance(&mainnet.id, 20_000)
+            .unwrap();
+
+        assert_eq!(db.get_mainnet_bitcoin_balance_sats().unwrap(), 20_000);
+    }
 }
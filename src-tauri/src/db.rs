@@ -1,3 +1,7 @@
+use crate::wallet::bitcoin::amount::sats_to_btc;
+use crate::wallet::bitcoin::config::BitcoinNetwork;
+use crate::wallet::bitcoin::descriptor::{BitcoinKeychain, BitcoinScriptType, WalletDescriptors};
+use crate::wallet::bitcoin::multisig::{MultisigPolicy, MultisigScriptType};
 use crate::wallet::security::backend::SecretBackend;
 use crate::wallet::security::secret_envelope::{StoredSecret, SECRET_FORMAT_PLAINTEXT_V0};
 use crate::wallet::security::types::{PasswordAuthState, PasswordKdfParams};
@@ -6,7 +10,11 @@ use crate::wallet::state::types::FreshnessMetadata;
 use crate::wallet::transaction_types::{
     BitcoinTransaction, EvmTransaction, TransactionStatus, TransactionType,
 };
-use crate::wallet::types::WalletInfo;
+use crate::wallet::types::{
+    Bip329Record, BitcoinAddressInfo, BitcoinBackendConfig, BitcoinBackendKind, BitcoinLabel,
+    BitcoinLabelType, BitcoinUtxoMetadata, EvmCustomToken, EvmNetwork, SaveBitcoinBackendRequest,
+    WalletInfo,
+};
 use chrono::Utc;
 use rusqlite::{params, Connection, Result as SqliteResult};
 use std::sync::Mutex;
@@ -40,6 +48,15 @@ pub struct AssetBalanceData {
     pub valuation_status: String,
 }
 
+/// How a new Bitcoin wallet derives its addresses, saved with the wallet row.
+#[derive(Debug, Clone)]
+pub struct BitcoinWalletSetup {
+    pub script_type: BitcoinScriptType,
+    pub network: BitcoinNetwork,
+    pub descriptors: WalletDescriptors,
+    pub multisig: Option<MultisigPolicy>,
+}
+
 impl Database {
     pub fn new(db_path: &str) -> SqliteResult<Self> {
         let conn = Connection::open(db_path)?;
@@ -65,7 +82,7 @@ impl Database {
     }
 
     fn init_tables(&self) -> SqliteResult<()> {
-        let conn = self.conn.lock().unwrap();
+        let mut conn = self.conn.lock().unwrap();
 
         // Bitcoin wallets table - stores only address, not private key
         conn.execute(
@@ -75,6 +92,7 @@ impl Database {
                 wallet_type TEXT NOT NULL,
                 address TEXT NOT NULL UNIQUE,
                 balance REAL NOT NULL DEFAULT 0,
+                balance_sats INTEGER NOT NULL DEFAULT 0,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL
             )",
@@ -158,7 +176,7 @@ impl Database {
             "CREATE TABLE IF NOT EXISTS bitcoin_transactions (
                 id TEXT PRIMARY KEY,
                 wallet_id TEXT NOT NULL,
-                tx_hash TEXT NOT NULL UNIQUE,
+                tx_hash TEXT NOT NULL,
                 tx_type TEXT NOT NULL,
                 from_address TEXT NOT NULL,
                 to_address TEXT NOT NULL,
@@ -169,7 +187,16 @@ impl Database {
                 block_height INTEGER,
                 timestamp TEXT NOT NULL,
                 created_at TEXT NOT NULL,
-                FOREIGN KEY (wallet_id) REFERENCES bitcoin_wallets(id)
+                replaces_tx_hash TEXT,
+                replaced_by_tx_hash TEXT,
+                parent_tx_hash TEXT,
+                batch_id TEXT,
+                output_index INTEGER NOT NULL DEFAULT 0,
+                amount_sats INTEGER NOT NULL DEFAULT 0,
+                fee_sats INTEGER NOT NULL DEFAULT 0,
+                sender_resolved INTEGER NOT NULL DEFAULT 0,
+                FOREIGN KEY (wallet_id) REFERENCES bitcoin_wallets(id),
+                UNIQUE(wallet_id, tx_hash, output_index)
             )",
             [],
         )?;
@@ -228,8 +255,118 @@ impl Database {
             [],
         )?;
 
+        // Bitcoin HD wallet addresses - one row per scanned receive/change index
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS bitcoin_wallet_addresses (
+                wallet_id TEXT NOT NULL,
+                keychain TEXT NOT NULL,
+                derivation_index INTEGER NOT NULL,
+                address TEXT NOT NULL,
+                balance_sats INTEGER NOT NULL DEFAULT 0,
+                tx_count INTEGER NOT NULL DEFAULT 0,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY (wallet_id, keychain, derivation_index),
+                FOREIGN KEY (wallet_id) REFERENCES bitcoin_wallets(id)
+            )",
+            [],
+        )?;
+
+        // Bitcoin coin control - labels and freeze flags keyed by outpoint
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS bitcoin_utxo_metadata (
+                wallet_id TEXT NOT NULL,
+                outpoint TEXT NOT NULL,
+                label TEXT,
+                origin TEXT,
+                frozen INTEGER NOT NULL DEFAULT 0,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY (wallet_id, outpoint),
+                FOREIGN KEY (wallet_id) REFERENCES bitcoin_wallets(id)
+            )",
+            [],
+        )?;
+
+        // BIP329 labels for transactions, addresses, keys and inputs
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS bitcoin_labels (
+                wallet_id TEXT NOT NULL,
+                label_type TEXT NOT NULL,
+                ref TEXT NOT NULL,
+                label TEXT NOT NULL,
+                origin TEXT,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY (wallet_id, label_type, ref),
+                FOREIGN KEY (wallet_id) REFERENCES bitcoin_wallets(id)
+            )",
+            [],
+        )?;
+
+        // User-configured Bitcoin chain data sources, tried in priority order per network
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS bitcoin_backends (
+                id TEXT PRIMARY KEY,
+                network TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                url TEXT NOT NULL,
+                rpc_username TEXT,
+                rpc_password TEXT,
+                rpc_password_format TEXT,
+                priority INTEGER NOT NULL DEFAULT 0,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // User-defined EVM networks, tracked next to the built-in chains
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS evm_networks (
+                chain_id INTEGER PRIMARY KEY,
+                name TEXT NOT NULL UNIQUE,
+                display_name TEXT NOT NULL,
+                native_symbol TEXT NOT NULL,
+                native_decimals INTEGER NOT NULL,
+                rpc_url TEXT NOT NULL,
+                wss_url TEXT,
+                explorer_api_url TEXT,
+                is_testnet INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // ERC-20 tokens added by contract address, tracked next to the built-in assets
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS evm_custom_tokens (
+                id TEXT PRIMARY KEY,
+                chain_id INTEGER NOT NULL,
+                contract_address TEXT NOT NULL,
+                symbol TEXT NOT NULL,
+                name TEXT NOT NULL,
+                decimals INTEGER NOT NULL,
+                created_at TEXT NOT NULL,
+                UNIQUE(chain_id, contract_address)
+            )",
+            [],
+        )?;
+
         Self::migrate_phase2_sync_metadata(&conn)?;
         Self::migrate_secret_storage_metadata(&conn)?;
+        Self::migrate_bitcoin_descriptor_metadata(&conn)?;
+        Self::migrate_bitcoin_transaction_links(&conn)?;
+        Self::migrate_bitcoin_satoshi_amounts(&conn)?;
+        Self::migrate_bitcoin_transaction_batches(&mut conn)?;
+        Self::add_column_if_missing(
+            &conn,
+            "bitcoin_transactions",
+            "sender_resolved",
+            "INTEGER NOT NULL DEFAULT 0",
+        )?;
+        Self::add_column_if_missing(&conn, "bitcoin_utxo_metadata", "origin", "TEXT")?;
+        Self::migrate_bitcoin_multisig_metadata(&conn)?;
+        Self::migrate_evm_transaction_nonces(&conn)?;
 
         crate::compute::db::init_compute_tables(&conn)?;
 
@@ -311,6 +448,155 @@ impl Database {
         Ok(())
     }
 
+    fn migrate_bitcoin_descriptor_metadata(conn: &Connection) -> SqliteResult<()> {
+        let additive_columns = [
+            ("bitcoin_wallets", "external_descriptor", "TEXT"),
+            ("bitcoin_wallets", "internal_descriptor", "TEXT"),
+            (
+                "bitcoin_wallets",
+                "script_type",
+                "TEXT NOT NULL DEFAULT 'p2tr'",
+            ),
+            (
+                "bitcoin_wallets",
+                "network",
+                "TEXT NOT NULL DEFAULT 'mainnet'",
+            ),
+            ("bitcoin_backends", "rpc_password_format", "TEXT"),
+        ];
+
+        for (table, column, definition) in additive_columns {
+            Self::add_column_if_missing(conn, table, column, definition)?;
+        }
+
+        Ok(())
+    }
+
+    fn migrate_bitcoin_transaction_links(conn: &Connection) -> SqliteResult<()> {
+        let additive_columns = [
+            ("bitcoin_transactions", "replaces_tx_hash", "TEXT"),
+            ("bitcoin_transactions", "replaced_by_tx_hash", "TEXT"),
+            ("bitcoin_transactions", "parent_tx_hash", "TEXT"),
+        ];
+
+        for (table, column, definition) in additive_columns {
+            Self::add_column_if_missing(conn, table, column, definition)?;
+        }
+
+        Ok(())
+    }
+
+    fn migrate_evm_transaction_nonces(conn: &Connection) -> SqliteResult<()> {
+        let additive_columns = [
+            ("evm_transactions", "nonce", "INTEGER"),
+            ("evm_transactions", "replaces_tx_hash", "TEXT"),
+            ("evm_transactions", "replaced_by_tx_hash", "TEXT"),
+        ];
+
+        for (table, column, definition) in additive_columns {
+            Self::add_column_if_missing(conn, table, column, definition)?;
+        }
+
+        Ok(())
+    }
+
+    /// Lets one transaction own several rows, one per batch output and per
+    /// local wallet it touches. Older databases declared `tx_hash` (later
+    /// `tx_hash, output_index`) unique, which SQLite can only relax by
+    /// rebuilding the table.
+    fn migrate_bitcoin_transaction_batches(conn: &mut Connection) -> SqliteResult<()> {
+        Self::add_column_if_missing(conn, "bitcoin_transactions", "batch_id", "TEXT")?;
+        Self::add_column_if_missing(
+            conn,
+            "bitcoin_transactions",
+            "output_index",
+            "INTEGER NOT NULL DEFAULT 0",
+        )?;
+
+        let table_sql: String = conn.query_row(
+            "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'bitcoin_transactions'",
+            [],
+            |row| row.get(0),
+        )?;
+        if table_sql.contains("UNIQUE(wallet_id, tx_hash, output_index)") {
+            return Ok(());
+        }
+
+        let tx = conn.transaction()?;
+        tx.execute_batch(
+            "CREATE TABLE bitcoin_transactions_batched (
+                id TEXT PRIMARY KEY,
+                wallet_id TEXT NOT NULL,
+                tx_hash TEXT NOT NULL,
+                tx_type TEXT NOT NULL,
+                from_address TEXT NOT NULL,
+                to_address TEXT NOT NULL,
+                amount REAL NOT NULL,
+                fee REAL NOT NULL,
+                status TEXT NOT NULL,
+                confirmations INTEGER NOT NULL DEFAULT 0,
+                block_height INTEGER,
+                timestamp TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                replaces_tx_hash TEXT,
+                replaced_by_tx_hash TEXT,
+                parent_tx_hash TEXT,
+                batch_id TEXT,
+                output_index INTEGER NOT NULL DEFAULT 0,
+                amount_sats INTEGER NOT NULL DEFAULT 0,
+                fee_sats INTEGER NOT NULL DEFAULT 0,
+                FOREIGN KEY (wallet_id) REFERENCES bitcoin_wallets(id),
+                UNIQUE(wallet_id, tx_hash, output_index)
+             );
+             INSERT INTO bitcoin_transactions_batched
+                (id, wallet_id, tx_hash, tx_type, from_address, to_address, amount, fee, status, confirmations, block_height, timestamp, created_at, replaces_tx_hash, replaced_by_tx_hash, parent_tx_hash, batch_id, output_index, amount_sats, fee_sats)
+             SELECT id, wallet_id, tx_hash, tx_type, from_address, to_address, amount, fee, status, confirmations, block_height, timestamp, created_at, replaces_tx_hash, replaced_by_tx_hash, parent_tx_hash, batch_id, output_index, amount_sats, fee_sats
+             FROM bitcoin_transactions;
+             DROP TABLE bitcoin_transactions;
+             ALTER TABLE bitcoin_transactions_batched RENAME TO bitcoin_transactions;",
+        )?;
+        tx.commit()
+    }
+
+    /// Adds integer satoshi columns next to the REAL BTC ones and fills them
+    /// from the existing values the first time they appear.
+    fn migrate_bitcoin_satoshi_amounts(conn: &Connection) -> SqliteResult<()> {
+        let columns = [
+            ("bitcoin_wallets", "balance_sats", "balance"),
+            ("bitcoin_transactions", "amount_sats", "amount"),
+            ("bitcoin_transactions", "fee_sats", "fee"),
+        ];
+
+        for (table, column, btc_column) in columns {
+            if Self::table_has_column(conn, table, column)? {
+                continue;
+            }
+            Self::add_column_if_missing(conn, table, column, "INTEGER NOT NULL DEFAULT 0")?;
+            conn.execute(
+                &format!(
+                    "UPDATE {table} SET {column} = CAST(ROUND({btc_column} * 100000000) AS INTEGER)"
+                ),
+                [],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn migrate_bitcoin_multisig_metadata(conn: &Connection) -> SqliteResult<()> {
+        let additive_columns = [
+            ("bitcoin_wallets", "multisig_threshold", "INTEGER"),
+            ("bitcoin_wallets", "multisig_cosigners", "INTEGER"),
+            ("bitcoin_wallets", "multisig_script_type", "TEXT"),
+        ];
+
+        for (table, column, definition) in additive_columns {
+            Self::add_column_if_missing(conn, table, column, definition)?;
+        }
+
+        Ok(())
+    }
+
     fn legacy_secret_rows(conn: &Connection, table: &str) -> SqliteResult<Vec<(String, String)>> {
         let select_sql = format!(
             "SELECT wallet_id, secret_data FROM {table} WHERE secret_format = ?1 OR secret_format IS NULL OR TRIM(secret_format) = ''"
@@ -328,6 +614,25 @@ impl Database {
         Ok(legacy_rows)
     }
 
+    /// Backend RPC passwords saved before they went through the secret backend.
+    fn legacy_backend_password_rows(conn: &Connection) -> SqliteResult<Vec<(String, String)>> {
+        let mut stmt = conn.prepare(
+            "SELECT id, rpc_password FROM bitcoin_backends
+             WHERE rpc_password IS NOT NULL
+               AND (rpc_password_format = ?1 OR rpc_password_format IS NULL OR TRIM(rpc_password_format) = '')",
+        )?;
+        let rows = stmt.query_map(params![SECRET_FORMAT_PLAINTEXT_V0], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let mut legacy_rows = Vec::new();
+        for row in rows {
+            legacy_rows.push(row?);
+        }
+
+        Ok(legacy_rows)
+    }
+
     fn count_legacy_secret_rows(conn: &Connection, table: &str) -> SqliteResult<usize> {
         let count_sql = format!(
             "SELECT COUNT(*) FROM {table} WHERE secret_format = ?1 OR secret_format IS NULL OR TRIM(secret_format) = ''"
@@ -473,6 +778,105 @@ impl Database {
             wallet_type,
             address,
             balance: 0.0,
+            balance_sats: Some(0),
+            created_at: now.clone(),
+            updated_at: now,
+        })
+    }
+
+    /// Inserts a wallet whose keys live elsewhere. It has no secret row.
+    pub fn insert_bitcoin_watch_only_wallet(
+        &self,
+        label: String,
+        address: String,
+    ) -> SqliteResult<WalletInfo> {
+        let conn = self.conn.lock().unwrap();
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+        let wallet_type = "watch-only".to_string();
+
+        conn.execute(
+            "INSERT INTO bitcoin_wallets (id, label, wallet_type, address, balance, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![&id, &label, &wallet_type, &address, 0.0, &now, &now],
+        )?;
+
+        Ok(WalletInfo {
+            id,
+            label,
+            wallet_type,
+            address,
+            balance: 0.0,
+            balance_sats: Some(0),
+            created_at: now.clone(),
+            updated_at: now,
+        })
+    }
+
+    /// Inserts a wallet together with its setup and, unless it is watch-only,
+    /// its secret. Nothing is saved if any part fails.
+    pub fn insert_bitcoin_wallet_with_setup(
+        &self,
+        label: String,
+        wallet_type: String,
+        address: String,
+        secret: Option<(StoredSecret, String)>,
+        setup: &BitcoinWalletSetup,
+    ) -> SqliteResult<WalletInfo> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+        let (threshold, cosigners, multisig_script_type) = match &setup.multisig {
+            Some(policy) => (
+                Some(policy.threshold),
+                Some(policy.cosigners),
+                Some(policy.script_type.as_str()),
+            ),
+            None => (None, None, None),
+        };
+
+        tx.execute(
+            "INSERT INTO bitcoin_wallets
+                (id, label, wallet_type, address, balance, created_at, updated_at,
+                 script_type, network, external_descriptor, internal_descriptor,
+                 multisig_threshold, multisig_cosigners, multisig_script_type)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            params![
+                &id,
+                &label,
+                &wallet_type,
+                &address,
+                0.0,
+                &now,
+                &now,
+                setup.script_type.as_str(),
+                setup.network.as_str(),
+                &setup.descriptors.external,
+                &setup.descriptors.internal,
+                threshold,
+                cosigners,
+                multisig_script_type
+            ],
+        )?;
+
+        if let Some((stored_secret, secret_type)) = secret {
+            tx.execute(
+                "INSERT OR REPLACE INTO bitcoin_wallet_secrets (wallet_id, secret_data, secret_type, secret_format)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![&id, &stored_secret.secret_data, &secret_type, &stored_secret.secret_format],
+            )?;
+        }
+
+        tx.commit()?;
+
+        Ok(WalletInfo {
+            id,
+            label,
+            wallet_type,
+            address,
+            balance: 0.0,
+            balance_sats: Some(0),
             created_at: now.clone(),
             updated_at: now,
         })
@@ -550,8 +954,9 @@ impl Database {
         let conn = self.conn.lock().unwrap();
         let bitcoin = Self::count_legacy_secret_rows(&conn, "bitcoin_wallet_secrets")?;
         let evm = Self::count_legacy_secret_rows(&conn, "evm_wallet_secrets")?;
+        let backends = Self::legacy_backend_password_rows(&conn)?.len();
 
-        Ok(bitcoin + evm)
+        Ok(bitcoin + evm + backends)
     }
 
     pub fn has_any_wallet_secret_rows(&self) -> SqliteResult<bool> {
@@ -574,6 +979,9 @@ impl Database {
         let tx = conn.transaction()?;
 
         tx.execute("DELETE FROM evm_asset_balances", [])?;
+        tx.execute("DELETE FROM bitcoin_wallet_addresses", [])?;
+        tx.execute("DELETE FROM bitcoin_utxo_metadata", [])?;
+        tx.execute("DELETE FROM bitcoin_labels", [])?;
         tx.execute("DELETE FROM bitcoin_transactions", [])?;
         tx.execute("DELETE FROM evm_transactions", [])?;
         tx.execute("DELETE FROM portfolio_history", [])?;
@@ -615,11 +1023,23 @@ impl Database {
     pub fn delete_bitcoin_wallet(&self, wallet_id: &str) -> SqliteResult<bool> {
         let conn = self.conn.lock().unwrap();
 
-        // Delete wallet secret first
+        // Delete wallet secret, derived addresses, coin metadata and labels first
         conn.execute(
             "DELETE FROM bitcoin_wallet_secrets WHERE wallet_id = ?1",
             params![wallet_id],
         )?;
+        conn.execute(
+            "DELETE FROM bitcoin_wallet_addresses WHERE wallet_id = ?1",
+            params![wallet_id],
+        )?;
+        conn.execute(
+            "DELETE FROM bitcoin_utxo_metadata WHERE wallet_id = ?1",
+            params![wallet_id],
+        )?;
+        conn.execute(
+            "DELETE FROM bitcoin_labels WHERE wallet_id = ?1",
+            params![wallet_id],
+        )?;
 
         // Delete wallet
         let rows_affected = conn.execute(
@@ -633,21 +1053,11 @@ impl Database {
     pub fn get_bitcoin_wallets(&self) -> SqliteResult<Vec<WalletInfo>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, label, wallet_type, address, balance, created_at, updated_at
+            "SELECT id, label, wallet_type, address, balance_sats, created_at, updated_at
              FROM bitcoin_wallets ORDER BY created_at DESC",
         )?;
 
-        let wallets = stmt.query_map([], |row| {
-            Ok(WalletInfo {
-                id: row.get(0)?,
-                label: row.get(1)?,
-                wallet_type: row.get(2)?,
-                address: row.get(3)?,
-                balance: row.get(4)?,
-                created_at: row.get(5)?,
-                updated_at: row.get(6)?,
-            })
-        })?;
+        let wallets = stmt.query_map([], Self::bitcoin_wallet_from_row)?;
 
         let mut result = Vec::new();
         for wallet in wallets {
@@ -657,24 +1067,28 @@ impl Database {
         Ok(result)
     }
 
+    fn bitcoin_wallet_from_row(row: &rusqlite::Row<'_>) -> SqliteResult<WalletInfo> {
+        let balance_sats = row.get::<_, i64>(4)?.max(0) as u64;
+        Ok(WalletInfo {
+            id: row.get(0)?,
+            label: row.get(1)?,
+            wallet_type: row.get(2)?,
+            address: row.get(3)?,
+            balance: sats_to_btc(balance_sats),
+            balance_sats: Some(balance_sats),
+            created_at: row.get(5)?,
+            updated_at: row.get(6)?,
+        })
+    }
+
     pub fn get_bitcoin_wallet(&self, wallet_id: &str) -> SqliteResult<Option<WalletInfo>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, label, wallet_type, address, balance, created_at, updated_at
+            "SELECT id, label, wallet_type, address, balance_sats, created_at, updated_at
              FROM bitcoin_wallets WHERE id = ?1",
         )?;
 
-        let result = stmt.query_row(params![wallet_id], |row| {
-            Ok(WalletInfo {
-                id: row.get(0)?,
-                label: row.get(1)?,
-                wallet_type: row.get(2)?,
-                address: row.get(3)?,
-                balance: row.get(4)?,
-                created_at: row.get(5)?,
-                updated_at: row.get(6)?,
-            })
-        });
+        let result = stmt.query_row(params![wallet_id], Self::bitcoin_wallet_from_row);
 
         match result {
             Ok(wallet) => Ok(Some(wallet)),
@@ -683,17 +1097,28 @@ impl Database {
         }
     }
 
-    pub fn update_bitcoin_wallet_balance(&self, wallet_id: &str, balance: f64) -> SqliteResult<()> {
+    pub fn update_bitcoin_wallet_balance(
+        &self,
+        wallet_id: &str,
+        balance_sats: u64,
+    ) -> SqliteResult<()> {
         let conn = self.conn.lock().unwrap();
         let now = Utc::now().to_rfc3339();
         let balance_updated_at = Utc::now().timestamp();
 
+        // The REAL column is kept in step for older builds reading this file.
         conn.execute(
             "UPDATE bitcoin_wallets
-             SET balance = ?1, updated_at = ?2, balance_updated_at = ?3
-             WHERE id = ?4",
-            params![balance, &now, balance_updated_at, wallet_id],
-        )?;
+             SET balance_sats = ?1, balance = ?2, updated_at = ?3, balance_updated_at = ?4
+             WHERE id = ?5",
+            params![
+                balance_sats as i64,
+                sats_to_btc(balance_sats),
+                &now,
+                balance_updated_at,
+                wallet_id
+            ],
+        )?;
 
         Ok(())
     }
@@ -716,552 +1141,1602 @@ impl Database {
         Ok(())
     }
 
-    // EVM Wallet Methods
-    pub fn insert_evm_wallet_with_secret(
+    pub fn set_bitcoin_wallet_descriptors(
         &self,
-        label: String,
-        wallet_type: String,
-        address: String,
-        stored_secret: StoredSecret,
-        secret_type: String,
-    ) -> SqliteResult<WalletInfo> {
-        let mut conn = self.conn.lock().unwrap();
-        let tx = conn.transaction()?;
-        let id = Uuid::new_v4().to_string();
-        let now = Utc::now().to_rfc3339();
+        wallet_id: &str,
+        descriptors: &WalletDescriptors,
+    ) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
 
-        tx.execute(
-            "INSERT INTO evm_wallets (id, label, wallet_type, address, balance, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            params![&id, &label, &wallet_type, &address, 0.0, &now, &now],
+        conn.execute(
+            "UPDATE bitcoin_wallets SET external_descriptor = ?1, internal_descriptor = ?2 WHERE id = ?3",
+            params![&descriptors.external, &descriptors.internal, wallet_id],
         )?;
 
-        tx.execute(
-            "INSERT OR REPLACE INTO evm_wallet_secrets (wallet_id, secret_data, secret_type, secret_format)
-             VALUES (?1, ?2, ?3, ?4)",
-            params![&id, &stored_secret.secret_data, &secret_type, &stored_secret.secret_format],
-        )?;
+        Ok(())
+    }
 
-        tx.commit()?;
+    pub fn get_bitcoin_wallet_descriptors(
+        &self,
+        wallet_id: &str,
+    ) -> SqliteResult<Option<WalletDescriptors>> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row(
+            "SELECT external_descriptor, internal_descriptor FROM bitcoin_wallets WHERE id = ?1",
+            params![wallet_id],
+            |row| {
+                let external: Option<String> = row.get(0)?;
+                let internal: Option<String> = row.get(1)?;
+                Ok(external.map(|external| WalletDescriptors { external, internal }))
+            },
+        );
 
-        Ok(WalletInfo {
-            id,
-            label,
-            wallet_type,
-            address,
-            balance: 0.0,
-            created_at: now.clone(),
-            updated_at: now,
-        })
+        match result {
+            Ok(descriptors) => Ok(descriptors),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
     }
 
-    pub(crate) fn load_evm_wallet_secret(
+    pub fn set_bitcoin_wallet_multisig(
         &self,
         wallet_id: &str,
-    ) -> SqliteResult<Option<(String, String, String)>> {
+        policy: &MultisigPolicy,
+    ) -> SqliteResult<()> {
         let conn = self.conn.lock().unwrap();
-        let mut stmt = conn.prepare(
-            "SELECT secret_data, secret_type, COALESCE(secret_format, 'plaintext_v0')
-             FROM evm_wallet_secrets WHERE wallet_id = ?1",
+
+        conn.execute(
+            "UPDATE bitcoin_wallets
+             SET multisig_threshold = ?1, multisig_cosigners = ?2, multisig_script_type = ?3
+             WHERE id = ?4",
+            params![
+                policy.threshold,
+                policy.cosigners,
+                policy.script_type.as_str(),
+                wallet_id
+            ],
         )?;
 
-        let result = stmt.query_row(params![wallet_id], |row| {
-            Ok((
-                row.get::<_, String>(0)?,
-                row.get::<_, String>(1)?,
-                row.get::<_, String>(2)?,
-            ))
-        });
+        Ok(())
+    }
+
+    /// The multisig policy of a wallet, or `None` for single-key wallets.
+    pub fn get_bitcoin_wallet_multisig(
+        &self,
+        wallet_id: &str,
+    ) -> SqliteResult<Option<MultisigPolicy>> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row(
+            "SELECT multisig_threshold, multisig_cosigners, multisig_script_type
+             FROM bitcoin_wallets WHERE id = ?1",
+            params![wallet_id],
+            |row| {
+                let threshold: Option<u32> = row.get(0)?;
+                let cosigners: Option<u32> = row.get(1)?;
+                let script_type: Option<String> = row.get(2)?;
+                Ok(threshold
+                    .zip(cosigners)
+                    .map(|(threshold, cosigners)| MultisigPolicy {
+                        threshold,
+                        cosigners,
+                        script_type: MultisigScriptType::from_str(
+                            script_type.as_deref().unwrap_or_default(),
+                        ),
+                    }))
+            },
+        );
 
         match result {
-            Ok(secret) => Ok(Some(secret)),
+            Ok(policy) => Ok(policy),
             Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
             Err(e) => Err(e),
         }
     }
 
-    pub fn delete_evm_wallet(&self, wallet_id: &str) -> SqliteResult<bool> {
+    #[cfg(test)]
+    pub fn set_bitcoin_wallet_script_type(
+        &self,
+        wallet_id: &str,
+        script_type: BitcoinScriptType,
+    ) -> SqliteResult<()> {
         let conn = self.conn.lock().unwrap();
 
-        // Delete asset balances first (to satisfy foreign key constraints)
         conn.execute(
-            "DELETE FROM evm_asset_balances WHERE wallet_id = ?1",
+            "UPDATE bitcoin_wallets SET script_type = ?1 WHERE id = ?2",
+            params![script_type.as_str(), wallet_id],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get_bitcoin_wallet_script_type(
+        &self,
+        wallet_id: &str,
+    ) -> SqliteResult<BitcoinScriptType> {
+        let conn = self.conn.lock().unwrap();
+        let script_type: String = conn.query_row(
+            "SELECT script_type FROM bitcoin_wallets WHERE id = ?1",
             params![wallet_id],
+            |row| row.get(0),
         )?;
 
-        // Delete wallet secret
+        Ok(BitcoinScriptType::from_str(&script_type))
+    }
+
+    pub fn set_bitcoin_wallet_network(
+        &self,
+        wallet_id: &str,
+        network: BitcoinNetwork,
+    ) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+
         conn.execute(
-            "DELETE FROM evm_wallet_secrets WHERE wallet_id = ?1",
-            params![wallet_id],
+            "UPDATE bitcoin_wallets SET network = ?1 WHERE id = ?2",
+            params![network.as_str(), wallet_id],
         )?;
 
-        // Delete wallet
-        let rows_affected =
-            conn.execute("DELETE FROM evm_wallets WHERE id = ?1", params![wallet_id])?;
+        Ok(())
+    }
 
-        Ok(rows_affected > 0)
+    /// Combined balance of the mainnet wallets. Test networks' coins have no
+    /// USD value, so they never count towards portfolio totals.
+    pub fn get_mainnet_bitcoin_balance_sats(&self) -> SqliteResult<u64> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT network, balance_sats FROM bitcoin_wallets")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                BitcoinNetwork::from_str(&row.get::<_, String>(0)?),
+                row.get::<_, i64>(1)?.max(0) as u64,
+            ))
+        })?;
+
+        let mut total = 0u64;
+        for row in rows {
+            let (network, balance_sats) = row?;
+            if network == BitcoinNetwork::Mainnet {
+                total = total.saturating_add(balance_sats);
+            }
+        }
+
+        Ok(total)
     }
 
-    pub fn get_evm_wallets(&self) -> SqliteResult<Vec<WalletInfo>> {
+    pub fn get_bitcoin_wallet_network(&self, wallet_id: &str) -> SqliteResult<BitcoinNetwork> {
         let conn = self.conn.lock().unwrap();
-        let mut stmt = conn.prepare(
-            "SELECT id, label, wallet_type, address, balance, created_at, updated_at
-             FROM evm_wallets ORDER BY created_at DESC",
+        let network: String = conn.query_row(
+            "SELECT network FROM bitcoin_wallets WHERE id = ?1",
+            params![wallet_id],
+            |row| row.get(0),
         )?;
 
-        let wallets = stmt.query_map([], |row| {
-            Ok(WalletInfo {
-                id: row.get(0)?,
-                label: row.get(1)?,
-                wallet_type: row.get(2)?,
-                address: row.get(3)?,
-                balance: row.get(4)?,
-                created_at: row.get(5)?,
-                updated_at: row.get(6)?,
-            })
-        })?;
+        Ok(BitcoinNetwork::from_str(&network))
+    }
 
-        let mut result = Vec::new();
-        for wallet in wallets {
-            result.push(wallet?);
+    pub fn upsert_bitcoin_wallet_addresses(
+        &self,
+        addresses: &[BitcoinAddressInfo],
+    ) -> SqliteResult<()> {
+        if addresses.is_empty() {
+            return Ok(());
         }
 
-        Ok(result)
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        for address in addresses {
+            tx.execute(
+                "INSERT INTO bitcoin_wallet_addresses
+                 (wallet_id, keychain, derivation_index, address, balance_sats, tx_count, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                 ON CONFLICT(wallet_id, keychain, derivation_index) DO UPDATE SET
+                    address = excluded.address,
+                    balance_sats = excluded.balance_sats,
+                    tx_count = excluded.tx_count,
+                    updated_at = excluded.updated_at",
+                params![
+                    &address.wallet_id,
+                    address.keychain.as_str(),
+                    address.derivation_index,
+                    &address.address,
+                    address.balance_sats,
+                    address.tx_count,
+                    &address.updated_at,
+                ],
+            )?;
+        }
+
+        tx.commit()
     }
 
-    pub fn get_evm_wallet(&self, wallet_id: &str) -> SqliteResult<Option<WalletInfo>> {
+    pub fn get_bitcoin_wallet_addresses(
+        &self,
+        wallet_id: &str,
+    ) -> SqliteResult<Vec<BitcoinAddressInfo>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, label, wallet_type, address, balance, created_at, updated_at
-             FROM evm_wallets WHERE id = ?1",
+            "SELECT wallet_id, keychain, derivation_index, address, balance_sats, tx_count, updated_at
+             FROM bitcoin_wallet_addresses
+             WHERE wallet_id = ?1
+             ORDER BY keychain, derivation_index",
         )?;
 
-        let result = stmt.query_row(params![wallet_id], |row| {
-            Ok(WalletInfo {
-                id: row.get(0)?,
-                label: row.get(1)?,
-                wallet_type: row.get(2)?,
+        let addresses = stmt.query_map(params![wallet_id], |row| {
+            Ok(BitcoinAddressInfo {
+                wallet_id: row.get(0)?,
+                keychain: BitcoinKeychain::from_str(&row.get::<_, String>(1)?),
+                derivation_index: row.get(2)?,
                 address: row.get(3)?,
-                balance: row.get(4)?,
-                created_at: row.get(5)?,
+                balance_sats: row.get(4)?,
+                tx_count: row.get(5)?,
                 updated_at: row.get(6)?,
             })
-        });
+        })?;
 
-        match result {
-            Ok(wallet) => Ok(Some(wallet)),
-            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-            Err(e) => Err(e),
+        let mut result = Vec::new();
+        for address in addresses {
+            result.push(address?);
         }
+
+        Ok(result)
     }
 
-    pub fn update_evm_wallet_sync_metadata(
+    pub fn set_bitcoin_utxo_frozen(
         &self,
         wallet_id: &str,
-        balance: f64,
-        balance_updated_at: Option<i64>,
-        failed_sources: &[String],
+        outpoint: &str,
+        frozen: bool,
     ) -> SqliteResult<()> {
         let conn = self.conn.lock().unwrap();
-        let now = Utc::now().to_rfc3339();
-
         conn.execute(
-            "UPDATE evm_wallets
-             SET balance = ?1, updated_at = ?2, balance_updated_at = ?3, balance_failed_sources = ?4
-             WHERE id = ?5",
-            params![
-                balance,
-                &now,
-                balance_updated_at,
-                serde_json::to_string(failed_sources).unwrap_or_else(|_| "[]".to_string()),
-                wallet_id
-            ],
+            "INSERT INTO bitcoin_utxo_metadata (wallet_id, outpoint, frozen, updated_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(wallet_id, outpoint) DO UPDATE SET
+                frozen = excluded.frozen,
+                updated_at = excluded.updated_at",
+            params![wallet_id, outpoint, frozen, Utc::now().to_rfc3339()],
         )?;
-
         Ok(())
     }
 
-    // EVM Asset Balance Methods
-    pub fn save_evm_asset_balance(
+    pub fn set_bitcoin_utxo_label(
         &self,
-        wallet_id: String,
-        chain: String,
-        chain_id: u64,
-        asset_symbol: String,
-        asset_name: String,
-        asset_decimals: u8,
-        contract_address: Option<String>,
-        balance: String,
-        balance_float: f64,
-        usd_price: Option<f64>,
-        usd_value: Option<f64>,
-        valuation_status: String,
+        wallet_id: &str,
+        outpoint: &str,
+        label: Option<&str>,
     ) -> SqliteResult<()> {
         let conn = self.conn.lock().unwrap();
-        let id = Uuid::new_v4().to_string();
-        let now = Utc::now().to_rfc3339();
-        let now_ts = Utc::now().timestamp();
-
         conn.execute(
-            "INSERT OR REPLACE INTO evm_asset_balances 
-             (id, wallet_id, chain, chain_id, asset_symbol, asset_name, asset_decimals, contract_address, balance, balance_float, usd_price, usd_value, valuation_status, updated_at, balance_updated_at, price_updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
-            params![
-                &id, &wallet_id, &chain, chain_id, &asset_symbol, &asset_name,
-                asset_decimals, &contract_address, &balance, balance_float, usd_price, usd_value,
-                &valuation_status, &now, now_ts,
-                if valuation_status == "valued" { Some(now_ts) } else { None }
-            ],
+            "INSERT INTO bitcoin_utxo_metadata (wallet_id, outpoint, label, updated_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(wallet_id, outpoint) DO UPDATE SET
+                label = excluded.label,
+                updated_at = excluded.updated_at",
+            params![wallet_id, outpoint, label, Utc::now().to_rfc3339()],
         )?;
-
         Ok(())
     }
 
-    pub fn batch_save_evm_asset_balances(&self, assets: &[AssetBalanceData]) -> SqliteResult<()> {
-        if assets.is_empty() {
-            return Ok(());
+    pub fn get_bitcoin_utxo_metadata(
+        &self,
+        wallet_id: &str,
+    ) -> SqliteResult<Vec<BitcoinUtxoMetadata>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT wallet_id, outpoint, label, origin, frozen, updated_at
+             FROM bitcoin_utxo_metadata
+             WHERE wallet_id = ?1
+             ORDER BY outpoint",
+        )?;
+
+        let rows = stmt.query_map(params![wallet_id], |row| {
+            Ok(BitcoinUtxoMetadata {
+                wallet_id: row.get(0)?,
+                outpoint: row.get(1)?,
+                label: row.get(2)?,
+                origin: row.get(3)?,
+                frozen: row.get(4)?,
+                updated_at: row.get(5)?,
+            })
+        })?;
+
+        let mut result = Vec::new();
+        for row in rows {
+            result.push(row?);
         }
 
-        let mut conn = self.conn.lock().unwrap();
-        let tx = conn.transaction()?;
-        let now = Utc::now().to_rfc3339();
-        let now_ts = Utc::now().timestamp();
+        Ok(result)
+    }
 
-        for asset in assets {
-            let id = Uuid::new_v4().to_string();
-            tx.execute(
-                "INSERT OR REPLACE INTO evm_asset_balances (
-                    id, wallet_id, chain, chain_id, asset_symbol, asset_name,
-                    asset_decimals, contract_address, balance, balance_float,
-                    usd_price, usd_value, valuation_status, updated_at, balance_updated_at, price_updated_at
-                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
-                params![
-                    &id,
-                    &asset.wallet_id,
-                    &asset.chain,
-                    asset.chain_id,
-                    &asset.symbol,
-                    &asset.name,
-                    asset.decimals,
-                    &asset.contract_address,
-                    &asset.balance,
-                    asset.balance_float,
-                    asset.usd_price,
-                    asset.usd_value,
-                    &asset.valuation_status,
-                    &now,
-                    now_ts,
-                    if asset.valuation_status == "valued" {
-                        Some(now_ts)
-                    } else {
-                        None
-                    },
-                ],
-            )?;
+    /// Outpoints the user froze; sends never select these automatically.
+    pub fn get_frozen_bitcoin_utxos(&self, wallet_id: &str) -> SqliteResult<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT outpoint FROM bitcoin_utxo_metadata
+             WHERE wallet_id = ?1 AND frozen = 1
+             ORDER BY outpoint",
+        )?;
+
+        let rows = stmt.query_map(params![wallet_id], |row| row.get(0))?;
+
+        let mut result = Vec::new();
+        for row in rows {
+            result.push(row?);
         }
 
-        tx.commit()?;
+        Ok(result)
+    }
+
+    /// Stores a label, or removes it when `label` is `None`.
+    pub fn set_bitcoin_label(
+        &self,
+        wallet_id: &str,
+        label_type: BitcoinLabelType,
+        reference: &str,
+        label: Option<&str>,
+        origin: Option<&str>,
+    ) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        match label {
+            Some(label) => conn.execute(
+                "INSERT INTO bitcoin_labels (wallet_id, label_type, ref, label, origin, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                 ON CONFLICT(wallet_id, label_type, ref) DO UPDATE SET
+                    label = excluded.label,
+                    origin = excluded.origin,
+                    updated_at = excluded.updated_at",
+                params![
+                    wallet_id,
+                    label_type.as_str(),
+                    reference,
+                    label,
+                    origin,
+                    Utc::now().to_rfc3339()
+                ],
+            )?,
+            None => conn.execute(
+                "DELETE FROM bitcoin_labels WHERE wallet_id = ?1 AND label_type = ?2 AND ref = ?3",
+                params![wallet_id, label_type.as_str(), reference],
+            )?,
+        };
         Ok(())
     }
 
-    pub fn get_evm_asset_balances(
+    /// Saves checked BIP329 records in one transaction, so a failed import
+    /// leaves the wallet's labels as they were.
+    pub fn import_bitcoin_labels(
         &self,
         wallet_id: &str,
-    ) -> SqliteResult<
-        Vec<(
-            String,
-            String,
-            u64,
-            String,
-            Option<String>,
-            u8,
-            String,
-            f64,
-            Option<f64>,
-            Option<f64>,
-            String,
-        )>,
-    > {
+        records: &[Bip329Record],
+    ) -> SqliteResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let now = Utc::now().to_rfc3339();
+
+        for record in records {
+            match (record.label_type, record.label.as_deref()) {
+                (BitcoinLabelType::Output, label) => {
+                    if let Some(label) = label {
+                        tx.execute(
+                            "INSERT INTO bitcoin_utxo_metadata (wallet_id, outpoint, label, origin, updated_at)
+                             VALUES (?1, ?2, ?3, ?4, ?5)
+                             ON CONFLICT(wallet_id, outpoint) DO UPDATE SET
+                                label = excluded.label,
+                                origin = excluded.origin,
+                                updated_at = excluded.updated_at",
+                            params![wallet_id, &record.reference, label, &record.origin, &now],
+                        )?;
+                    }
+                    if let Some(spendable) = record.spendable {
+                        tx.execute(
+                            "INSERT INTO bitcoin_utxo_metadata (wallet_id, outpoint, frozen, updated_at)
+                             VALUES (?1, ?2, ?3, ?4)
+                             ON CONFLICT(wallet_id, outpoint) DO UPDATE SET
+                                frozen = excluded.frozen,
+                                updated_at = excluded.updated_at",
+                            params![wallet_id, &record.reference, !spendable, &now],
+                        )?;
+                    }
+                }
+                (label_type, Some(label)) => {
+                    tx.execute(
+                        "INSERT INTO bitcoin_labels (wallet_id, label_type, ref, label, origin, updated_at)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                         ON CONFLICT(wallet_id, label_type, ref) DO UPDATE SET
+                            label = excluded.label,
+                            origin = excluded.origin,
+                            updated_at = excluded.updated_at",
+                        params![
+                            wallet_id,
+                            label_type.as_str(),
+                            &record.reference,
+                            label,
+                            &record.origin,
+                            &now
+                        ],
+                    )?;
+                }
+                (_, None) => {}
+            }
+        }
+
+        tx.commit()
+    }
+
+    pub fn get_bitcoin_labels(&self, wallet_id: &str) -> SqliteResult<Vec<BitcoinLabel>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT chain, asset_symbol, chain_id, asset_name, contract_address, asset_decimals, balance, balance_float, usd_price, usd_value, valuation_status
-             FROM evm_asset_balances 
-             WHERE wallet_id = ?1 
-             ORDER BY chain, asset_symbol",
+            "SELECT wallet_id, label_type, ref, label, origin, updated_at
+             FROM bitcoin_labels
+             WHERE wallet_id = ?1
+             ORDER BY label_type, ref",
         )?;
 
-        let balances = stmt.query_map(params![wallet_id], |row| {
-            Ok((
-                row.get::<_, String>(0)?,         // chain
-                row.get::<_, String>(1)?,         // asset_symbol
-                row.get::<_, u64>(2)?,            // chain_id
-                row.get::<_, String>(3)?,         // asset_name
-                row.get::<_, Option<String>>(4)?, // contract_address
-                row.get::<_, u8>(5)?,             // asset_decimals
-                row.get::<_, String>(6)?,         // balance
-                row.get::<_, f64>(7)?,            // balance_float
-                row.get::<_, Option<f64>>(8)?,    // usd_price
-                row.get::<_, Option<f64>>(9)?,    // usd_value
-                row.get::<_, String>(10)?,        // valuation_status
-            ))
+        let rows = stmt.query_map(params![wallet_id], |row| {
+            let label_type = row.get::<_, String>(1)?;
+            Ok(BitcoinLabel {
+                wallet_id: row.get(0)?,
+                label_type: BitcoinLabelType::from_str(&label_type).ok_or_else(|| {
+                    rusqlite::Error::InvalidColumnType(
+                        1,
+                        "label_type".to_string(),
+                        rusqlite::types::Type::Text,
+                    )
+                })?,
+                reference: row.get(2)?,
+                label: row.get(3)?,
+                origin: row.get(4)?,
+                updated_at: row.get(5)?,
+            })
         })?;
 
         let mut result = Vec::new();
-        for balance in balances {
-            result.push(balance?);
+        for row in rows {
+            result.push(row?);
         }
 
         Ok(result)
     }
 
-    pub fn clear_evm_asset_balances(&self, wallet_id: &str) -> SqliteResult<()> {
-        let conn = self.conn.lock().unwrap();
+    /// Inserts or updates a backend. A new backend without a priority goes last,
+    /// and an update without a password keeps the saved one.
+    pub fn save_bitcoin_backend(
+        &self,
+        request: &SaveBitcoinBackendRequest,
+        rpc_password: Option<&StoredSecret>,
+    ) -> SqliteResult<BitcoinBackendConfig> {
+        let id = request
+            .id
+            .clone()
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        let now = Utc::now().to_rfc3339();
 
-        conn.execute(
-            "DELETE FROM evm_asset_balances WHERE wallet_id = ?1",
-            params![wallet_id],
-        )?;
+        {
+            let conn = self.conn.lock().unwrap();
+            conn.execute(
+                "INSERT INTO bitcoin_backends
+                    (id, network, kind, url, rpc_username, rpc_password, rpc_password_format, priority, enabled, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?10,
+                    COALESCE(?7, (SELECT COALESCE(MAX(priority) + 1, 0) FROM bitcoin_backends WHERE network = ?2)),
+                    ?8, ?9, ?9)
+                 ON CONFLICT(id) DO UPDATE SET
+                    network = excluded.network,
+                    kind = excluded.kind,
+                    url = excluded.url,
+                    rpc_username = excluded.rpc_username,
+                    rpc_password = COALESCE(excluded.rpc_password, bitcoin_backends.rpc_password),
+                    rpc_password_format = CASE WHEN excluded.rpc_password IS NULL
+                        THEN bitcoin_backends.rpc_password_format
+                        ELSE excluded.rpc_password_format END,
+                    priority = COALESCE(?7, bitcoin_backends.priority),
+                    enabled = excluded.enabled,
+                    updated_at = excluded.updated_at",
+                params![
+                    id,
+                    request.network.as_str(),
+                    request.kind.as_str(),
+                    request.url,
+                    request.rpc_username,
+                    rpc_password.map(|secret| &secret.secret_data),
+                    request.priority,
+                    request.enabled,
+                    now,
+                    rpc_password.map(|secret| &secret.secret_format),
+                ],
+            )?;
+        }
 
-        Ok(())
+        self.get_bitcoin_backend(&id)?
+            .ok_or(rusqlite::Error::QueryReturnedNoRows)
     }
 
-    // Bitcoin Transaction Methods
-    pub fn add_bitcoin_transaction(&self, tx: &BitcoinTransaction) -> SqliteResult<()> {
+    pub fn get_bitcoin_backend(&self, id: &str) -> SqliteResult<Option<BitcoinBackendConfig>> {
         let conn = self.conn.lock().unwrap();
-
-        conn.execute(
-            "INSERT OR REPLACE INTO bitcoin_transactions
-             (id, wallet_id, tx_hash, tx_type, from_address, to_address, amount, fee, status, confirmations, block_height, timestamp, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
-            params![
-                &tx.id,
-                &tx.wallet_id,
-                &tx.tx_hash,
-                tx.tx_type.as_str(),
-                &tx.from_address,
-                &tx.to_address,
-                tx.amount,
-                tx.fee,
-                tx.status.as_str(),
-                tx.confirmations,
-                tx.block_height,
-                &tx.timestamp,
-                &tx.created_at,
-            ],
+        let mut stmt = conn.prepare(
+            "SELECT id, network, kind, url, rpc_username, rpc_password IS NOT NULL, priority, enabled, created_at, updated_at
+             FROM bitcoin_backends
+             WHERE id = ?1",
         )?;
 
-        Ok(())
+        match stmt.query_row(params![id], Self::map_bitcoin_backend) {
+            Ok(backend) => Ok(Some(backend)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
     }
 
-    pub fn get_bitcoin_transactions(
+    /// Backends of one network (or all networks), in the order they are tried.
+    pub fn get_bitcoin_backends(
         &self,
-        wallet_id: &str,
-    ) -> SqliteResult<Vec<BitcoinTransaction>> {
+        network: Option<BitcoinNetwork>,
+    ) -> SqliteResult<Vec<BitcoinBackendConfig>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, wallet_id, tx_hash, tx_type, from_address, to_address, amount, fee, status, confirmations, block_height, timestamp, created_at
-             FROM bitcoin_transactions
-             WHERE wallet_id = ?1
-             ORDER BY timestamp DESC",
+            "SELECT id, network, kind, url, rpc_username, rpc_password IS NOT NULL, priority, enabled, created_at, updated_at
+             FROM bitcoin_backends
+             WHERE ?1 IS NULL OR network = ?1
+             ORDER BY network, priority, created_at",
         )?;
 
-        let transactions = stmt.query_map(params![wallet_id], |row| {
-            Ok(BitcoinTransaction {
-                id: row.get(0)?,
-                wallet_id: row.get(1)?,
-                tx_hash: row.get(2)?,
-                tx_type: TransactionType::from_str(&row.get::<_, String>(3)?),
-                from_address: row.get(4)?,
-                to_address: row.get(5)?,
-                amount: row.get(6)?,
-                fee: row.get(7)?,
-                status: TransactionStatus::from_str(&row.get::<_, String>(8)?),
-                confirmations: row.get(9)?,
-                block_height: row.get(10)?,
-                timestamp: row.get(11)?,
-                created_at: row.get(12)?,
-            })
-        })?;
+        let rows = stmt.query_map(
+            params![network.map(|network| network.as_str())],
+            Self::map_bitcoin_backend,
+        )?;
 
         let mut result = Vec::new();
-        for tx in transactions {
-            result.push(tx?);
+        for row in rows {
+            result.push(row?);
         }
 
         Ok(result)
     }
 
-    pub fn get_all_bitcoin_transactions(&self) -> SqliteResult<Vec<BitcoinTransaction>> {
+    /// The stored RPC password of a backend, as written by the secret backend.
+    /// Passwords saved before encryption are plaintext rows without a format.
+    pub fn get_bitcoin_backend_password(&self, id: &str) -> SqliteResult<Option<StoredSecret>> {
         let conn = self.conn.lock().unwrap();
-        let mut stmt = conn.prepare(
-            "SELECT id, wallet_id, tx_hash, tx_type, from_address, to_address, amount, fee, status, confirmations, block_height, timestamp, created_at
-             FROM bitcoin_transactions
-             ORDER BY timestamp DESC",
-        )?;
-
-        let transactions = stmt.query_map([], |row| {
-            Ok(BitcoinTransaction {
-                id: row.get(0)?,
-                wallet_id: row.get(1)?,
-                tx_hash: row.get(2)?,
-                tx_type: TransactionType::from_str(&row.get::<_, String>(3)?),
-                from_address: row.get(4)?,
-                to_address: row.get(5)?,
-                amount: row.get(6)?,
-                fee: row.get(7)?,
-                status: TransactionStatus::from_str(&row.get::<_, String>(8)?),
-                confirmations: row.get(9)?,
-                block_height: row.get(10)?,
-                timestamp: row.get(11)?,
-                created_at: row.get(12)?,
-            })
-        })?;
+        let result = conn.query_row(
+            "SELECT rpc_password, rpc_password_format FROM bitcoin_backends WHERE id = ?1",
+            params![id],
+            |row| {
+                Ok((
+                    row.get::<_, Option<String>>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                ))
+            },
+        );
 
-        let mut result = Vec::new();
-        for tx in transactions {
-            result.push(tx?);
+        match result {
+            Ok((Some(secret_data), secret_format)) => Ok(Some(StoredSecret {
+                secret_data,
+                secret_format: secret_format
+                    .unwrap_or_else(|| SECRET_FORMAT_PLAINTEXT_V0.to_string()),
+            })),
+            Ok((None, _)) | Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
         }
-
-        Ok(result)
     }
 
-    // EVM Transaction Methods
-    pub fn add_evm_transaction(&self, tx: &EvmTransaction) -> SqliteResult<()> {
+    pub fn delete_bitcoin_backend(&self, id: &str) -> SqliteResult<bool> {
         let conn = self.conn.lock().unwrap();
+        let rows = conn.execute("DELETE FROM bitcoin_backends WHERE id = ?1", params![id])?;
+        Ok(rows > 0)
+    }
 
+    fn map_bitcoin_backend(row: &rusqlite::Row) -> SqliteResult<BitcoinBackendConfig> {
+        let kind: String = row.get(2)?;
+        Ok(BitcoinBackendConfig {
+            id: row.get(0)?,
+            network: BitcoinNetwork::from_str(&row.get::<_, String>(1)?),
+            kind: BitcoinBackendKind::from_str(&kind).ok_or_else(|| {
+                rusqlite::Error::InvalidColumnType(
+                    2,
+                    "kind".to_string(),
+                    rusqlite::types::Type::Text,
+                )
+            })?,
+            url: row.get(3)?,
+            rpc_username: row.get(4)?,
+            rpc_password: None,
+            has_rpc_password: row.get(5)?,
+            priority: row.get(6)?,
+            enabled: row.get(7)?,
+            created_at: row.get(8)?,
+            updated_at: row.get(9)?,
+        })
+    }
+
+    /// Adds a network, or updates the one with the same chain id.
+    pub fn save_evm_network(&self, network: &EvmNetwork) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
         conn.execute(
-            "INSERT OR REPLACE INTO evm_transactions
-             (id, wallet_id, tx_hash, tx_type, from_address, to_address, amount, amount_float, asset_symbol, asset_name, contract_address, chain, chain_id, gas_used, gas_price, fee, status, block_number, timestamp, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20)",
+            "INSERT INTO evm_networks
+                (chain_id, name, display_name, native_symbol, native_decimals, rpc_url, wss_url,
+                 explorer_api_url, is_testnet, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+             ON CONFLICT(chain_id) DO UPDATE SET
+                name = excluded.name,
+                display_name = excluded.display_name,
+                native_symbol = excluded.native_symbol,
+                native_decimals = excluded.native_decimals,
+                rpc_url = excluded.rpc_url,
+                wss_url = excluded.wss_url,
+                explorer_api_url = excluded.explorer_api_url,
+                is_testnet = excluded.is_testnet,
+                updated_at = excluded.updated_at",
             params![
-                &tx.id,
-                &tx.wallet_id,
-                &tx.tx_hash,
-                tx.tx_type.as_str(),
-                &tx.from_address,
-                &tx.to_address,
-                &tx.amount,
-                tx.amount_float,
-                &tx.asset_symbol,
-                &tx.asset_name,
-                &tx.contract_address,
-                &tx.chain,
-                tx.chain_id,
-                &tx.gas_used,
-                &tx.gas_price,
-                tx.fee,
-                tx.status.as_str(),
-                tx.block_number,
-                &tx.timestamp,
-                &tx.created_at,
+                network.chain_id,
+                network.name,
+                network.display_name,
+                network.native_symbol,
+                network.native_decimals,
+                network.rpc_url,
+                network.wss_url,
+                network.explorer_api_url,
+                network.is_testnet,
+                network.created_at,
+                network.updated_at
             ],
         )?;
-
         Ok(())
     }
 
-    pub fn get_evm_transactions(&self, wallet_id: &str) -> SqliteResult<Vec<EvmTransaction>> {
+    pub fn get_evm_networks(&self) -> SqliteResult<Vec<EvmNetwork>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, wallet_id, tx_hash, tx_type, from_address, to_address, amount, amount_float, asset_symbol, asset_name, contract_address, chain, chain_id, gas_used, gas_price, fee, status, block_number, timestamp, created_at
-             FROM evm_transactions
-             WHERE wallet_id = ?1
-             ORDER BY timestamp DESC",
+            "SELECT chain_id, name, display_name, native_symbol, native_decimals, rpc_url, wss_url,
+                    explorer_api_url, is_testnet, created_at, updated_at
+             FROM evm_networks
+             ORDER BY created_at",
         )?;
 
-        let transactions = stmt.query_map(params![wallet_id], |row| {
-            Ok(EvmTransaction {
-                id: row.get(0)?,
-                wallet_id: row.get(1)?,
-                tx_hash: row.get(2)?,
-                tx_type: TransactionType::from_str(&row.get::<_, String>(3)?),
-                from_address: row.get(4)?,
-                to_address: row.get(5)?,
-                amount: row.get(6)?,
-                amount_float: row.get(7)?,
-                asset_symbol: row.get(8)?,
-                asset_name: row.get(9)?,
-                contract_address: row.get(10)?,
-                chain: row.get(11)?,
-                chain_id: row.get(12)?,
-                gas_used: row.get(13)?,
-                gas_price: row.get(14)?,
-                fee: row.get(15)?,
-                status: TransactionStatus::from_str(&row.get::<_, String>(16)?),
-                block_number: row.get(17)?,
-                timestamp: row.get(18)?,
-                created_at: row.get(19)?,
+        let rows = stmt.query_map([], |row| {
+            Ok(EvmNetwork {
+                chain_id: row.get(0)?,
+                name: row.get(1)?,
+                display_name: row.get(2)?,
+                native_symbol: row.get(3)?,
+                native_decimals: row.get(4)?,
+                rpc_url: row.get(5)?,
+                wss_url: row.get(6)?,
+                explorer_api_url: row.get(7)?,
+                is_testnet: row.get(8)?,
+                created_at: row.get(9)?,
+                updated_at: row.get(10)?,
             })
         })?;
 
         let mut result = Vec::new();
-        for tx in transactions {
-            result.push(tx?);
+        for row in rows {
+            result.push(row?);
         }
 
         Ok(result)
     }
 
-    pub fn get_all_evm_transactions(&self) -> SqliteResult<Vec<EvmTransaction>> {
+    /// Removes a network with its custom tokens and cached balances. Its
+    /// transactions stay in history.
+    pub fn delete_evm_network(&self, chain_id: u64) -> SqliteResult<bool> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM evm_asset_balances WHERE chain_id = ?1",
+            params![chain_id],
+        )?;
+        tx.execute(
+            "DELETE FROM evm_custom_tokens WHERE chain_id = ?1",
+            params![chain_id],
+        )?;
+        let rows = tx.execute(
+            "DELETE FROM evm_networks WHERE chain_id = ?1",
+            params![chain_id],
+        )?;
+        tx.commit()?;
+        Ok(rows > 0)
+    }
+
+    /// Adds a custom token, or refreshes the metadata of one already added.
+    pub fn save_evm_custom_token(&self, token: &EvmCustomToken) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO evm_custom_tokens
+                (id, chain_id, contract_address, symbol, name, decimals, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(chain_id, contract_address) DO UPDATE SET
+                symbol = excluded.symbol,
+                name = excluded.name,
+                decimals = excluded.decimals",
+            params![
+                token.id,
+                token.chain_id,
+                token.contract_address,
+                token.symbol,
+                token.name,
+                token.decimals,
+                token.created_at
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_evm_custom_tokens(
+        &self,
+        chain_id: Option<u64>,
+    ) -> SqliteResult<Vec<EvmCustomToken>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, wallet_id, tx_hash, tx_type, from_address, to_address, amount, amount_float, asset_symbol, asset_name, contract_address, chain, chain_id, gas_used, gas_price, fee, status, block_number, timestamp, created_at
-             FROM evm_transactions
-             ORDER BY timestamp DESC",
+            "SELECT id, chain_id, contract_address, symbol, name, decimals, created_at
+             FROM evm_custom_tokens
+             WHERE ?1 IS NULL OR chain_id = ?1
+             ORDER BY chain_id, created_at",
         )?;
 
-        let transactions = stmt.query_map([], |row| {
-            Ok(EvmTransaction {
+        let rows = stmt.query_map(params![chain_id], |row| {
+            Ok(EvmCustomToken {
                 id: row.get(0)?,
-                wallet_id: row.get(1)?,
-                tx_hash: row.get(2)?,
-                tx_type: TransactionType::from_str(&row.get::<_, String>(3)?),
-                from_address: row.get(4)?,
-                to_address: row.get(5)?,
-                amount: row.get(6)?,
-                amount_float: row.get(7)?,
-                asset_symbol: row.get(8)?,
-                asset_name: row.get(9)?,
-                contract_address: row.get(10)?,
-                chain: row.get(11)?,
-                chain_id: row.get(12)?,
-                gas_used: row.get(13)?,
-                gas_price: row.get(14)?,
-                fee: row.get(15)?,
-                status: TransactionStatus::from_str(&row.get::<_, String>(16)?),
-                block_number: row.get(17)?,
-                timestamp: row.get(18)?,
-                created_at: row.get(19)?,
+                chain_id: row.get(1)?,
+                contract_address: row.get(2)?,
+                symbol: row.get(3)?,
+                name: row.get(4)?,
+                decimals: row.get(5)?,
+                created_at: row.get(6)?,
             })
         })?;
 
         let mut result = Vec::new();
-        for tx in transactions {
-            result.push(tx?);
+        for row in rows {
+            result.push(row?);
         }
 
         Ok(result)
     }
 
-    pub fn update_evm_transaction_lifecycle(
+    /// Removes a custom token together with the balances cached for it.
+    pub fn delete_evm_custom_token(
         &self,
-        tx_hash: &str,
         chain_id: u64,
-        status: TransactionStatus,
-        block_number: Option<u64>,
-    ) -> SqliteResult<()> {
-        let conn = self.conn.lock().unwrap();
-
-        conn.execute(
-            "UPDATE evm_transactions SET status = ?1, block_number = ?2 WHERE tx_hash = ?3 AND chain_id = ?4",
-            params![status.as_str(), block_number, tx_hash, chain_id],
+        contract_address: &str,
+    ) -> SqliteResult<bool> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM evm_asset_balances
+             WHERE chain_id = ?1 AND lower(contract_address) = lower(?2)",
+            params![chain_id, contract_address],
         )?;
-
-        Ok(())
+        let rows = tx.execute(
+            "DELETE FROM evm_custom_tokens
+             WHERE chain_id = ?1 AND lower(contract_address) = lower(?2)",
+            params![chain_id, contract_address],
+        )?;
+        tx.commit()?;
+        Ok(rows > 0)
     }
 
-    // Dashboard Methods
-    pub fn get_dashboard_stats(
+    // EVM Wallet Methods
+    pub fn insert_evm_wallet_with_secret(
+        &self,
+        label: String,
+        wallet_type: String,
+        address: String,
+        stored_secret: StoredSecret,
+        secret_type: String,
+    ) -> SqliteResult<WalletInfo> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        tx.execute(
+            "INSERT INTO evm_wallets (id, label, wallet_type, address, balance, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![&id, &label, &wallet_type, &address, 0.0, &now, &now],
+        )?;
+
+        tx.execute(
+            "INSERT OR REPLACE INTO evm_wallet_secrets (wallet_id, secret_data, secret_type, secret_format)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![&id, &stored_secret.secret_data, &secret_type, &stored_secret.secret_format],
+        )?;
+
+        tx.commit()?;
+
+        Ok(WalletInfo {
+            id,
+            label,
+            wallet_type,
+            address,
+            balance: 0.0,
+            balance_sats: None,
+            created_at: now.clone(),
+            updated_at: now,
+        })
+    }
+
+    pub(crate) fn load_evm_wallet_secret(
+        &self,
+        wallet_id: &str,
+    ) -> SqliteResult<Option<(String, String, String)>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT secret_data, secret_type, COALESCE(secret_format, 'plaintext_v0')
+             FROM evm_wallet_secrets WHERE wallet_id = ?1",
+        )?;
+
+        let result = stmt.query_row(params![wallet_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        });
+
+        match result {
+            Ok(secret) => Ok(Some(secret)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn delete_evm_wallet(&self, wallet_id: &str) -> SqliteResult<bool> {
+        let conn = self.conn.lock().unwrap();
+
+        // Delete asset balances first (to satisfy foreign key constraints)
+        conn.execute(
+            "DELETE FROM evm_asset_balances WHERE wallet_id = ?1",
+            params![wallet_id],
+        )?;
+
+        // Delete wallet secret
+        conn.execute(
+            "DELETE FROM evm_wallet_secrets WHERE wallet_id = ?1",
+            params![wallet_id],
+        )?;
+
+        // Delete wallet
+        let rows_affected =
+            conn.execute("DELETE FROM evm_wallets WHERE id = ?1", params![wallet_id])?;
+
+        Ok(rows_affected > 0)
+    }
+
+    pub fn get_evm_wallets(&self) -> SqliteResult<Vec<WalletInfo>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, label, wallet_type, address, balance, created_at, updated_at
+             FROM evm_wallets ORDER BY created_at DESC",
+        )?;
+
+        let wallets = stmt.query_map([], |row| {
+            Ok(WalletInfo {
+                id: row.get(0)?,
+                label: row.get(1)?,
+                wallet_type: row.get(2)?,
+                address: row.get(3)?,
+                balance: row.get(4)?,
+                balance_sats: None,
+                created_at: row.get(5)?,
+                updated_at: row.get(6)?,
+            })
+        })?;
+
+        let mut result = Vec::new();
+        for wallet in wallets {
+            result.push(wallet?);
+        }
+
+        Ok(result)
+    }
+
+    pub fn get_evm_wallet(&self, wallet_id: &str) -> SqliteResult<Option<WalletInfo>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, label, wallet_type, address, balance, created_at, updated_at
+             FROM evm_wallets WHERE id = ?1",
+        )?;
+
+        let result = stmt.query_row(params![wallet_id], |row| {
+            Ok(WalletInfo {
+                id: row.get(0)?,
+                label: row.get(1)?,
+                wallet_type: row.get(2)?,
+                address: row.get(3)?,
+                balance: row.get(4)?,
+                balance_sats: None,
+                created_at: row.get(5)?,
+                updated_at: row.get(6)?,
+            })
+        });
+
+        match result {
+            Ok(wallet) => Ok(Some(wallet)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn update_evm_wallet_sync_metadata(
+        &self,
+        wallet_id: &str,
+        balance: f64,
+        balance_updated_at: Option<i64>,
+        failed_sources: &[String],
+    ) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        let now = Utc::now().to_rfc3339();
+
+        conn.execute(
+            "UPDATE evm_wallets
+             SET balance = ?1, updated_at = ?2, balance_updated_at = ?3, balance_failed_sources = ?4
+             WHERE id = ?5",
+            params![
+                balance,
+                &now,
+                balance_updated_at,
+                serde_json::to_string(failed_sources).unwrap_or_else(|_| "[]".to_string()),
+                wallet_id
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    // EVM Asset Balance Methods
+    pub fn save_evm_asset_balance(
+        &self,
+        wallet_id: String,
+        chain: String,
+        chain_id: u64,
+        asset_symbol: String,
+        asset_name: String,
+        asset_decimals: u8,
+        contract_address: Option<String>,
+        balance: String,
+        balance_float: f64,
+        usd_price: Option<f64>,
+        usd_value: Option<f64>,
+        valuation_status: String,
+    ) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+        let now_ts = Utc::now().timestamp();
+
+        conn.execute(
+            "INSERT OR REPLACE INTO evm_asset_balances 
+             (id, wallet_id, chain, chain_id, asset_symbol, asset_name, asset_decimals, contract_address, balance, balance_float, usd_price, usd_value, valuation_status, updated_at, balance_updated_at, price_updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+            params![
+                &id, &wallet_id, &chain, chain_id, &asset_symbol, &asset_name,
+                asset_decimals, &contract_address, &balance, balance_float, usd_price, usd_value,
+                &valuation_status, &now, now_ts,
+                if valuation_status == "valued" { Some(now_ts) } else { None }
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn batch_save_evm_asset_balances(&self, assets: &[AssetBalanceData]) -> SqliteResult<()> {
+        if assets.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let now = Utc::now().to_rfc3339();
+        let now_ts = Utc::now().timestamp();
+
+        for asset in assets {
+            let id = Uuid::new_v4().to_string();
+            tx.execute(
+                "INSERT OR REPLACE INTO evm_asset_balances (
+                    id, wallet_id, chain, chain_id, asset_symbol, asset_name,
+                    asset_decimals, contract_address, balance, balance_float,
+                    usd_price, usd_value, valuation_status, updated_at, balance_updated_at, price_updated_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+                params![
+                    &id,
+                    &asset.wallet_id,
+                    &asset.chain,
+                    asset.chain_id,
+                    &asset.symbol,
+                    &asset.name,
+                    asset.decimals,
+                    &asset.contract_address,
+                    &asset.balance,
+                    asset.balance_float,
+                    asset.usd_price,
+                    asset.usd_value,
+                    &asset.valuation_status,
+                    &now,
+                    now_ts,
+                    if asset.valuation_status == "valued" {
+                        Some(now_ts)
+                    } else {
+                        None
+                    },
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn get_evm_asset_balances(
+        &self,
+        wallet_id: &str,
+    ) -> SqliteResult<
+        Vec<(
+            String,
+            String,
+            u64,
+            String,
+            Option<String>,
+            u8,
+            String,
+            f64,
+            Option<f64>,
+            Option<f64>,
+            String,
+        )>,
+    > {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT chain, asset_symbol, chain_id, asset_name, contract_address, asset_decimals, balance, balance_float, usd_price, usd_value, valuation_status
+             FROM evm_asset_balances 
+             WHERE wallet_id = ?1 
+             ORDER BY chain, asset_symbol",
+        )?;
+
+        let balances = stmt.query_map(params![wallet_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,         // chain
+                row.get::<_, String>(1)?,         // asset_symbol
+                row.get::<_, u64>(2)?,            // chain_id
+                row.get::<_, String>(3)?,         // asset_name
+                row.get::<_, Option<String>>(4)?, // contract_address
+                row.get::<_, u8>(5)?,             // asset_decimals
+                row.get::<_, String>(6)?,         // balance
+                row.get::<_, f64>(7)?,            // balance_float
+                row.get::<_, Option<f64>>(8)?,    // usd_price
+                row.get::<_, Option<f64>>(9)?,    // usd_value
+                row.get::<_, String>(10)?,        // valuation_status
+            ))
+        })?;
+
+        let mut result = Vec::new();
+        for balance in balances {
+            result.push(balance?);
+        }
+
+        Ok(result)
+    }
+
+    pub fn clear_evm_asset_balances(&self, wallet_id: &str) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "DELETE FROM evm_asset_balances WHERE wallet_id = ?1",
+            params![wallet_id],
+        )?;
+
+        Ok(())
+    }
+
+    // Bitcoin Transaction Methods
+    pub fn add_bitcoin_transaction(&self, tx: &BitcoinTransaction) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        Self::write_bitcoin_transaction(&conn, tx)
+    }
+
+    /// Adds the rows of one transaction together, so its history is never partial.
+    pub fn add_bitcoin_transactions(&self, rows: &[BitcoinTransaction]) -> SqliteResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        for row in rows {
+            Self::write_bitcoin_transaction(&tx, row)?;
+        }
+
+        tx.commit()
+    }
+
+    /// Records a fee-bump replacement: its rows are added and the original is
+    /// marked replaced in one transaction, so both are never left live.
+    pub fn record_bitcoin_replacement(
+        &self,
+        original_tx_hash: &str,
+        replacement_rows: &[BitcoinTransaction],
+    ) -> SqliteResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        for row in replacement_rows {
+            Self::write_bitcoin_transaction(&tx, row)?;
+        }
+        if let Some(replacement) = replacement_rows.first() {
+            Self::mark_bitcoin_transaction_replaced(
+                &tx,
+                &replacement.wallet_id,
+                original_tx_hash,
+                &replacement.tx_hash,
+            )?;
+        }
+
+        tx.commit()
+    }
+
+    fn write_bitcoin_transaction(conn: &Connection, tx: &BitcoinTransaction) -> SqliteResult<()> {
+        // History refreshes describe a transaction as a single row. When the
+        // transaction was a batch payment, keep its per-output rows and only
+        // carry over the confirmation state.
+        if tx.batch_id.is_none() {
+            let updated = conn.execute(
+                "UPDATE bitcoin_transactions
+                 SET status = CASE WHEN status = ?1 THEN status ELSE ?2 END,
+                     confirmations = ?3,
+                     block_height = ?4
+                 WHERE tx_hash = ?5 AND wallet_id = ?6 AND batch_id IS NOT NULL",
+                params![
+                    TransactionStatus::Replaced.as_str(),
+                    tx.status.as_str(),
+                    tx.confirmations,
+                    tx.block_height,
+                    &tx.tx_hash,
+                    &tx.wallet_id,
+                ],
+            )?;
+            if updated > 0 {
+                return Ok(());
+            }
+        }
+
+        // A fee-bumped transaction stays replaced whatever a refresh reports.
+        conn.execute(
+            "INSERT OR REPLACE INTO bitcoin_transactions
+             (id, wallet_id, tx_hash, tx_type, from_address, to_address, amount, fee, status, confirmations, block_height, timestamp, created_at, replaces_tx_hash, replaced_by_tx_hash, parent_tx_hash, batch_id, output_index, amount_sats, fee_sats, sender_resolved)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8,
+                     CASE WHEN (SELECT status FROM bitcoin_transactions WHERE wallet_id = ?2 AND tx_hash = ?3 AND output_index = ?18) = ?21 THEN ?21 ELSE ?9 END,
+                     ?10, ?11, ?12, ?13,
+                     COALESCE(?14, (SELECT replaces_tx_hash FROM bitcoin_transactions WHERE wallet_id = ?2 AND tx_hash = ?3 AND output_index = ?18)),
+                     COALESCE(?15, (SELECT replaced_by_tx_hash FROM bitcoin_transactions WHERE wallet_id = ?2 AND tx_hash = ?3 AND output_index = ?18)),
+                     COALESCE(?16, (SELECT parent_tx_hash FROM bitcoin_transactions WHERE wallet_id = ?2 AND tx_hash = ?3 AND output_index = ?18)),
+                     ?17, ?18, ?19, ?20,
+                     COALESCE((SELECT sender_resolved FROM bitcoin_transactions WHERE wallet_id = ?2 AND tx_hash = ?3 AND output_index = ?18), 0))",
+            params![
+                &tx.id,
+                &tx.wallet_id,
+                &tx.tx_hash,
+                tx.tx_type.as_str(),
+                &tx.from_address,
+                &tx.to_address,
+                sats_to_btc(tx.amount_sats),
+                sats_to_btc(tx.fee_sats),
+                tx.status.as_str(),
+                tx.confirmations,
+                tx.block_height,
+                &tx.timestamp,
+                &tx.created_at,
+                &tx.replaces_tx_hash,
+                &tx.replaced_by_tx_hash,
+                &tx.parent_tx_hash,
+                &tx.batch_id,
+                tx.output_index,
+                tx.amount_sats as i64,
+                tx.fee_sats as i64,
+                TransactionStatus::Replaced.as_str(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Whether the backends were already asked for the sender of a received
+    /// transaction, whatever they answered.
+    pub fn is_bitcoin_sender_resolved(&self, wallet_id: &str, tx_hash: &str) -> SqliteResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM bitcoin_transactions
+             WHERE wallet_id = ?1 AND tx_hash = ?2 AND sender_resolved = 1)",
+            params![wallet_id, tx_hash],
+            |row| row.get(0),
+        )
+    }
+
+    pub fn mark_bitcoin_sender_resolved(&self, wallet_id: &str, tx_hash: &str) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE bitcoin_transactions SET sender_resolved = 1 WHERE wallet_id = ?1 AND tx_hash = ?2",
+            params![wallet_id, tx_hash],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get_bitcoin_transaction_by_hash(
+        &self,
+        tx_hash: &str,
+    ) -> SqliteResult<Option<BitcoinTransaction>> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row(
+            "SELECT id, wallet_id, tx_hash, tx_type, from_address, to_address, amount, fee, status, confirmations, block_height, timestamp, created_at, replaces_tx_hash, replaced_by_tx_hash, parent_tx_hash, batch_id, output_index, amount_sats, fee_sats,
+                (SELECT label FROM bitcoin_labels
+                 WHERE bitcoin_labels.wallet_id = bitcoin_transactions.wallet_id
+                   AND label_type = 'tx' AND ref = bitcoin_transactions.tx_hash)
+             FROM bitcoin_transactions
+             WHERE tx_hash = ?1
+             ORDER BY tx_type = 'receive', output_index
+             LIMIT 1",
+            params![tx_hash],
+            Self::bitcoin_transaction_from_row,
+        );
+
+        match result {
+            Ok(tx) => Ok(Some(tx)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Marks `original_tx_hash` as replaced and links it to its replacement.
+    fn mark_bitcoin_transaction_replaced(
+        conn: &Connection,
+        wallet_id: &str,
+        original_tx_hash: &str,
+        replacement_tx_hash: &str,
+    ) -> SqliteResult<()> {
+        conn.execute(
+            "UPDATE bitcoin_transactions SET status = ?1, replaced_by_tx_hash = ?2 WHERE tx_hash = ?3 AND wallet_id = ?4",
+            params![
+                TransactionStatus::Replaced.as_str(),
+                replacement_tx_hash,
+                original_tx_hash,
+                wallet_id
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn bitcoin_transaction_from_row(row: &rusqlite::Row<'_>) -> SqliteResult<BitcoinTransaction> {
+        let amount_sats = row.get::<_, i64>(18)?.max(0) as u64;
+        let fee_sats = row.get::<_, i64>(19)?.max(0) as u64;
+        Ok(BitcoinTransaction {
+            id: row.get(0)?,
+            wallet_id: row.get(1)?,
+            tx_hash: row.get(2)?,
+            tx_type: TransactionType::from_str(&row.get::<_, String>(3)?),
+            from_address: row.get(4)?,
+            to_address: row.get(5)?,
+            amount_sats,
+            fee_sats,
+            amount: sats_to_btc(amount_sats),
+            fee: sats_to_btc(fee_sats),
+            status: TransactionStatus::from_str(&row.get::<_, String>(8)?),
+            confirmations: row.get(9)?,
+            block_height: row.get(10)?,
+            timestamp: row.get(11)?,
+            created_at: row.get(12)?,
+            replaces_tx_hash: row.get(13)?,
+            replaced_by_tx_hash: row.get(14)?,
+            parent_tx_hash: row.get(15)?,
+            batch_id: row.get(16)?,
+            output_index: row.get(17)?,
+            label: row.get(20)?,
+        })
+    }
+
+    pub fn get_bitcoin_transactions(
+        &self,
+        wallet_id: &str,
+    ) -> SqliteResult<Vec<BitcoinTransaction>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, wallet_id, tx_hash, tx_type, from_address, to_address, amount, fee, status, confirmations, block_height, timestamp, created_at, replaces_tx_hash, replaced_by_tx_hash, parent_tx_hash, batch_id, output_index, amount_sats, fee_sats,
+                (SELECT label FROM bitcoin_labels
+                 WHERE bitcoin_labels.wallet_id = bitcoin_transactions.wallet_id
+                   AND label_type = 'tx' AND ref = bitcoin_transactions.tx_hash)
+             FROM bitcoin_transactions
+             WHERE wallet_id = ?1
+             ORDER BY timestamp DESC, tx_hash, output_index",
+        )?;
+
+        let transactions =
+            stmt.query_map(params![wallet_id], Self::bitcoin_transaction_from_row)?;
+
+        let mut result = Vec::new();
+        for tx in transactions {
+            result.push(tx?);
+        }
+
+        Ok(result)
+    }
+
+    pub fn get_all_bitcoin_transactions(&self) -> SqliteResult<Vec<BitcoinTransaction>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, wallet_id, tx_hash, tx_type, from_address, to_address, amount, fee, status, confirmations, block_height, timestamp, created_at, replaces_tx_hash, replaced_by_tx_hash, parent_tx_hash, batch_id, output_index, amount_sats, fee_sats,
+                (SELECT label FROM bitcoin_labels
+                 WHERE bitcoin_labels.wallet_id = bitcoin_transactions.wallet_id
+                   AND label_type = 'tx' AND ref = bitcoin_transactions.tx_hash)
+             FROM bitcoin_transactions
+             ORDER BY timestamp DESC, tx_hash, output_index",
+        )?;
+
+        let transactions = stmt.query_map([], Self::bitcoin_transaction_from_row)?;
+
+        let mut result = Vec::new();
+        for tx in transactions {
+            result.push(tx?);
+        }
+
+        Ok(result)
+    }
+
+    // EVM Transaction Methods
+    pub fn add_evm_transaction(&self, tx: &EvmTransaction) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        Self::write_evm_transaction(&conn, tx)
+    }
+
+    /// Stores a speed-up or cancel and links the transaction it replaces to
+    /// it in one database transaction.
+    pub fn record_evm_replacement(
+        &self,
+        original_tx_hash: &str,
+        replacement: &EvmTransaction,
+    ) -> SqliteResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        Self::write_evm_transaction(&tx, replacement)?;
+        Self::mark_evm_transaction_replaced_by(
+            &tx,
+            original_tx_hash,
+            &replacement.tx_hash,
+            replacement.chain_id,
+        )?;
+
+        tx.commit()
+    }
+
+    fn write_evm_transaction(conn: &Connection, tx: &EvmTransaction) -> SqliteResult<()> {
+        conn.execute(
+            "INSERT OR REPLACE INTO evm_transactions
+             (id, wallet_id, tx_hash, tx_type, from_address, to_address, amount, amount_float, asset_symbol, asset_name, contract_address, chain, chain_id, gas_used, gas_price, fee, status, block_number, timestamp, created_at, nonce, replaces_tx_hash, replaced_by_tx_hash)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20,
+                     COALESCE(?21, (SELECT nonce FROM evm_transactions WHERE wallet_id = ?2 AND tx_hash = ?3 AND chain = ?12)),
+                     COALESCE(?22, (SELECT replaces_tx_hash FROM evm_transactions WHERE wallet_id = ?2 AND tx_hash = ?3 AND chain = ?12)),
+                     COALESCE(?23, (SELECT replaced_by_tx_hash FROM evm_transactions WHERE wallet_id = ?2 AND tx_hash = ?3 AND chain = ?12)))",
+            params![
+                &tx.id,
+                &tx.wallet_id,
+                &tx.tx_hash,
+                tx.tx_type.as_str(),
+                &tx.from_address,
+                &tx.to_address,
+                &tx.amount,
+                tx.amount_float,
+                &tx.asset_symbol,
+                &tx.asset_name,
+                &tx.contract_address,
+                &tx.chain,
+                tx.chain_id,
+                &tx.gas_used,
+                &tx.gas_price,
+                tx.fee,
+                tx.status.as_str(),
+                tx.block_number,
+                &tx.timestamp,
+                &tx.created_at,
+                tx.nonce,
+                &tx.replaces_tx_hash,
+                &tx.replaced_by_tx_hash,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn evm_transaction_from_row(row: &rusqlite::Row<'_>) -> SqliteResult<EvmTransaction> {
+        Ok(EvmTransaction {
+            id: row.get(0)?,
+            wallet_id: row.get(1)?,
+            tx_hash: row.get(2)?,
+            tx_type: TransactionType::from_str(&row.get::<_, String>(3)?),
+            from_address: row.get(4)?,
+            to_address: row.get(5)?,
+            amount: row.get(6)?,
+            amount_float: row.get(7)?,
+            asset_symbol: row.get(8)?,
+            asset_name: row.get(9)?,
+            contract_address: row.get(10)?,
+            chain: row.get(11)?,
+            chain_id: row.get(12)?,
+            gas_used: row.get(13)?,
+            gas_price: row.get(14)?,
+            fee: row.get(15)?,
+            status: TransactionStatus::from_str(&row.get::<_, String>(16)?),
+            block_number: row.get(17)?,
+            timestamp: row.get(18)?,
+            created_at: row.get(19)?,
+            nonce: row.get(20)?,
+            replaces_tx_hash: row.get(21)?,
+            replaced_by_tx_hash: row.get(22)?,
+        })
+    }
+
+    /// The wallet's own row of a transaction; a transfer between two local
+    /// wallets has one row for each.
+    pub fn get_evm_transaction(
+        &self,
+        wallet_id: &str,
+        tx_hash: &str,
+        chain_id: u64,
+    ) -> SqliteResult<Option<EvmTransaction>> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row(
+            "SELECT id, wallet_id, tx_hash, tx_type, from_address, to_address, amount, amount_float, asset_symbol, asset_name, contract_address, chain, chain_id, gas_used, gas_price, fee, status, block_number, timestamp, created_at, nonce, replaces_tx_hash, replaced_by_tx_hash
+             FROM evm_transactions
+             WHERE wallet_id = ?1 AND tx_hash = ?2 AND chain_id = ?3
+             LIMIT 1",
+            params![wallet_id, tx_hash, chain_id],
+            Self::evm_transaction_from_row,
+        );
+
+        match result {
+            Ok(tx) => Ok(Some(tx)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn get_evm_transactions(&self, wallet_id: &str) -> SqliteResult<Vec<EvmTransaction>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, wallet_id, tx_hash, tx_type, from_address, to_address, amount, amount_float, asset_symbol, asset_name, contract_address, chain, chain_id, gas_used, gas_price, fee, status, block_number, timestamp, created_at, nonce, replaces_tx_hash, replaced_by_tx_hash
+             FROM evm_transactions
+             WHERE wallet_id = ?1
+             ORDER BY timestamp DESC",
+        )?;
+
+        let transactions = stmt.query_map(params![wallet_id], Self::evm_transaction_from_row)?;
+
+        let mut result = Vec::new();
+        for tx in transactions {
+            result.push(tx?);
+        }
+
+        Ok(result)
+    }
+
+    pub fn get_all_evm_transactions(&self) -> SqliteResult<Vec<EvmTransaction>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, wallet_id, tx_hash, tx_type, from_address, to_address, amount, amount_float, asset_symbol, asset_name, contract_address, chain, chain_id, gas_used, gas_price, fee, status, block_number, timestamp, created_at, nonce, replaces_tx_hash, replaced_by_tx_hash
+             FROM evm_transactions
+             ORDER BY timestamp DESC",
+        )?;
+
+        let transactions = stmt.query_map([], Self::evm_transaction_from_row)?;
+
+        let mut result = Vec::new();
+        for tx in transactions {
+            result.push(tx?);
+        }
+
+        Ok(result)
+    }
+
+    pub fn update_evm_transaction_lifecycle(
+        &self,
+        tx_hash: &str,
+        chain_id: u64,
+        status: TransactionStatus,
+        block_number: Option<u64>,
+    ) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "UPDATE evm_transactions SET status = ?1, block_number = ?2 WHERE tx_hash = ?3 AND chain_id = ?4",
+            params![status.as_str(), block_number, tx_hash, chain_id],
+        )?;
+
+        Ok(())
+    }
+
+    /// Hash, nonce and time first stored of this address's transactions that
+    /// are still in flight, lowest nonce first.
+    pub fn get_in_flight_evm_nonces(
+        &self,
+        from_address: &str,
+        chain_id: u64,
+    ) -> SqliteResult<Vec<(String, u64, String)>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT tx_hash, nonce, MIN(created_at) FROM evm_transactions
+             WHERE lower(from_address) = lower(?1) AND chain_id = ?2 AND status IN (?3, ?4)
+               AND nonce IS NOT NULL
+             GROUP BY tx_hash, nonce
+             ORDER BY nonce",
+        )?;
+        let rows = stmt.query_map(
+            params![
+                from_address,
+                chain_id,
+                TransactionStatus::Broadcasted.as_str(),
+                TransactionStatus::Pending.as_str()
+            ],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+
+        rows.collect()
+    }
+
+    /// Links a speed-up or cancel to the transaction it replaces. The original
+    /// stays in flight until one of them is mined.
+    fn mark_evm_transaction_replaced_by(
+        conn: &Connection,
+        original_tx_hash: &str,
+        replacement_tx_hash: &str,
+        chain_id: u64,
+    ) -> SqliteResult<()> {
+        conn.execute(
+            "UPDATE evm_transactions SET replaced_by_tx_hash = ?1 WHERE tx_hash = ?2 AND chain_id = ?3",
+            params![replacement_tx_hash, original_tx_hash, chain_id],
+        )?;
+
+        Ok(())
+    }
+
+    /// Marks the in-flight transactions that shared a nonce with a mined one
+    /// as replaced by it.
+    pub fn mark_evm_nonce_siblings_replaced(
+        &self,
+        from_address: &str,
+        chain_id: u64,
+        nonce: u64,
+        mined_tx_hash: &str,
+    ) -> SqliteResult<usize> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "UPDATE evm_transactions SET status = ?1, replaced_by_tx_hash = ?2
+             WHERE lower(from_address) = lower(?3) AND chain_id = ?4 AND nonce = ?5
+               AND tx_hash != ?2 AND status IN (?6, ?7)",
+            params![
+                TransactionStatus::Replaced.as_str(),
+                mined_tx_hash,
+                from_address,
+                chain_id,
+                nonce,
+                TransactionStatus::Broadcasted.as_str(),
+                TransactionStatus::Pending.as_str()
+            ],
+        )
+    }
+
+    pub fn update_evm_transaction_fee(
+        &self,
+        tx_hash: &str,
+        chain_id: u64,
+        gas_used: &str,
+        effective_gas_price: &str,
+        fee: f64,
+    ) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "UPDATE evm_transactions SET gas_used = ?1, gas_price = ?2, fee = ?3 WHERE tx_hash = ?4 AND chain_id = ?5",
+            params![gas_used, effective_gas_price, fee, tx_hash, chain_id],
+        )?;
+
+        Ok(())
+    }
+
+    // Dashboard Methods
+    pub fn get_dashboard_stats(
         &self,
     ) -> SqliteResult<Option<(f64, f64, f64, f64, String, String)>> {
         let conn = self.conn.lock().unwrap();
@@ -1362,7 +2837,8 @@ impl Database {
 
         let mut bitcoin_rows = Self::legacy_secret_rows(&conn, "bitcoin_wallet_secrets")?;
         let mut evm_rows = Self::legacy_secret_rows(&conn, "evm_wallet_secrets")?;
-        report.attempted_rows = bitcoin_rows.len() + evm_rows.len();
+        let mut backend_rows = Self::legacy_backend_password_rows(&conn)?;
+        report.attempted_rows = bitcoin_rows.len() + evm_rows.len() + backend_rows.len();
 
         if report.attempted_rows == 0 {
             return Ok(report);
@@ -1407,6 +2883,21 @@ impl Database {
             }
         }
 
+        for (backend_id, rpc_password) in backend_rows.drain(..) {
+            match secret_backend.prepare_encrypted_secret(&rpc_password) {
+                Ok(stored_secret) => {
+                    tx.execute(
+                        "UPDATE bitcoin_backends SET rpc_password = ?1, rpc_password_format = ?2 WHERE id = ?3",
+                        params![stored_secret.secret_data, stored_secret.secret_format, backend_id],
+                    )?;
+                    report.migrated_rows += 1;
+                }
+                Err(_) => {
+                    report.failed_rows += 1;
+                }
+            }
+        }
+
         tx.commit()?;
         report.skipped_rows = report.attempted_rows - report.migrated_rows - report.failed_rows;
         Ok(report)
@@ -1415,20 +2906,35 @@ impl Database {
 
 #[cfg(test)]
 mod tests {
-    use super::{Database, SecretMigrationReport};
+    use super::{BitcoinWalletSetup, Database, SecretMigrationReport};
+    use crate::wallet::bitcoin::config::BitcoinNetwork;
+    use crate::wallet::bitcoin::descriptor::{
+        BitcoinKeychain, BitcoinScriptType, WalletDescriptors,
+    };
+    use crate::wallet::bitcoin::multisig::{MultisigPolicy, MultisigScriptType};
     use crate::wallet::security::backend::{SecretBackend, SecretBackendAdapter};
     use crate::wallet::security::secret_envelope::{
         decrypt_secret, encrypt_secret, SecretEnvelopeError, StoredSecret,
         SECRET_FORMAT_KEYRING_AES256_GCM_V1, SECRET_FORMAT_PLAINTEXT_V0,
     };
     use crate::wallet::state::types::FreshnessStatus;
-    use crate::wallet::transaction_types::{EvmTransaction, TransactionStatus, TransactionType};
+    use crate::wallet::transaction_types::{
+        BitcoinTransaction, EvmTransaction, TransactionStatus, TransactionType,
+    };
+    use crate::wallet::types::{
+        BitcoinAddressInfo, BitcoinBackendKind, BitcoinLabelType, EvmCustomToken,
+        SaveBitcoinBackendRequest,
+    };
     use chrono::Utc;
     use rusqlite::{params, Connection};
     use std::sync::Arc;
     use uuid::Uuid;
 
     fn legacy_database() -> Database {
+        Database::from_connection(legacy_connection()).unwrap()
+    }
+
+    fn legacy_connection() -> Connection {
         let conn = Connection::open_in_memory().unwrap();
         Database::configure_connection(&conn).unwrap();
         conn.execute_batch(
@@ -1590,7 +3096,7 @@ mod tests {
         )
         .unwrap();
 
-        Database::from_connection(conn).unwrap()
+        conn
     }
 
     // M-LG-1
@@ -1636,6 +3142,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn legacy_btc_amounts_are_backfilled_as_satoshis() {
+        let conn = legacy_connection();
+        conn.execute(
+            "INSERT INTO bitcoin_transactions (
+                id, wallet_id, tx_hash, tx_type, from_address, to_address,
+                amount, fee, status, confirmations, block_height, timestamp, created_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            params![
+                "legacy-tx-row",
+                "btc-wallet-1",
+                "legacy-tx",
+                "receive",
+                "Unknown",
+                "bc1legacy",
+                0.00012345_f64,
+                0.0000015_f64,
+                "confirmed",
+                6_u32,
+                800_000_u32,
+                "2026-04-18T00:00:00Z",
+                "2026-04-18T00:00:00Z"
+            ],
+        )
+        .unwrap();
+
+        let db = Database::from_connection(conn).unwrap();
+
+        let wallet = db.get_bitcoin_wallet("btc-wallet-1").unwrap().unwrap();
+        assert_eq!(wallet.balance_sats, Some(150_000_000));
+        assert_eq!(wallet.balance, 1.5);
+
+        let tx = db
+            .get_bitcoin_transaction_by_hash("legacy-tx")
+            .unwrap()
+            .unwrap();
+        assert_eq!(tx.amount_sats, 12_345);
+        assert_eq!(tx.fee_sats, 150);
+        assert_eq!(tx.amount, 0.00012345);
+
+        db.update_bitcoin_wallet_balance("btc-wallet-1", 30_000_000)
+            .unwrap();
+        let wallet = db.get_bitcoin_wallet("btc-wallet-1").unwrap().unwrap();
+        assert_eq!(wallet.balance_sats, Some(30_000_000));
+        assert_eq!(wallet.balance, 0.3);
+    }
+
     // M-LG-2
     #[test]
     fn legacy_row_without_balance_updated_at_reads_as_cached() {
@@ -1761,113 +3314,698 @@ mod tests {
             encrypt_should_fail: true,
         }));
 
-        let report = db.run_secret_storage_migration(&backend).unwrap();
+        let report = db.run_secret_storage_migration(&backend).unwrap();
+
+        assert_eq!(report.attempted_rows, 2);
+        assert_eq!(report.migrated_rows, 0);
+        assert_eq!(report.skipped_rows, 2);
+        assert_eq!(report.failed_rows, 0);
+
+        let conn = db.conn.lock().unwrap();
+        let formats: Vec<String> = ["bitcoin_wallet_secrets", "evm_wallet_secrets"]
+            .iter()
+            .map(|table| {
+                conn.query_row(
+                    &format!("SELECT secret_format FROM {table} LIMIT 1"),
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap()
+            })
+            .collect();
+
+        assert!(formats
+            .iter()
+            .all(|format| format == SECRET_FORMAT_PLAINTEXT_V0));
+        drop(conn);
+
+        let bitcoin_secret = db
+            .load_bitcoin_wallet_secret("btc-wallet-1")
+            .unwrap()
+            .unwrap();
+        let evm_secret = db.load_evm_wallet_secret("evm-wallet-1").unwrap().unwrap();
+
+        assert_eq!(
+            decrypt_secret(&bitcoin_secret.0, &bitcoin_secret.2).unwrap(),
+            "seed words"
+        );
+        assert_eq!(
+            decrypt_secret(&evm_secret.0, &evm_secret.2).unwrap(),
+            "0xdeadbeef"
+        );
+    }
+
+    #[test]
+    fn migrates_legacy_secret_rows_to_encrypted_format() {
+        let db = legacy_database();
+
+        let conn = db.conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT INTO bitcoin_wallet_secrets (wallet_id, secret_data, secret_type, secret_format)
+             VALUES (?1, ?2, ?3, ?4)",
+            params!["btc-wallet-1", "seed words", "mnemonic", SECRET_FORMAT_PLAINTEXT_V0],
+        )
+        .unwrap();
+
+        conn.execute(
+            "INSERT INTO evm_wallet_secrets (wallet_id, secret_data, secret_type, secret_format)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                "evm-wallet-1",
+                "0xdeadbeef",
+                "private-key",
+                SECRET_FORMAT_PLAINTEXT_V0
+            ],
+        )
+        .unwrap();
+
+        drop(conn);
+
+        let backend = SecretBackend::with_adapter(Arc::new(StubAdapter {
+            encrypt_should_fail: false,
+        }));
+
+        let report: SecretMigrationReport = db.run_secret_storage_migration(&backend).unwrap();
+
+        assert_eq!(report.attempted_rows, 2);
+        assert_eq!(report.migrated_rows, 2);
+        assert_eq!(report.skipped_rows, 0);
+        assert_eq!(report.failed_rows, 0);
+
+        let conn = db.conn.lock().unwrap();
+        let bitcoin_secret: (String, String, String) = conn
+            .query_row(
+                "SELECT secret_data, secret_type, secret_format FROM bitcoin_wallet_secrets WHERE wallet_id = ?1",
+                params!["btc-wallet-1"],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        let evm_secret: (String, String, String) = conn
+            .query_row(
+                "SELECT secret_data, secret_type, secret_format FROM evm_wallet_secrets WHERE wallet_id = ?1",
+                params!["evm-wallet-1"],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+
+        assert_eq!(bitcoin_secret.2, SECRET_FORMAT_KEYRING_AES256_GCM_V1);
+        assert_eq!(evm_secret.2, SECRET_FORMAT_KEYRING_AES256_GCM_V1);
+        assert_eq!(
+            decrypt_secret(&bitcoin_secret.0, &bitcoin_secret.2).unwrap(),
+            "seed words"
+        );
+        assert_eq!(
+            decrypt_secret(&evm_secret.0, &evm_secret.2).unwrap(),
+            "0xdeadbeef"
+        );
+    }
+
+    #[test]
+    fn bitcoin_wallet_addresses_upsert_and_delete_with_wallet() {
+        let db = legacy_database();
+        db.set_bitcoin_wallet_descriptors(
+            "btc-wallet-1",
+            &WalletDescriptors {
+                external: "tr(xpub/0/*)".to_string(),
+                internal: Some("tr(xpub/1/*)".to_string()),
+            },
+        )
+        .unwrap();
+
+        let row = |index: u32, balance_sats: i64| BitcoinAddressInfo {
+            wallet_id: "btc-wallet-1".to_string(),
+            keychain: BitcoinKeychain::External,
+            derivation_index: index,
+            address: format!("bc1p-index-{index}"),
+            balance_sats,
+            tx_count: 1,
+            updated_at: "2026-04-18T00:00:00Z".to_string(),
+        };
+        db.upsert_bitcoin_wallet_addresses(&[row(0, 100), row(1, 200)])
+            .unwrap();
+        db.upsert_bitcoin_wallet_addresses(&[row(1, 50)]).unwrap();
+
+        let addresses = db.get_bitcoin_wallet_addresses("btc-wallet-1").unwrap();
+        assert_eq!(addresses.len(), 2);
+        assert_eq!(addresses[1].balance_sats, 50);
+        assert_eq!(
+            db.get_bitcoin_wallet_descriptors("btc-wallet-1")
+                .unwrap()
+                .unwrap()
+                .internal
+                .as_deref(),
+            Some("tr(xpub/1/*)")
+        );
+
+        assert!(db.delete_bitcoin_wallet("btc-wallet-1").unwrap());
+        assert!(db
+            .get_bitcoin_wallet_addresses("btc-wallet-1")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn bitcoin_utxo_freeze_and_label_are_independent() {
+        let db = legacy_database();
+        let outpoint = format!("{}:1", "ab".repeat(32));
+
+        db.set_bitcoin_utxo_label("btc-wallet-1", &outpoint, Some("cold storage"))
+            .unwrap();
+        db.set_bitcoin_utxo_frozen("btc-wallet-1", &outpoint, true)
+            .unwrap();
+
+        let metadata = db.get_bitcoin_utxo_metadata("btc-wallet-1").unwrap();
+        assert_eq!(metadata.len(), 1);
+        assert_eq!(metadata[0].label.as_deref(), Some("cold storage"));
+        assert!(metadata[0].frozen);
+        assert_eq!(
+            db.get_frozen_bitcoin_utxos("btc-wallet-1").unwrap(),
+            vec![outpoint.clone()]
+        );
+
+        db.set_bitcoin_utxo_frozen("btc-wallet-1", &outpoint, false)
+            .unwrap();
+        assert!(db
+            .get_frozen_bitcoin_utxos("btc-wallet-1")
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            db.get_bitcoin_utxo_metadata("btc-wallet-1").unwrap()[0]
+                .label
+                .as_deref(),
+            Some("cold storage")
+        );
+
+        assert!(db.delete_bitcoin_wallet("btc-wallet-1").unwrap());
+        assert!(db
+            .get_bitcoin_utxo_metadata("btc-wallet-1")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn bitcoin_backends_keep_order_and_saved_rpc_password() {
+        let db = legacy_database();
+        let request = |url: &str| SaveBitcoinBackendRequest {
+            id: None,
+            network: BitcoinNetwork::Mainnet,
+            kind: BitcoinBackendKind::BitcoinRpc,
+            url: url.to_string(),
+            rpc_username: Some("rpcuser".to_string()),
+            rpc_password: Some("rpcpass".to_string()),
+            priority: None,
+            enabled: true,
+        };
+
+        let password = StoredSecret {
+            secret_data: "encrypted-rpcpass".to_string(),
+            secret_format: SECRET_FORMAT_KEYRING_AES256_GCM_V1.to_string(),
+        };
+        let node = db
+            .save_bitcoin_backend(&request("http://node:8332"), Some(&password))
+            .unwrap();
+        let backup = db
+            .save_bitcoin_backend(&request("http://backup:8332"), Some(&password))
+            .unwrap();
+        assert_eq!((node.priority, backup.priority), (0, 1));
+
+        let updated = db
+            .save_bitcoin_backend(
+                &SaveBitcoinBackendRequest {
+                    id: Some(node.id.clone()),
+                    url: "http://node:18332".to_string(),
+                    rpc_password: None,
+                    enabled: false,
+                    ..request("")
+                },
+                None,
+            )
+            .unwrap();
+        assert_eq!(updated.url, "http://node:18332");
+        assert_eq!(updated.priority, 0);
+        assert_eq!(updated.rpc_password, None);
+        assert!(updated.has_rpc_password);
+        assert_eq!(
+            db.get_bitcoin_backend_password(&node.id).unwrap(),
+            Some(password)
+        );
+        assert!(!updated.enabled);
+        assert!(serde_json::to_value(&updated)
+            .unwrap()
+            .get("rpc_password")
+            .is_none());
+
+        assert_eq!(
+            db.get_bitcoin_backends(Some(BitcoinNetwork::Mainnet))
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(db
+            .get_bitcoin_backends(Some(BitcoinNetwork::Testnet))
+            .unwrap()
+            .is_empty());
+        assert!(db.delete_bitcoin_backend(&backup.id).unwrap());
+        assert_eq!(db.get_bitcoin_backends(None).unwrap(), vec![updated]);
+    }
+
+    #[test]
+    fn plaintext_backend_passwords_are_migrated_to_the_secret_backend() {
+        let db = legacy_database();
+        let backend = db
+            .save_bitcoin_backend(
+                &SaveBitcoinBackendRequest {
+                    id: None,
+                    network: BitcoinNetwork::Mainnet,
+                    kind: BitcoinBackendKind::BitcoinRpc,
+                    url: "http://node:8332".to_string(),
+                    rpc_username: Some("rpcuser".to_string()),
+                    rpc_password: None,
+                    priority: None,
+                    enabled: true,
+                },
+                None,
+            )
+            .unwrap();
+        db.conn
+            .lock()
+            .unwrap()
+            .execute(
+                "UPDATE bitcoin_backends SET rpc_password = 'rpcpass' WHERE id = ?1",
+                params![backend.id],
+            )
+            .unwrap();
+        assert_eq!(
+            db.get_bitcoin_backend_password(&backend.id)
+                .unwrap()
+                .unwrap()
+                .secret_format,
+            SECRET_FORMAT_PLAINTEXT_V0
+        );
+
+        let secret_backend = SecretBackend::with_adapter(Arc::new(StubAdapter {
+            encrypt_should_fail: false,
+        }));
+        let report = db.run_secret_storage_migration(&secret_backend).unwrap();
+        assert_eq!(report.migrated_rows, 1);
+
+        let migrated = db
+            .get_bitcoin_backend_password(&backend.id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(migrated.secret_format, SECRET_FORMAT_KEYRING_AES256_GCM_V1);
+        assert_eq!(
+            decrypt_secret(&migrated.secret_data, &migrated.secret_format).unwrap(),
+            "rpcpass"
+        );
+    }
+
+    #[test]
+    fn bitcoin_wallet_script_type_defaults_to_taproot_for_legacy_rows() {
+        let db = legacy_database();
+
+        assert_eq!(
+            db.get_bitcoin_wallet_script_type("btc-wallet-1").unwrap(),
+            BitcoinScriptType::P2tr
+        );
+
+        db.set_bitcoin_wallet_script_type("btc-wallet-1", BitcoinScriptType::P2shP2wpkh)
+            .unwrap();
+        assert_eq!(
+            db.get_bitcoin_wallet_script_type("btc-wallet-1").unwrap(),
+            BitcoinScriptType::P2shP2wpkh
+        );
+    }
+
+    #[test]
+    fn bitcoin_wallet_network_defaults_to_mainnet_for_legacy_rows() {
+        let db = legacy_database();
+
+        assert_eq!(
+            db.get_bitcoin_wallet_network("btc-wallet-1").unwrap(),
+            BitcoinNetwork::Mainnet
+        );
+
+        db.set_bitcoin_wallet_network("btc-wallet-1", BitcoinNetwork::Regtest)
+            .unwrap();
+        assert_eq!(
+            db.get_bitcoin_wallet_network("btc-wallet-1").unwrap(),
+            BitcoinNetwork::Regtest
+        );
+    }
 
-        assert_eq!(report.attempted_rows, 2);
-        assert_eq!(report.migrated_rows, 0);
-        assert_eq!(report.skipped_rows, 2);
-        assert_eq!(report.failed_rows, 0);
+    #[test]
+    fn bitcoin_wallet_multisig_policy_is_absent_for_single_key_rows() {
+        let db = legacy_database();
+        let policy = MultisigPolicy {
+            threshold: 2,
+            cosigners: 3,
+            script_type: MultisigScriptType::P2tr,
+        };
 
-        let conn = db.conn.lock().unwrap();
-        let formats: Vec<String> = ["bitcoin_wallet_secrets", "evm_wallet_secrets"]
-            .iter()
-            .map(|table| {
-                conn.query_row(
-                    &format!("SELECT secret_format FROM {table} LIMIT 1"),
-                    [],
-                    |row| row.get(0),
-                )
-                .unwrap()
-            })
-            .collect();
+        assert_eq!(
+            db.get_bitcoin_wallet_multisig("btc-wallet-1").unwrap(),
+            None
+        );
 
-        assert!(formats
-            .iter()
-            .all(|format| format == SECRET_FORMAT_PLAINTEXT_V0));
-        drop(conn);
+        db.set_bitcoin_wallet_multisig("btc-wallet-1", &policy)
+            .unwrap();
+        assert_eq!(
+            db.get_bitcoin_wallet_multisig("btc-wallet-1").unwrap(),
+            Some(policy)
+        );
+        assert_eq!(db.get_bitcoin_wallet_multisig("missing").unwrap(), None);
+    }
 
-        let bitcoin_secret = db
-            .load_bitcoin_wallet_secret("btc-wallet-1")
-            .unwrap()
+    #[test]
+    fn bitcoin_wallet_setup_is_saved_with_the_wallet_row() {
+        let db = legacy_database();
+        let policy = MultisigPolicy {
+            threshold: 2,
+            cosigners: 3,
+            script_type: MultisigScriptType::P2wsh,
+        };
+        let setup = BitcoinWalletSetup {
+            script_type: BitcoinScriptType::P2wpkh,
+            network: BitcoinNetwork::Testnet,
+            descriptors: WalletDescriptors {
+                external: "wsh(sortedmulti(2,a,b,c))".to_string(),
+                internal: Some("wsh(sortedmulti(2,d,e,f))".to_string()),
+            },
+            multisig: Some(policy),
+        };
+
+        let wallet = db
+            .insert_bitcoin_wallet_with_setup(
+                "Vault".to_string(),
+                "watch-only".to_string(),
+                "tb1qvault".to_string(),
+                None,
+                &setup,
+            )
             .unwrap();
-        let evm_secret = db.load_evm_wallet_secret("evm-wallet-1").unwrap().unwrap();
 
         assert_eq!(
-            decrypt_secret(&bitcoin_secret.0, &bitcoin_secret.2).unwrap(),
-            "seed words"
+            db.get_bitcoin_wallet_script_type(&wallet.id).unwrap(),
+            BitcoinScriptType::P2wpkh
         );
         assert_eq!(
-            decrypt_secret(&evm_secret.0, &evm_secret.2).unwrap(),
-            "0xdeadbeef"
+            db.get_bitcoin_wallet_network(&wallet.id).unwrap(),
+            BitcoinNetwork::Testnet
+        );
+        assert_eq!(
+            db.get_bitcoin_wallet_descriptors(&wallet.id).unwrap(),
+            Some(setup.descriptors.clone())
+        );
+        assert_eq!(
+            db.get_bitcoin_wallet_multisig(&wallet.id).unwrap(),
+            Some(policy)
         );
     }
 
     #[test]
-    fn migrates_legacy_secret_rows_to_encrypted_format() {
+    fn bitcoin_replacement_links_survive_history_rewrites() {
         let db = legacy_database();
+        let tx = |tx_hash: &str, replaces: Option<&str>| BitcoinTransaction {
+            id: format!("id-{tx_hash}"),
+            wallet_id: "btc-wallet-1".to_string(),
+            tx_hash: tx_hash.to_string(),
+            tx_type: TransactionType::Send,
+            from_address: "bc1ptestaddress".to_string(),
+            to_address: "bc1precipient".to_string(),
+            amount_sats: 100_000,
+            fee_sats: 1_000,
+            amount: 0.001,
+            fee: 0.00001,
+            status: TransactionStatus::Broadcasted,
+            confirmations: 0,
+            block_height: None,
+            timestamp: "2026-04-18T00:00:00Z".to_string(),
+            created_at: "2026-04-18T00:00:00Z".to_string(),
+            replaces_tx_hash: replaces.map(str::to_string),
+            replaced_by_tx_hash: None,
+            parent_tx_hash: None,
+            batch_id: None,
+            output_index: 0,
+            label: None,
+        };
 
-        let conn = db.conn.lock().unwrap();
+        db.add_bitcoin_transaction(&tx("original", None)).unwrap();
+        // Another wallet of this device sees the same transaction.
+        let other = db
+            .insert_bitcoin_watch_only_wallet("Watcher".to_string(), "bc1pwatcher".to_string())
+            .unwrap();
+        let mut shared = tx("original", None);
+        shared.id = "id-shared".to_string();
+        shared.wallet_id = other.id.clone();
+        db.add_bitcoin_transaction(&shared).unwrap();
+        db.record_bitcoin_replacement("original", &[tx("replacement", Some("original"))])
+            .unwrap();
 
-        conn.execute(
-            "INSERT INTO bitcoin_wallet_secrets (wallet_id, secret_data, secret_type, secret_format)
-             VALUES (?1, ?2, ?3, ?4)",
-            params!["btc-wallet-1", "seed words", "mnemonic", SECRET_FORMAT_PLAINTEXT_V0],
+        // A history refresh rewrites rows without link information, and may
+        // still report the original as pending.
+        db.add_bitcoin_transaction(&tx("replacement", None))
+            .unwrap();
+        db.add_bitcoin_transaction(&tx("original", None)).unwrap();
+
+        let original = db
+            .get_bitcoin_transactions("btc-wallet-1")
+            .unwrap()
+            .into_iter()
+            .find(|row| row.tx_hash == "original")
+            .unwrap();
+        let replacement = db
+            .get_bitcoin_transaction_by_hash("replacement")
+            .unwrap()
+            .unwrap();
+        assert_eq!(original.status, TransactionStatus::Replaced);
+        assert_eq!(original.replaced_by_tx_hash.as_deref(), Some("replacement"));
+        assert_eq!(replacement.replaces_tx_hash.as_deref(), Some("original"));
+        let shared = db
+            .get_bitcoin_transactions(&other.id)
+            .unwrap()
+            .remove(0);
+        assert_eq!(shared.status, TransactionStatus::Broadcasted);
+        assert!(shared.replaced_by_tx_hash.is_none());
+        assert!(db
+            .get_bitcoin_transaction_by_hash("missing")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn bitcoin_tx_labels_join_history() {
+        let db = legacy_database();
+        db.add_bitcoin_transaction(&BitcoinTransaction {
+            id: "labelled".to_string(),
+            wallet_id: "btc-wallet-1".to_string(),
+            tx_hash: "labelled-tx".to_string(),
+            tx_type: TransactionType::Receive,
+            from_address: "bc1psender".to_string(),
+            to_address: "bc1ptestaddress".to_string(),
+            amount_sats: 100_000,
+            fee_sats: 0,
+            amount: 0.001,
+            fee: 0.0,
+            status: TransactionStatus::Confirmed,
+            confirmations: 1,
+            block_height: Some(100),
+            timestamp: "2026-04-18T00:00:00Z".to_string(),
+            created_at: "2026-04-18T00:00:00Z".to_string(),
+            replaces_tx_hash: None,
+            replaced_by_tx_hash: None,
+            parent_tx_hash: None,
+            batch_id: None,
+            output_index: 0,
+            label: None,
+        })
+        .unwrap();
+
+        db.set_bitcoin_label(
+            "btc-wallet-1",
+            BitcoinLabelType::Tx,
+            "labelled-tx",
+            Some("Salary"),
+            None,
+        )
+        .unwrap();
+        db.set_bitcoin_label(
+            "btc-wallet-1",
+            BitcoinLabelType::Addr,
+            "bc1ptestaddress",
+            Some("Main"),
+            None,
         )
         .unwrap();
 
-        conn.execute(
-            "INSERT INTO evm_wallet_secrets (wallet_id, secret_data, secret_type, secret_format)
-             VALUES (?1, ?2, ?3, ?4)",
-            params![
-                "evm-wallet-1",
-                "0xdeadbeef",
-                "private-key",
-                SECRET_FORMAT_PLAINTEXT_V0
-            ],
+        let history = db.get_bitcoin_transactions("btc-wallet-1").unwrap();
+        assert_eq!(history[0].label.as_deref(), Some("Salary"));
+        assert_eq!(db.get_bitcoin_labels("btc-wallet-1").unwrap().len(), 2);
+
+        db.set_bitcoin_label(
+            "btc-wallet-1",
+            BitcoinLabelType::Tx,
+            "labelled-tx",
+            None,
+            None,
         )
         .unwrap();
+        assert!(db.get_bitcoin_transactions("btc-wallet-1").unwrap()[0]
+            .label
+            .is_none());
+        assert_eq!(db.get_bitcoin_labels("btc-wallet-1").unwrap().len(), 1);
+    }
 
-        drop(conn);
+    #[test]
+    fn legacy_bitcoin_transactions_accept_batch_outputs() {
+        let db = legacy_database();
+        let wallet = db
+            .insert_bitcoin_watch_only_wallet("Batch".to_string(), "bc1pbatch".to_string())
+            .unwrap();
+        let output = |output_index: u32| BitcoinTransaction {
+            id: format!("batch-row-{output_index}"),
+            wallet_id: wallet.id.clone(),
+            tx_hash: "batch".to_string(),
+            tx_type: TransactionType::Send,
+            from_address: "bc1pbatch".to_string(),
+            to_address: format!("bc1precipient{output_index}"),
+            amount_sats: 100_000,
+            fee_sats: 0,
+            amount: 0.001,
+            fee: 0.0,
+            status: TransactionStatus::Broadcasted,
+            confirmations: 0,
+            block_height: None,
+            timestamp: "2026-05-01T00:00:00Z".to_string(),
+            created_at: "2026-05-01T00:00:00Z".to_string(),
+            replaces_tx_hash: None,
+            replaced_by_tx_hash: None,
+            parent_tx_hash: None,
+            batch_id: Some("batch-1".to_string()),
+            output_index,
+            label: None,
+        };
 
-        let backend = SecretBackend::with_adapter(Arc::new(StubAdapter {
-            encrypt_should_fail: false,
-        }));
+        db.add_bitcoin_transaction(&output(0)).unwrap();
+        db.add_bitcoin_transaction(&output(1)).unwrap();
 
-        let report: SecretMigrationReport = db.run_secret_storage_migration(&backend).unwrap();
+        let rows = db.get_bitcoin_transactions(&wallet.id).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].to_address, "bc1precipient1");
+        assert_eq!(
+            db.get_bitcoin_transaction_by_hash("batch")
+                .unwrap()
+                .unwrap()
+                .output_index,
+            0
+        );
+    }
 
-        assert_eq!(report.attempted_rows, 2);
-        assert_eq!(report.migrated_rows, 2);
-        assert_eq!(report.skipped_rows, 0);
-        assert_eq!(report.failed_rows, 0);
+    #[test]
+    fn local_receiver_keeps_its_own_row_for_a_local_batch() {
+        let db = Database::new(":memory:").unwrap();
+        let sender = db
+            .insert_bitcoin_watch_only_wallet("Sender".to_string(), "bc1psender".to_string())
+            .unwrap();
+        let receiver = db
+            .insert_bitcoin_watch_only_wallet("Receiver".to_string(), "bc1preceiver".to_string())
+            .unwrap();
+        let row = |wallet_id: &str, tx_type: TransactionType, batch_id: Option<&str>| {
+            BitcoinTransaction {
+                id: Uuid::new_v4().to_string(),
+                wallet_id: wallet_id.to_string(),
+                tx_hash: "shared-batch".to_string(),
+                tx_type,
+                from_address: "bc1psender".to_string(),
+                to_address: "bc1preceiver".to_string(),
+                amount_sats: 50_000,
+                fee_sats: 0,
+                amount: 0.0005,
+                fee: 0.0,
+                status: TransactionStatus::Confirmed,
+                confirmations: 3,
+                block_height: Some(900_000),
+                timestamp: "2026-05-01T00:00:00Z".to_string(),
+                created_at: "2026-05-01T00:00:00Z".to_string(),
+                replaces_tx_hash: None,
+                replaced_by_tx_hash: None,
+                parent_tx_hash: None,
+                batch_id: batch_id.map(str::to_string),
+                output_index: 0,
+                label: None,
+            }
+        };
 
-        let conn = db.conn.lock().unwrap();
-        let bitcoin_secret: (String, String, String) = conn
-            .query_row(
-                "SELECT secret_data, secret_type, secret_format FROM bitcoin_wallet_secrets WHERE wallet_id = ?1",
-                params!["btc-wallet-1"],
-                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
-            )
+        db.add_bitcoin_transaction(&row(&sender.id, TransactionType::Send, Some("batch-1")))
             .unwrap();
-        let evm_secret: (String, String, String) = conn
-            .query_row(
-                "SELECT secret_data, secret_type, secret_format FROM evm_wallet_secrets WHERE wallet_id = ?1",
-                params!["evm-wallet-1"],
-                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
-            )
+        db.add_bitcoin_transaction(&row(&receiver.id, TransactionType::Receive, None))
             .unwrap();
 
-        assert_eq!(bitcoin_secret.2, SECRET_FORMAT_KEYRING_AES256_GCM_V1);
-        assert_eq!(evm_secret.2, SECRET_FORMAT_KEYRING_AES256_GCM_V1);
-        assert_eq!(
-            decrypt_secret(&bitcoin_secret.0, &bitcoin_secret.2).unwrap(),
-            "seed words"
-        );
+        let received = db.get_bitcoin_transactions(&receiver.id).unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].tx_type, TransactionType::Receive);
+        let sent = db.get_bitcoin_transactions(&sender.id).unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].batch_id.as_deref(), Some("batch-1"));
         assert_eq!(
-            decrypt_secret(&evm_secret.0, &evm_secret.2).unwrap(),
-            "0xdeadbeef"
+            db.get_bitcoin_transaction_by_hash("shared-batch")
+                .unwrap()
+                .unwrap()
+                .wallet_id,
+            sender.id
         );
     }
 
+    #[test]
+    fn resolved_sender_lookups_survive_history_refreshes() {
+        let db = Database::new(":memory:").unwrap();
+        let wallet = db
+            .insert_bitcoin_watch_only_wallet("Receiver".to_string(), "bc1preceiver".to_string())
+            .unwrap();
+        let row = BitcoinTransaction {
+            id: Uuid::new_v4().to_string(),
+            wallet_id: wallet.id.clone(),
+            tx_hash: "unknown-sender".to_string(),
+            tx_type: TransactionType::Receive,
+            from_address: "Unknown".to_string(),
+            to_address: "bc1preceiver".to_string(),
+            amount_sats: 50_000,
+            fee_sats: 0,
+            amount: 0.0005,
+            fee: 0.0,
+            status: TransactionStatus::Pending,
+            confirmations: 0,
+            block_height: None,
+            timestamp: "2026-05-01T00:00:00Z".to_string(),
+            created_at: "2026-05-01T00:00:00Z".to_string(),
+            replaces_tx_hash: None,
+            replaced_by_tx_hash: None,
+            parent_tx_hash: None,
+            batch_id: None,
+            output_index: 0,
+            label: None,
+        };
+
+        db.add_bitcoin_transaction(&row).unwrap();
+        assert!(!db
+            .is_bitcoin_sender_resolved(&wallet.id, "unknown-sender")
+            .unwrap());
+
+        db.mark_bitcoin_sender_resolved(&wallet.id, "unknown-sender")
+            .unwrap();
+        db.add_bitcoin_transaction(&BitcoinTransaction {
+            id: Uuid::new_v4().to_string(),
+            status: TransactionStatus::Confirmed,
+            confirmations: 1,
+            ..row
+        })
+        .unwrap();
+        assert!(db
+            .is_bitcoin_sender_resolved(&wallet.id, "unknown-sender")
+            .unwrap());
+    }
+
     #[test]
     fn update_evm_transaction_lifecycle_persists_terminal_status_and_block() {
         let db = Database::new(":memory:").unwrap();
@@ -1905,6 +4043,9 @@ mod tests {
             block_number: None,
             timestamp: Utc::now().to_rfc3339(),
             created_at: Utc::now().to_rfc3339(),
+            nonce: None,
+            replaces_tx_hash: None,
+            replaced_by_tx_hash: None,
         })
         .unwrap();
 
@@ -1920,4 +4061,147 @@ mod tests {
         assert_eq!(tx.status, TransactionStatus::Confirmed);
         assert_eq!(tx.block_number, Some(123));
     }
+
+    #[test]
+    fn update_evm_transaction_fee_persists_the_receipt_fee() {
+        let db = Database::new(":memory:").unwrap();
+        let wallet = db
+            .insert_evm_wallet_with_secret(
+                "Main".to_string(),
+                "private-key".to_string(),
+                "0xabc".to_string(),
+                StoredSecret {
+                    secret_data: "test-secret".to_string(),
+                    secret_format: SECRET_FORMAT_PLAINTEXT_V0.to_string(),
+                },
+                "private-key".to_string(),
+            )
+            .unwrap();
+
+        db.add_evm_transaction(&EvmTransaction {
+            id: Uuid::new_v4().to_string(),
+            wallet_id: wallet.id,
+            tx_hash: "0xtx".to_string(),
+            tx_type: TransactionType::Send,
+            from_address: "0xfrom".to_string(),
+            to_address: "0xto".to_string(),
+            amount: "1".to_string(),
+            amount_float: 1.0,
+            asset_symbol: "ETH".to_string(),
+            asset_name: "Ethereum".to_string(),
+            contract_address: None,
+            chain: "sepolia".to_string(),
+            chain_id: 11155111,
+            gas_used: "21000".to_string(),
+            gas_price: "1".to_string(),
+            fee: 0.0,
+            status: TransactionStatus::Broadcasted,
+            block_number: None,
+            timestamp: Utc::now().to_rfc3339(),
+            created_at: Utc::now().to_rfc3339(),
+            nonce: None,
+            replaces_tx_hash: None,
+            replaced_by_tx_hash: None,
+        })
+        .unwrap();
+
+        db.update_evm_transaction_fee("0xtx", 11155111, "21000", "1500000000", 0.0000315)
+            .unwrap();
+
+        let tx = db.get_all_evm_transactions().unwrap().pop().unwrap();
+        assert_eq!(tx.gas_used, "21000");
+        assert_eq!(tx.gas_price, "1500000000");
+        assert_eq!(tx.fee, 0.0000315);
+        assert_eq!(tx.status, TransactionStatus::Broadcasted);
+    }
+
+    #[test]
+    fn evm_replacement_is_stored_with_the_link_to_its_original() {
+        let db = Database::new(":memory:").unwrap();
+        let wallet = db
+            .insert_evm_wallet_with_secret(
+                "Main".to_string(),
+                "private-key".to_string(),
+                "0xabc".to_string(),
+                StoredSecret {
+                    secret_data: "test-secret".to_string(),
+                    secret_format: SECRET_FORMAT_PLAINTEXT_V0.to_string(),
+                },
+                "private-key".to_string(),
+            )
+            .unwrap();
+        let tx = |tx_hash: &str, replaces: Option<&str>| EvmTransaction {
+            id: Uuid::new_v4().to_string(),
+            wallet_id: wallet.id.clone(),
+            tx_hash: tx_hash.to_string(),
+            tx_type: TransactionType::Send,
+            from_address: "0xabc".to_string(),
+            to_address: "0xto".to_string(),
+            amount: "1".to_string(),
+            amount_float: 1.0,
+            asset_symbol: "ETH".to_string(),
+            asset_name: "Ethereum".to_string(),
+            contract_address: None,
+            chain: "sepolia".to_string(),
+            chain_id: 11155111,
+            gas_used: "21000".to_string(),
+            gas_price: "1".to_string(),
+            fee: 0.0,
+            status: TransactionStatus::Broadcasted,
+            block_number: None,
+            timestamp: Utc::now().to_rfc3339(),
+            created_at: Utc::now().to_rfc3339(),
+            nonce: Some(4),
+            replaces_tx_hash: replaces.map(str::to_string),
+            replaced_by_tx_hash: None,
+        };
+
+        db.add_evm_transaction(&tx("0xoriginal", None)).unwrap();
+        db.record_evm_replacement("0xoriginal", &tx("0xspeedup", Some("0xoriginal")))
+            .unwrap();
+
+        let original = db
+            .get_evm_transaction(&wallet.id, "0xoriginal", 11155111)
+            .unwrap()
+            .unwrap();
+        let replacement = db
+            .get_evm_transaction(&wallet.id, "0xspeedup", 11155111)
+            .unwrap()
+            .unwrap();
+        assert_eq!(original.replaced_by_tx_hash.as_deref(), Some("0xspeedup"));
+        assert_eq!(replacement.replaces_tx_hash.as_deref(), Some("0xoriginal"));
+    }
+
+    #[test]
+    fn evm_custom_tokens_upsert_by_contract_and_delete_case_insensitively() {
+        let db = Database::new(":memory:").unwrap();
+        let token = EvmCustomToken {
+            id: "token-1".to_string(),
+            chain_id: 1,
+            contract_address: "0x6B175474E89094C44Da98b954EedeAC495271d0F".to_string(),
+            symbol: "DAI".to_string(),
+            name: "Dai".to_string(),
+            decimals: 18,
+            created_at: "2026-05-01T00:00:00Z".to_string(),
+        };
+
+        db.save_evm_custom_token(&token).unwrap();
+        db.save_evm_custom_token(&EvmCustomToken {
+            id: "token-2".to_string(),
+            name: "Dai Stablecoin".to_string(),
+            ..token.clone()
+        })
+        .unwrap();
+
+        let tokens = db.get_evm_custom_tokens(Some(1)).unwrap();
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].id, "token-1");
+        assert_eq!(tokens[0].name, "Dai Stablecoin");
+        assert!(db.get_evm_custom_tokens(Some(10)).unwrap().is_empty());
+
+        assert!(db
+            .delete_evm_custom_token(1, "0x6b175474e89094c44da98b954eedeac495271d0f")
+            .unwrap());
+        assert!(db.get_evm_custom_tokens(None).unwrap().is_empty());
+    }
 }
@@ -11,8 +11,8 @@ use std::time::Duration;
 use std::{env, fs, path::Path, path::PathBuf, time::SystemTime};
 use tauri_plugin_window_state::Builder as WindowStatePlugin;
 use wallet::bitcoin::{
-    commands as bitcoin_commands, mnemonic as bitcoin_mnemonic, private_key as bitcoin_private_key,
-    wallet as bitcoin_wallet,
+    commands as bitcoin_commands, message as bitcoin_message, mnemonic as bitcoin_mnemonic,
+    private_key as bitcoin_private_key, wallet as bitcoin_wallet,
 };
 use wallet::evm::{
     commands as evm_commands, mnemonic as evm_mnemonic, private_key as evm_private_key,
@@ -76,6 +76,14 @@ fn stable_db_path(debug: bool) -> PathBuf {
     app_data_dir().join("aiigo_desktop").join(file_name)
 }
 
+/// Local BDK wallet state, one file per wallet next to the main database.
+#[cfg(not(test))]
+pub(crate) fn bdk_store_dir() -> PathBuf {
+    let debug = cfg!(debug_assertions);
+    let dir_name = if debug { "aiigo_debug_bdk" } else { "bdk_wallets" };
+    stable_db_path(debug).with_file_name(dir_name)
+}
+
 fn app_data_dir() -> PathBuf {
     #[cfg(target_os = "macos")]
     {
@@ -235,11 +243,21 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(WindowStatePlugin::default().build())
         .setup(|_app| {
+            // Register user-defined EVM networks before anything looks up a chain
+            if let Err(error) = wallet::evm::networks::reload_custom_networks() {
+                tracing::warn!(%error, "failed to load custom EVM networks");
+            }
+
             // Initialize price manager with background refresh
             tauri::async_runtime::spawn(async move {
                 wallet::evm::price_manager::start_background_refresh().await;
             });
 
+            // Keep local Bitcoin wallet state synced in the background
+            tauri::async_runtime::spawn(async move {
+                wallet::bitcoin::local_state::start_background_sync().await;
+            });
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -247,13 +265,30 @@ pub fn run() {
             bitcoin_mnemonic::bitcoin_create_mnemonic,
             bitcoin_mnemonic::bitcoin_import_mnemonic,
             bitcoin_wallet::bitcoin_create_wallet_from_mnemonic,
+            bitcoin_wallet::bitcoin_import_watch_only_wallet,
+            bitcoin_wallet::bitcoin_create_multisig_wallet,
             bitcoin_private_key::bitcoin_create_wallet_from_private_key,
             bitcoin_private_key::bitcoin_export_mnemonic,
             bitcoin_private_key::bitcoin_export_private_key,
+            bitcoin_message::bitcoin_sign_message,
+            bitcoin_message::bitcoin_verify_message,
             bitcoin_commands::bitcoin_get_wallets,
             bitcoin_commands::bitcoin_get_wallet,
             bitcoin_commands::query_bitcoin_wallet_balance,
             bitcoin_commands::refresh_bitcoin_wallet_balance,
+            bitcoin_commands::bitcoin_get_wallet_addresses,
+            bitcoin_commands::bitcoin_get_next_receive_address,
+            bitcoin_commands::bitcoin_list_utxos,
+            bitcoin_commands::bitcoin_set_utxo_frozen,
+            bitcoin_commands::bitcoin_set_utxo_label,
+            bitcoin_commands::bitcoin_set_label,
+            bitcoin_commands::bitcoin_get_labels,
+            bitcoin_commands::bitcoin_import_labels,
+            bitcoin_commands::bitcoin_export_labels,
+            bitcoin_commands::bitcoin_get_backends,
+            bitcoin_commands::bitcoin_save_backend,
+            bitcoin_commands::bitcoin_delete_backend,
+            bitcoin_commands::bitcoin_test_backend,
             bitcoin_commands::bitcoin_delete_wallet,
             // EVM handlers
             evm_mnemonic::evm_create_mnemonic,
@@ -267,12 +302,31 @@ pub fn run() {
             evm_commands::query_evm_wallet_balances,
             evm_commands::refresh_evm_wallet_balances,
             evm_commands::evm_delete_wallet,
+            evm_commands::evm_add_custom_token,
+            evm_commands::evm_remove_custom_token,
+            evm_commands::evm_get_custom_tokens,
+            evm_commands::evm_get_networks,
+            evm_commands::evm_save_network,
+            evm_commands::evm_delete_network,
             // Transaction handlers
             transaction_commands::send_bitcoin,
+            transaction_commands::send_bitcoin_batch,
+            transaction_commands::bitcoin_bump_fee,
+            transaction_commands::bitcoin_accelerate_transaction,
+            transaction_commands::bitcoin_preview_send,
+            transaction_commands::bitcoin_preview_batch,
+            transaction_commands::bitcoin_create_psbt,
+            transaction_commands::bitcoin_sign_psbt,
+            transaction_commands::bitcoin_combine_psbts,
+            transaction_commands::bitcoin_broadcast_psbt,
+            transaction_commands::bitcoin_parse_payment_uri,
+            transaction_commands::bitcoin_create_payment_uri,
             transaction_commands::get_bitcoin_transactions,
             transaction_commands::get_all_bitcoin_transactions,
             transaction_commands::fetch_bitcoin_history,
             transaction_commands::bitcoin_estimate_fees,
+            transaction_commands::bitcoin_estimate_fee_for_target,
+            transaction_commands::bitcoin_preview_send_fee,
             transaction_commands::send_evm,
             transaction_commands::evm_estimate_gas,
             transaction_commands::get_evm_transactions,
@@ -281,6 +335,8 @@ pub fn run() {
             transaction_commands::get_supported_evm_history_chains,
             transaction_commands::evm_send_transaction,
             transaction_commands::evm_approve_token,
+            transaction_commands::evm_speed_up_transaction,
+            transaction_commands::evm_cancel_transaction,
             transaction_commands::refresh_evm_transaction_lifecycle,
             // Dashboard handlers
             dashboard::commands::get_dashboard_stats,
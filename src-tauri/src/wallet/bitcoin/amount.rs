@@ -0,0 +1,31 @@
+/// Satoshis in one bitcoin
+pub const SATS_PER_BTC: u64 = 100_000_000;
+
+/// BTC value of `sats` for display. Amounts are kept in satoshis everywhere
+/// else; this is only for the float fields shown to users.
+pub fn sats_to_btc(sats: u64) -> f64 {
+    sats as f64 / SATS_PER_BTC as f64
+}
+
+/// Satoshis of a BTC amount reported by an external API as a JSON number.
+pub(crate) fn btc_to_sats(amount: f64) -> u64 {
+    (amount * SATS_PER_BTC as f64).round() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{btc_to_sats, sats_to_btc};
+
+    #[test]
+    fn converts_without_drifting() {
+        // 0.1 + 0.2 style inputs must land on the exact satoshi.
+        assert_eq!(btc_to_sats(0.000_000_07), 7);
+        assert_eq!(btc_to_sats(0.1 + 0.2), 30_000_000);
+        assert_eq!(btc_to_sats(20_999_999.976_9), 2_099_999_997_690_000);
+        assert_eq!(sats_to_btc(12_345), 0.00012345);
+        assert_eq!(
+            btc_to_sats(sats_to_btc(2_099_999_997_690_000)),
+            2_099_999_997_690_000
+        );
+    }
+}
@@ -0,0 +1,1553 @@
+use crate::wallet::bitcoin::amount::btc_to_sats;
+use crate::wallet::bitcoin::balance::AddressStats;
+use crate::wallet::bitcoin::config::BitcoinNetwork;
+use crate::wallet::bitcoin::descriptor::ADDRESS_GAP_LIMIT;
+use crate::wallet::security::secret_envelope::decrypt_secret;
+use crate::wallet::types::{BitcoinBackendConfig, BitcoinBackendKind, SaveBitcoinBackendRequest};
+use crate::DB;
+use bdk::bitcoin::{Address, ScriptBuf, Transaction, Txid};
+use bdk::blockchain::esplora::EsploraBlockchainConfig;
+use bdk::blockchain::rpc::{Auth, RpcConfig, RpcSyncParams};
+use bdk::blockchain::{
+    AnyBlockchain, AnyBlockchainConfig, ConfigurableBlockchain, ElectrumBlockchainConfig, GetHeight,
+};
+use bdk::electrum_client::{Client, ConfigBuilder, ElectrumApi};
+use chrono::DateTime;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
+use std::str::FromStr;
+use std::time::Duration;
+
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+/// Esplora pages confirmed address history 25 transactions at a time.
+const ESPLORA_CHAIN_PAGE_SIZE: usize = 25;
+const BUILTIN_ID_PREFIX: &str = "builtin-";
+/// Bitcoin Core keeps one watch-only wallet per app wallet for BDK sync.
+const CORE_WALLET_PREFIX: &str = "aiigo-";
+
+/// A transaction in the Esplora (Blockstream) JSON shape. Electrum and
+/// Bitcoin Core RPC results are converted into it so callers see one format.
+#[derive(Debug, Deserialize)]
+pub(crate) struct BlockstreamTx {
+    pub(crate) txid: String,
+    pub(crate) vin: Vec<BlockstreamVin>,
+    pub(crate) vout: Vec<BlockstreamVout>,
+    pub(crate) weight: u64,
+    pub(crate) fee: u64,
+    pub(crate) status: BlockstreamTxStatus,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct BlockstreamVin {
+    pub(crate) prevout: Option<BlockstreamVout>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct BlockstreamVout {
+    pub(crate) scriptpubkey_address: Option<String>,
+    pub(crate) value: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct BlockstreamTxStatus {
+    pub(crate) confirmed: bool,
+    pub(crate) block_height: Option<u32>,
+    pub(crate) block_time: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct EsploraUtxo {
+    pub(crate) txid: String,
+    pub(crate) vout: u32,
+    pub(crate) value: u64,
+    pub(crate) status: EsploraUtxoStatus,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct EsploraUtxoStatus {
+    pub(crate) confirmed: bool,
+    pub(crate) block_height: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EsploraAddress {
+    chain_stats: EsploraAddressStats,
+    mempool_stats: EsploraAddressStats,
+}
+
+#[derive(Debug, Deserialize)]
+struct EsploraAddressStats {
+    funded_txo_sum: u64,
+    spent_txo_sum: u64,
+    #[serde(default)]
+    tx_count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct EsploraMempool {
+    fee_histogram: Vec<(f64, u64)>,
+}
+
+/// What a backend reports about the fee market: fee rates in sat/vB keyed
+/// by confirmation target in blocks, the mempool fee histogram as
+/// `(sat/vB, vbytes)` buckets from the highest rate down, and the rate below
+/// which its node no longer relays transactions.
+#[derive(Debug)]
+pub(crate) struct FeeMarket {
+    pub(crate) estimates: BTreeMap<u32, f64>,
+    pub(crate) histogram: Option<Vec<(f64, u64)>>,
+    pub(crate) min_relay_fee_rate: Option<f64>,
+}
+
+/// Backends tried for a network, in order. Without any configured backend
+/// the network's public Esplora and Electrum endpoints are used.
+pub(crate) fn network_backends(
+    network: BitcoinNetwork,
+) -> Result<Vec<BitcoinBackendConfig>, String> {
+    let stored = {
+        let db = DB.lock().unwrap();
+        db.get_bitcoin_backends(Some(network))
+            .map_err(|e| format!("Failed to get Bitcoin backends: {}", e))?
+    };
+
+    select_backends(network, stored)
+        .into_iter()
+        .map(|mut backend| {
+            if backend.has_rpc_password {
+                backend.rpc_password = load_rpc_password(&backend.id)?;
+            }
+            Ok(backend)
+        })
+        .collect()
+}
+
+/// Decrypts the saved RPC password of a backend.
+pub(crate) fn load_rpc_password(backend_id: &str) -> Result<Option<String>, String> {
+    let stored = {
+        let db = DB.lock().unwrap();
+        db.get_bitcoin_backend_password(backend_id)
+            .map_err(|e| format!("Failed to get RPC password: {}", e))?
+    };
+
+    stored
+        .map(|stored| {
+            decrypt_secret(&stored.secret_data, &stored.secret_format)
+                .map_err(|e| format!("Failed to decrypt RPC password: {}", e))
+        })
+        .transpose()
+}
+
+/// Once the user configures any backend for a network, only their enabled
+/// backends are used, so addresses never reach the public defaults.
+fn select_backends(
+    network: BitcoinNetwork,
+    stored: Vec<BitcoinBackendConfig>,
+) -> Vec<BitcoinBackendConfig> {
+    if stored.is_empty() {
+        return builtin_backends(network);
+    }
+
+    let mut backends: Vec<_> = stored
+        .into_iter()
+        .filter(|backend| backend.enabled && backend.network == network)
+        .collect();
+    backends.sort_by_key(|backend| backend.priority);
+    backends
+}
+
+fn builtin_backends(network: BitcoinNetwork) -> Vec<BitcoinBackendConfig> {
+    [
+        (BitcoinBackendKind::Esplora, network.esplora_url()),
+        (BitcoinBackendKind::Electrum, network.electrum_url()),
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(priority, (kind, url))| BitcoinBackendConfig {
+        id: format!("{}{}", BUILTIN_ID_PREFIX, kind.as_str()),
+        network,
+        kind,
+        url,
+        rpc_username: None,
+        rpc_password: None,
+        has_rpc_password: false,
+        priority: priority as i64,
+        enabled: true,
+        created_at: String::new(),
+        updated_at: String::new(),
+    })
+    .collect()
+}
+
+pub(crate) fn uses_builtin_backends(backends: &[BitcoinBackendConfig]) -> bool {
+    backends
+        .iter()
+        .all(|backend| backend.id.starts_with(BUILTIN_ID_PREFIX))
+}
+
+/// Human-readable name of a backend for logs and errors.
+pub(crate) fn backend_label(backend: &BitcoinBackendConfig) -> String {
+    format!("{} {}", backend.kind.as_str(), backend.url)
+}
+
+/// Checks the URL scheme against the backend kind and trims it.
+pub(crate) fn normalize_backend_request(
+    mut request: SaveBitcoinBackendRequest,
+) -> Result<SaveBitcoinBackendRequest, String> {
+    let url = request.url.trim();
+    let schemes: &[&str] = match request.kind {
+        BitcoinBackendKind::Esplora | BitcoinBackendKind::BitcoinRpc => &["http://", "https://"],
+        BitcoinBackendKind::Electrum => &["tcp://", "ssl://"],
+    };
+    if !schemes.iter().any(|scheme| url.starts_with(scheme)) {
+        return Err(format!(
+            "{} backend URL must start with {}",
+            request.kind.as_str(),
+            schemes.join(" or ")
+        ));
+    }
+
+    request.url = match request.kind {
+        BitcoinBackendKind::Electrum => url.to_string(),
+        _ => url.trim_end_matches('/').to_string(),
+    };
+    request.rpc_username = request
+        .rpc_username
+        .map(|username| username.trim().to_string())
+        .filter(|username| !username.is_empty());
+    Ok(request)
+}
+
+async fn with_fallback<T, F, Fut>(
+    network: BitcoinNetwork,
+    operation: &str,
+    call: F,
+) -> Result<T, String>
+where
+    F: FnMut(BitcoinBackendConfig) -> Fut,
+    Fut: Future<Output = Result<T, String>>,
+{
+    try_backends(network_backends(network)?, operation, call).await
+}
+
+async fn try_backends<T, F, Fut>(
+    backends: Vec<BitcoinBackendConfig>,
+    operation: &str,
+    mut call: F,
+) -> Result<T, String>
+where
+    F: FnMut(BitcoinBackendConfig) -> Fut,
+    Fut: Future<Output = Result<T, String>>,
+{
+    if backends.is_empty() {
+        return Err("No enabled Bitcoin backend for this network".to_string());
+    }
+
+    let mut failures = Vec::new();
+    for backend in backends {
+        let label = backend_label(&backend);
+        match call(backend).await {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                crate::safe_log!("[WARN] Failed to {} via {}: {}", operation, label, e);
+                failures.push(format!("{}: {}", label, e));
+            }
+        }
+    }
+
+    Err(format!(
+        "Failed to {} from all Bitcoin backends: {}",
+        operation,
+        failures.join("; ")
+    ))
+}
+
+/// Current chain tip height
+pub(crate) async fn tip_height(network: BitcoinNetwork) -> Result<u32, String> {
+    with_fallback(network, "fetch block height", |backend| async move {
+        backend_tip_height(&backend).await
+    })
+    .await
+}
+
+/// Every transaction touching an address, newest first
+pub(crate) async fn address_transactions(
+    address: &str,
+    network: BitcoinNetwork,
+) -> Result<Vec<BlockstreamTx>, String> {
+    with_fallback(network, "fetch address history", |backend| async move {
+        backend_address_transactions(&backend, address).await
+    })
+    .await
+}
+
+/// A single transaction with its prevouts and confirmation status
+pub(crate) async fn transaction(
+    txid: &str,
+    network: BitcoinNetwork,
+) -> Result<BlockstreamTx, String> {
+    with_fallback(network, "fetch transaction", |backend| async move {
+        backend_transaction(&backend, txid).await
+    })
+    .await
+}
+
+/// Unspent outputs locked to an address
+pub(crate) async fn address_utxos(
+    address: &str,
+    network: BitcoinNetwork,
+) -> Result<Vec<EsploraUtxo>, String> {
+    with_fallback(network, "fetch address UTXOs", |backend| async move {
+        backend_address_utxos(&backend, address).await
+    })
+    .await
+}
+
+/// Fee estimates covering `targets` (in blocks), with mempool state where available
+pub(crate) async fn fee_market(
+    network: BitcoinNetwork,
+    targets: &[u32],
+) -> Result<FeeMarket, String> {
+    with_fallback(network, "fetch fee estimates", |backend| async move {
+        backend_fee_market(&backend, targets).await
+    })
+    .await
+}
+
+pub(crate) async fn backend_tip_height(backend: &BitcoinBackendConfig) -> Result<u32, String> {
+    match backend.kind {
+        BitcoinBackendKind::Esplora => {
+            let text = esplora_get(&backend.url, "/blocks/tip/height")
+                .await?
+                .text()
+                .await
+                .map_err(|e| format!("Failed to read response: {}", e))?;
+            text.trim()
+                .parse()
+                .map_err(|e| format!("Failed to parse block height: {}", e))
+        }
+        BitcoinBackendKind::Electrum => {
+            let url = backend.url.clone();
+            electrum_blocking(move || {
+                let client = electrum_client(&url)?;
+                let header = client
+                    .block_headers_subscribe()
+                    .map_err(|e| format!("Failed to fetch block height: {}", e))?;
+                Ok(header.height as u32)
+            })
+            .await
+        }
+        BitcoinBackendKind::BitcoinRpc => {
+            rpc_call(
+                backend,
+                "getblockcount",
+                serde_json::json!([]),
+                REQUEST_TIMEOUT_SECS,
+            )
+            .await
+        }
+    }
+}
+
+pub(crate) async fn backend_address_stats(
+    backend: &BitcoinBackendConfig,
+    address: &str,
+) -> Result<AddressStats, String> {
+    match backend.kind {
+        BitcoinBackendKind::Esplora => {
+            let data: EsploraAddress =
+                esplora_json(&backend.url, &format!("/address/{}", address)).await?;
+
+            // Calculate balance: (funded - spent) for both confirmed and mempool
+            let confirmed_balance =
+                data.chain_stats.funded_txo_sum as i64 - data.chain_stats.spent_txo_sum as i64;
+            let mempool_balance =
+                data.mempool_stats.funded_txo_sum as i64 - data.mempool_stats.spent_txo_sum as i64;
+
+            Ok(AddressStats {
+                balance_sats: confirmed_balance + mempool_balance,
+                tx_count: data.chain_stats.tx_count + data.mempool_stats.tx_count,
+            })
+        }
+        BitcoinBackendKind::Electrum => {
+            let (url, network) = (backend.url.clone(), backend.network);
+            let script = address_script(address, network)?;
+            electrum_blocking(move || {
+                let client = electrum_client(&url)?;
+                let balance = client
+                    .script_get_balance(&script)
+                    .map_err(|e| format!("Failed to fetch balance: {}", e))?;
+                let history = client
+                    .script_get_history(&script)
+                    .map_err(|e| format!("Failed to fetch history: {}", e))?;
+                Ok(AddressStats {
+                    balance_sats: balance.confirmed as i64 + balance.unconfirmed,
+                    tx_count: history.len() as u64,
+                })
+            })
+            .await
+        }
+        BitcoinBackendKind::BitcoinRpc => {
+            // Scanning the UTXO set misses every spent output, so it cannot
+            // stand in for an address index here.
+            Err("Bitcoin Core RPC has no address index for address balances".to_string())
+        }
+    }
+}
+
+async fn backend_address_transactions(
+    backend: &BitcoinBackendConfig,
+    address: &str,
+) -> Result<Vec<BlockstreamTx>, String> {
+    match backend.kind {
+        BitcoinBackendKind::Esplora => esplora_address_history(&backend.url, address).await,
+        BitcoinBackendKind::Electrum => {
+            let (url, network) = (backend.url.clone(), backend.network);
+            let script = address_script(address, network)?;
+            electrum_blocking(move || {
+                let client = electrum_client(&url)?;
+                let history = client
+                    .script_get_history(&script)
+                    .map_err(|e| format!("Failed to fetch history: {}", e))?;
+                // Electrum lists confirmed transactions oldest first, then the
+                // mempool; return them newest first like Esplora.
+                let heights: HashMap<Txid, i32> = history
+                    .iter()
+                    .map(|entry| (entry.tx_hash, entry.height))
+                    .collect();
+                let txids: Vec<Txid> = history.iter().rev().map(|entry| entry.tx_hash).collect();
+                electrum_transactions(&client, &txids, &heights, network)
+            })
+            .await
+        }
+        BitcoinBackendKind::BitcoinRpc => {
+            Err("Bitcoin Core RPC has no address index for transaction history".to_string())
+        }
+    }
+}
+
+/// Walks the whole Esplora history of an address: the first page holds the
+/// mempool and the newest confirmed transactions, and each following page
+/// continues the confirmed chain after the last transaction seen.
+async fn esplora_address_history(
+    base_url: &str,
+    address: &str,
+) -> Result<Vec<BlockstreamTx>, String> {
+    let mut page: Vec<BlockstreamTx> =
+        esplora_json(base_url, &format!("/address/{}/txs", address)).await?;
+    let mut history = Vec::new();
+
+    loop {
+        let confirmed: Vec<&BlockstreamTx> = page.iter().filter(|tx| tx.status.confirmed).collect();
+        // A page short of the page size is the end of the chain.
+        let last_seen = match confirmed.last() {
+            Some(tx) if confirmed.len() >= ESPLORA_CHAIN_PAGE_SIZE => Some(tx.txid.clone()),
+            _ => None,
+        };
+        history.extend(page);
+
+        let Some(last_seen) = last_seen else {
+            break;
+        };
+        page = esplora_json(
+            base_url,
+            &format!("/address/{}/txs/chain/{}", address, last_seen),
+        )
+        .await?;
+    }
+
+    Ok(history)
+}
+
+async fn backend_transaction(
+    backend: &BitcoinBackendConfig,
+    txid: &str,
+) -> Result<BlockstreamTx, String> {
+    match backend.kind {
+        BitcoinBackendKind::Esplora => esplora_json(&backend.url, &format!("/tx/{}", txid)).await,
+        BitcoinBackendKind::Electrum => {
+            let (url, network) = (backend.url.clone(), backend.network);
+            let txid =
+                Txid::from_str(txid).map_err(|e| format!("Invalid transaction hash: {}", e))?;
+            electrum_blocking(move || {
+                let client = electrum_client(&url)?;
+                let tx = client
+                    .transaction_get(&txid)
+                    .map_err(|e| format!("Failed to fetch transaction: {}", e))?;
+                // Electrum has no txid lookup for the height; find it in the
+                // history of one of the transaction's own outputs.
+                let height = match tx.output.first() {
+                    Some(output) => client
+                        .script_get_history(&output.script_pubkey)
+                        .map_err(|e| format!("Failed to fetch history: {}", e))?
+                        .into_iter()
+                        .find(|entry| entry.tx_hash == txid)
+                        .map_or(0, |entry| entry.height),
+                    None => 0,
+                };
+                let heights = HashMap::from([(txid, height)]);
+                electrum_transactions(&client, &[txid], &heights, network)?
+                    .pop()
+                    .ok_or_else(|| "Transaction not found".to_string())
+            })
+            .await
+        }
+        BitcoinBackendKind::BitcoinRpc => {
+            // Transactions outside the mempool need `txindex=1` on the node.
+            let tx: RpcTransaction = rpc_call(
+                backend,
+                "getrawtransaction",
+                serde_json::json!([txid, 2]),
+                REQUEST_TIMEOUT_SECS,
+            )
+            .await?;
+            let block_height = match &tx.blockhash {
+                Some(blockhash) => {
+                    let header: RpcBlockHeader = rpc_call(
+                        backend,
+                        "getblockheader",
+                        serde_json::json!([blockhash]),
+                        REQUEST_TIMEOUT_SECS,
+                    )
+                    .await?;
+                    Some(header.height)
+                }
+                None => None,
+            };
+            Ok(tx_from_rpc(tx, block_height))
+        }
+    }
+}
+
+async fn backend_address_utxos(
+    backend: &BitcoinBackendConfig,
+    address: &str,
+) -> Result<Vec<EsploraUtxo>, String> {
+    match backend.kind {
+        BitcoinBackendKind::Esplora => {
+            esplora_json(&backend.url, &format!("/address/{}/utxo", address)).await
+        }
+        BitcoinBackendKind::Electrum => {
+            let (url, network) = (backend.url.clone(), backend.network);
+            let script = address_script(address, network)?;
+            electrum_blocking(move || {
+                let client = electrum_client(&url)?;
+                let unspent = client
+                    .script_list_unspent(&script)
+                    .map_err(|e| format!("Failed to fetch UTXOs: {}", e))?;
+                Ok(unspent
+                    .into_iter()
+                    .map(|utxo| EsploraUtxo {
+                        txid: utxo.tx_hash.to_string(),
+                        vout: utxo.tx_pos as u32,
+                        value: utxo.value,
+                        status: EsploraUtxoStatus {
+                            confirmed: utxo.height > 0,
+                            block_height: (utxo.height > 0).then_some(utxo.height as u32),
+                        },
+                    })
+                    .collect())
+            })
+            .await
+        }
+        BitcoinBackendKind::BitcoinRpc => {
+            // Wallets synced through the node read their UTXOs from the local store.
+            Err("Bitcoin Core RPC has no address index for address UTXOs".to_string())
+        }
+    }
+}
+
+async fn backend_fee_market(
+    backend: &BitcoinBackendConfig,
+    targets: &[u32],
+) -> Result<FeeMarket, String> {
+    match backend.kind {
+        BitcoinBackendKind::Esplora => {
+            let estimates: HashMap<String, f64> =
+                esplora_json(&backend.url, "/fee-estimates").await?;
+            // The histogram only refines the estimates, so a backend without
+            // it still answers.
+            let histogram = esplora_json::<EsploraMempool>(&backend.url, "/mempool")
+                .await
+                .map(|mempool| mempool.fee_histogram)
+                .ok();
+            Ok(FeeMarket {
+                estimates: estimates
+                    .into_iter()
+                    .filter_map(|(target, rate)| Some((target.parse().ok()?, rate)))
+                    .collect(),
+                histogram,
+                min_relay_fee_rate: None,
+            })
+        }
+        BitcoinBackendKind::Electrum => {
+            let (url, targets) = (backend.url.clone(), targets.to_vec());
+            electrum_blocking(move || {
+                let client = electrum_client(&url)?;
+                let mut estimates = BTreeMap::new();
+                for target in targets {
+                    let estimate = client
+                        .estimate_fee(target as usize)
+                        .map_err(|e| format!("Failed to fetch fee estimate: {}", e))?;
+                    // Servers answer -1 while their node has no estimate yet.
+                    if estimate > 0.0 {
+                        estimates.insert(target, btc_per_kvb_to_sat_per_vb(estimate));
+                    }
+                }
+                let histogram = client
+                    .raw_call("mempool.get_fee_histogram", [])
+                    .ok()
+                    .and_then(|value| serde_json::from_value(value).ok());
+                Ok(FeeMarket {
+                    estimates,
+                    histogram,
+                    min_relay_fee_rate: client.relay_fee().ok().map(btc_per_kvb_to_sat_per_vb),
+                })
+            })
+            .await
+        }
+        BitcoinBackendKind::BitcoinRpc => {
+            let mut estimates = BTreeMap::new();
+            for &target in targets {
+                let estimate: RpcSmartFee = rpc_call(
+                    backend,
+                    "estimatesmartfee",
+                    serde_json::json!([target]),
+                    REQUEST_TIMEOUT_SECS,
+                )
+                .await?;
+                if let Some(rate) = estimate.feerate {
+                    estimates.insert(
+                        estimate.blocks.unwrap_or(target),
+                        btc_per_kvb_to_sat_per_vb(rate),
+                    );
+                }
+            }
+            let mempool: Option<RpcMempoolInfo> = rpc_call(
+                backend,
+                "getmempoolinfo",
+                serde_json::json!([]),
+                REQUEST_TIMEOUT_SECS,
+            )
+            .await
+            .ok();
+            // A full mempool raises the admission floor above the relay fee.
+            Ok(FeeMarket {
+                estimates,
+                histogram: None,
+                min_relay_fee_rate: mempool.map(|info| {
+                    btc_per_kvb_to_sat_per_vb(info.minrelaytxfee.max(info.mempoolminfee))
+                }),
+            })
+        }
+    }
+}
+
+/// Electrum and Bitcoin Core quote fee rates in BTC per 1000 vbytes.
+fn btc_per_kvb_to_sat_per_vb(rate: f64) -> f64 {
+    btc_to_sats(rate) as f64 / 1000.0
+}
+
+/// Connects BDK to the first reachable backend of the network, in the
+/// configured order, for wallet sync and broadcast. A Bitcoin Core backend
+/// syncs through a watch-only node wallet named after `wallet_id`.
+pub(crate) fn connect_blockchain(
+    network: BitcoinNetwork,
+    wallet_id: &str,
+) -> Result<AnyBlockchain, String> {
+    let backends = network_backends(network)?;
+    if backends.is_empty() {
+        return Err("No enabled Bitcoin backend for this network".to_string());
+    }
+    let start_time = wallet_start_time(wallet_id)?;
+
+    let mut failures = Vec::new();
+    for backend in &backends {
+        let label = backend_label(backend);
+        crate::safe_log!("[INFO] Connecting to {}...", label);
+        match open_blockchain(backend, wallet_id, start_time) {
+            Ok(blockchain) => {
+                crate::safe_log!("[INFO] Connected to {}", label);
+                return Ok(blockchain);
+            }
+            Err(e) => {
+                crate::safe_log!("[WARN] Failed to connect to {}: {}", label, e);
+                failures.push(format!("{}: {}", label, e));
+            }
+        }
+    }
+
+    Err(format!(
+        "Failed to connect to any Bitcoin backend: {}",
+        failures.join("; ")
+    ))
+}
+
+/// When the wallet was added, in unix seconds. A Bitcoin Core node rescans
+/// from here on the first sync rather than from genesis.
+fn wallet_start_time(wallet_id: &str) -> Result<u64, String> {
+    let wallet = {
+        let db = DB.lock().unwrap();
+        db.get_bitcoin_wallet(wallet_id)
+            .map_err(|e| format!("Failed to get wallet info: {}", e))?
+    };
+    Ok(wallet
+        .and_then(|wallet| DateTime::parse_from_rfc3339(&wallet.created_at).ok())
+        .map_or(0, |created_at| created_at.timestamp().max(0) as u64))
+}
+
+fn open_blockchain(
+    backend: &BitcoinBackendConfig,
+    wallet_id: &str,
+    start_time: u64,
+) -> Result<AnyBlockchain, String> {
+    let config = blockchain_config(backend, wallet_id, start_time);
+    let blockchain = AnyBlockchain::from_config(&config).map_err(|e| e.to_string())?;
+    // Esplora connects lazily; ask every kind for the tip so an unreachable
+    // backend fails here rather than halfway through a sync.
+    blockchain.get_height().map_err(|e| e.to_string())?;
+    Ok(blockchain)
+}
+
+fn blockchain_config(
+    backend: &BitcoinBackendConfig,
+    wallet_id: &str,
+    start_time: u64,
+) -> AnyBlockchainConfig {
+    match backend.kind {
+        BitcoinBackendKind::Esplora => AnyBlockchainConfig::Esplora(EsploraBlockchainConfig {
+            base_url: backend.url.clone(),
+            proxy: None,
+            concurrency: None,
+            stop_gap: ADDRESS_GAP_LIMIT as usize,
+            timeout: Some(REQUEST_TIMEOUT_SECS),
+        }),
+        BitcoinBackendKind::Electrum => AnyBlockchainConfig::Electrum(ElectrumBlockchainConfig {
+            url: backend.url.clone(),
+            socks5: None,
+            retry: 1,
+            timeout: Some(REQUEST_TIMEOUT_SECS as u8),
+            stop_gap: ADDRESS_GAP_LIMIT as usize,
+            validate_domain: true,
+        }),
+        BitcoinBackendKind::BitcoinRpc => AnyBlockchainConfig::Rpc(RpcConfig {
+            url: backend.url.clone(),
+            auth: match &backend.rpc_username {
+                Some(username) => Auth::UserPass {
+                    username: username.clone(),
+                    password: backend.rpc_password.clone().unwrap_or_default(),
+                },
+                None => Auth::None,
+            },
+            network: backend.network.bdk_network(),
+            wallet_name: format!("{}{}", CORE_WALLET_PREFIX, wallet_id),
+            // The first sync rescans from when the wallet was added; later
+            // syncs resume from the last block the node wallet saw.
+            sync_params: Some(RpcSyncParams {
+                start_time,
+                ..RpcSyncParams::default()
+            }),
+        }),
+    }
+}
+
+async fn esplora_get(base_url: &str, path: &str) -> Result<reqwest::Response, String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let response = client
+        .get(format!("{}{}", base_url, path))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    Ok(response)
+}
+
+async fn esplora_json<T: DeserializeOwned>(base_url: &str, path: &str) -> Result<T, String> {
+    esplora_get(base_url, path)
+        .await?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))
+}
+
+fn electrum_client(url: &str) -> Result<Client, String> {
+    let config = ConfigBuilder::new()
+        .timeout(Some(REQUEST_TIMEOUT_SECS as u8))
+        .build();
+    Client::from_config(url, config).map_err(|e| format!("Failed to connect to Electrum: {}", e))
+}
+
+/// The Electrum client is blocking; keep it off the async runtime threads.
+async fn electrum_blocking<T, F>(call: F) -> Result<T, String>
+where
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(call)
+        .await
+        .map_err(|e| format!("Electrum request failed: {}", e))?
+}
+
+fn address_script(address: &str, network: BitcoinNetwork) -> Result<ScriptBuf, String> {
+    Address::from_str(address)
+        .and_then(|address| address.require_network(network.bdk_network()))
+        .map(|address| address.script_pubkey())
+        .map_err(|e| format!("Invalid address: {}", e))
+}
+
+/// Fetches transactions and their prevouts in two batches and converts them.
+fn electrum_transactions(
+    client: &Client,
+    txids: &[Txid],
+    heights: &HashMap<Txid, i32>,
+    network: BitcoinNetwork,
+) -> Result<Vec<BlockstreamTx>, String> {
+    let transactions = client
+        .batch_transaction_get(txids.iter())
+        .map_err(|e| format!("Failed to fetch transactions: {}", e))?;
+
+    let prevout_txids: Vec<Txid> = transactions
+        .iter()
+        .flat_map(|tx| tx.input.iter())
+        .filter(|input| !input.previous_output.is_null())
+        .map(|input| input.previous_output.txid)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let prevouts: HashMap<Txid, Transaction> = client
+        .batch_transaction_get(prevout_txids.iter())
+        .map_err(|e| format!("Failed to fetch previous transactions: {}", e))?
+        .into_iter()
+        .map(|tx| (tx.txid(), tx))
+        .collect();
+
+    let confirmed_heights: Vec<u32> = heights
+        .values()
+        .filter(|height| **height > 0)
+        .map(|height| *height as u32)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let block_times: HashMap<u32, u32> = client
+        .batch_block_header(confirmed_heights.iter())
+        .map_err(|e| format!("Failed to fetch block headers: {}", e))?
+        .into_iter()
+        .zip(confirmed_heights.iter())
+        .map(|(header, height)| (*height, header.time))
+        .collect();
+
+    Ok(transactions
+        .iter()
+        .map(|tx| {
+            let height = heights.get(&tx.txid()).copied().unwrap_or(0);
+            let block_time = u32::try_from(height)
+                .ok()
+                .and_then(|height| block_times.get(&height).copied());
+            tx_from_electrum(tx, &prevouts, height, block_time, network)
+        })
+        .collect())
+}
+
+/// `height` follows Electrum: positive when confirmed, zero or negative in the mempool.
+fn tx_from_electrum(
+    tx: &Transaction,
+    prevouts: &HashMap<Txid, Transaction>,
+    height: i32,
+    block_time: Option<u32>,
+    network: BitcoinNetwork,
+) -> BlockstreamTx {
+    let address_of = |script: &bdk::bitcoin::Script| {
+        Address::from_script(script, network.bdk_network())
+            .ok()
+            .map(|address| address.to_string())
+    };
+
+    let vin: Vec<BlockstreamVin> = tx
+        .input
+        .iter()
+        .map(|input| BlockstreamVin {
+            prevout: prevouts
+                .get(&input.previous_output.txid)
+                .and_then(|prev| prev.output.get(input.previous_output.vout as usize))
+                .map(|output| BlockstreamVout {
+                    scriptpubkey_address: address_of(&output.script_pubkey),
+                    value: output.value,
+                }),
+        })
+        .collect();
+    let vout: Vec<BlockstreamVout> = tx
+        .output
+        .iter()
+        .map(|output| BlockstreamVout {
+            scriptpubkey_address: address_of(&output.script_pubkey),
+            value: output.value,
+        })
+        .collect();
+
+    // A missing prevout (or a coinbase) leaves the fee unknown rather than wrong.
+    let input_total: Option<u64> = vin
+        .iter()
+        .map(|input| input.prevout.as_ref().map(|prevout| prevout.value))
+        .sum();
+    let output_total: u64 = vout.iter().map(|output| output.value).sum();
+    let fee = input_total.map_or(0, |input_total| input_total.saturating_sub(output_total));
+
+    BlockstreamTx {
+        txid: tx.txid().to_string(),
+        vin,
+        vout,
+        weight: tx.weight().to_wu(),
+        fee,
+        status: BlockstreamTxStatus {
+            confirmed: height > 0,
+            block_height: (height > 0).then_some(height as u32),
+            block_time: block_time.map(u64::from),
+        },
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcTransaction {
+    txid: String,
+    weight: u64,
+    vin: Vec<RpcVin>,
+    vout: Vec<RpcVout>,
+    blockhash: Option<String>,
+    blocktime: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcVin {
+    prevout: Option<RpcVout>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcVout {
+    value: f64,
+    #[serde(rename = "scriptPubKey")]
+    script_pub_key: RpcScriptPubKey,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcScriptPubKey {
+    address: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcBlockHeader {
+    height: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcSmartFee {
+    feerate: Option<f64>,
+    blocks: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcMempoolInfo {
+    minrelaytxfee: f64,
+    mempoolminfee: f64,
+}
+
+async fn rpc_call<T: DeserializeOwned>(
+    backend: &BitcoinBackendConfig,
+    method: &str,
+    params: serde_json::Value,
+    timeout_secs: u64,
+) -> Result<T, String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(timeout_secs))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let mut request = client.post(&backend.url).json(&serde_json::json!({
+        "jsonrpc": "1.0",
+        "id": "aiigo",
+        "method": method,
+        "params": params,
+    }));
+    if let Some(username) = &backend.rpc_username {
+        request = request.basic_auth(username, backend.rpc_password.as_ref());
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    // Bitcoin Core reports RPC errors with a 500 status and a JSON body.
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        return Err("Bitcoin Core RPC authentication failed".to_string());
+    }
+    let body: RpcResponse<T> = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse RPC response: {}", e))?;
+
+    match (body.result, body.error) {
+        (_, Some(error)) => Err(format!("RPC error {}: {}", error.code, error.message)),
+        (Some(result), None) => Ok(result),
+        (None, None) => Err(format!("RPC {} returned no result", method)),
+    }
+}
+
+fn tx_from_rpc(tx: RpcTransaction, block_height: Option<u32>) -> BlockstreamTx {
+    let to_vout = |output: RpcVout| BlockstreamVout {
+        scriptpubkey_address: output.script_pub_key.address,
+        value: btc_to_sats(output.value),
+    };
+
+    let vin: Vec<BlockstreamVin> = tx
+        .vin
+        .into_iter()
+        .map(|input| BlockstreamVin {
+            prevout: input.prevout.map(to_vout),
+        })
+        .collect();
+    let vout: Vec<BlockstreamVout> = tx.vout.into_iter().map(to_vout).collect();
+
+    let input_total: Option<u64> = vin
+        .iter()
+        .map(|input| input.prevout.as_ref().map(|prevout| prevout.value))
+        .sum();
+    let output_total: u64 = vout.iter().map(|output| output.value).sum();
+    let fee = input_total.map_or(0, |input_total| input_total.saturating_sub(output_total));
+
+    BlockstreamTx {
+        txid: tx.txid,
+        vin,
+        vout,
+        weight: tx.weight,
+        fee,
+        status: BlockstreamTxStatus {
+            confirmed: block_height.is_some(),
+            block_height,
+            block_time: block_height.and(tx.blocktime),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        backend_address_stats, backend_address_utxos, backend_fee_market, backend_tip_height,
+        blockchain_config, esplora_address_history, normalize_backend_request, select_backends,
+        try_backends, tx_from_electrum, tx_from_rpc, uses_builtin_backends, RpcTransaction,
+    };
+    use crate::wallet::bitcoin::config::BitcoinNetwork;
+    use crate::wallet::types::{
+        BitcoinBackendConfig, BitcoinBackendKind, SaveBitcoinBackendRequest,
+    };
+    use bdk::bitcoin::absolute::LockTime;
+    use bdk::bitcoin::{Address, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Witness};
+    use bdk::blockchain::rpc::Auth;
+    use bdk::blockchain::AnyBlockchainConfig;
+    use std::collections::HashMap;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn backend(
+        id: &str,
+        network: BitcoinNetwork,
+        kind: BitcoinBackendKind,
+        url: &str,
+        priority: i64,
+        enabled: bool,
+    ) -> BitcoinBackendConfig {
+        BitcoinBackendConfig {
+            id: id.to_string(),
+            network,
+            kind,
+            url: url.to_string(),
+            rpc_username: None,
+            rpc_password: None,
+            has_rpc_password: false,
+            priority,
+            enabled,
+            created_at: "2026-04-18T00:00:00Z".to_string(),
+            updated_at: "2026-04-18T00:00:00Z".to_string(),
+        }
+    }
+
+    fn save_request(kind: BitcoinBackendKind, url: &str) -> SaveBitcoinBackendRequest {
+        SaveBitcoinBackendRequest {
+            id: None,
+            network: BitcoinNetwork::Regtest,
+            kind,
+            url: url.to_string(),
+            rpc_username: Some("  ".to_string()),
+            rpc_password: None,
+            priority: None,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn blockchain_config_follows_the_backend_kind() {
+        let esplora = backend(
+            "esplora",
+            BitcoinNetwork::Regtest,
+            BitcoinBackendKind::Esplora,
+            "http://127.0.0.1:3002",
+            0,
+            true,
+        );
+        match blockchain_config(&esplora, "wallet-1", 1_776_470_400) {
+            AnyBlockchainConfig::Esplora(config) => {
+                assert_eq!(config.base_url, "http://127.0.0.1:3002");
+                assert_eq!(config.stop_gap, 20);
+            }
+            other => panic!("expected an Esplora config, got {:?}", other),
+        }
+
+        let mut node = backend(
+            "node",
+            BitcoinNetwork::Regtest,
+            BitcoinBackendKind::BitcoinRpc,
+            "http://127.0.0.1:18443",
+            1,
+            true,
+        );
+        node.rpc_username = Some("alice".to_string());
+        node.rpc_password = Some("secret".to_string());
+        match blockchain_config(&node, "wallet-1", 1_776_470_400) {
+            AnyBlockchainConfig::Rpc(config) => {
+                assert_eq!(config.url, "http://127.0.0.1:18443");
+                assert_eq!(config.wallet_name, "aiigo-wallet-1");
+                assert_eq!(config.network, bdk::bitcoin::Network::Regtest);
+                assert_eq!(
+                    config.sync_params.map(|params| params.start_time),
+                    Some(1_776_470_400)
+                );
+                assert!(matches!(
+                    config.auth,
+                    Auth::UserPass { ref username, ref password }
+                        if username == "alice" && password == "secret"
+                ));
+            }
+            other => panic!("expected an RPC config, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn rpc_backend_declines_address_lookups() {
+        let node = backend(
+            "node",
+            BitcoinNetwork::Regtest,
+            BitcoinBackendKind::BitcoinRpc,
+            "http://127.0.0.1:1",
+            0,
+            true,
+        );
+
+        let address = "bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080";
+
+        let stats = backend_address_stats(&node, address).await;
+        let utxos = backend_address_utxos(&node, address).await;
+
+        assert!(stats.unwrap_err().contains("no address index"));
+        assert!(utxos.unwrap_err().contains("no address index"));
+    }
+
+    #[test]
+    fn configured_backends_replace_public_defaults() {
+        let defaults = select_backends(BitcoinNetwork::Mainnet, Vec::new());
+        assert_eq!(defaults.len(), 2);
+        assert!(uses_builtin_backends(&defaults));
+        assert_eq!(defaults[0].kind, BitcoinBackendKind::Esplora);
+        assert_eq!(defaults[1].kind, BitcoinBackendKind::Electrum);
+
+        let selected = select_backends(
+            BitcoinNetwork::Mainnet,
+            vec![
+                backend(
+                    "rpc",
+                    BitcoinNetwork::Mainnet,
+                    BitcoinBackendKind::BitcoinRpc,
+                    "http://node:8332",
+                    2,
+                    true,
+                ),
+                backend(
+                    "electrs",
+                    BitcoinNetwork::Mainnet,
+                    BitcoinBackendKind::Electrum,
+                    "tcp://node:50001",
+                    1,
+                    true,
+                ),
+                backend(
+                    "off",
+                    BitcoinNetwork::Mainnet,
+                    BitcoinBackendKind::Esplora,
+                    "http://node:3002",
+                    0,
+                    false,
+                ),
+            ],
+        );
+        let ids: Vec<_> = selected.iter().map(|backend| backend.id.as_str()).collect();
+        assert_eq!(ids, vec!["electrs", "rpc"]);
+        assert!(!uses_builtin_backends(&selected));
+
+        // Disabling every configured backend must not fall back to the public ones.
+        let selected = select_backends(
+            BitcoinNetwork::Mainnet,
+            vec![backend(
+                "off",
+                BitcoinNetwork::Mainnet,
+                BitcoinBackendKind::Esplora,
+                "http://node:3002",
+                0,
+                false,
+            )],
+        );
+        assert!(selected.is_empty());
+    }
+
+    #[test]
+    fn backend_urls_must_match_their_protocol() {
+        let request = normalize_backend_request(save_request(
+            BitcoinBackendKind::Esplora,
+            " http://127.0.0.1:3002/ ",
+        ))
+        .unwrap();
+        assert_eq!(request.url, "http://127.0.0.1:3002");
+        assert_eq!(request.rpc_username, None);
+
+        assert_eq!(
+            normalize_backend_request(save_request(
+                BitcoinBackendKind::Electrum,
+                "http://127.0.0.1:50001"
+            ))
+            .unwrap_err(),
+            "electrum backend URL must start with tcp:// or ssl://"
+        );
+        assert!(normalize_backend_request(save_request(
+            BitcoinBackendKind::BitcoinRpc,
+            "tcp://127.0.0.1:8332"
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn electrum_transactions_resolve_prevouts_and_fee() {
+        let network = BitcoinNetwork::Regtest;
+        let ours = Address::p2wsh(&ScriptBuf::from(vec![0x51]), network.bdk_network());
+        let theirs = Address::p2wsh(&ScriptBuf::from(vec![0x52]), network.bdk_network());
+
+        let parent = Transaction {
+            version: 2,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![TxOut {
+                value: 100_000,
+                script_pubkey: ours.script_pubkey(),
+            }],
+        };
+        let spend = Transaction {
+            version: 2,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::new(parent.txid(), 0),
+                script_sig: Default::default(),
+                sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                witness: Witness::new(),
+            }],
+            output: vec![TxOut {
+                value: 99_000,
+                script_pubkey: theirs.script_pubkey(),
+            }],
+        };
+        let prevouts = HashMap::from([(parent.txid(), parent)]);
+
+        let confirmed = tx_from_electrum(&spend, &prevouts, 120, Some(1_700_000_000), network);
+        assert_eq!(confirmed.txid, spend.txid().to_string());
+        assert_eq!(confirmed.fee, 1_000);
+        assert_eq!(
+            confirmed.vin[0]
+                .prevout
+                .as_ref()
+                .unwrap()
+                .scriptpubkey_address,
+            Some(ours.to_string())
+        );
+        assert_eq!(
+            confirmed.vout[0].scriptpubkey_address,
+            Some(theirs.to_string())
+        );
+        assert!(confirmed.status.confirmed);
+        assert_eq!(confirmed.status.block_height, Some(120));
+        assert_eq!(confirmed.status.block_time, Some(1_700_000_000));
+
+        // Unknown prevouts leave the fee at zero, and mempool heights are unconfirmed.
+        let pending = tx_from_electrum(&spend, &HashMap::new(), -1, None, network);
+        assert_eq!(pending.fee, 0);
+        assert!(!pending.status.confirmed);
+        assert_eq!(pending.status.block_height, None);
+    }
+
+    #[test]
+    fn rpc_transactions_convert_amounts_to_satoshis() {
+        let tx: RpcTransaction = serde_json::from_value(serde_json::json!({
+            "txid": "ab".repeat(32),
+            "hash": "ab".repeat(32),
+            "version": 2,
+            "size": 222,
+            "vsize": 141,
+            "weight": 561,
+            "locktime": 0,
+            "vin": [{
+                "txid": "cd".repeat(32),
+                "vout": 0,
+                "scriptSig": { "asm": "", "hex": "" },
+                "txinwitness": [],
+                "prevout": {
+                    "generated": false,
+                    "height": 100,
+                    "value": 0.001,
+                    "scriptPubKey": { "hex": "", "address": "bcrt1qsender", "type": "witness_v0_keyhash" }
+                },
+                "sequence": 4294967293u32
+            }],
+            "vout": [{
+                "value": 0.0009859,
+                "n": 0,
+                "scriptPubKey": { "hex": "", "address": "bcrt1qrecipient", "type": "witness_v0_keyhash" }
+            }],
+            "fee": 0.0000141,
+            "blockhash": "ef".repeat(32),
+            "confirmations": 3,
+            "time": 1_700_000_000u64,
+            "blocktime": 1_700_000_000u64
+        }))
+        .unwrap();
+
+        let tx = tx_from_rpc(tx, Some(101));
+
+        assert_eq!(tx.fee, 1_410);
+        assert_eq!(tx.weight, 561);
+        assert_eq!(tx.vout[0].value, 98_590);
+        assert_eq!(
+            tx.vin[0]
+                .prevout
+                .as_ref()
+                .unwrap()
+                .scriptpubkey_address
+                .as_deref(),
+            Some("bcrt1qsender")
+        );
+        assert!(tx.status.confirmed);
+        assert_eq!(tx.status.block_height, Some(101));
+        assert_eq!(tx.status.block_time, Some(1_700_000_000));
+    }
+
+    /// Serves a single Esplora `/blocks/tip/height` response on a local port.
+    async fn local_esplora(tip_height: u32) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await.unwrap();
+            let body = tip_height.to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+        url
+    }
+
+    /// Serves Esplora JSON bodies by request path until the test ends;
+    /// unknown paths get a 404.
+    async fn local_esplora_routes(routes: Vec<(String, String)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = [0u8; 1024];
+                let read = socket.read(&mut request).await.unwrap();
+                let request = String::from_utf8_lossy(&request[..read]);
+                let path = request.split_whitespace().nth(1).unwrap_or_default();
+                let response = match routes.iter().find(|(route, _)| route == path) {
+                    Some((_, body)) => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    ),
+                    None => {
+                        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                            .to_string()
+                    }
+                };
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        url
+    }
+
+    fn esplora_page(txids: &[String], confirmed: bool) -> serde_json::Value {
+        serde_json::Value::Array(
+            txids
+                .iter()
+                .map(|txid| {
+                    serde_json::json!({
+                        "txid": txid,
+                        "vin": [],
+                        "vout": [],
+                        "weight": 400,
+                        "fee": 100,
+                        "status": { "confirmed": confirmed, "block_height": confirmed.then_some(100) },
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    #[tokio::test]
+    async fn esplora_history_follows_the_confirmed_chain() {
+        let address = "bcrt1qaddress";
+        let txid = |index: usize| format!("{:064x}", index);
+        let mempool: Vec<String> = vec![txid(999)];
+        let first: Vec<String> = (0..25).map(txid).collect();
+        let second: Vec<String> = (25..50).map(txid).collect();
+        let last: Vec<String> = (50..53).map(txid).collect();
+
+        let mut first_page = esplora_page(&mempool, false);
+        first_page
+            .as_array_mut()
+            .unwrap()
+            .extend(esplora_page(&first, true).as_array().unwrap().clone());
+        let url = local_esplora_routes(vec![
+            (format!("/address/{}/txs", address), first_page.to_string()),
+            (
+                format!("/address/{}/txs/chain/{}", address, txid(24)),
+                esplora_page(&second, true).to_string(),
+            ),
+            (
+                format!("/address/{}/txs/chain/{}", address, txid(49)),
+                esplora_page(&last, true).to_string(),
+            ),
+        ])
+        .await;
+
+        let history = esplora_address_history(&url, address).await.unwrap();
+
+        let txids: Vec<String> = history.into_iter().map(|tx| tx.txid).collect();
+        let expected: Vec<String> = mempool
+            .into_iter()
+            .chain(first)
+            .chain(second)
+            .chain(last)
+            .collect();
+        assert_eq!(txids, expected);
+    }
+
+    #[tokio::test]
+    async fn esplora_fee_market_reads_estimates_and_histogram() {
+        let estimates = r#"{"1": 25.5, "6": 12.0, "144": 1.2}"#.to_string();
+        let mempool = r#"{"count": 3, "vsize": 3000, "total_fee": 40000, "fee_histogram": [[30.0, 1000], [10.0, 2000]]}"#.to_string();
+        let url = local_esplora_routes(vec![
+            ("/fee-estimates".to_string(), estimates.clone()),
+            ("/mempool".to_string(), mempool),
+        ])
+        .await;
+        let esplora = backend(
+            "esplora",
+            BitcoinNetwork::Regtest,
+            BitcoinBackendKind::Esplora,
+            &url,
+            0,
+            true,
+        );
+
+        let market = backend_fee_market(&esplora, &[6]).await.unwrap();
+        assert_eq!(
+            market.estimates.into_iter().collect::<Vec<_>>(),
+            vec![(1, 25.5), (6, 12.0), (144, 1.2)]
+        );
+        assert_eq!(market.histogram, Some(vec![(30.0, 1000), (10.0, 2000)]));
+        assert_eq!(market.min_relay_fee_rate, None);
+
+        // Without a mempool endpoint the estimates still answer
+        let url = local_esplora_routes(vec![("/fee-estimates".to_string(), estimates)]).await;
+        let esplora = backend(
+            "esplora",
+            BitcoinNetwork::Regtest,
+            BitcoinBackendKind::Esplora,
+            &url,
+            0,
+            true,
+        );
+        let market = backend_fee_market(&esplora, &[6]).await.unwrap();
+        assert_eq!(market.estimates.len(), 3);
+        assert_eq!(market.histogram, None);
+    }
+
+    #[tokio::test]
+    async fn unreachable_backends_fall_through_to_the_next() {
+        let dead = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let dead_url = format!("http://{}", dead.local_addr().unwrap());
+        drop(dead);
+        let live_url = local_esplora(840_000).await;
+
+        let height = try_backends(
+            vec![
+                backend(
+                    "dead",
+                    BitcoinNetwork::Regtest,
+                    BitcoinBackendKind::Esplora,
+                    &dead_url,
+                    0,
+                    true,
+                ),
+                backend(
+                    "live",
+                    BitcoinNetwork::Regtest,
+                    BitcoinBackendKind::Esplora,
+                    &live_url,
+                    1,
+                    true,
+                ),
+            ],
+            "fetch block height",
+            |backend| async move { backend_tip_height(&backend).await },
+        )
+        .await
+        .unwrap();
+        assert_eq!(height, 840_000);
+
+        let error = try_backends(Vec::new(), "fetch block height", |backend| async move {
+            backend_tip_height(&backend).await
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(error, "No enabled Bitcoin backend for this network");
+    }
+}
@@ -1,3 +1,7 @@
+use crate::wallet::bitcoin::backend::{
+    backend_address_stats, backend_label, network_backends, uses_builtin_backends,
+};
+use crate::wallet::bitcoin::config::BitcoinNetwork;
 use crate::wallet::chain::traits::{ChainAdapter, ChainAssetBalanceSnapshot, ChainBalanceSnapshot};
 use crate::wallet::security::sanitize;
 use serde::Deserialize;
@@ -12,39 +16,33 @@ const REQUEST_TIMEOUT_SECS: u64 = 10;
 #[derive(Debug, Deserialize)]
 struct BlockchainInfoResponse {
     final_balance: u64,
-    #[allow(dead_code)]
     n_tx: u64,
     #[allow(dead_code)]
     total_received: u64,
 }
 
-#[derive(Debug, Deserialize)]
-struct BlockstreamResponse {
-    #[allow(dead_code)]
-    address: String,
-    chain_stats: ChainStats,
-    mempool_stats: MempoolStats,
-}
-
-#[derive(Debug, Deserialize)]
-struct ChainStats {
-    funded_txo_sum: u64,
-    spent_txo_sum: u64,
+/// Balance and activity of a single address, in satoshis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressStats {
+    pub balance_sats: i64,
+    pub tx_count: u64,
 }
 
-#[derive(Debug, Deserialize)]
-struct MempoolStats {
-    funded_txo_sum: u64,
-    spent_txo_sum: u64,
+impl AddressStats {
+    pub fn is_used(&self) -> bool {
+        self.tx_count > 0
+    }
 }
 
 pub struct BitcoinChainAdapter {
+    network: BitcoinNetwork,
     wallet_address: String,
 }
 
 impl BitcoinChainAdapter {
-    pub fn new(wallet_address: impl Into<String>) -> Self {
+    pub fn new(network: BitcoinNetwork, wallet_address: impl Into<String>) -> Self {
         Self {
+            network,
             wallet_address: wallet_address.into(),
         }
     }
@@ -71,7 +69,7 @@ impl ChainAdapter for BitcoinChainAdapter {
         &'a self,
     ) -> Pin<Box<dyn Future<Output = Result<ChainBalanceSnapshot, String>> + Send + 'a>> {
         Box::pin(async move {
-            let balance = query_btc_balance(&self.wallet_address).await?;
+            let balance = query_btc_balance(&self.wallet_address, self.network).await?;
 
             Ok(ChainBalanceSnapshot {
                 chain_family: self.chain_family(),
@@ -92,63 +90,88 @@ impl ChainAdapter for BitcoinChainAdapter {
 }
 
 /// Query BTC balance for an address using blockchain APIs
-pub async fn query_btc_balance(address: &str) -> Result<f64, String> {
-    // Try multiple blockchain explorer APIs
-    let apis = vec![
-        (
-            "Blockstream",
-            format!("https://blockstream.info/api/address/{}", address),
-        ),
-        (
-            "Blockchain.info",
-            format!("https://blockchain.info/rawaddr/{}", address),
-        ),
-    ];
-
-    for (api_name, url) in &apis {
-        for attempt in 1..=RETRY_ATTEMPTS {
-            match try_query_from_api(api_name, url).await {
-                Ok(balance) => {
-                    tracing::info!(
+pub async fn query_btc_balance(address: &str, network: BitcoinNetwork) -> Result<f64, String> {
+    let stats = query_btc_address_stats(address, network).await?;
+    Ok(stats.balance_sats as f64 / 100_000_000.0)
+}
+
+/// Query balance and transaction count for an address using blockchain APIs
+pub async fn query_btc_address_stats(
+    address: &str,
+    network: BitcoinNetwork,
+) -> Result<AddressStats, String> {
+    let backends = network_backends(network)?;
+
+    for backend in &backends {
+        let api_name = backend_label(backend);
+        let result = with_retries(&api_name, || backend_address_stats(backend, address)).await;
+        if let Ok(stats) = result {
+            return Ok(stats);
+        }
+    }
+
+    // Blockchain.info backs up the public defaults only; once the user
+    // configures their own backends, addresses must not leave them.
+    if network.has_blockchain_info_fallback() && uses_builtin_backends(&backends) {
+        let result = with_retries("Blockchain.info", || query_blockchain_info(address)).await;
+        if let Ok(stats) = result {
+            return Ok(stats);
+        }
+    }
+
+    Err("Failed to query balance from all blockchain APIs".to_string())
+}
+
+async fn with_retries<F, Fut>(api_name: &str, mut query: F) -> Result<AddressStats, String>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<AddressStats, String>>,
+{
+    let mut last_error = String::new();
+
+    for attempt in 1..=RETRY_ATTEMPTS {
+        match query().await {
+            Ok(stats) => {
+                tracing::info!(
+                    api = %sanitize(&format!("{}", api_name)),
+                    balance_sats = %sanitize(&format!("{}", stats.balance_sats)),
+                    "Retrieved BTC balance"
+                );
+                return Ok(stats);
+            }
+            Err(e) => {
+                if attempt < RETRY_ATTEMPTS {
+                    let delay_ms = INITIAL_RETRY_DELAY_MS * (2_u64.pow(attempt - 1));
+                    tracing::warn!(
+                        attempt = %sanitize(&format!("{}", attempt)),
                         api = %sanitize(&format!("{}", api_name)),
-                        balance = %sanitize(&format!("{}", balance)),
-                        "Retrieved BTC balance"
+                        delay_ms = %sanitize(&format!("{}", delay_ms)),
+                        error = %sanitize(&format!("{}", e)),
+                        "Retrying BTC balance query"
+                    );
+                    tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+                } else {
+                    tracing::warn!(
+                        api = %sanitize(&format!("{}", api_name)),
+                        "All attempts failed; trying next API"
                     );
-                    return Ok(balance);
-                }
-                Err(e) => {
-                    if attempt < RETRY_ATTEMPTS {
-                        let delay_ms = INITIAL_RETRY_DELAY_MS * (2_u64.pow(attempt - 1));
-                        tracing::warn!(
-                            attempt = %sanitize(&format!("{}", attempt)),
-                            api = %sanitize(&format!("{}", api_name)),
-                            delay_ms = %sanitize(&format!("{}", delay_ms)),
-                            error = %sanitize(&format!("{}", e)),
-                            "Retrying BTC balance query"
-                        );
-                        tokio::time::sleep(Duration::from_millis(delay_ms)).await;
-                    } else {
-                        tracing::warn!(
-                            api = %sanitize(&format!("{}", api_name)),
-                            "All attempts failed; trying next API"
-                        );
-                    }
                 }
+                last_error = e;
             }
         }
     }
 
-    Err("Failed to query balance from all blockchain APIs".to_string())
+    Err(last_error)
 }
 
-async fn try_query_from_api(api_name: &str, url: &str) -> Result<f64, String> {
+async fn query_blockchain_info(address: &str) -> Result<AddressStats, String> {
     let client = reqwest::Client::builder()
         .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
         .build()
         .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
 
     let response = client
-        .get(url)
+        .get(format!("https://blockchain.info/rawaddr/{}", address))
         .send()
         .await
         .map_err(|e| format!("HTTP request failed: {}", e))?;
@@ -157,47 +180,27 @@ async fn try_query_from_api(api_name: &str, url: &str) -> Result<f64, String> {
         return Err(format!("HTTP error: {}", response.status()));
     }
 
-    match api_name {
-        "Blockstream" => {
-            let data: BlockstreamResponse = response
-                .json()
-                .await
-                .map_err(|e| format!("Failed to parse response: {}", e))?;
-
-            // Calculate balance: (funded - spent) for both confirmed and mempool
-            let confirmed_balance =
-                data.chain_stats.funded_txo_sum as i64 - data.chain_stats.spent_txo_sum as i64;
-            let mempool_balance =
-                data.mempool_stats.funded_txo_sum as i64 - data.mempool_stats.spent_txo_sum as i64;
-
-            let total_satoshis = confirmed_balance + mempool_balance;
-            let btc_balance = total_satoshis as f64 / 100_000_000.0;
-
-            Ok(btc_balance)
-        }
-        "Blockchain.info" => {
-            let data: BlockchainInfoResponse = response
-                .json()
-                .await
-                .map_err(|e| format!("Failed to parse response: {}", e))?;
-
-            // final_balance is in satoshis
-            let btc_balance = data.final_balance as f64 / 100_000_000.0;
+    let data: BlockchainInfoResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
 
-            Ok(btc_balance)
-        }
-        _ => Err(format!("Unknown API: {}", api_name)),
-    }
+    // final_balance is in satoshis
+    Ok(AddressStats {
+        balance_sats: data.final_balance as i64,
+        tx_count: data.n_tx,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::BitcoinChainAdapter;
+    use crate::wallet::bitcoin::config::BitcoinNetwork;
     use crate::wallet::chain::traits::ChainAdapter;
 
     #[test]
     fn bitcoin_chain_adapter_reports_expected_identity() {
-        let adapter = BitcoinChainAdapter::new("bc1qtest");
+        let adapter = BitcoinChainAdapter::new(BitcoinNetwork::Mainnet, "bc1qtest");
 
         assert_eq!(adapter.chain_family(), "bitcoin");
         assert_eq!(adapter.chain_name(), "bitcoin");
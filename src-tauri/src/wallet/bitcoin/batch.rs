@@ -0,0 +1,548 @@
+use crate::wallet::bitcoin::amount::sats_to_btc;
+use crate::wallet::bitcoin::backend::connect_blockchain;
+use crate::wallet::bitcoin::config::BitcoinNetwork;
+use crate::wallet::bitcoin::preview::{
+    ensure_matches_preview, forget_preview, pending_batch_preview,
+};
+use crate::wallet::bitcoin::private_key::map_security_error;
+use crate::wallet::bitcoin::transaction::{
+    apply_coin_control, broadcast_transaction, load_signing_secret, open_signing_wallet,
+    parse_recipient_address, sign_transaction, spendable_balance, sync_signing_wallet,
+};
+use crate::wallet::bitcoin::utxo::CoinControl;
+use crate::wallet::security::backend::SecretBackend;
+use crate::wallet::security::keystore::Keystore;
+use crate::wallet::security::session::SessionManager;
+use crate::wallet::transaction_types::{
+    BitcoinBatchOutput, BitcoinTransaction, SendBitcoinBatchRequest, SendBitcoinBatchResponse,
+    TransactionStatus, TransactionType,
+};
+use crate::DB;
+use bdk::bitcoin::psbt::PartiallySignedTransaction;
+use bdk::bitcoin::{Address, TxOut};
+use bdk::database::SqliteDatabase;
+use bdk::psbt::PsbtUtils;
+use bdk::{FeeRate, Wallet};
+use chrono::Utc;
+use uuid::Uuid;
+
+/// Pay every output of the request from one transaction
+pub async fn send_bitcoin_batch(
+    request: SendBitcoinBatchRequest,
+    secret_backend: &SecretBackend,
+    keystore: &(dyn Keystore + Send + Sync),
+    session_manager: &SessionManager,
+) -> Result<SendBitcoinBatchResponse, String> {
+    let (wallet_info, network) = {
+        let db = DB.lock().unwrap();
+        let wallet_info = db
+            .get_bitcoin_wallet(&request.wallet_id)
+            .map_err(|e| format!("Failed to get wallet info: {}", e))?
+            .ok_or_else(|| "Wallet not found".to_string())?;
+        let network = db
+            .get_bitcoin_wallet_network(&request.wallet_id)
+            .map_err(|e| format!("Failed to get wallet network: {}", e))?;
+        (wallet_info, network)
+    };
+
+    // Every row is checked before the secret is touched or a backend is
+    // contacted, so one bad row fails the whole batch locally.
+    let outputs = batch_outputs(&request)?;
+    let recipients = validate_outputs(&outputs, network)?;
+    crate::safe_log!(
+        "[INFO] Sending Bitcoin batch of {} outputs from wallet: {}",
+        recipients.len(),
+        request.wallet_id
+    );
+
+    let signing_secret =
+        load_signing_secret(&wallet_info, secret_backend, keystore, session_manager)
+            .map_err(map_security_error)?
+            .ok_or_else(|| "Wallet secret not found".to_string())?;
+    // Opening the signing wallet stores its public descriptors, so batches
+    // follow the same preview rule as single sends from the first one on.
+    let (wallet, network) = open_signing_wallet(&wallet_info, signing_secret)?;
+    let preview_txid = request
+        .preview_txid
+        .clone()
+        .ok_or_else(|| "Preview the batch before sending it".to_string())?;
+    // A batch signs exactly the transaction that was reviewed.
+    let psbt = pending_batch_preview(&wallet_info.id, &request, &outputs, &preview_txid)?;
+
+    let (wallet, blockchain) =
+        sync_signing_wallet(wallet, network, &wallet_info.id, connect_blockchain).await?;
+
+    let total_sats: u64 = recipients.iter().map(|(_, amount)| amount).sum();
+    let fee_sats = psbt.fee_amount().unwrap_or(0);
+    let vouts = recipient_vouts(&recipients, &psbt.unsigned_tx.output)?;
+
+    let tx = sign_transaction(&wallet, psbt)?;
+    ensure_matches_preview(&tx, &preview_txid)?;
+    broadcast_transaction(blockchain, &tx).await?;
+    forget_preview(&preview_txid);
+    let tx_hash = tx.txid().to_string();
+
+    let batch_id = Uuid::new_v4().to_string();
+    let records = batch_records(
+        &request.wallet_id,
+        &wallet_info.address,
+        &tx_hash,
+        &batch_id,
+        &recipients,
+        &vouts,
+        fee_sats,
+    );
+    {
+        let db = DB.lock().unwrap();
+        db.add_bitcoin_transactions(&records)
+            .map_err(|e| format!("Failed to save transaction: {}", e))?;
+    }
+
+    crate::safe_log!("[SUCCESS] Bitcoin batch payment completed: {}", tx_hash);
+
+    Ok(SendBitcoinBatchResponse {
+        tx_hash,
+        batch_id,
+        output_count: records.len(),
+        total_sats,
+        fee_sats,
+        message: "Batch payment sent successfully".to_string(),
+    })
+}
+
+/// Builds the unsigned batch at `fee_rate` from the coins coin control allows.
+pub(super) fn build_batch_psbt(
+    wallet: &Wallet<SqliteDatabase>,
+    recipients: &[(Address, u64)],
+    coin_control: &CoinControl,
+    fee_rate: f64,
+) -> Result<PartiallySignedTransaction, String> {
+    let total_sats: u64 = recipients.iter().map(|(_, amount)| amount).sum();
+    let total_balance = spendable_balance(wallet, coin_control)?;
+    if total_sats > total_balance {
+        return Err(format!(
+            "Insufficient funds: batch pays {} satoshis but only {} are spendable",
+            total_sats, total_balance
+        ));
+    }
+
+    let mut tx_builder = wallet.build_tx();
+    apply_coin_control(&mut tx_builder, coin_control)?;
+    for (address, amount_sats) in recipients {
+        tx_builder.add_recipient(address.payload.script_pubkey(), *amount_sats);
+    }
+    // Signal BIP125 replaceability so a stuck batch can be fee-bumped later
+    tx_builder.enable_rbf();
+    tx_builder.fee_rate(FeeRate::from_sat_per_vb(fee_rate as f32));
+    let (psbt, _) = tx_builder
+        .finish()
+        .map_err(|e| format!("Failed to build transaction: {}", e))?;
+    Ok(psbt)
+}
+
+/// Inline outputs followed by those imported from CSV text or a CSV file.
+pub(super) fn batch_outputs(
+    request: &SendBitcoinBatchRequest,
+) -> Result<Vec<BitcoinBatchOutput>, String> {
+    let mut outputs = request.outputs.clone();
+    match (request.csv.as_deref(), request.csv_path.as_deref()) {
+        (Some(_), Some(_)) => {
+            return Err("Provide either CSV text or a CSV file, not both".to_string());
+        }
+        (Some(csv), None) => outputs.extend(parse_batch_csv(csv)?),
+        (None, Some(path)) => {
+            let csv = std::fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read CSV file: {}", e))?;
+            outputs.extend(parse_batch_csv(&csv)?);
+        }
+        (None, None) => {}
+    }
+
+    Ok(outputs)
+}
+
+/// Reads `address,amount_sats` rows. Blank lines, `#` comments and a leading
+/// `address,...` header row are skipped.
+fn parse_batch_csv(csv: &str) -> Result<Vec<BitcoinBatchOutput>, String> {
+    let mut outputs = Vec::new();
+    let mut seen_row = false;
+
+    for (index, line) in csv.trim_start_matches('\u{feff}').lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line_number = index + 1;
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let [address, amount] = fields[..] else {
+            return Err(format!(
+                "CSV line {}: expected 'address,amount_sats'",
+                line_number
+            ));
+        };
+
+        let is_header = !seen_row && address.eq_ignore_ascii_case("address");
+        seen_row = true;
+        if is_header {
+            continue;
+        }
+
+        let amount_sats = amount
+            .parse::<u64>()
+            .map_err(|_| format!("CSV line {}: invalid amount '{}'", line_number, amount))?;
+        outputs.push(BitcoinBatchOutput {
+            address: address.to_string(),
+            amount_sats,
+        });
+    }
+
+    Ok(outputs)
+}
+
+/// Parses every output address for the wallet's network and rejects empty
+/// or dust amounts, naming the offending output.
+pub(super) fn validate_outputs(
+    outputs: &[BitcoinBatchOutput],
+    network: BitcoinNetwork,
+) -> Result<Vec<(Address, u64)>, String> {
+    if outputs.is_empty() {
+        return Err("Batch payment needs at least one output".to_string());
+    }
+
+    outputs
+        .iter()
+        .enumerate()
+        .map(|(index, output)| {
+            let position = index + 1;
+            let address = parse_recipient_address(&output.address, network)
+                .map_err(|e| format!("Output {}: {}", position, e))?;
+            let dust_limit = address.payload.script_pubkey().dust_value().to_sat();
+            if output.amount_sats < dust_limit {
+                return Err(format!(
+                    "Output {}: amount {} is below the dust limit of {} satoshis",
+                    position, output.amount_sats, dust_limit
+                ));
+            }
+            Ok((address, output.amount_sats))
+        })
+        .collect()
+}
+
+/// The vout of every recipient. BDK shuffles outputs, so each recipient is
+/// matched by script and amount; equal recipients take distinct vouts in order.
+fn recipient_vouts(recipients: &[(Address, u64)], outputs: &[TxOut]) -> Result<Vec<u32>, String> {
+    let mut used = vec![false; outputs.len()];
+    let mut vouts = Vec::with_capacity(recipients.len());
+
+    for (index, (address, amount_sats)) in recipients.iter().enumerate() {
+        let script_pubkey = address.payload.script_pubkey();
+        let vout = (0..outputs.len())
+            .find(|&vout| {
+                !used[vout]
+                    && outputs[vout].value == *amount_sats
+                    && outputs[vout].script_pubkey == script_pubkey
+            })
+            .ok_or_else(|| format!("Output {} is missing from the transaction", index + 1))?;
+        used[vout] = true;
+        vouts.push(vout as u32);
+    }
+
+    Ok(vouts)
+}
+
+/// One history row per output, sharing the batch id and keyed by the
+/// output's vout. The fee is recorded on the first row only so summing the
+/// rows counts it once.
+fn batch_records(
+    wallet_id: &str,
+    from_address: &str,
+    tx_hash: &str,
+    batch_id: &str,
+    recipients: &[(Address, u64)],
+    vouts: &[u32],
+    fee_sats: u64,
+) -> Vec<BitcoinTransaction> {
+    let now = Utc::now().to_rfc3339();
+
+    recipients
+        .iter()
+        .zip(vouts)
+        .enumerate()
+        .map(
+            |(index, ((address, amount_sats), vout))| BitcoinTransaction {
+                id: Uuid::new_v4().to_string(),
+                wallet_id: wallet_id.to_string(),
+                tx_hash: tx_hash.to_string(),
+                tx_type: TransactionType::Send,
+                from_address: from_address.to_string(),
+                to_address: address.to_string(),
+                amount_sats: *amount_sats,
+                fee_sats: if index == 0 { fee_sats } else { 0 },
+                amount: sats_to_btc(*amount_sats),
+                fee: if index == 0 {
+                    sats_to_btc(fee_sats)
+                } else {
+                    0.0
+                },
+                status: TransactionStatus::after_broadcast(),
+                confirmations: 0,
+                block_height: None,
+                timestamp: now.clone(),
+                created_at: now.clone(),
+                replaces_tx_hash: None,
+                replaced_by_tx_hash: None,
+                parent_tx_hash: None,
+                batch_id: Some(batch_id.to_string()),
+                output_index: *vout,
+                label: None,
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        batch_records, parse_batch_csv, recipient_vouts, send_bitcoin_batch, validate_outputs,
+    };
+    use crate::wallet::bitcoin::config::BitcoinNetwork;
+    use crate::wallet::security::backend::{SecretBackend, SecretBackendAdapter};
+    use crate::wallet::security::keystore::SqliteKeystore;
+    use crate::wallet::security::secret_envelope::{
+        decrypt_secret, encrypt_secret, SecretEnvelopeError, StoredSecret,
+        SECRET_FORMAT_PLAINTEXT_V0,
+    };
+    use crate::wallet::security::session::SessionManager;
+    use crate::wallet::security::types::SignerOperation;
+    use crate::wallet::transaction_types::{
+        BitcoinBatchOutput, SendBitcoinBatchRequest, TransactionStatus,
+    };
+    use crate::DB;
+    use bdk::bitcoin::{ScriptBuf, TxOut};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    const MAINNET_ADDRESS: &str = "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq";
+    const MAINNET_P2PKH: &str = "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2";
+    const TESTNET_ADDRESS: &str = "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx";
+
+    struct TestSecretBackendAdapter;
+
+    impl SecretBackendAdapter for TestSecretBackendAdapter {
+        fn probe(&self) -> Result<(), SecretEnvelopeError> {
+            Ok(())
+        }
+
+        fn initialize_empty_store(&self) -> Result<(), SecretEnvelopeError> {
+            Ok(())
+        }
+
+        fn encrypt(&self, plaintext: &str) -> Result<StoredSecret, SecretEnvelopeError> {
+            encrypt_secret(plaintext)
+        }
+
+        fn decrypt(
+            &self,
+            secret_data: &str,
+            secret_format: &str,
+        ) -> Result<String, SecretEnvelopeError> {
+            decrypt_secret(secret_data, secret_format)
+        }
+    }
+
+    fn output(address: &str, amount_sats: u64) -> BitcoinBatchOutput {
+        BitcoinBatchOutput {
+            address: address.to_string(),
+            amount_sats,
+        }
+    }
+
+    #[test]
+    fn csv_skips_header_comments_and_blank_lines() {
+        let csv = format!(
+            "\u{feff}Address, Amount_Sats\n# payroll\n\n{}, 15000\n{},2000\n",
+            MAINNET_ADDRESS, MAINNET_P2PKH
+        );
+
+        let outputs = parse_batch_csv(&csv).unwrap();
+
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[0].address, MAINNET_ADDRESS);
+        assert_eq!(outputs[0].amount_sats, 15_000);
+        assert_eq!(outputs[1].address, MAINNET_P2PKH);
+        assert_eq!(outputs[1].amount_sats, 2_000);
+    }
+
+    #[test]
+    fn csv_errors_name_the_line() {
+        assert_eq!(
+            parse_batch_csv(&format!("{},1000\n{}", MAINNET_ADDRESS, MAINNET_ADDRESS)).unwrap_err(),
+            "CSV line 2: expected 'address,amount_sats'"
+        );
+        assert_eq!(
+            parse_batch_csv(&format!("{},0.5", MAINNET_ADDRESS)).unwrap_err(),
+            "CSV line 1: invalid amount '0.5'"
+        );
+    }
+
+    #[test]
+    fn every_output_is_validated_before_sending() {
+        let recipients = validate_outputs(
+            &[output(MAINNET_ADDRESS, 10_000), output(MAINNET_P2PKH, 546)],
+            BitcoinNetwork::Mainnet,
+        )
+        .unwrap();
+        assert_eq!(recipients.len(), 2);
+
+        let wrong_network = validate_outputs(
+            &[
+                output(MAINNET_ADDRESS, 10_000),
+                output(TESTNET_ADDRESS, 10_000),
+            ],
+            BitcoinNetwork::Mainnet,
+        )
+        .unwrap_err();
+        assert!(wrong_network.starts_with("Output 2: Address network mismatch"));
+
+        assert_eq!(
+            validate_outputs(&[output(MAINNET_P2PKH, 545)], BitcoinNetwork::Mainnet).unwrap_err(),
+            "Output 1: amount 545 is below the dust limit of 546 satoshis"
+        );
+        assert_eq!(
+            validate_outputs(&[], BitcoinNetwork::Mainnet).unwrap_err(),
+            "Batch payment needs at least one output"
+        );
+    }
+
+    #[test]
+    fn batch_rows_share_the_batch_and_survive_history_refresh() {
+        let recipients = validate_outputs(
+            &[
+                output(MAINNET_ADDRESS, 10_000),
+                output(MAINNET_P2PKH, 20_000),
+            ],
+            BitcoinNetwork::Mainnet,
+        )
+        .unwrap();
+        // The change output landed first and the recipients were shuffled.
+        let tx_outputs = vec![
+            TxOut {
+                value: 70_000,
+                script_pubkey: ScriptBuf::new(),
+            },
+            TxOut {
+                value: 20_000,
+                script_pubkey: recipients[1].0.payload.script_pubkey(),
+            },
+            TxOut {
+                value: 10_000,
+                script_pubkey: recipients[0].0.payload.script_pubkey(),
+            },
+        ];
+        let vouts = recipient_vouts(&recipients, &tx_outputs).unwrap();
+        assert_eq!(vouts, vec![2, 1]);
+        assert!(recipient_vouts(&recipients, &tx_outputs[..2]).is_err());
+
+        let unique = Uuid::new_v4().simple().to_string();
+        let db = DB.lock().unwrap();
+        let wallet = db
+            .insert_bitcoin_watch_only_wallet(
+                format!("BTC batch {unique}"),
+                format!("btc-batch-{unique}"),
+            )
+            .unwrap();
+        let records = batch_records(
+            &wallet.id,
+            &wallet.address,
+            &format!("batch-tx-{unique}"),
+            "batch-1",
+            &recipients,
+            &vouts,
+            500,
+        );
+        db.add_bitcoin_transactions(&records).unwrap();
+
+        // A history refresh rewrites the transaction as a single row.
+        let mut refreshed = records[0].clone();
+        refreshed.batch_id = None;
+        refreshed.to_address = "Unknown".to_string();
+        refreshed.amount_sats = 30_000;
+        refreshed.status = TransactionStatus::Confirmed;
+        refreshed.confirmations = 6;
+        refreshed.block_height = Some(800_000);
+        db.add_bitcoin_transaction(&refreshed).unwrap();
+
+        let rows = db.get_bitcoin_transactions(&wallet.id).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].to_address, MAINNET_P2PKH);
+        assert_eq!(rows[0].amount_sats, 20_000);
+        assert_eq!(rows[0].fee_sats, 0);
+        assert_eq!(rows[0].output_index, 1);
+        assert_eq!(rows[1].to_address, MAINNET_ADDRESS);
+        assert_eq!(rows[1].fee_sats, 500);
+        assert_eq!(rows[1].output_index, 2);
+        for row in &rows {
+            assert_eq!(row.batch_id.as_deref(), Some("batch-1"));
+            assert_eq!(row.status, TransactionStatus::Confirmed);
+            assert_eq!(row.block_height, Some(800_000));
+        }
+    }
+
+    #[tokio::test]
+    async fn batch_requires_a_preview() {
+        let unique = Uuid::new_v4().simple().to_string();
+        let wallet = DB
+            .lock()
+            .unwrap()
+            .insert_bitcoin_wallet_with_secret(
+                format!("BTC batch gate {unique}"),
+                "mnemonic".to_string(),
+                format!("btc-batch-gate-{unique}"),
+                StoredSecret {
+                    secret_data: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_string(),
+                    secret_format: SECRET_FORMAT_PLAINTEXT_V0.to_string(),
+                },
+                "mnemonic".to_string(),
+            )
+            .unwrap();
+        let secret_backend = SecretBackend::with_adapter(Arc::new(TestSecretBackendAdapter));
+        let keystore = SqliteKeystore::new(
+            &DB,
+            Arc::new(SecretBackend::with_adapter(Arc::new(
+                TestSecretBackendAdapter,
+            ))),
+        );
+        let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
+        session
+            .authorize_verified_operation(SignerOperation::Send)
+            .unwrap();
+
+        let result = send_bitcoin_batch(
+            SendBitcoinBatchRequest {
+                wallet_id: wallet.id.clone(),
+                outputs: vec![output(MAINNET_ADDRESS, 10_000)],
+                csv: None,
+                csv_path: None,
+                fee_rate: Some(5.0),
+                target_blocks: None,
+                include_outpoints: None,
+                exclude_outpoints: None,
+                preview_txid: None,
+            },
+            &secret_backend,
+            &keystore,
+            &session,
+        )
+        .await;
+        let descriptors = DB
+            .lock()
+            .unwrap()
+            .get_bitcoin_wallet_descriptors(&wallet.id)
+            .unwrap();
+        let _ = DB.lock().unwrap().delete_bitcoin_wallet(&wallet.id);
+
+        assert_eq!(result.unwrap_err(), "Preview the batch before sending it");
+        assert!(descriptors.is_some());
+    }
+}
@@ -0,0 +1,321 @@
+use crate::wallet::bitcoin::config::BitcoinNetwork;
+use crate::wallet::bitcoin::descriptor::BitcoinKeychain;
+use crate::wallet::bitcoin::discovery;
+use crate::wallet::bitcoin::transaction::parse_recipient_address;
+use crate::wallet::transaction_types::{
+    BitcoinPaymentUri, BitcoinPaymentUriResponse, CreateBitcoinPaymentUriRequest,
+    SendBitcoinRequest,
+};
+use crate::DB;
+use bdk::bitcoin::{Amount, Denomination};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use std::collections::BTreeMap;
+
+const URI_SCHEME: &str = "bitcoin:";
+
+/// Characters left unescaped in URI parameter values (RFC 3986 unreserved).
+const QUERY_VALUE: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// The fields of a BIP21 URI, with the address checked against the network.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PaymentUri {
+    pub(crate) address: String,
+    pub(crate) amount_sats: Option<u64>,
+    pub(crate) label: Option<String>,
+    pub(crate) message: Option<String>,
+    pub(crate) lightning: Option<String>,
+    pub(crate) extra_params: BTreeMap<String, String>,
+}
+
+/// Decodes a `bitcoin:` URI into a send request for `wallet_id`, using the
+/// same address and network checks as sending.
+pub fn parse_bitcoin_payment_uri(wallet_id: &str, uri: &str) -> Result<BitcoinPaymentUri, String> {
+    let network = {
+        let db = DB.lock().unwrap();
+        db.get_bitcoin_wallet(wallet_id)
+            .map_err(|e| format!("Failed to get wallet: {}", e))?
+            .ok_or_else(|| "Wallet not found".to_string())?;
+        db.get_bitcoin_wallet_network(wallet_id)
+            .map_err(|e| format!("Failed to get wallet network: {}", e))?
+    };
+
+    let parsed = parse_payment_uri(uri, network)?;
+    Ok(BitcoinPaymentUri {
+        request: SendBitcoinRequest {
+            wallet_id: wallet_id.to_string(),
+            to_address: parsed.address,
+            amount_sats: parsed.amount_sats.unwrap_or(0),
+            fee_rate: None,
+            target_blocks: None,
+            send_all: None,
+            include_outpoints: None,
+            exclude_outpoints: None,
+            preview_txid: None,
+        },
+        amount_specified: parsed.amount_sats.is_some(),
+        label: parsed.label,
+        message: parsed.message,
+        lightning: parsed.lightning,
+        extra_params: parsed.extra_params,
+    })
+}
+
+/// Builds a `bitcoin:` URI paying one of the wallet's receive addresses,
+/// the next unused one unless the request names one.
+pub async fn create_bitcoin_payment_uri(
+    request: CreateBitcoinPaymentUriRequest,
+) -> Result<BitcoinPaymentUriResponse, String> {
+    let (wallet, network, known_addresses) = {
+        let db = DB.lock().unwrap();
+        let wallet = db
+            .get_bitcoin_wallet(&request.wallet_id)
+            .map_err(|e| format!("Failed to get wallet: {}", e))?
+            .ok_or_else(|| "Wallet not found".to_string())?;
+        let network = db
+            .get_bitcoin_wallet_network(&request.wallet_id)
+            .map_err(|e| format!("Failed to get wallet network: {}", e))?;
+        let known_addresses = db
+            .get_bitcoin_wallet_addresses(&request.wallet_id)
+            .map_err(|e| format!("Failed to get wallet addresses: {}", e))?;
+        (wallet, network, known_addresses)
+    };
+
+    if request.amount_sats == Some(0) {
+        return Err("Payment amount must be greater than zero".to_string());
+    }
+
+    let address = match request.address.as_deref().map(str::trim) {
+        Some(address) => {
+            let is_receive_address = address == wallet.address
+                || known_addresses.iter().any(|known| {
+                    known.keychain == BitcoinKeychain::External && known.address == address
+                });
+            if !is_receive_address {
+                return Err("Address is not a receive address of this wallet".to_string());
+            }
+            address.to_string()
+        }
+        None => {
+            discovery::next_unused_receive_address(&wallet)
+                .await?
+                .address
+        }
+    };
+    let address = parse_recipient_address(&address, network)?.to_string();
+
+    Ok(BitcoinPaymentUriResponse {
+        uri: payment_uri(
+            &address,
+            request.amount_sats,
+            request.label.as_deref(),
+            request.message.as_deref(),
+        ),
+        address,
+    })
+}
+
+pub(crate) fn parse_payment_uri(uri: &str, network: BitcoinNetwork) -> Result<PaymentUri, String> {
+    let uri = uri.trim();
+    // The scheme is case-insensitive so uppercase QR payloads parse too.
+    let rest = uri
+        .get(..URI_SCHEME.len())
+        .filter(|scheme| scheme.eq_ignore_ascii_case(URI_SCHEME))
+        .map(|_| &uri[URI_SCHEME.len()..])
+        .ok_or_else(|| "Payment URI must start with 'bitcoin:'".to_string())?;
+    let (address, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+    let mut parsed = PaymentUri {
+        address: String::new(),
+        amount_sats: None,
+        label: None,
+        message: None,
+        lightning: None,
+        extra_params: BTreeMap::new(),
+    };
+
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let key = key.to_ascii_lowercase();
+        let value = percent_decode_str(value)
+            .decode_utf8()
+            .map_err(|e| format!("Invalid payment URI parameter '{}': {}", key, e))?
+            .into_owned();
+
+        let slot = match key.as_str() {
+            "amount" => {
+                if parsed.amount_sats.is_some() {
+                    return Err("Payment URI repeats the 'amount' parameter".to_string());
+                }
+                let amount = Amount::from_str_in(&value, Denomination::Bitcoin)
+                    .map_err(|e| format!("Invalid payment URI amount '{}': {}", value, e))?;
+                if amount == Amount::ZERO {
+                    return Err("Payment amount must be greater than zero".to_string());
+                }
+                parsed.amount_sats = Some(amount.to_sat());
+                continue;
+            }
+            "label" => &mut parsed.label,
+            "message" => &mut parsed.message,
+            "lightning" => &mut parsed.lightning,
+            // BIP21: a required parameter the wallet does not understand
+            // makes the whole URI unusable.
+            _ if key.starts_with("req-") => {
+                return Err(format!(
+                    "Payment URI requires unsupported parameter '{}'",
+                    key
+                ));
+            }
+            _ => {
+                parsed.extra_params.insert(key, value);
+                continue;
+            }
+        };
+        if slot.replace(value).is_some() {
+            return Err(format!("Payment URI repeats the '{}' parameter", key));
+        }
+    }
+
+    if address.is_empty() {
+        return Err("Payment URI has no on-chain address".to_string());
+    }
+    parsed.address = parse_recipient_address(address, network)?.to_string();
+
+    Ok(parsed)
+}
+
+pub(crate) fn payment_uri(
+    address: &str,
+    amount_sats: Option<u64>,
+    label: Option<&str>,
+    message: Option<&str>,
+) -> String {
+    let mut params = Vec::new();
+    if let Some(sats) = amount_sats {
+        params.push(format!(
+            "amount={}",
+            Amount::from_sat(sats).to_string_in(Denomination::Bitcoin)
+        ));
+    }
+    for (key, value) in [("label", label), ("message", message)] {
+        if let Some(value) = value.map(str::trim).filter(|value| !value.is_empty()) {
+            params.push(format!(
+                "{}={}",
+                key,
+                utf8_percent_encode(value, QUERY_VALUE)
+            ));
+        }
+    }
+
+    if params.is_empty() {
+        format!("{}{}", URI_SCHEME, address)
+    } else {
+        format!("{}{}?{}", URI_SCHEME, address, params.join("&"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_payment_uri, payment_uri};
+    use crate::wallet::bitcoin::config::BitcoinNetwork;
+
+    const MAINNET_ADDRESS: &str = "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq";
+
+    #[test]
+    fn parses_amount_label_message_and_lightning() {
+        let uri = format!(
+            "bitcoin:{}?amount=0.0005&label=Coffee%20Shop&message=Order%20%2342&lightning=lnbc500u1example&pj=https://example.com",
+            MAINNET_ADDRESS
+        );
+
+        let parsed = parse_payment_uri(&uri, BitcoinNetwork::Mainnet).unwrap();
+
+        assert_eq!(parsed.address, MAINNET_ADDRESS);
+        assert_eq!(parsed.amount_sats, Some(50_000));
+        assert_eq!(parsed.label.as_deref(), Some("Coffee Shop"));
+        assert_eq!(parsed.message.as_deref(), Some("Order #42"));
+        assert_eq!(parsed.lightning.as_deref(), Some("lnbc500u1example"));
+        assert_eq!(parsed.extra_params["pj"], "https://example.com");
+    }
+
+    #[test]
+    fn accepts_uppercase_qr_payloads() {
+        let uri = format!("BITCOIN:{}", MAINNET_ADDRESS.to_uppercase());
+
+        let parsed = parse_payment_uri(&uri, BitcoinNetwork::Mainnet).unwrap();
+
+        assert_eq!(parsed.address, MAINNET_ADDRESS);
+        assert_eq!(parsed.amount_sats, None);
+    }
+
+    #[test]
+    fn rejects_unknown_required_params_bad_amounts_and_wrong_network() {
+        let cases = [
+            (
+                format!("bitcoin:{}?req-somethingnew=1", MAINNET_ADDRESS),
+                "Payment URI requires unsupported parameter 'req-somethingnew'",
+            ),
+            (
+                format!("bitcoin:{}?amount=0.000000001", MAINNET_ADDRESS),
+                "Invalid payment URI amount '0.000000001': amount has a too high precision",
+            ),
+            (
+                format!("bitcoin:{}?amount=1&amount=2", MAINNET_ADDRESS),
+                "Payment URI repeats the 'amount' parameter",
+            ),
+            (
+                "bitcoin:?lightning=lnbc1example".to_string(),
+                "Payment URI has no on-chain address",
+            ),
+            (
+                MAINNET_ADDRESS.to_string(),
+                "Payment URI must start with 'bitcoin:'",
+            ),
+        ];
+        for (uri, expected) in cases {
+            assert_eq!(
+                parse_payment_uri(&uri, BitcoinNetwork::Mainnet).unwrap_err(),
+                expected
+            );
+        }
+
+        assert!(parse_payment_uri(
+            &format!("bitcoin:{}", MAINNET_ADDRESS),
+            BitcoinNetwork::Testnet
+        )
+        .unwrap_err()
+        .starts_with("Address network mismatch"));
+    }
+
+    #[test]
+    fn generated_uris_round_trip() {
+        let uri = payment_uri(
+            MAINNET_ADDRESS,
+            Some(150_000),
+            Some("Rent & utilities"),
+            None,
+        );
+
+        assert_eq!(
+            uri,
+            format!(
+                "bitcoin:{}?amount=0.0015&label=Rent%20%26%20utilities",
+                MAINNET_ADDRESS
+            )
+        );
+        let parsed = parse_payment_uri(&uri, BitcoinNetwork::Mainnet).unwrap();
+        assert_eq!(parsed.amount_sats, Some(150_000));
+        assert_eq!(parsed.label.as_deref(), Some("Rent & utilities"));
+        assert_eq!(
+            payment_uri(MAINNET_ADDRESS, None, Some("  "), None),
+            format!("bitcoin:{}", MAINNET_ADDRESS)
+        );
+        assert_eq!(
+            payment_uri(MAINNET_ADDRESS, Some(1), None, None),
+            format!("bitcoin:{}?amount=0.00000001", MAINNET_ADDRESS)
+        );
+    }
+}
@@ -1,8 +1,14 @@
+use crate::wallet::bitcoin::config::BitcoinNetwork;
+use crate::wallet::bitcoin::private_key::map_security_error;
+use crate::wallet::bitcoin::{backend, discovery, labels, utxo, wallet_store};
+use crate::wallet::security::commands::AppSecurity;
 use crate::wallet::state::types::BalanceState;
 use crate::wallet::sync::engine;
 use crate::wallet::sync::types::SyncReason;
 use crate::wallet::types::{
-    BitcoinWalletBalanceResponse, FreshnessBackedBitcoinBalance, WalletInfo,
+    BitcoinAddressInfo, BitcoinBackendConfig, BitcoinLabel, BitcoinLabelType, BitcoinUtxo,
+    BitcoinWalletBalanceResponse, FreshnessBackedBitcoinBalance, ImportBitcoinLabelsRequest,
+    ImportBitcoinLabelsResponse, SaveBitcoinBackendRequest, WalletInfo,
 };
 use crate::DB;
 
@@ -26,7 +32,7 @@ fn load_bitcoin_balance_state(wallet_id: &str) -> Result<BalanceState, String> {
         .ok_or_else(|| "Bitcoin wallet freshness not found".to_string())?;
 
     Ok(BalanceState {
-        raw_amount: wallet.balance.to_string(),
+        raw_amount: wallet.balance_sats.unwrap_or(0).to_string(),
         display_amount: wallet.balance,
         chain_id: None,
         freshness,
@@ -94,9 +100,166 @@ pub async fn refresh_bitcoin_wallet_balance(
     Ok(to_bitcoin_balance_response(wallet, balance_state))
 }
 
+#[tauri::command]
+pub fn bitcoin_get_wallet_addresses(wallet_id: String) -> Result<Vec<BitcoinAddressInfo>, String> {
+    let db = DB.lock().map_err(|e| e.to_string())?;
+    db.get_bitcoin_wallet_addresses(&wallet_id)
+        .map_err(|e| format!("Failed to get wallet addresses: {}", e))
+}
+
+#[tauri::command]
+pub async fn bitcoin_get_next_receive_address(
+    wallet_id: String,
+) -> Result<BitcoinAddressInfo, String> {
+    let wallet = {
+        let db = DB.lock().map_err(|e| e.to_string())?;
+        db.get_bitcoin_wallet(&wallet_id)
+            .map_err(|e| format!("Failed to get wallet: {}", e))?
+            .ok_or_else(|| "Wallet not found".to_string())?
+    };
+
+    discovery::next_unused_receive_address(&wallet).await
+}
+
+#[tauri::command]
+pub async fn bitcoin_list_utxos(wallet_id: String) -> Result<Vec<BitcoinUtxo>, String> {
+    let wallet = {
+        let db = DB.lock().map_err(|e| e.to_string())?;
+        db.get_bitcoin_wallet(&wallet_id)
+            .map_err(|e| format!("Failed to get wallet: {}", e))?
+            .ok_or_else(|| "Wallet not found".to_string())?
+    };
+
+    utxo::list_wallet_utxos(&wallet).await
+}
+
+#[tauri::command]
+pub fn bitcoin_set_utxo_frozen(
+    wallet_id: String,
+    outpoint: String,
+    frozen: bool,
+) -> Result<(), String> {
+    let outpoint = utxo::normalize_outpoint(&outpoint)?;
+    let db = DB.lock().map_err(|e| e.to_string())?;
+    db.set_bitcoin_utxo_frozen(&wallet_id, &outpoint, frozen)
+        .map_err(|e| format!("Failed to update UTXO: {}", e))
+}
+
+#[tauri::command]
+pub fn bitcoin_set_utxo_label(
+    wallet_id: String,
+    outpoint: String,
+    label: Option<String>,
+) -> Result<(), String> {
+    let outpoint = utxo::normalize_outpoint(&outpoint)?;
+    let label = label
+        .map(|label| label.trim().to_string())
+        .filter(|label| !label.is_empty());
+    let db = DB.lock().map_err(|e| e.to_string())?;
+    db.set_bitcoin_utxo_label(&wallet_id, &outpoint, label.as_deref())
+        .map_err(|e| format!("Failed to update UTXO: {}", e))
+}
+
+#[tauri::command]
+pub fn bitcoin_set_label(
+    wallet_id: String,
+    label_type: BitcoinLabelType,
+    reference: String,
+    label: Option<String>,
+) -> Result<(), String> {
+    labels::set_bitcoin_label(&wallet_id, label_type, &reference, label)
+}
+
+#[tauri::command]
+pub fn bitcoin_get_labels(wallet_id: String) -> Result<Vec<BitcoinLabel>, String> {
+    let db = DB.lock().map_err(|e| e.to_string())?;
+    db.get_bitcoin_labels(&wallet_id)
+        .map_err(|e| format!("Failed to get wallet labels: {}", e))
+}
+
+#[tauri::command]
+pub fn bitcoin_import_labels(
+    request: ImportBitcoinLabelsRequest,
+) -> Result<ImportBitcoinLabelsResponse, String> {
+    labels::import_bitcoin_labels(request)
+}
+
+#[tauri::command]
+pub fn bitcoin_export_labels(
+    wallet_id: String,
+    output_path: Option<String>,
+) -> Result<String, String> {
+    labels::export_bitcoin_labels(&wallet_id, output_path.as_deref())
+}
+
+#[tauri::command]
+pub fn bitcoin_get_backends(
+    network: Option<BitcoinNetwork>,
+) -> Result<Vec<BitcoinBackendConfig>, String> {
+    let db = DB.lock().map_err(|e| e.to_string())?;
+    db.get_bitcoin_backends(network)
+        .map_err(|e| format!("Failed to get Bitcoin backends: {}", e))
+}
+
+#[tauri::command]
+pub fn bitcoin_save_backend(
+    request: SaveBitcoinBackendRequest,
+    state: tauri::State<'_, AppSecurity>,
+) -> Result<BitcoinBackendConfig, String> {
+    let request = backend::normalize_backend_request(request)?;
+    let rpc_password = request
+        .rpc_password
+        .as_deref()
+        .map(|password| state.secret_backend().prepare_encrypted_secret(password))
+        .transpose()
+        .map_err(map_security_error)?;
+    let db = DB.lock().map_err(|e| e.to_string())?;
+    db.save_bitcoin_backend(&request, rpc_password.as_ref())
+        .map_err(|e| format!("Failed to save Bitcoin backend: {}", e))
+}
+
+#[tauri::command]
+pub fn bitcoin_delete_backend(backend_id: String) -> Result<bool, String> {
+    let db = DB.lock().map_err(|e| e.to_string())?;
+    db.delete_bitcoin_backend(&backend_id)
+        .map_err(|e| format!("Failed to delete Bitcoin backend: {}", e))
+}
+
+/// Checks a backend before saving it; returns the chain tip height it reports.
+#[tauri::command]
+pub async fn bitcoin_test_backend(request: SaveBitcoinBackendRequest) -> Result<u32, String> {
+    let request = backend::normalize_backend_request(request)?;
+    // An edit form does not resend the saved password.
+    let rpc_password = match (request.rpc_password, &request.id) {
+        (Some(password), _) => Some(password),
+        (None, Some(id)) => backend::load_rpc_password(id)?,
+        (None, None) => None,
+    };
+
+    let config = BitcoinBackendConfig {
+        id: request.id.unwrap_or_default(),
+        network: request.network,
+        kind: request.kind,
+        url: request.url,
+        rpc_username: request.rpc_username,
+        has_rpc_password: rpc_password.is_some(),
+        rpc_password,
+        priority: 0,
+        enabled: request.enabled,
+        created_at: String::new(),
+        updated_at: String::new(),
+    };
+
+    backend::backend_tip_height(&config).await
+}
+
 #[tauri::command]
 pub fn bitcoin_delete_wallet(wallet_id: String) -> Result<bool, String> {
-    let db = DB.lock().unwrap();
-    db.delete_bitcoin_wallet(&wallet_id)
-        .map_err(|e| format!("Failed to delete wallet: {}", e))
+    let deleted = {
+        let db = DB.lock().unwrap();
+        db.delete_bitcoin_wallet(&wallet_id)
+            .map_err(|e| format!("Failed to delete wallet: {}", e))?
+    };
+    wallet_store::clear_wallet_state(&wallet_id)?;
+    Ok(deleted)
 }
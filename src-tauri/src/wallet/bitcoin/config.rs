@@ -0,0 +1,159 @@
+use bdk::bitcoin::Network as BdkNetwork;
+use bitcoin::Network;
+use serde::{Deserialize, Serialize};
+
+fn env_with_fallback(keys: &[&str], fallback: &str) -> String {
+    for key in keys {
+        if let Ok(value) = std::env::var(key) {
+            if !value.trim().is_empty() {
+                return value;
+            }
+        }
+    }
+    fallback.to_string()
+}
+
+/// Bitcoin network a wallet lives on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BitcoinNetwork {
+    #[default]
+    Mainnet,
+    Testnet,
+    Signet,
+    Regtest,
+}
+
+impl BitcoinNetwork {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Mainnet => "mainnet",
+            Self::Testnet => "testnet",
+            Self::Signet => "signet",
+            Self::Regtest => "regtest",
+        }
+    }
+
+    /// Parses a stored network. Rows written before networks existed are mainnet.
+    pub fn from_str(value: &str) -> Self {
+        match value {
+            "testnet" => Self::Testnet,
+            "signet" => Self::Signet,
+            "regtest" => Self::Regtest,
+            _ => Self::Mainnet,
+        }
+    }
+
+    pub fn network(&self) -> Network {
+        match self {
+            Self::Mainnet => Network::Bitcoin,
+            Self::Testnet => Network::Testnet,
+            Self::Signet => Network::Signet,
+            Self::Regtest => Network::Regtest,
+        }
+    }
+
+    /// The same network in the `bitcoin` version re-exported by BDK.
+    pub fn bdk_network(&self) -> BdkNetwork {
+        match self {
+            Self::Mainnet => BdkNetwork::Bitcoin,
+            Self::Testnet => BdkNetwork::Testnet,
+            Self::Signet => BdkNetwork::Signet,
+            Self::Regtest => BdkNetwork::Regtest,
+        }
+    }
+
+    /// SLIP-44 coin type: 0 on mainnet, 1 on every test network.
+    pub fn coin_type(&self) -> u32 {
+        match self {
+            Self::Mainnet => 0,
+            Self::Testnet | Self::Signet | Self::Regtest => 1,
+        }
+    }
+
+    /// Default Esplora REST base URL (no trailing slash), used until the user
+    /// configures their own backends for this network
+    pub fn esplora_url(&self) -> String {
+        match self {
+            Self::Mainnet => env_with_fallback(
+                &["AIIGO_BITCOIN_MAINNET_ESPLORA_URL"],
+                "https://blockstream.info/api",
+            ),
+            Self::Testnet => env_with_fallback(
+                &["AIIGO_BITCOIN_TESTNET_ESPLORA_URL"],
+                "https://blockstream.info/testnet/api",
+            ),
+            Self::Signet => env_with_fallback(
+                &["AIIGO_BITCOIN_SIGNET_ESPLORA_URL"],
+                "https://mempool.space/signet/api",
+            ),
+            Self::Regtest => env_with_fallback(
+                &["AIIGO_BITCOIN_REGTEST_ESPLORA_URL"],
+                "http://127.0.0.1:3002",
+            ),
+        }
+        .trim_end_matches('/')
+        .to_string()
+    }
+
+    /// Default Electrum server, used until the user configures their own backends
+    pub fn electrum_url(&self) -> String {
+        match self {
+            Self::Mainnet => env_with_fallback(
+                &["AIIGO_BITCOIN_MAINNET_ELECTRUM_URL"],
+                "ssl://electrum.blockstream.info:50002",
+            ),
+            Self::Testnet => env_with_fallback(
+                &["AIIGO_BITCOIN_TESTNET_ELECTRUM_URL"],
+                "ssl://electrum.blockstream.info:60002",
+            ),
+            Self::Signet => env_with_fallback(
+                &["AIIGO_BITCOIN_SIGNET_ELECTRUM_URL"],
+                "ssl://mempool.space:60602",
+            ),
+            Self::Regtest => env_with_fallback(
+                &["AIIGO_BITCOIN_REGTEST_ELECTRUM_URL"],
+                "tcp://127.0.0.1:60401",
+            ),
+        }
+    }
+
+    /// Regtest has no fee market; the minimum relay fee always confirms.
+    pub fn has_fee_market(&self) -> bool {
+        !matches!(self, Self::Regtest)
+    }
+
+    /// Blockchain.info only indexes mainnet, so it is the fallback there only.
+    pub fn has_blockchain_info_fallback(&self) -> bool {
+        matches!(self, Self::Mainnet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BitcoinNetwork;
+    use bdk::bitcoin::Network as BdkNetwork;
+
+    #[test]
+    fn network_round_trips_and_defaults_to_mainnet() {
+        for network in [
+            BitcoinNetwork::Mainnet,
+            BitcoinNetwork::Testnet,
+            BitcoinNetwork::Signet,
+            BitcoinNetwork::Regtest,
+        ] {
+            assert_eq!(BitcoinNetwork::from_str(network.as_str()), network);
+        }
+        assert_eq!(BitcoinNetwork::from_str(""), BitcoinNetwork::Mainnet);
+    }
+
+    #[test]
+    fn test_networks_use_testnet_coin_type_and_endpoints() {
+        assert_eq!(BitcoinNetwork::Mainnet.coin_type(), 0);
+        assert_eq!(BitcoinNetwork::Signet.coin_type(), 1);
+        assert_eq!(BitcoinNetwork::Regtest.bdk_network(), BdkNetwork::Regtest);
+        assert!(BitcoinNetwork::Testnet.esplora_url().contains("testnet"));
+        assert!(!BitcoinNetwork::Regtest.has_fee_market());
+        assert!(!BitcoinNetwork::Signet.has_blockchain_info_fallback());
+    }
+}
@@ -0,0 +1,644 @@
+use crate::wallet::bitcoin::config::BitcoinNetwork;
+use crate::wallet::security::mnemonic_secret::MnemonicSecret;
+use bdk::bitcoin::Network;
+use bdk::miniscript::descriptor::{Descriptor, DescriptorPublicKey, DescriptorType};
+use bdk::miniscript::ForEachKey;
+use bitcoin::bip32::{DerivationPath, Xpriv, Xpub};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Number of consecutive unused addresses that ends a discovery pass (BIP44 default).
+pub const ADDRESS_GAP_LIMIT: u32 = 20;
+
+/// Output script used by a wallet, paired with its BIP44/49/84/86 derivation purpose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BitcoinScriptType {
+    /// BIP44 legacy P2PKH (`1...`).
+    P2pkh,
+    /// BIP49 nested SegWit P2SH-P2WPKH (`3...`).
+    P2shP2wpkh,
+    /// BIP84 native SegWit P2WPKH (`bc1q...`).
+    P2wpkh,
+    /// BIP86 Taproot key-path P2TR (`bc1p...`).
+    #[default]
+    P2tr,
+}
+
+impl BitcoinScriptType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::P2pkh => "p2pkh",
+            Self::P2shP2wpkh => "p2sh-p2wpkh",
+            Self::P2wpkh => "p2wpkh",
+            Self::P2tr => "p2tr",
+        }
+    }
+
+    /// Parses a stored script type. Rows written before script types existed
+    /// are Taproot.
+    pub fn from_str(value: &str) -> Self {
+        match value {
+            "p2pkh" => Self::P2pkh,
+            "p2sh-p2wpkh" => Self::P2shP2wpkh,
+            "p2wpkh" => Self::P2wpkh,
+            _ => Self::P2tr,
+        }
+    }
+
+    pub fn purpose(&self) -> u32 {
+        match self {
+            Self::P2pkh => 44,
+            Self::P2shP2wpkh => 49,
+            Self::P2wpkh => 84,
+            Self::P2tr => 86,
+        }
+    }
+
+    pub fn account_path(&self, network: BitcoinNetwork) -> String {
+        format!("m/{}'/{}'/0'", self.purpose(), network.coin_type())
+    }
+
+    /// Wraps a key expression in this script type's descriptor function.
+    pub fn wrap_key(&self, key: &str) -> String {
+        match self {
+            Self::P2pkh => format!("pkh({})", key),
+            Self::P2shP2wpkh => format!("sh(wpkh({}))", key),
+            Self::P2wpkh => format!("wpkh({})", key),
+            Self::P2tr => format!("tr({})", key),
+        }
+    }
+}
+
+/// BIP44-style address chain inside an account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BitcoinKeychain {
+    External,
+    Internal,
+}
+
+impl BitcoinKeychain {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::External => "external",
+            Self::Internal => "internal",
+        }
+    }
+
+    pub fn from_str(value: &str) -> Self {
+        match value {
+            "internal" => Self::Internal,
+            _ => Self::External,
+        }
+    }
+
+    pub(crate) fn chain_index(&self) -> u32 {
+        match self {
+            Self::External => 0,
+            Self::Internal => 1,
+        }
+    }
+}
+
+/// Receive/change descriptor pair for one wallet.
+///
+/// `internal` is `None` for single-key wallets, whose change goes back to the
+/// only address they have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalletDescriptors {
+    pub external: String,
+    pub internal: Option<String>,
+}
+
+impl WalletDescriptors {
+    pub fn for_keychain(&self, keychain: BitcoinKeychain) -> Option<&str> {
+        match keychain {
+            BitcoinKeychain::External => Some(self.external.as_str()),
+            BitcoinKeychain::Internal => self.internal.as_deref(),
+        }
+    }
+}
+
+/// Signing descriptors (carrying the account xprv) alongside their public
+/// counterparts, which are safe to persist for watch-only derivation.
+pub struct AccountDescriptors {
+    pub signing: WalletDescriptors,
+    pub public: WalletDescriptors,
+}
+
+pub(crate) fn account_descriptors_from_mnemonic(
+    mnemonic_phrase: &str,
+    script_type: BitcoinScriptType,
+    network: BitcoinNetwork,
+    secp: &bitcoin::secp256k1::Secp256k1<bitcoin::secp256k1::All>,
+) -> Result<AccountDescriptors, String> {
+    let seed = MnemonicSecret::decode(mnemonic_phrase).to_seed()?;
+    let master_xprv = Xpriv::new_master(network.network(), &seed)
+        .map_err(|e| format!("Failed to create master key: {}", e))?;
+
+    let account_path = DerivationPath::from_str(&script_type.account_path(network))
+        .map_err(|e| format!("Invalid derivation path: {}", e))?;
+    let account_xprv = master_xprv
+        .derive_priv(secp, &account_path)
+        .map_err(|e| format!("Failed to derive account key: {}", e))?;
+    let account_xpub = Xpub::from_priv(secp, &account_xprv);
+
+    let origin = format!("[{}/{}]", master_xprv.fingerprint(secp), account_path);
+    let descriptor = |key: &str, keychain: BitcoinKeychain| {
+        script_type.wrap_key(&format!("{}{}/{}/*", origin, key, keychain.chain_index()))
+    };
+    let account_xprv = account_xprv.to_string();
+    let account_xpub = account_xpub.to_string();
+
+    Ok(AccountDescriptors {
+        signing: WalletDescriptors {
+            external: descriptor(&account_xprv, BitcoinKeychain::External),
+            internal: Some(descriptor(&account_xprv, BitcoinKeychain::Internal)),
+        },
+        public: WalletDescriptors {
+            external: descriptor(&account_xpub, BitcoinKeychain::External),
+            internal: Some(descriptor(&account_xpub, BitcoinKeychain::Internal)),
+        },
+    })
+}
+
+/// Public single-key descriptor for a wallet imported from a private key.
+pub(crate) fn single_key_public_descriptor(
+    public_key: &bitcoin::key::PublicKey,
+    script_type: BitcoinScriptType,
+) -> WalletDescriptors {
+    WalletDescriptors {
+        external: script_type.wrap_key(&public_key.to_string()),
+        internal: None,
+    }
+}
+
+/// Public descriptors for a watch-only wallet imported from an extended public
+/// key (xpub/ypub/zpub and their testnet forms) or a full output descriptor.
+/// Returns the descriptors and the script type they spend with.
+pub(crate) fn watch_only_descriptors(
+    source: &str,
+    script_type: Option<BitcoinScriptType>,
+    network: BitcoinNetwork,
+) -> Result<(WalletDescriptors, BitcoinScriptType), String> {
+    let source = source.trim();
+    let descriptors = if source.contains('(') {
+        watch_only_descriptors_from_descriptor(source, network)?
+    } else {
+        watch_only_descriptors_from_extended_key(source, script_type, network)?
+    };
+
+    derive_address(&descriptors.0.external, 0, network)?;
+    if let Some(internal) = &descriptors.0.internal {
+        derive_address(internal, 0, network)?;
+    }
+
+    Ok(descriptors)
+}
+
+fn watch_only_descriptors_from_extended_key(
+    key: &str,
+    script_type: Option<BitcoinScriptType>,
+    network: BitcoinNetwork,
+) -> Result<(WalletDescriptors, BitcoinScriptType), String> {
+    let mut data =
+        bitcoin::base58::decode_check(key).map_err(|e| format!("Invalid extended key: {}", e))?;
+    if data.len() != 78 {
+        return Err("Invalid extended key: unexpected length".to_string());
+    }
+
+    // SLIP-132 prefixes pin the script type; plain xpub/tpub leave it to the caller.
+    let (implied, mainnet) = match data[..4] {
+        [0x04, 0x88, 0xb2, 0x1e] => (None, true),
+        [0x04, 0x9d, 0x7c, 0xb2] => (Some(BitcoinScriptType::P2shP2wpkh), true),
+        [0x04, 0xb2, 0x47, 0x46] => (Some(BitcoinScriptType::P2wpkh), true),
+        [0x04, 0x35, 0x87, 0xcf] => (None, false),
+        [0x04, 0x4a, 0x52, 0x62] => (Some(BitcoinScriptType::P2shP2wpkh), false),
+        [0x04, 0x5f, 0x1c, 0xf6] => (Some(BitcoinScriptType::P2wpkh), false),
+        [0x04, 0x88, 0xad, 0xe4] | [0x04, 0x35, 0x83, 0x94] => {
+            return Err(
+                "Extended private keys cannot be imported as watch-only wallets".to_string(),
+            )
+        }
+        _ => return Err("Unsupported extended key version".to_string()),
+    };
+    if mainnet != (network == BitcoinNetwork::Mainnet) {
+        return Err(format!(
+            "Extended key does not belong to {}",
+            network.as_str()
+        ));
+    }
+
+    let script_type = match (implied, script_type) {
+        (Some(implied), Some(requested)) if implied != requested => {
+            return Err(format!(
+                "Extended key is for {} addresses, not {}",
+                implied.as_str(),
+                requested.as_str()
+            ));
+        }
+        (Some(implied), _) => implied,
+        (None, requested) => requested.unwrap_or_default(),
+    };
+
+    let version: [u8; 4] = if mainnet {
+        [0x04, 0x88, 0xb2, 0x1e]
+    } else {
+        [0x04, 0x35, 0x87, 0xcf]
+    };
+    data[..4].copy_from_slice(&version);
+    let xpub = Xpub::decode(&data).map_err(|e| format!("Invalid extended key: {}", e))?;
+
+    Ok((
+        WalletDescriptors {
+            external: script_type.wrap_key(&format!("{}/0/*", xpub)),
+            internal: Some(script_type.wrap_key(&format!("{}/1/*", xpub))),
+        },
+        script_type,
+    ))
+}
+
+fn watch_only_descriptors_from_descriptor(
+    descriptor: &str,
+    network: BitcoinNetwork,
+) -> Result<(WalletDescriptors, BitcoinScriptType), String> {
+    if Descriptor::<DescriptorPublicKey>::from_str(descriptor).is_err()
+        && bdk::miniscript::Descriptor::parse_descriptor(
+            &bdk::bitcoin::secp256k1::Secp256k1::new(),
+            descriptor,
+        )
+        .is_ok_and(|(_, keys)| !keys.is_empty())
+    {
+        return Err(
+            "Descriptor contains private keys; import the wallet normally instead".to_string(),
+        );
+    }
+
+    // The checksum covers the exact text, so drop it before deriving the change branch.
+    let body = descriptor.split('#').next().unwrap_or_default();
+    let descriptors = if body.contains("/<0;1>/*") {
+        WalletDescriptors {
+            external: body.replace("/<0;1>/*", "/0/*"),
+            internal: Some(body.replace("/<0;1>/*", "/1/*")),
+        }
+    } else if body.contains("/0/*") && !body.contains("/1/*") {
+        WalletDescriptors {
+            external: descriptor.to_string(),
+            internal: Some(body.replace("/0/*", "/1/*")),
+        }
+    } else {
+        WalletDescriptors {
+            external: descriptor.to_string(),
+            internal: None,
+        }
+    };
+    let parsed = parse_public_descriptor(&descriptors.external)?;
+
+    // Extended keys carry their network; bare public keys fit any network.
+    let mainnet = network == BitcoinNetwork::Mainnet;
+    let keys_match_network = parsed.for_each_key(|key| match key {
+        DescriptorPublicKey::Single(_) => true,
+        DescriptorPublicKey::XPub(xkey) => (xkey.xkey.network == Network::Bitcoin) == mainnet,
+        DescriptorPublicKey::MultiXPub(xkey) => (xkey.xkey.network == Network::Bitcoin) == mainnet,
+    });
+    if !keys_match_network {
+        return Err(format!(
+            "Descriptor keys do not belong to {}",
+            network.as_str()
+        ));
+    }
+
+    let script_type = match &parsed {
+        Descriptor::Pkh(_) => BitcoinScriptType::P2pkh,
+        Descriptor::Wpkh(_) => BitcoinScriptType::P2wpkh,
+        Descriptor::Sh(_) if parsed.desc_type() == DescriptorType::ShWpkh => {
+            BitcoinScriptType::P2shP2wpkh
+        }
+        Descriptor::Tr(tr) if tr.taptree().is_none() => BitcoinScriptType::P2tr,
+        _ => {
+            return Err(
+                "Unsupported descriptor; watch-only wallets take single-key pkh, sh(wpkh), wpkh or tr descriptors"
+                    .to_string(),
+            )
+        }
+    };
+
+    Ok((descriptors, script_type))
+}
+
+pub(crate) fn is_ranged_descriptor(descriptor: &str) -> Result<bool, String> {
+    Ok(parse_public_descriptor(descriptor)?.has_wildcard())
+}
+
+/// Derives the address at `index` from a public descriptor, encoded for
+/// `network`. Non-ranged descriptors ignore the index.
+pub(crate) fn derive_address(
+    descriptor: &str,
+    index: u32,
+    network: BitcoinNetwork,
+) -> Result<String, String> {
+    let descriptor = parse_public_descriptor(descriptor)?;
+    let definite = descriptor
+        .at_derivation_index(index)
+        .map_err(|e| format!("Failed to derive descriptor index {}: {}", index, e))?;
+    let address = definite
+        .address(network.bdk_network())
+        .map_err(|e| format!("Failed to derive address: {}", e))?;
+
+    Ok(address.to_string())
+}
+
+fn parse_public_descriptor(descriptor: &str) -> Result<Descriptor<DescriptorPublicKey>, String> {
+    Descriptor::<DescriptorPublicKey>::from_str(descriptor)
+        .map_err(|e| format!("Invalid descriptor: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        account_descriptors_from_mnemonic, derive_address, is_ranged_descriptor,
+        single_key_public_descriptor, watch_only_descriptors, BitcoinKeychain, BitcoinScriptType,
+    };
+    use crate::wallet::bitcoin::config::BitcoinNetwork;
+
+    const TEST_MNEMONIC: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn bip86_account_descriptors_match_reference_vectors() {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let descriptors = account_descriptors_from_mnemonic(
+            TEST_MNEMONIC,
+            BitcoinScriptType::P2tr,
+            BitcoinNetwork::Mainnet,
+            &secp,
+        )
+        .unwrap();
+        let external = descriptors
+            .public
+            .for_keychain(BitcoinKeychain::External)
+            .unwrap();
+        let internal = descriptors
+            .public
+            .for_keychain(BitcoinKeychain::Internal)
+            .unwrap();
+
+        assert!(external.starts_with("tr([73c5da0a/86'/0'/0']xpub"));
+        assert_eq!(
+            derive_address(external, 0, BitcoinNetwork::Mainnet).unwrap(),
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+        );
+        assert_eq!(
+            derive_address(external, 1, BitcoinNetwork::Mainnet).unwrap(),
+            "bc1p4qhjn9zdvkux4e44uhx8tc55attvtyu358kutcqkudyccelu0was9fqzwh"
+        );
+        assert_eq!(
+            derive_address(internal, 0, BitcoinNetwork::Mainnet).unwrap(),
+            "bc1p3qkhfews2uk44qtvauqyr2ttdsw7svhkl9nkm9s9c3x4ax5h60wqwruhk7"
+        );
+    }
+
+    #[test]
+    fn account_descriptors_follow_script_type_purpose() {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let expected = [
+            (
+                BitcoinScriptType::P2pkh,
+                "pkh([73c5da0a/44'/0'/0']xpub",
+                "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA",
+            ),
+            (
+                BitcoinScriptType::P2shP2wpkh,
+                "sh(wpkh([73c5da0a/49'/0'/0']xpub",
+                "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf",
+            ),
+            (
+                BitcoinScriptType::P2wpkh,
+                "wpkh([73c5da0a/84'/0'/0']xpub",
+                "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            ),
+        ];
+
+        for (script_type, prefix, address) in expected {
+            let descriptors = account_descriptors_from_mnemonic(
+                TEST_MNEMONIC,
+                script_type,
+                BitcoinNetwork::Mainnet,
+                &secp,
+            )
+            .unwrap();
+
+            assert!(descriptors.public.external.starts_with(prefix));
+            assert_eq!(
+                derive_address(&descriptors.public.external, 0, BitcoinNetwork::Mainnet).unwrap(),
+                address
+            );
+        }
+    }
+
+    #[test]
+    fn test_network_descriptors_use_coin_type_one_and_network_encoding() {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let descriptors = account_descriptors_from_mnemonic(
+            TEST_MNEMONIC,
+            BitcoinScriptType::P2wpkh,
+            BitcoinNetwork::Testnet,
+            &secp,
+        )
+        .unwrap();
+
+        assert!(descriptors
+            .public
+            .external
+            .starts_with("wpkh([73c5da0a/84'/1'/0']tpub"));
+        assert_eq!(
+            derive_address(&descriptors.public.external, 0, BitcoinNetwork::Testnet).unwrap(),
+            "tb1q6rz28mcfaxtmd6v789l9rrlrusdprr9pqcpvkl"
+        );
+        assert!(
+            derive_address(&descriptors.public.external, 0, BitcoinNetwork::Regtest)
+                .unwrap()
+                .starts_with("bcrt1q")
+        );
+    }
+
+    #[test]
+    fn script_type_round_trips_and_defaults_to_taproot() {
+        for script_type in [
+            BitcoinScriptType::P2pkh,
+            BitcoinScriptType::P2shP2wpkh,
+            BitcoinScriptType::P2wpkh,
+            BitcoinScriptType::P2tr,
+        ] {
+            assert_eq!(
+                BitcoinScriptType::from_str(script_type.as_str()),
+                script_type
+            );
+        }
+        assert_eq!(BitcoinScriptType::from_str(""), BitcoinScriptType::P2tr);
+        assert_eq!(
+            serde_json::to_string(&BitcoinScriptType::P2shP2wpkh).unwrap(),
+            "\"p2sh-p2wpkh\""
+        );
+    }
+
+    #[test]
+    fn signing_descriptors_never_leak_into_public_pair() {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let descriptors = account_descriptors_from_mnemonic(
+            TEST_MNEMONIC,
+            BitcoinScriptType::P2tr,
+            BitcoinNetwork::Mainnet,
+            &secp,
+        )
+        .unwrap();
+
+        assert!(descriptors.signing.external.contains("xprv"));
+        assert!(!descriptors.public.external.contains("xprv"));
+        assert!(!descriptors
+            .public
+            .internal
+            .as_deref()
+            .unwrap()
+            .contains("xprv"));
+    }
+
+    #[test]
+    fn single_key_descriptor_is_not_ranged() {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let secret = bitcoin::secp256k1::SecretKey::from_slice(&[1u8; 32]).unwrap();
+        let public_key = bitcoin::key::PublicKey::new(secret.public_key(&secp));
+        let descriptors = single_key_public_descriptor(&public_key, BitcoinScriptType::P2tr);
+
+        assert!(descriptors.internal.is_none());
+        assert!(!is_ranged_descriptor(&descriptors.external).unwrap());
+        assert_eq!(
+            derive_address(&descriptors.external, 7, BitcoinNetwork::Mainnet).unwrap(),
+            derive_address(&descriptors.external, 0, BitcoinNetwork::Mainnet).unwrap()
+        );
+    }
+
+    // BIP84 and BIP49 account keys of the test mnemonic, in SLIP-132 encoding.
+    const TEST_ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+    const TEST_YPUB: &str = "ypub6Ww3ibxVfGzLrAH1PNcjyAWenMTbbAosGNB6VvmSEgytSER9azLDWCxoJwW7Ke7icmizBMXrzBx9979FfaHxHcrArf3zbeJJJUZPf663zsP";
+
+    #[test]
+    fn watch_only_slip132_keys_imply_script_type() {
+        let (descriptors, script_type) =
+            watch_only_descriptors(TEST_ZPUB, None, BitcoinNetwork::Mainnet).unwrap();
+        assert_eq!(script_type, BitcoinScriptType::P2wpkh);
+        assert_eq!(
+            derive_address(&descriptors.external, 0, BitcoinNetwork::Mainnet).unwrap(),
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"
+        );
+        assert!(descriptors.internal.unwrap().ends_with("/1/*)"));
+
+        let (descriptors, script_type) =
+            watch_only_descriptors(TEST_YPUB, None, BitcoinNetwork::Mainnet).unwrap();
+        assert_eq!(script_type, BitcoinScriptType::P2shP2wpkh);
+        assert_eq!(
+            derive_address(&descriptors.external, 0, BitcoinNetwork::Mainnet).unwrap(),
+            "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf"
+        );
+
+        assert_eq!(
+            watch_only_descriptors(
+                TEST_ZPUB,
+                Some(BitcoinScriptType::P2tr),
+                BitcoinNetwork::Mainnet
+            )
+            .unwrap_err(),
+            "Extended key is for p2wpkh addresses, not p2tr"
+        );
+        assert_eq!(
+            watch_only_descriptors(TEST_ZPUB, None, BitcoinNetwork::Testnet).unwrap_err(),
+            "Extended key does not belong to testnet"
+        );
+    }
+
+    #[test]
+    fn watch_only_descriptor_import_adds_change_branch() {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let account = account_descriptors_from_mnemonic(
+            TEST_MNEMONIC,
+            BitcoinScriptType::P2tr,
+            BitcoinNetwork::Mainnet,
+            &secp,
+        )
+        .unwrap();
+
+        let (descriptors, script_type) =
+            watch_only_descriptors(&account.public.external, None, BitcoinNetwork::Mainnet)
+                .unwrap();
+
+        assert_eq!(script_type, BitcoinScriptType::P2tr);
+        assert_eq!(descriptors.internal, account.public.internal);
+
+        let multipath = account.public.external.replace("/0/*", "/<0;1>/*");
+        let (descriptors, _) =
+            watch_only_descriptors(&multipath, None, BitcoinNetwork::Mainnet).unwrap();
+        assert_eq!(descriptors.external, account.public.external);
+        assert_eq!(descriptors.internal, account.public.internal);
+    }
+
+    #[test]
+    fn watch_only_descriptor_import_checks_key_network_and_type() {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let account = account_descriptors_from_mnemonic(
+            TEST_MNEMONIC,
+            BitcoinScriptType::P2wpkh,
+            BitcoinNetwork::Mainnet,
+            &secp,
+        )
+        .unwrap();
+        let xpub_key = account
+            .public
+            .external
+            .trim_start_matches("wpkh(")
+            .split(')')
+            .next()
+            .unwrap()
+            .to_string();
+
+        assert_eq!(
+            watch_only_descriptors(&account.public.external, None, BitcoinNetwork::Testnet)
+                .unwrap_err(),
+            "Descriptor keys do not belong to testnet"
+        );
+
+        let multisig = format!("wsh(multi(1,{}))", xpub_key);
+        assert!(
+            watch_only_descriptors(&multisig, None, BitcoinNetwork::Mainnet)
+                .unwrap_err()
+                .starts_with("Unsupported descriptor")
+        );
+    }
+
+    #[test]
+    fn watch_only_import_rejects_private_keys() {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let account = account_descriptors_from_mnemonic(
+            TEST_MNEMONIC,
+            BitcoinScriptType::P2wpkh,
+            BitcoinNetwork::Mainnet,
+            &secp,
+        )
+        .unwrap();
+
+        assert_eq!(
+            watch_only_descriptors(&account.signing.external, None, BitcoinNetwork::Mainnet)
+                .unwrap_err(),
+            "Descriptor contains private keys; import the wallet normally instead"
+        );
+        assert_eq!(
+            watch_only_descriptors(
+                "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+                None,
+                BitcoinNetwork::Mainnet
+            )
+            .unwrap_err(),
+            "Extended private keys cannot be imported as watch-only wallets"
+        );
+    }
+}
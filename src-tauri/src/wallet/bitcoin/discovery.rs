@@ -0,0 +1,330 @@
+use crate::wallet::bitcoin::balance::{query_btc_address_stats, AddressStats};
+use crate::wallet::bitcoin::config::BitcoinNetwork;
+use crate::wallet::bitcoin::descriptor::{
+    derive_address, is_ranged_descriptor, BitcoinKeychain, WalletDescriptors, ADDRESS_GAP_LIMIT,
+};
+use crate::wallet::types::{BitcoinAddressInfo, WalletInfo};
+use crate::DB;
+use chrono::Utc;
+use std::future::Future;
+
+/// Walks the receive and change chains of a wallet until `ADDRESS_GAP_LIMIT`
+/// consecutive unused addresses are seen, then persists every scanned address
+/// with its balance. Returns the scanned rows.
+pub async fn sync_wallet_addresses(
+    wallet_id: &str,
+    descriptors: &WalletDescriptors,
+    network: BitcoinNetwork,
+) -> Result<Vec<BitcoinAddressInfo>, String> {
+    let mut discovered = Vec::new();
+
+    for keychain in [BitcoinKeychain::External, BitcoinKeychain::Internal] {
+        let Some(descriptor) = descriptors.for_keychain(keychain) else {
+            continue;
+        };
+
+        let scanned = scan_keychain(
+            wallet_id,
+            descriptor,
+            keychain,
+            network,
+            ADDRESS_GAP_LIMIT,
+            |address| async move { query_btc_address_stats(&address, network).await },
+        )
+        .await?;
+        discovered.extend(scanned);
+    }
+
+    {
+        let db = DB.lock().unwrap();
+        db.upsert_bitcoin_wallet_addresses(&discovered)
+            .map_err(|e| format!("Failed to save wallet addresses: {}", e))?;
+    }
+
+    crate::safe_log!(
+        "[INFO] Discovered {} addresses for wallet {}",
+        discovered.len(),
+        wallet_id
+    );
+
+    Ok(discovered)
+}
+
+/// Returns the first receive address after the highest used one, checking it
+/// against the explorer so a stale local view never hands out a used address.
+pub async fn next_unused_receive_address(
+    wallet: &WalletInfo,
+) -> Result<BitcoinAddressInfo, String> {
+    let (descriptors, network, known_addresses) = {
+        let db = DB.lock().unwrap();
+        let descriptors = db
+            .get_bitcoin_wallet_descriptors(&wallet.id)
+            .map_err(|e| format!("Failed to get wallet descriptors: {}", e))?;
+        let network = db
+            .get_bitcoin_wallet_network(&wallet.id)
+            .map_err(|e| format!("Failed to get wallet network: {}", e))?;
+        let known_addresses = db
+            .get_bitcoin_wallet_addresses(&wallet.id)
+            .map_err(|e| format!("Failed to get wallet addresses: {}", e))?;
+        (descriptors, network, known_addresses)
+    };
+
+    let Some(descriptors) = descriptors else {
+        // Wallets created before descriptors were stored only know their primary address.
+        return Ok(primary_address_info(wallet, &known_addresses));
+    };
+
+    if !is_ranged_descriptor(&descriptors.external)? {
+        return Ok(primary_address_info(wallet, &known_addresses));
+    }
+
+    let mut index = first_unused_receive_index(&known_addresses);
+    loop {
+        let address = derive_address(&descriptors.external, index, network)?;
+        let stats = match query_btc_address_stats(&address, network).await {
+            Ok(stats) => stats,
+            Err(error) => {
+                crate::safe_log!(
+                    "[WARN] Could not verify receive address {}: {}",
+                    address,
+                    error
+                );
+                AddressStats {
+                    balance_sats: 0,
+                    tx_count: 0,
+                }
+            }
+        };
+        let info = address_info(&wallet.id, BitcoinKeychain::External, index, address, stats);
+
+        {
+            let db = DB.lock().unwrap();
+            db.upsert_bitcoin_wallet_addresses(std::slice::from_ref(&info))
+                .map_err(|e| format!("Failed to save wallet address: {}", e))?;
+        }
+
+        if !stats.is_used() {
+            return Ok(info);
+        }
+        index += 1;
+    }
+}
+
+async fn scan_keychain<F, Fut>(
+    wallet_id: &str,
+    descriptor: &str,
+    keychain: BitcoinKeychain,
+    network: BitcoinNetwork,
+    gap_limit: u32,
+    mut lookup: F,
+) -> Result<Vec<BitcoinAddressInfo>, String>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<AddressStats, String>>,
+{
+    let ranged = is_ranged_descriptor(descriptor)?;
+    let mut scanned = Vec::new();
+    let mut consecutive_unused = 0;
+    let mut index = 0;
+
+    loop {
+        let address = derive_address(descriptor, index, network)?;
+        let stats = lookup(address.clone()).await?;
+
+        if stats.is_used() {
+            consecutive_unused = 0;
+        } else {
+            consecutive_unused += 1;
+        }
+        scanned.push(address_info(wallet_id, keychain, index, address, stats));
+
+        if !ranged || consecutive_unused >= gap_limit {
+            break;
+        }
+        index += 1;
+    }
+
+    Ok(scanned)
+}
+
+fn first_unused_receive_index(addresses: &[BitcoinAddressInfo]) -> u32 {
+    addresses
+        .iter()
+        .filter(|address| address.keychain == BitcoinKeychain::External && address.tx_count > 0)
+        .map(|address| address.derivation_index + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+fn primary_address_info(
+    wallet: &WalletInfo,
+    known_addresses: &[BitcoinAddressInfo],
+) -> BitcoinAddressInfo {
+    known_addresses
+        .iter()
+        .find(|address| address.address == wallet.address)
+        .cloned()
+        .unwrap_or_else(|| BitcoinAddressInfo {
+            wallet_id: wallet.id.clone(),
+            keychain: BitcoinKeychain::External,
+            derivation_index: 0,
+            address: wallet.address.clone(),
+            balance_sats: wallet.balance_sats.unwrap_or(0) as i64,
+            tx_count: 0,
+            updated_at: wallet.updated_at.clone(),
+        })
+}
+
+fn address_info(
+    wallet_id: &str,
+    keychain: BitcoinKeychain,
+    derivation_index: u32,
+    address: String,
+    stats: AddressStats,
+) -> BitcoinAddressInfo {
+    BitcoinAddressInfo {
+        wallet_id: wallet_id.to_string(),
+        keychain,
+        derivation_index,
+        address,
+        balance_sats: stats.balance_sats,
+        tx_count: stats.tx_count,
+        updated_at: Utc::now().to_rfc3339(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{first_unused_receive_index, scan_keychain};
+    use crate::wallet::bitcoin::balance::AddressStats;
+    use crate::wallet::bitcoin::config::BitcoinNetwork;
+    use crate::wallet::bitcoin::descriptor::{
+        account_descriptors_from_mnemonic, derive_address, BitcoinKeychain, BitcoinScriptType,
+    };
+    use std::collections::HashMap;
+
+    const TEST_MNEMONIC: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn external_descriptor() -> String {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        account_descriptors_from_mnemonic(
+            TEST_MNEMONIC,
+            BitcoinScriptType::P2tr,
+            BitcoinNetwork::Mainnet,
+            &secp,
+        )
+        .unwrap()
+        .public
+        .external
+    }
+
+    #[tokio::test]
+    async fn scan_finds_funds_past_unused_addresses_within_gap_limit() {
+        let descriptor = external_descriptor();
+        let funded = HashMap::from([
+            (
+                derive_address(&descriptor, 0, BitcoinNetwork::Mainnet).unwrap(),
+                1_000,
+            ),
+            (
+                derive_address(&descriptor, 4, BitcoinNetwork::Mainnet).unwrap(),
+                25_000,
+            ),
+        ]);
+
+        let scanned = scan_keychain(
+            "wallet-1",
+            &descriptor,
+            BitcoinKeychain::External,
+            BitcoinNetwork::Mainnet,
+            5,
+            |address| {
+                let balance = funded.get(&address).copied();
+                async move {
+                    Ok(AddressStats {
+                        balance_sats: balance.unwrap_or(0),
+                        tx_count: u64::from(balance.is_some()),
+                    })
+                }
+            },
+        )
+        .await
+        .unwrap();
+
+        // Indexes 0..=4 plus five unused trailing addresses.
+        assert_eq!(scanned.len(), 10);
+        assert_eq!(
+            scanned.iter().map(|row| row.balance_sats).sum::<i64>(),
+            26_000
+        );
+        assert_eq!(scanned.last().unwrap().derivation_index, 9);
+    }
+
+    #[tokio::test]
+    async fn scan_stops_at_gap_limit_for_empty_wallet() {
+        let descriptor = external_descriptor();
+
+        let scanned = scan_keychain(
+            "wallet-1",
+            &descriptor,
+            BitcoinKeychain::External,
+            BitcoinNetwork::Mainnet,
+            3,
+            |_| async {
+                Ok(AddressStats {
+                    balance_sats: 0,
+                    tx_count: 0,
+                })
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(scanned.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn scan_propagates_lookup_failures() {
+        let descriptor = external_descriptor();
+
+        let result = scan_keychain(
+            "wallet-1",
+            &descriptor,
+            BitcoinKeychain::External,
+            BitcoinNetwork::Mainnet,
+            3,
+            |_| async { Err("explorer offline".to_string()) },
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err(), "explorer offline");
+    }
+
+    #[tokio::test]
+    async fn first_unused_index_skips_past_highest_used_receive_address() {
+        let descriptor = external_descriptor();
+        let scanned = scan_keychain(
+            "wallet-1",
+            &descriptor,
+            BitcoinKeychain::External,
+            BitcoinNetwork::Mainnet,
+            3,
+            |address| {
+                let used =
+                    address == derive_address(&descriptor, 2, BitcoinNetwork::Mainnet).unwrap();
+                async move {
+                    Ok(AddressStats {
+                        balance_sats: 0,
+                        tx_count: u64::from(used),
+                    })
+                }
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(first_unused_receive_index(&scanned), 3);
+        assert_eq!(first_unused_receive_index(&[]), 0);
+    }
+}
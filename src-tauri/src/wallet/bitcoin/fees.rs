@@ -0,0 +1,357 @@
+use crate::wallet::bitcoin::amount::sats_to_btc;
+use crate::wallet::bitcoin::backend::{self, connect_blockchain, FeeMarket};
+use crate::wallet::bitcoin::config::BitcoinNetwork;
+use crate::wallet::bitcoin::psbt::{load_wallet_info, open_building_wallet};
+use crate::wallet::bitcoin::transaction::{build_send_psbt, plan_send, sync_signing_wallet};
+use crate::wallet::security::backend::SecretBackend;
+use crate::wallet::security::keystore::Keystore;
+use crate::wallet::security::session::SessionManager;
+use crate::wallet::transaction_types::{
+    BitcoinFeeConfidence, BitcoinFeeEstimate, BitcoinFeeEstimationResponse, BitcoinFeePreview,
+    SendBitcoinRequest,
+};
+use crate::wallet::types::WalletInfo;
+use bdk::blockchain::AnyBlockchain;
+use bdk::psbt::PsbtUtils;
+
+/// Bitcoin Core's default relay floor, used when a backend does not report its own
+pub(crate) const MIN_RELAY_FEE_RATE: f64 = 1.0;
+
+/// Confirmation target of sends that give neither a fee rate nor a target
+pub(crate) const DEFAULT_TARGET_BLOCKS: u32 = 6;
+
+/// Targets behind the `fast`, `half_hour` and `hour` buckets
+const BUCKET_TARGETS: [u32; 3] = [1, 3, 6];
+
+/// Block capacity in vbytes (4M weight units)
+const BLOCK_VSIZE: u64 = 1_000_000;
+
+/// Estimate fee rates for the next block, half an hour and an hour
+pub async fn estimate_bitcoin_fees(
+    network: BitcoinNetwork,
+) -> Result<BitcoinFeeEstimationResponse, String> {
+    let market = load_fee_market(network, &BUCKET_TARGETS).await?;
+    let [fast, half_hour, hour] =
+        BUCKET_TARGETS.map(|target| estimate_from_market(&market, target));
+
+    Ok(BitcoinFeeEstimationResponse {
+        fast: fast?.fee_rate,
+        half_hour: half_hour?.fee_rate,
+        hour: hour?.fee_rate,
+        min_relay_fee_rate: min_relay_fee_rate(&market),
+    })
+}
+
+/// Estimate the fee rate for confirmation within `target_blocks`
+pub async fn estimate_fee_for_target(
+    network: BitcoinNetwork,
+    target_blocks: u32,
+) -> Result<BitcoinFeeEstimate, String> {
+    validate_target(target_blocks)?;
+    let market = load_fee_market(network, &[target_blocks]).await?;
+    estimate_from_market(&market, target_blocks)
+}
+
+async fn load_fee_market(network: BitcoinNetwork, targets: &[u32]) -> Result<FeeMarket, String> {
+    if !network.has_fee_market() {
+        return Ok(FeeMarket {
+            estimates: targets
+                .iter()
+                .map(|&target| (target, MIN_RELAY_FEE_RATE))
+                .collect(),
+            histogram: None,
+            min_relay_fee_rate: Some(MIN_RELAY_FEE_RATE),
+        });
+    }
+    backend::fee_market(network, targets).await
+}
+
+fn validate_target(target_blocks: u32) -> Result<(), String> {
+    if target_blocks == 0 {
+        return Err("Confirmation target must be at least 1 block".to_string());
+    }
+    Ok(())
+}
+
+fn min_relay_fee_rate(market: &FeeMarket) -> f64 {
+    market
+        .min_relay_fee_rate
+        .filter(|rate| *rate > 0.0)
+        .unwrap_or(MIN_RELAY_FEE_RATE)
+}
+
+/// Combines the estimator's rate for the target with the rate the current
+/// mempool needs to be cleared within it, never going below the relay floor.
+pub(crate) fn estimate_from_market(
+    market: &FeeMarket,
+    target_blocks: u32,
+) -> Result<BitcoinFeeEstimate, String> {
+    // The rate for the longest target not beyond ours confirms in time;
+    // shorter targets than any estimated fall back to the fastest one.
+    let estimated = market
+        .estimates
+        .range(..=target_blocks)
+        .next_back()
+        .or_else(|| market.estimates.iter().next())
+        .map(|(_, rate)| *rate)
+        .ok_or_else(|| format!("No fee estimate available for {} blocks", target_blocks))?;
+    let min_relay_fee_rate = min_relay_fee_rate(market);
+
+    let mempool_rate = market
+        .histogram
+        .as_deref()
+        .map(|histogram| histogram_rate(histogram, target_blocks));
+    let fee_rate = estimated
+        .max(mempool_rate.unwrap_or(0.0))
+        .max(min_relay_fee_rate);
+
+    let confidence = match mempool_rate {
+        None => BitcoinFeeConfidence::Medium,
+        Some(rate) if rate <= estimated => BitcoinFeeConfidence::High,
+        Some(_) => BitcoinFeeConfidence::Low,
+    };
+
+    Ok(BitcoinFeeEstimate {
+        target_blocks,
+        fee_rate,
+        min_relay_fee_rate,
+        projected_blocks: market
+            .histogram
+            .as_deref()
+            .map(|histogram| projected_blocks(histogram, fee_rate)),
+        confidence,
+    })
+}
+
+/// Lowest rate that fits in the first `target_blocks` blocks of the mempool,
+/// or zero when the whole mempool fits.
+fn histogram_rate(histogram: &[(f64, u64)], target_blocks: u32) -> f64 {
+    let capacity = target_blocks as u64 * BLOCK_VSIZE;
+    let mut ahead = 0;
+    for &(rate, vsize) in histogram {
+        ahead += vsize;
+        if ahead > capacity {
+            return rate;
+        }
+    }
+    0.0
+}
+
+/// Blocks until a transaction at `fee_rate` is mined, counting only what
+/// already waits in the mempool at a higher rate.
+fn projected_blocks(histogram: &[(f64, u64)], fee_rate: f64) -> u32 {
+    let ahead: u64 = histogram
+        .iter()
+        .filter(|(rate, _)| *rate > fee_rate)
+        .map(|(_, vsize)| vsize)
+        .sum();
+    (ahead / BLOCK_VSIZE) as u32 + 1
+}
+
+/// Fee rate a send pays: the one it names, otherwise the estimate for its
+/// confirmation target.
+pub(crate) async fn resolve_send_fee_rate(
+    request: &SendBitcoinRequest,
+    network: BitcoinNetwork,
+) -> Result<(f64, Option<BitcoinFeeEstimate>), String> {
+    resolve_fee_rate(request.fee_rate, request.target_blocks, network).await
+}
+
+/// The given fee rate, otherwise the estimate for the confirmation target
+pub(crate) async fn resolve_fee_rate(
+    fee_rate: Option<f64>,
+    target_blocks: Option<u32>,
+    network: BitcoinNetwork,
+) -> Result<(f64, Option<BitcoinFeeEstimate>), String> {
+    if let Some(fee_rate) = fee_rate {
+        return Ok((fee_rate, None));
+    }
+
+    let target_blocks = target_blocks.unwrap_or(DEFAULT_TARGET_BLOCKS);
+    let estimate = estimate_fee_for_target(network, target_blocks).await?;
+    crate::safe_log!(
+        "[INFO] Estimated {} sat/vB for confirmation within {} blocks",
+        estimate.fee_rate,
+        target_blocks
+    );
+    Ok((estimate.fee_rate, Some(estimate)))
+}
+
+/// Total fee a send would pay for the coins it selects and the outputs it
+/// creates, without signing it
+pub async fn preview_send_fee(
+    request: SendBitcoinRequest,
+    secret_backend: &SecretBackend,
+    keystore: &(dyn Keystore + Send + Sync),
+    session_manager: &SessionManager,
+) -> Result<BitcoinFeePreview, String> {
+    let wallet_info = load_wallet_info(&request.wallet_id)?;
+
+    preview_send_fee_resolved(
+        request,
+        wallet_info,
+        secret_backend,
+        keystore,
+        session_manager,
+        connect_blockchain,
+    )
+    .await
+}
+
+async fn preview_send_fee_resolved<F>(
+    mut request: SendBitcoinRequest,
+    wallet_info: WalletInfo,
+    secret_backend: &SecretBackend,
+    keystore: &(dyn Keystore + Send + Sync),
+    session_manager: &SessionManager,
+    connect_blockchain: F,
+) -> Result<BitcoinFeePreview, String>
+where
+    F: FnOnce(BitcoinNetwork, &str) -> Result<AnyBlockchain, String> + Send + 'static,
+{
+    if let Some(target_blocks) = request.target_blocks {
+        validate_target(target_blocks)?;
+    }
+
+    let (wallet, network) =
+        open_building_wallet(&wallet_info, secret_backend, keystore, session_manager)?;
+    let plan = plan_send(&wallet_info.id, &request, network)?;
+
+    let (wallet, _) =
+        sync_signing_wallet(wallet, network, &wallet_info.id, connect_blockchain).await?;
+
+    let (fee_rate, estimate) = resolve_send_fee_rate(&request, network).await?;
+    request.fee_rate = Some(fee_rate);
+    let psbt = build_send_psbt(&wallet, &request, &plan)?;
+    let fee_sats = psbt
+        .fee_amount()
+        .ok_or_else(|| "Failed to compute the transaction fee".to_string())?;
+
+    Ok(BitcoinFeePreview {
+        fee_rate,
+        fee_sats,
+        fee: sats_to_btc(fee_sats),
+        input_count: psbt.unsigned_tx.input.len(),
+        output_count: psbt.unsigned_tx.output.len(),
+        estimate,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{estimate_from_market, preview_send_fee_resolved, FeeMarket};
+    use crate::wallet::security::backend::SecretBackend;
+    use crate::wallet::security::keystore::Keystore;
+    use crate::wallet::security::session::SessionManager;
+    use crate::wallet::security::types::SecurityError;
+    use crate::wallet::transaction_types::{BitcoinFeeConfidence, SendBitcoinRequest};
+    use crate::wallet::types::WalletInfo;
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    struct PanicKeystore;
+
+    impl Keystore for PanicKeystore {
+        fn load_mnemonic(&self, _address: &str) -> Result<Option<String>, SecurityError> {
+            panic!("preview validation must not read the keystore")
+        }
+
+        fn load_private_key(&self, _address: &str) -> Result<Option<String>, SecurityError> {
+            panic!("preview validation must not read the keystore")
+        }
+    }
+
+    fn fee_market(estimates: &[(u32, f64)], histogram: Option<Vec<(f64, u64)>>) -> FeeMarket {
+        FeeMarket {
+            estimates: estimates.iter().copied().collect::<BTreeMap<_, _>>(),
+            histogram,
+            min_relay_fee_rate: None,
+        }
+    }
+
+    #[test]
+    fn estimate_uses_the_longest_target_within_reach() {
+        let market = fee_market(&[(1, 40.0), (3, 20.0), (6, 12.0), (144, 2.0)], None);
+
+        let estimate = estimate_from_market(&market, 10).unwrap();
+        assert_eq!(estimate.fee_rate, 12.0);
+        assert_eq!(estimate.confidence, BitcoinFeeConfidence::Medium);
+        assert_eq!(estimate.projected_blocks, None);
+
+        assert_eq!(estimate_from_market(&market, 1).unwrap().fee_rate, 40.0);
+        assert_eq!(estimate_from_market(&market, 1_008).unwrap().fee_rate, 2.0);
+        assert_eq!(
+            estimate_from_market(&fee_market(&[(2, 30.0)], None), 1)
+                .unwrap()
+                .fee_rate,
+            30.0
+        );
+        assert!(estimate_from_market(&fee_market(&[], None), 6).is_err());
+    }
+
+    #[test]
+    fn estimate_never_drops_below_the_relay_floor() {
+        let mut market = fee_market(&[(6, 0.4)], None);
+        assert_eq!(estimate_from_market(&market, 6).unwrap().fee_rate, 1.0);
+
+        market.min_relay_fee_rate = Some(3.0);
+        let estimate = estimate_from_market(&market, 6).unwrap();
+        assert_eq!(estimate.fee_rate, 3.0);
+        assert_eq!(estimate.min_relay_fee_rate, 3.0);
+    }
+
+    #[test]
+    fn mempool_histogram_raises_a_lagging_estimate() {
+        // 2.5 blocks of transactions wait at 10 sat/vB or more
+        let histogram = vec![(50.0, 500_000), (20.0, 1_000_000), (10.0, 1_000_000)];
+
+        let agreeing = estimate_from_market(&fee_market(&[(3, 15.0)], Some(histogram.clone())), 3);
+        let agreeing = agreeing.unwrap();
+        assert_eq!(agreeing.fee_rate, 15.0);
+        assert_eq!(agreeing.confidence, BitcoinFeeConfidence::High);
+        assert_eq!(agreeing.projected_blocks, Some(2));
+
+        let lagging = estimate_from_market(&fee_market(&[(1, 5.0)], Some(histogram)), 1).unwrap();
+        assert_eq!(lagging.fee_rate, 20.0);
+        assert_eq!(lagging.confidence, BitcoinFeeConfidence::Low);
+        assert_eq!(lagging.projected_blocks, Some(1));
+    }
+
+    #[tokio::test]
+    async fn preview_rejects_a_zero_block_target_before_reading_secrets() {
+        let wallet_info = WalletInfo {
+            id: "fee-preview-wallet".to_string(),
+            label: "Fee preview".to_string(),
+            wallet_type: "mnemonic".to_string(),
+            address: "bcrt1qfeepreview".to_string(),
+            balance: 0.0,
+            balance_sats: Some(0),
+            created_at: "2026-04-18T00:00:00Z".to_string(),
+            updated_at: "2026-04-18T00:00:00Z".to_string(),
+        };
+        let request = SendBitcoinRequest {
+            wallet_id: wallet_info.id.clone(),
+            to_address: "bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080".to_string(),
+            amount_sats: 10_000,
+            fee_rate: None,
+            target_blocks: Some(0),
+            send_all: None,
+            include_outpoints: None,
+            exclude_outpoints: None,
+            preview_txid: None,
+        };
+
+        let error = preview_send_fee_resolved(
+            request,
+            wallet_info,
+            &SecretBackend::new(),
+            &PanicKeystore,
+            &SessionManager::new(Duration::from_secs(30), Duration::from_secs(90)),
+            |_, _| panic!("preview must not connect with an invalid target"),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(error, "Confirmation target must be at least 1 block");
+    }
+}
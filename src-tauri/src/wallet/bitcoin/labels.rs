@@ -0,0 +1,346 @@
+use crate::wallet::bitcoin::config::BitcoinNetwork;
+use crate::wallet::bitcoin::utxo;
+use crate::wallet::types::{
+    Bip329Record, BitcoinLabelType, ImportBitcoinLabelsRequest, ImportBitcoinLabelsResponse,
+};
+use crate::DB;
+use bdk::bitcoin::bip32::ExtendedPubKey;
+use bdk::bitcoin::{Address, PublicKey, Txid};
+use std::str::FromStr;
+
+/// BIP329 lets importers truncate labels beyond this many characters.
+const MAX_LABEL_CHARS: usize = 255;
+
+/// Stores a label for one reference, or removes it when `label` is empty.
+/// Output labels are the coin-control labels shown on UTXOs.
+pub fn set_bitcoin_label(
+    wallet_id: &str,
+    label_type: BitcoinLabelType,
+    reference: &str,
+    label: Option<String>,
+) -> Result<(), String> {
+    let network = wallet_network(wallet_id)?;
+    let reference = normalize_reference(label_type, reference, network)?;
+    let label = label.and_then(|label| clean_label(&label));
+
+    let db = DB.lock().map_err(|e| e.to_string())?;
+    match label_type {
+        BitcoinLabelType::Output => {
+            db.set_bitcoin_utxo_label(wallet_id, &reference, label.as_deref())
+        }
+        _ => db.set_bitcoin_label(wallet_id, label_type, &reference, label.as_deref(), None),
+    }
+    .map_err(|e| format!("Failed to save label: {}", e))
+}
+
+/// Exports every label of a wallet as BIP329 JSONL, one record per line.
+/// Frozen outputs are exported with `"spendable": false`.
+pub fn export_bitcoin_labels(wallet_id: &str, output_path: Option<&str>) -> Result<String, String> {
+    let (labels, outputs) = {
+        let db = DB.lock().map_err(|e| e.to_string())?;
+        let labels = db
+            .get_bitcoin_labels(wallet_id)
+            .map_err(|e| format!("Failed to get wallet labels: {}", e))?;
+        let outputs = db
+            .get_bitcoin_utxo_metadata(wallet_id)
+            .map_err(|e| format!("Failed to get UTXO metadata: {}", e))?;
+        (labels, outputs)
+    };
+
+    let records = labels
+        .into_iter()
+        .map(|label| Bip329Record {
+            label_type: label.label_type,
+            reference: label.reference,
+            label: Some(label.label),
+            origin: label.origin,
+            spendable: None,
+        })
+        .chain(
+            outputs
+                .into_iter()
+                .filter(|output| output.label.is_some() || output.frozen)
+                .map(|output| Bip329Record {
+                    label_type: BitcoinLabelType::Output,
+                    reference: output.outpoint,
+                    label: output.label,
+                    origin: output.origin,
+                    spendable: Some(!output.frozen),
+                }),
+        );
+
+    let mut jsonl = String::new();
+    for record in records {
+        let line = serde_json::to_string(&record)
+            .map_err(|e| format!("Failed to serialize label: {}", e))?;
+        jsonl.push_str(&line);
+        jsonl.push('\n');
+    }
+
+    if let Some(path) = output_path {
+        std::fs::write(path, &jsonl).map_err(|e| format!("Failed to write label file: {}", e))?;
+    }
+
+    Ok(jsonl)
+}
+
+/// Imports BIP329 JSONL, overwriting existing labels of the same references.
+/// Records of unknown types or with references that do not parse for this
+/// wallet's network are skipped; a line that is not JSON fails the import.
+pub fn import_bitcoin_labels(
+    request: ImportBitcoinLabelsRequest,
+) -> Result<ImportBitcoinLabelsResponse, String> {
+    let jsonl = match (request.jsonl, request.jsonl_path.as_deref()) {
+        (Some(_), Some(_)) => {
+            return Err("Provide either label text or a label file, not both".to_string());
+        }
+        (Some(jsonl), None) => jsonl,
+        (None, Some(path)) => std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read label file: {}", e))?,
+        (None, None) => return Err("No labels to import".to_string()),
+    };
+
+    let network = wallet_network(&request.wallet_id)?;
+    let records = parse_bip329(&jsonl)?;
+
+    let mut response = ImportBitcoinLabelsResponse {
+        imported: 0,
+        skipped: 0,
+    };
+    let mut accepted = Vec::new();
+    for record in records {
+        let Some(record) = record else {
+            response.skipped += 1;
+            continue;
+        };
+        let Ok(reference) = normalize_reference(record.label_type, &record.reference, network)
+        else {
+            response.skipped += 1;
+            continue;
+        };
+        let label = record.label.as_deref().and_then(clean_label);
+        let has_content = match record.label_type {
+            BitcoinLabelType::Output => label.is_some() || record.spendable.is_some(),
+            _ => label.is_some(),
+        };
+        if !has_content {
+            response.skipped += 1;
+            continue;
+        }
+
+        accepted.push(Bip329Record {
+            reference,
+            label,
+            ..record
+        });
+        response.imported += 1;
+    }
+
+    {
+        let db = DB.lock().map_err(|e| e.to_string())?;
+        db.import_bitcoin_labels(&request.wallet_id, &accepted)
+            .map_err(|e| format!("Failed to save labels: {}", e))?;
+    }
+
+    crate::safe_log!(
+        "[INFO] Imported {} labels for wallet {} ({} skipped)",
+        response.imported,
+        request.wallet_id,
+        response.skipped
+    );
+
+    Ok(response)
+}
+
+/// Parses JSONL into records; `None` marks a record of a type this wallet
+/// does not know. Blank lines are ignored.
+fn parse_bip329(jsonl: &str) -> Result<Vec<Option<Bip329Record>>, String> {
+    let mut records = Vec::new();
+    for (index, line) in jsonl.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let value: serde_json::Value = serde_json::from_str(line)
+            .map_err(|e| format!("Invalid label on line {}: {}", index + 1, e))?;
+        let known_type = value
+            .get("type")
+            .and_then(|label_type| label_type.as_str())
+            .and_then(BitcoinLabelType::from_str)
+            .is_some();
+        if !known_type {
+            records.push(None);
+            continue;
+        }
+
+        let record = serde_json::from_value(value)
+            .map_err(|e| format!("Invalid label on line {}: {}", index + 1, e))?;
+        records.push(Some(record));
+    }
+
+    Ok(records)
+}
+
+/// Checks a reference against its label type and returns its canonical form.
+fn normalize_reference(
+    label_type: BitcoinLabelType,
+    reference: &str,
+    network: BitcoinNetwork,
+) -> Result<String, String> {
+    let reference = reference.trim();
+    match label_type {
+        BitcoinLabelType::Tx => Txid::from_str(reference)
+            .map(|txid| txid.to_string())
+            .map_err(|e| format!("Invalid transaction id '{}': {}", reference, e)),
+        BitcoinLabelType::Addr => Address::from_str(reference)
+            .and_then(|address| address.require_network(network.bdk_network()))
+            .map(|address| address.to_string())
+            .map_err(|e| format!("Invalid address '{}': {}", reference, e)),
+        BitcoinLabelType::Pubkey => PublicKey::from_str(reference)
+            .map(|key| key.to_string())
+            .map_err(|e| format!("Invalid public key '{}': {}", reference, e)),
+        BitcoinLabelType::Input | BitcoinLabelType::Output => utxo::normalize_outpoint(reference),
+        BitcoinLabelType::Xpub => ExtendedPubKey::from_str(reference)
+            .map(|xpub| xpub.to_string())
+            .map_err(|e| format!("Invalid xpub '{}': {}", reference, e)),
+    }
+}
+
+fn clean_label(label: &str) -> Option<String> {
+    let label = label.trim();
+    if label.is_empty() {
+        return None;
+    }
+    Some(label.chars().take(MAX_LABEL_CHARS).collect())
+}
+
+fn wallet_network(wallet_id: &str) -> Result<BitcoinNetwork, String> {
+    let db = DB.lock().map_err(|e| e.to_string())?;
+    db.get_bitcoin_wallet_network(wallet_id)
+        .map_err(|e| format!("Failed to get wallet network: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::security::secret_envelope::{StoredSecret, SECRET_FORMAT_PLAINTEXT_V0};
+    use uuid::Uuid;
+
+    const TXID: &str = "f91d0a8a78462bc59398f2c5d7a84fcff491c26ba54c4833478b202796c8aafd";
+    const ADDRESS: &str = "bc1q34aq5drpuwy3wgl9lhup9892qp6svr8ldzyy7c";
+
+    fn insert_wallet() -> String {
+        let unique = Uuid::new_v4().simple().to_string();
+        DB.lock()
+            .unwrap()
+            .insert_bitcoin_wallet_with_secret(
+                format!("BTC labels {unique}"),
+                "mnemonic".to_string(),
+                format!("btc-labels-{unique}"),
+                StoredSecret {
+                    secret_data: "unused".to_string(),
+                    secret_format: SECRET_FORMAT_PLAINTEXT_V0.to_string(),
+                },
+                "mnemonic".to_string(),
+            )
+            .unwrap()
+            .id
+    }
+
+    #[test]
+    fn bip329_labels_round_trip() {
+        let wallet_id = insert_wallet();
+        let jsonl = format!(
+            concat!(
+                "{{\"type\":\"tx\",\"ref\":\"{txid}\",\"label\":\"Rent\",\"origin\":\"wpkh([d34db33f/84'/0'/0'])\"}}\n",
+                "{{\"type\":\"addr\",\"ref\":\"{address}\",\"label\":\"Savings\"}}\n",
+                "\n",
+                "{{\"type\":\"output\",\"ref\":\"{txid}:1\",\"label\":\"Change\",\"origin\":\"wpkh([d34db33f/84'/0'/0'])\",\"spendable\":false}}\n",
+                "{{\"type\":\"addr\",\"ref\":\"not-an-address\",\"label\":\"Broken\"}}\n",
+                "{{\"type\":\"future\",\"ref\":\"x\",\"label\":\"Unknown\"}}\n",
+            ),
+            txid = TXID,
+            address = ADDRESS
+        );
+
+        let imported = import_bitcoin_labels(ImportBitcoinLabelsRequest {
+            wallet_id: wallet_id.clone(),
+            jsonl: Some(jsonl),
+            jsonl_path: None,
+        });
+        let exported = export_bitcoin_labels(&wallet_id, None);
+        let metadata = DB.lock().unwrap().get_bitcoin_utxo_metadata(&wallet_id);
+        let _ = DB.lock().unwrap().delete_bitcoin_wallet(&wallet_id);
+
+        assert_eq!(
+            imported.unwrap(),
+            ImportBitcoinLabelsResponse {
+                imported: 3,
+                skipped: 2,
+            }
+        );
+        let metadata = metadata.unwrap();
+        assert_eq!(metadata[0].label.as_deref(), Some("Change"));
+        assert!(metadata[0].frozen);
+
+        let records: Vec<Bip329Record> = exported
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].label_type, BitcoinLabelType::Addr);
+        assert_eq!(records[0].label.as_deref(), Some("Savings"));
+        assert_eq!(records[1].label_type, BitcoinLabelType::Tx);
+        assert_eq!(
+            records[1].origin.as_deref(),
+            Some("wpkh([d34db33f/84'/0'/0'])")
+        );
+        assert_eq!(records[2].reference, format!("{}:1", TXID));
+        assert_eq!(records[2].spendable, Some(false));
+        assert_eq!(
+            records[2].origin.as_deref(),
+            Some("wpkh([d34db33f/84'/0'/0'])")
+        );
+    }
+
+    #[test]
+    fn import_reports_the_line_of_invalid_json() {
+        let wallet_id = insert_wallet();
+        let result = import_bitcoin_labels(ImportBitcoinLabelsRequest {
+            wallet_id: wallet_id.clone(),
+            jsonl: Some(format!(
+                "{{\"type\":\"tx\",\"ref\":\"{}\",\"label\":\"Rent\"}}\n{{not json\n",
+                TXID
+            )),
+            jsonl_path: None,
+        });
+        let labels = DB.lock().unwrap().get_bitcoin_labels(&wallet_id);
+        let _ = DB.lock().unwrap().delete_bitcoin_wallet(&wallet_id);
+
+        let error = result.unwrap_err();
+        assert!(error.contains("line 2"), "{}", error);
+        assert!(labels.unwrap().is_empty());
+    }
+
+    #[test]
+    fn labels_are_truncated_and_cleared() {
+        let wallet_id = insert_wallet();
+        let long = "a".repeat(300);
+        set_bitcoin_label(&wallet_id, BitcoinLabelType::Tx, TXID, Some(long)).unwrap();
+        let stored = DB.lock().unwrap().get_bitcoin_labels(&wallet_id).unwrap();
+        set_bitcoin_label(
+            &wallet_id,
+            BitcoinLabelType::Tx,
+            TXID,
+            Some("  ".to_string()),
+        )
+        .unwrap();
+        let cleared = DB.lock().unwrap().get_bitcoin_labels(&wallet_id).unwrap();
+        let _ = DB.lock().unwrap().delete_bitcoin_wallet(&wallet_id);
+
+        assert_eq!(stored[0].label.chars().count(), MAX_LABEL_CHARS);
+        assert!(cleared.is_empty());
+    }
+}
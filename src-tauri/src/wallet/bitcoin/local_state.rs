@@ -0,0 +1,504 @@
+use crate::wallet::bitcoin::backend::connect_blockchain;
+use crate::wallet::bitcoin::config::BitcoinNetwork;
+use crate::wallet::bitcoin::descriptor::{
+    is_ranged_descriptor, BitcoinKeychain, WalletDescriptors, ADDRESS_GAP_LIMIT,
+};
+use crate::wallet::bitcoin::wallet_store;
+use crate::wallet::security::sanitize;
+use crate::wallet::sync::engine;
+use crate::wallet::sync::types::SyncReason;
+use crate::wallet::types::{BitcoinAddressInfo, WalletInfo};
+use crate::DB;
+use bdk::bitcoin::{Address, ScriptBuf, Txid};
+use bdk::database::{BatchDatabase, SqliteDatabase};
+use bdk::wallet::AddressIndex;
+use bdk::{KeychainKind, LocalUtxo, SyncOptions, TransactionDetails, Wallet};
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+const BACKGROUND_SYNC_INTERVAL: Duration = Duration::from_secs(120);
+
+/// One lock per wallet, so the background loop, a user refresh and the
+/// signing paths never write the same wallet state at once, while syncs of
+/// different wallets still run side by side.
+static SYNC_LOCKS: Lazy<Mutex<HashMap<String, Arc<Mutex<()>>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Runs `f` while holding the sync lock of `wallet_id`. Every `Wallet::sync`
+/// against the persistent store must go through here, off the async runtime.
+pub(crate) fn with_sync_lock<T>(wallet_id: &str, f: impl FnOnce() -> T) -> T {
+    let lock = SYNC_LOCKS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .entry(wallet_id.to_string())
+        .or_default()
+        .clone();
+    let _guard = lock.lock().unwrap_or_else(PoisonError::into_inner);
+    f()
+}
+
+/// Wallet state as last synced into the local BDK store.
+pub(crate) struct LocalWalletSnapshot {
+    pub(crate) network: BitcoinNetwork,
+    pub(crate) tip_height: u32,
+    pub(crate) transactions: Vec<TransactionDetails>,
+    pub(crate) utxos: Vec<LocalUtxo>,
+    pub(crate) addresses: Vec<BitcoinAddressInfo>,
+    /// Set when this refresh failed and the snapshot is the previously synced state.
+    pub(crate) sync_error: Option<String>,
+}
+
+impl LocalWalletSnapshot {
+    pub(crate) fn balance_sats(&self) -> u64 {
+        self.utxos.iter().map(|utxo| utxo.txout.value).sum()
+    }
+
+    pub(crate) fn address_of(&self, script: &ScriptBuf) -> Option<String> {
+        Address::from_script(script, self.network.bdk_network())
+            .ok()
+            .map(|address| address.to_string())
+    }
+
+    pub(crate) fn confirmation_height(&self, txid: &Txid) -> Option<u32> {
+        self.transactions
+            .iter()
+            .find(|tx| &tx.txid == txid)
+            .and_then(|tx| tx.confirmation_time.as_ref())
+            .map(|time| time.height)
+    }
+}
+
+fn load_public_descriptors(
+    wallet_id: &str,
+) -> Result<Option<(WalletDescriptors, BitcoinNetwork)>, String> {
+    let db = DB.lock().unwrap();
+    let descriptors = db
+        .get_bitcoin_wallet_descriptors(wallet_id)
+        .map_err(|e| format!("Failed to get wallet descriptors: {}", e))?;
+    let network = db
+        .get_bitcoin_wallet_network(wallet_id)
+        .map_err(|e| format!("Failed to get wallet network: {}", e))?;
+    Ok(descriptors.map(|descriptors| (descriptors, network)))
+}
+
+fn open_descriptor_wallet(
+    wallet_id: &str,
+    descriptors: &WalletDescriptors,
+    network: BitcoinNetwork,
+) -> Result<Wallet<SqliteDatabase>, String> {
+    wallet_store::open_wallet(
+        wallet_id,
+        descriptors.external.as_str(),
+        descriptors.internal.as_deref(),
+        network.bdk_network(),
+    )
+    .map_err(|e| format!("Failed to create wallet: {}", e))
+}
+
+/// Opens a watch-only BDK wallet from the stored public descriptors, if any
+pub(crate) fn open_public_wallet(
+    wallet_info: &WalletInfo,
+) -> Result<Option<(Wallet<SqliteDatabase>, BitcoinNetwork)>, String> {
+    let Some((descriptors, network)) = load_public_descriptors(&wallet_info.id)? else {
+        return Ok(None);
+    };
+
+    let wallet = open_descriptor_wallet(&wallet_info.id, &descriptors, network)?;
+    Ok(Some((wallet, network)))
+}
+
+/// Loads a descriptor wallet from the local store. With `refresh` the store is
+/// synced first; otherwise only a wallet that was never synced touches the
+/// network. Returns `None` for wallets without descriptors, or when nothing
+/// was ever synced and the sync failed, so callers can fall back to the
+/// explorer.
+pub(crate) async fn load_local_wallet(
+    wallet_id: &str,
+    refresh: bool,
+) -> Result<Option<LocalWalletSnapshot>, String> {
+    let wallet_id = wallet_id.to_string();
+    tokio::task::spawn_blocking(move || load_local_wallet_blocking(&wallet_id, refresh))
+        .await
+        .map_err(|e| format!("Wallet sync task failed: {}", e))?
+}
+
+fn load_local_wallet_blocking(
+    wallet_id: &str,
+    refresh: bool,
+) -> Result<Option<LocalWalletSnapshot>, String> {
+    let Some((descriptors, network)) = load_public_descriptors(wallet_id)? else {
+        return Ok(None);
+    };
+
+    with_sync_lock(wallet_id, || {
+        let wallet = open_descriptor_wallet(wallet_id, &descriptors, network)?;
+        let synced_before = last_sync_height(&wallet)?.is_some();
+
+        let mut sync_error = None;
+        if refresh || !synced_before {
+            if let Err(error) = sync_wallet(wallet_id, &wallet, network) {
+                crate::safe_log!("[WARN] Failed to sync wallet {}: {}", wallet_id, error);
+                if !synced_before {
+                    return Ok(None);
+                }
+                sync_error = Some(error);
+            }
+        }
+
+        snapshot(wallet_id, &wallet, &descriptors, network, sync_error).map(Some)
+    })
+}
+
+fn sync_wallet(
+    wallet_id: &str,
+    wallet: &Wallet<SqliteDatabase>,
+    network: BitcoinNetwork,
+) -> Result<(), String> {
+    let blockchain = connect_blockchain(network, wallet_id)?;
+    wallet
+        .sync(&blockchain, SyncOptions::default())
+        .map_err(|e| format!("Failed to sync wallet: {}", e))
+}
+
+fn last_sync_height<D: BatchDatabase>(wallet: &Wallet<D>) -> Result<Option<u32>, String> {
+    wallet
+        .database()
+        .get_sync_time()
+        .map(|sync_time| sync_time.map(|sync_time| sync_time.block_time.height))
+        .map_err(|e| format!("Failed to read wallet sync state: {}", e))
+}
+
+fn snapshot<D: BatchDatabase>(
+    wallet_id: &str,
+    wallet: &Wallet<D>,
+    descriptors: &WalletDescriptors,
+    network: BitcoinNetwork,
+    sync_error: Option<String>,
+) -> Result<LocalWalletSnapshot, String> {
+    let transactions = wallet
+        .list_transactions(true)
+        .map_err(|e| format!("Failed to list wallet transactions: {}", e))?;
+    let utxos = wallet
+        .list_unspent()
+        .map_err(|e| format!("Failed to list wallet UTXOs: {}", e))?;
+    let addresses = address_rows(wallet_id, wallet, descriptors, &transactions, &utxos)?;
+
+    Ok(LocalWalletSnapshot {
+        network,
+        tip_height: last_sync_height(wallet)?.unwrap_or(0),
+        transactions,
+        utxos,
+        addresses,
+        sync_error,
+    })
+}
+
+/// Builds the address rows the UI shows: every address up to `ADDRESS_GAP_LIMIT`
+/// past the last used one, with the balance and transaction count seen locally.
+fn address_rows<D: BatchDatabase>(
+    wallet_id: &str,
+    wallet: &Wallet<D>,
+    descriptors: &WalletDescriptors,
+    transactions: &[TransactionDetails],
+    utxos: &[LocalUtxo],
+) -> Result<Vec<BitcoinAddressInfo>, String> {
+    let activity = script_activity(transactions);
+    let mut balances: HashMap<&ScriptBuf, i64> = HashMap::new();
+    for utxo in utxos {
+        *balances.entry(&utxo.txout.script_pubkey).or_default() += utxo.txout.value as i64;
+    }
+
+    let mut last_used: HashMap<KeychainKind, u32> = HashMap::new();
+    for script in activity.keys() {
+        let path = wallet
+            .database()
+            .get_path_from_script_pubkey(script)
+            .map_err(|e| format!("Failed to read wallet addresses: {}", e))?;
+        if let Some((keychain, index)) = path {
+            let entry = last_used.entry(keychain).or_insert(index);
+            *entry = (*entry).max(index);
+        }
+    }
+
+    let updated_at = Utc::now().to_rfc3339();
+    let mut rows = Vec::new();
+    for (keychain, kind) in [
+        (BitcoinKeychain::External, KeychainKind::External),
+        (BitcoinKeychain::Internal, KeychainKind::Internal),
+    ] {
+        let Some(descriptor) = descriptors.for_keychain(keychain) else {
+            continue;
+        };
+        let last_index = if is_ranged_descriptor(descriptor)? {
+            last_used
+                .get(&kind)
+                .map_or(ADDRESS_GAP_LIMIT - 1, |index| index + ADDRESS_GAP_LIMIT)
+        } else {
+            0
+        };
+
+        for index in 0..=last_index {
+            let info = match kind {
+                KeychainKind::External => wallet.get_address(AddressIndex::Peek(index)),
+                KeychainKind::Internal => wallet.get_internal_address(AddressIndex::Peek(index)),
+            }
+            .map_err(|e| format!("Failed to derive wallet address: {}", e))?;
+            let script = info.address.script_pubkey();
+
+            rows.push(BitcoinAddressInfo {
+                wallet_id: wallet_id.to_string(),
+                keychain,
+                derivation_index: index,
+                address: info.address.to_string(),
+                balance_sats: balances.get(&script).copied().unwrap_or(0),
+                tx_count: activity.get(&script).map_or(0, |txids| txids.len() as u64),
+                updated_at: updated_at.clone(),
+            });
+        }
+    }
+
+    Ok(rows)
+}
+
+/// Transactions touching each script: those paying to it and those spending
+/// one of its outputs. Spends are only visible for outputs of wallet
+/// transactions, which every spend of a wallet script is.
+fn script_activity(transactions: &[TransactionDetails]) -> HashMap<ScriptBuf, HashSet<Txid>> {
+    let raw: HashMap<Txid, _> = transactions
+        .iter()
+        .filter_map(|tx| tx.transaction.as_ref().map(|raw| (tx.txid, raw)))
+        .collect();
+
+    let mut activity: HashMap<ScriptBuf, HashSet<Txid>> = HashMap::new();
+    for (txid, transaction) in &raw {
+        for output in &transaction.output {
+            activity
+                .entry(output.script_pubkey.clone())
+                .or_default()
+                .insert(*txid);
+        }
+        for input in &transaction.input {
+            let previous = &input.previous_output;
+            if let Some(output) = raw
+                .get(&previous.txid)
+                .and_then(|parent| parent.output.get(previous.vout as usize))
+            {
+                activity
+                    .entry(output.script_pubkey.clone())
+                    .or_default()
+                    .insert(*txid);
+            }
+        }
+    }
+
+    activity
+}
+
+/// Keeps the local wallet state of every Bitcoin wallet fresh, so queries can
+/// be answered without a round trip to the backends.
+pub async fn start_background_sync() {
+    let mut interval = tokio::time::interval(BACKGROUND_SYNC_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        let wallet_ids = {
+            let db = DB.lock().unwrap();
+            match db.get_bitcoin_wallets() {
+                Ok(wallets) => wallets.into_iter().map(|wallet| wallet.id).collect(),
+                Err(e) => {
+                    tracing::warn!(
+                        error = %sanitize(&format!("{}", e)),
+                        "Failed to list Bitcoin wallets for background sync"
+                    );
+                    Vec::new()
+                }
+            }
+        };
+
+        for wallet_id in wallet_ids {
+            if let Err(e) =
+                engine::sync_bitcoin_wallet_balance(&wallet_id, SyncReason::Periodic).await
+            {
+                tracing::warn!(
+                    error = %sanitize(&e),
+                    "Background Bitcoin wallet sync failed"
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{address_rows, script_activity, snapshot, with_sync_lock};
+    use crate::wallet::bitcoin::config::BitcoinNetwork;
+    use crate::wallet::bitcoin::descriptor::{
+        account_descriptors_from_mnemonic, BitcoinKeychain, BitcoinScriptType, ADDRESS_GAP_LIMIT,
+    };
+    use bdk::bitcoin::absolute::LockTime;
+    use bdk::bitcoin::{OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Witness};
+    use bdk::database::{BatchOperations, MemoryDatabase};
+    use bdk::wallet::AddressIndex;
+    use bdk::{BlockTime, KeychainKind, LocalUtxo, TransactionDetails, Wallet};
+
+    const TEST_MNEMONIC: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn transaction(inputs: Vec<OutPoint>, outputs: Vec<(ScriptBuf, u64)>) -> Transaction {
+        Transaction {
+            version: 2,
+            lock_time: LockTime::ZERO,
+            input: inputs
+                .into_iter()
+                .map(|previous_output| TxIn {
+                    previous_output,
+                    script_sig: ScriptBuf::new(),
+                    sequence: Sequence::MAX,
+                    witness: Witness::new(),
+                })
+                .collect(),
+            output: outputs
+                .into_iter()
+                .map(|(script_pubkey, value)| TxOut {
+                    value,
+                    script_pubkey,
+                })
+                .collect(),
+        }
+    }
+
+    fn details(transaction: &Transaction, height: Option<u32>) -> TransactionDetails {
+        TransactionDetails {
+            transaction: Some(transaction.clone()),
+            txid: transaction.txid(),
+            received: 0,
+            sent: 0,
+            fee: None,
+            confirmation_time: height.map(|height| BlockTime {
+                height,
+                timestamp: 1_700_000_000,
+            }),
+        }
+    }
+
+    #[test]
+    fn script_activity_counts_funding_and_spending_transactions() {
+        let ours = ScriptBuf::from(vec![0x00, 0x14, 0x01]);
+        let theirs = ScriptBuf::from(vec![0x00, 0x14, 0x02]);
+        let funding = transaction(vec![], vec![(ours.clone(), 10_000)]);
+        let spend = transaction(
+            vec![OutPoint::new(funding.txid(), 0)],
+            vec![(theirs.clone(), 9_000)],
+        );
+
+        let activity = script_activity(&[details(&funding, Some(10)), details(&spend, None)]);
+
+        assert_eq!(activity[&ours].len(), 2);
+        assert_eq!(activity[&theirs].len(), 1);
+    }
+
+    #[test]
+    fn local_snapshot_lists_addresses_past_the_last_used_index() {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let descriptors = account_descriptors_from_mnemonic(
+            TEST_MNEMONIC,
+            BitcoinScriptType::P2wpkh,
+            BitcoinNetwork::Testnet,
+            &secp,
+        )
+        .unwrap()
+        .public;
+        let mut database = MemoryDatabase::default();
+        let wallet = Wallet::new(
+            descriptors.external.as_str(),
+            descriptors.internal.as_deref(),
+            BitcoinNetwork::Testnet.bdk_network(),
+            MemoryDatabase::default(),
+        )
+        .unwrap();
+        let used = wallet.get_address(AddressIndex::Peek(3)).unwrap().address;
+        let funding = transaction(vec![], vec![(used.script_pubkey(), 25_000)]);
+        database.set_tx(&details(&funding, Some(100))).unwrap();
+
+        let wallet = Wallet::new(
+            descriptors.external.as_str(),
+            descriptors.internal.as_deref(),
+            BitcoinNetwork::Testnet.bdk_network(),
+            database,
+        )
+        .unwrap();
+        wallet.ensure_addresses_cached(30).unwrap();
+        let transactions = wallet.list_transactions(true).unwrap();
+        let utxos = vec![LocalUtxo {
+            outpoint: OutPoint::new(funding.txid(), 0),
+            txout: funding.output[0].clone(),
+            keychain: KeychainKind::External,
+            is_spent: false,
+        }];
+
+        let rows = address_rows("wallet-1", &wallet, &descriptors, &transactions, &utxos).unwrap();
+        let receive: Vec<_> = rows
+            .iter()
+            .filter(|row| row.keychain == BitcoinKeychain::External)
+            .collect();
+
+        assert_eq!(receive.len() as u32, 3 + ADDRESS_GAP_LIMIT + 1);
+        assert_eq!(receive[3].address, used.to_string());
+        assert_eq!(receive[3].balance_sats, 25_000);
+        assert_eq!(receive[3].tx_count, 1);
+        assert_eq!(
+            rows.iter()
+                .filter(|row| row.keychain == BitcoinKeychain::Internal)
+                .count() as u32,
+            ADDRESS_GAP_LIMIT
+        );
+
+        let snapshot = snapshot(
+            "wallet-1",
+            &wallet,
+            &descriptors,
+            BitcoinNetwork::Testnet,
+            None,
+        )
+        .unwrap();
+        assert_eq!(snapshot.tip_height, 0);
+        assert_eq!(snapshot.confirmation_height(&funding.txid()), Some(100));
+    }
+
+    #[test]
+    fn with_sync_lock_serializes_syncs_of_one_wallet_only() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        let max_active = |wallet_ids: [&'static str; 2]| {
+            let active = Arc::new(AtomicUsize::new(0));
+            let max_active = Arc::new(AtomicUsize::new(0));
+            let syncs: Vec<_> = wallet_ids
+                .into_iter()
+                .map(|wallet_id| {
+                    let active = Arc::clone(&active);
+                    let max_active = Arc::clone(&max_active);
+                    std::thread::spawn(move || {
+                        with_sync_lock(wallet_id, || {
+                            let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                            max_active.fetch_max(now, Ordering::SeqCst);
+                            std::thread::sleep(Duration::from_millis(200));
+                            active.fetch_sub(1, Ordering::SeqCst);
+                        })
+                    })
+                })
+                .collect();
+            for sync in syncs {
+                sync.join().unwrap();
+            }
+            max_active.load(Ordering::SeqCst)
+        };
+
+        assert_eq!(max_active(["sync-lock-a", "sync-lock-a"]), 1);
+        assert_eq!(max_active(["sync-lock-b", "sync-lock-c"]), 2);
+    }
+}
@@ -0,0 +1,690 @@
+use crate::wallet::bitcoin::config::BitcoinNetwork;
+use crate::wallet::bitcoin::descriptor::{
+    derive_address, single_key_public_descriptor, BitcoinKeychain, BitcoinScriptType,
+};
+use crate::wallet::bitcoin::private_key::{map_security_error, validate_private_key};
+use crate::wallet::bitcoin::transaction::{load_signing_secret_for, BitcoinSigningSecret};
+use crate::wallet::security::backend::SecretBackend;
+use crate::wallet::security::commands::AppSecurity;
+use crate::wallet::security::keystore::Keystore;
+use crate::wallet::security::mnemonic_secret::MnemonicSecret;
+use crate::wallet::security::session::SessionManager;
+use crate::wallet::security::types::SignerOperation;
+use crate::wallet::types::{
+    BitcoinMessageFormat, BitcoinMessageSignature, SignBitcoinMessageRequest,
+    VerifyBitcoinMessageRequest, VerifyBitcoinMessageResponse, WalletInfo,
+};
+use crate::DB;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use bitcoin::absolute::LockTime;
+use bitcoin::address::{Address, AddressType};
+use bitcoin::bip32::{DerivationPath, Xpriv};
+use bitcoin::hashes::{sha256, Hash, HashEngine};
+use bitcoin::key::{Keypair, TapTweak, XOnlyPublicKey};
+use bitcoin::opcodes::all::OP_RETURN;
+use bitcoin::opcodes::OP_0;
+use bitcoin::script::Builder;
+use bitcoin::secp256k1::{Message, Secp256k1, SecretKey};
+use bitcoin::sighash::{Prevouts, SighashCache, TapSighashType};
+use bitcoin::sign_message::{signed_msg_hash, MessageSignature};
+use bitcoin::transaction::Version;
+use bitcoin::{
+    Amount, OutPoint, PublicKey, Script, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Txid,
+    Witness,
+};
+use std::str::FromStr;
+
+/// BIP340 tag of the BIP322 message hash.
+const BIP322_TAG: &[u8] = b"BIP0322-signed-message";
+
+/// Where the key behind a wallet address sits.
+struct SigningTarget {
+    address: String,
+    keychain: BitcoinKeychain,
+    index: u32,
+    script_type: BitcoinScriptType,
+    network: BitcoinNetwork,
+}
+
+#[tauri::command]
+pub fn bitcoin_sign_message(
+    request: SignBitcoinMessageRequest,
+    state: tauri::State<'_, AppSecurity>,
+) -> Result<BitcoinMessageSignature, String> {
+    let (wallet_info, target) = resolve_signing_target(&request)?;
+
+    sign_wallet_message(
+        &wallet_info,
+        &target,
+        &request.message,
+        state.secret_backend(),
+        state.keystore(),
+        state.session_manager(),
+    )
+}
+
+#[tauri::command]
+pub fn bitcoin_verify_message(
+    request: VerifyBitcoinMessageRequest,
+) -> Result<VerifyBitcoinMessageResponse, String> {
+    verify_message(&request.address, &request.message, &request.signature)
+}
+
+/// Finds the wallet and the derivation of the address to sign for, before
+/// any secret is touched.
+fn resolve_signing_target(
+    request: &SignBitcoinMessageRequest,
+) -> Result<(WalletInfo, SigningTarget), String> {
+    let db = DB.lock().unwrap();
+    let wallet_info = db
+        .get_bitcoin_wallet(&request.wallet_id)
+        .map_err(|e| format!("Failed to get wallet: {}", e))?
+        .ok_or_else(|| "Wallet not found".to_string())?;
+    let script_type = db
+        .get_bitcoin_wallet_script_type(&request.wallet_id)
+        .map_err(|e| format!("Failed to get wallet script type: {}", e))?;
+    let network = db
+        .get_bitcoin_wallet_network(&request.wallet_id)
+        .map_err(|e| format!("Failed to get wallet network: {}", e))?;
+
+    let address = request
+        .address
+        .as_deref()
+        .map(str::trim)
+        .filter(|address| !address.is_empty())
+        .unwrap_or(&wallet_info.address)
+        .to_string();
+
+    // The primary address is the first receive address of the account.
+    let (keychain, index) = if address == wallet_info.address {
+        (BitcoinKeychain::External, 0)
+    } else {
+        let row = db
+            .get_bitcoin_wallet_addresses(&request.wallet_id)
+            .map_err(|e| format!("Failed to get wallet addresses: {}", e))?
+            .into_iter()
+            .find(|row| row.address == address)
+            .ok_or_else(|| "Address does not belong to this wallet".to_string())?;
+        (row.keychain, row.derivation_index)
+    };
+
+    Ok((
+        wallet_info,
+        SigningTarget {
+            address,
+            keychain,
+            index,
+            script_type,
+            network,
+        },
+    ))
+}
+
+fn sign_wallet_message(
+    wallet_info: &WalletInfo,
+    target: &SigningTarget,
+    message: &str,
+    secret_backend: &SecretBackend,
+    keystore: &(dyn Keystore + Send + Sync),
+    session_manager: &SessionManager,
+) -> Result<BitcoinMessageSignature, String> {
+    // A multisig address has no single key whose signature could prove control.
+    if wallet_info.wallet_type == "multisig" {
+        return Err("Message signing is not supported for multisig wallets".to_string());
+    }
+
+    let signing_secret = load_signing_secret_for(
+        wallet_info,
+        secret_backend,
+        keystore,
+        session_manager,
+        SignerOperation::SignMessage,
+    )
+    .map_err(map_security_error)?
+    .ok_or_else(|| "Wallet secret not found".to_string())?;
+
+    let secret_key = signing_key(signing_secret, target)?;
+    let signature = sign_message(&secret_key, target.script_type, &target.address, message)?;
+    crate::safe_log!("[INFO] Signed a message for {}", target.address);
+    Ok(signature)
+}
+
+/// The private key behind the target address, checked by deriving the address
+/// back from it.
+fn signing_key(
+    signing_secret: BitcoinSigningSecret,
+    target: &SigningTarget,
+) -> Result<SecretKey, String> {
+    let secp = Secp256k1::new();
+    let secret_key = match signing_secret {
+        BitcoinSigningSecret::PrivateKey(secret_data) => validate_private_key(&secret_data)?.0,
+        BitcoinSigningSecret::Mnemonic(secret_data) => {
+            let seed = MnemonicSecret::decode(&secret_data).to_seed()?;
+            let master_xprv = Xpriv::new_master(target.network.network(), &seed)
+                .map_err(|e| format!("Failed to create master key: {}", e))?;
+            let path = DerivationPath::from_str(&format!(
+                "{}/{}/{}",
+                target.script_type.account_path(target.network),
+                target.keychain.chain_index(),
+                target.index
+            ))
+            .map_err(|e| format!("Invalid derivation path: {}", e))?;
+            master_xprv
+                .derive_priv(&secp, &path)
+                .map_err(|e| format!("Failed to derive child key: {}", e))?
+                .private_key
+        }
+    };
+
+    let public_key = PublicKey::new(secret_key.public_key(&secp));
+    let descriptor = single_key_public_descriptor(&public_key, target.script_type);
+    if derive_address(&descriptor.external, 0, target.network)? != target.address {
+        return Err("Wallet key does not match the requested address".to_string());
+    }
+
+    Ok(secret_key)
+}
+
+/// Signs `message` for `address`: BIP322 simple for Taproot, the legacy
+/// Electrum-style format for every other script type.
+pub(crate) fn sign_message(
+    secret_key: &SecretKey,
+    script_type: BitcoinScriptType,
+    address: &str,
+    message: &str,
+) -> Result<BitcoinMessageSignature, String> {
+    let secp = Secp256k1::new();
+
+    let (signature, format) = if script_type == BitcoinScriptType::P2tr {
+        let address = parse_address(address)?;
+        let to_spend = bip322_to_spend(&address.script_pubkey(), message);
+        let to_sign = bip322_to_sign(&to_spend);
+        let sighash = SighashCache::new(&to_sign)
+            .taproot_key_spend_signature_hash(
+                0,
+                &Prevouts::All(&to_spend.output),
+                TapSighashType::Default,
+            )
+            .map_err(|e| format!("Failed to compute signature hash: {}", e))?;
+        let keypair = Keypair::from_secret_key(&secp, secret_key)
+            .tap_tweak(&secp, None)
+            .to_keypair();
+        let signature = bitcoin::taproot::Signature {
+            signature: secp
+                .sign_schnorr_no_aux_rand(&Message::from_digest(sighash.to_byte_array()), &keypair),
+            sighash_type: TapSighashType::Default,
+        };
+        let witness = Witness::p2tr_key_spend(&signature);
+        (
+            STANDARD.encode(bitcoin::consensus::serialize(&witness)),
+            BitcoinMessageFormat::Bip322Simple,
+        )
+    } else {
+        let digest = Message::from_digest(signed_msg_hash(message).to_byte_array());
+        let signature =
+            MessageSignature::new(secp.sign_ecdsa_recoverable(&digest, secret_key), true);
+        (
+            STANDARD.encode(signature.serialize()),
+            BitcoinMessageFormat::Legacy,
+        )
+    };
+
+    Ok(BitcoinMessageSignature {
+        address: address.to_string(),
+        message: message.to_string(),
+        signature,
+        format,
+    })
+}
+
+/// Checks a signature against an address. Taproot addresses take BIP322
+/// simple signatures; others take legacy signatures, or BIP322 for P2WPKH.
+/// A malformed signature is reported as invalid rather than as an error.
+pub(crate) fn verify_message(
+    address: &str,
+    message: &str,
+    signature: &str,
+) -> Result<VerifyBitcoinMessageResponse, String> {
+    let address = parse_address(address)?;
+    let Ok(bytes) = STANDARD.decode(signature.trim()) else {
+        return Ok(VerifyBitcoinMessageResponse {
+            valid: false,
+            format: if address.address_type() == Some(AddressType::P2tr) {
+                BitcoinMessageFormat::Bip322Simple
+            } else {
+                BitcoinMessageFormat::Legacy
+            },
+        });
+    };
+
+    let is_taproot = address.address_type() == Some(AddressType::P2tr);
+    if !is_taproot && bytes.len() == 65 {
+        return Ok(VerifyBitcoinMessageResponse {
+            valid: verify_legacy(&address, message, &bytes)?,
+            format: BitcoinMessageFormat::Legacy,
+        });
+    }
+
+    Ok(VerifyBitcoinMessageResponse {
+        valid: verify_bip322(&address, message, &bytes)?,
+        format: BitcoinMessageFormat::Bip322Simple,
+    })
+}
+
+fn parse_address(address: &str) -> Result<Address, String> {
+    // Only the output script matters here, which does not depend on the network.
+    Address::from_str(address.trim())
+        .map(|address| address.assume_checked())
+        .map_err(|e| format!("Invalid address: {}", e))
+}
+
+fn verify_legacy(address: &Address, message: &str, bytes: &[u8]) -> Result<bool, String> {
+    // BIP137 marks segwit script types in the header; the key recovery is the same.
+    let mut bytes = bytes.to_vec();
+    match bytes[0] {
+        35..=38 => bytes[0] -= 4,
+        39..=42 => bytes[0] -= 8,
+        _ => {}
+    }
+
+    let secp = Secp256k1::verification_only();
+    let Ok(public_key) = MessageSignature::from_slice(&bytes)
+        .and_then(|signature| signature.recover_pubkey(&secp, signed_msg_hash(message)))
+    else {
+        return Ok(false);
+    };
+
+    let expected = match address.address_type() {
+        Some(AddressType::P2pkh) => ScriptBuf::new_p2pkh(&public_key.pubkey_hash()),
+        Some(AddressType::P2wpkh) => match public_key.wpubkey_hash() {
+            Ok(hash) => ScriptBuf::new_p2wpkh(&hash),
+            Err(_) => return Ok(false),
+        },
+        // P2SH signatures are taken as nested P2WPKH, the only P2SH type we sign for.
+        Some(AddressType::P2sh) => match public_key.wpubkey_hash() {
+            Ok(hash) => ScriptBuf::new_p2sh(&ScriptBuf::new_p2wpkh(&hash).script_hash()),
+            Err(_) => return Ok(false),
+        },
+        _ => {
+            return Err(
+                "Legacy message signatures support P2PKH, P2SH-P2WPKH and P2WPKH addresses"
+                    .to_string(),
+            )
+        }
+    };
+
+    Ok(expected == address.script_pubkey())
+}
+
+fn verify_bip322(address: &Address, message: &str, bytes: &[u8]) -> Result<bool, String> {
+    let Ok(witness) = bitcoin::consensus::deserialize::<Witness>(bytes) else {
+        return Ok(false);
+    };
+    let script_pubkey = address.script_pubkey();
+    let to_spend = bip322_to_spend(&script_pubkey, message);
+    let to_sign = bip322_to_sign(&to_spend);
+    let secp = Secp256k1::verification_only();
+
+    match address.address_type() {
+        Some(AddressType::P2tr) => {
+            if witness.len() != 1 {
+                return Ok(false);
+            }
+            let Ok(signature) = bitcoin::taproot::Signature::from_slice(&witness[0]) else {
+                return Ok(false);
+            };
+            let Ok(output_key) = XOnlyPublicKey::from_slice(&script_pubkey.as_bytes()[2..]) else {
+                return Ok(false);
+            };
+            let sighash = SighashCache::new(&to_sign)
+                .taproot_key_spend_signature_hash(
+                    0,
+                    &Prevouts::All(&to_spend.output),
+                    signature.sighash_type,
+                )
+                .map_err(|e| format!("Failed to compute signature hash: {}", e))?;
+            Ok(secp
+                .verify_schnorr(
+                    &signature.signature,
+                    &Message::from_digest(sighash.to_byte_array()),
+                    &output_key,
+                )
+                .is_ok())
+        }
+        Some(AddressType::P2wpkh) => {
+            if witness.len() != 2 {
+                return Ok(false);
+            }
+            let (Ok(signature), Ok(public_key)) = (
+                bitcoin::ecdsa::Signature::from_slice(&witness[0]),
+                PublicKey::from_slice(&witness[1]),
+            ) else {
+                return Ok(false);
+            };
+            match public_key.wpubkey_hash() {
+                Ok(hash) if ScriptBuf::new_p2wpkh(&hash) == script_pubkey => {}
+                _ => return Ok(false),
+            }
+            let sighash = SighashCache::new(&to_sign)
+                .p2wpkh_signature_hash(0, &script_pubkey, Amount::ZERO, signature.sighash_type)
+                .map_err(|e| format!("Failed to compute signature hash: {}", e))?;
+            Ok(secp
+                .verify_ecdsa(
+                    &Message::from_digest(sighash.to_byte_array()),
+                    &signature.signature,
+                    &public_key.inner,
+                )
+                .is_ok())
+        }
+        _ => Err("BIP322 signatures are supported for P2TR and P2WPKH addresses".to_string()),
+    }
+}
+
+/// BIP340 tagged hash of the message, committed to by `to_spend`.
+fn bip322_message_hash(message: &str) -> [u8; 32] {
+    let tag = sha256::Hash::hash(BIP322_TAG);
+    let mut engine = sha256::Hash::engine();
+    engine.input(tag.as_ref());
+    engine.input(tag.as_ref());
+    engine.input(message.as_bytes());
+    sha256::Hash::from_engine(engine).to_byte_array()
+}
+
+/// The virtual transaction paying the signed address.
+fn bip322_to_spend(script_pubkey: &Script, message: &str) -> Transaction {
+    Transaction {
+        version: Version(0),
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint {
+                txid: Txid::all_zeros(),
+                vout: 0xFFFF_FFFF,
+            },
+            script_sig: Builder::new()
+                .push_opcode(OP_0)
+                .push_slice(bip322_message_hash(message))
+                .into_script(),
+            sequence: Sequence::ZERO,
+            witness: Witness::new(),
+        }],
+        output: vec![TxOut {
+            value: Amount::ZERO,
+            script_pubkey: script_pubkey.to_owned(),
+        }],
+    }
+}
+
+/// The virtual transaction spending `to_spend`, whose witness is the signature.
+fn bip322_to_sign(to_spend: &Transaction) -> Transaction {
+    Transaction {
+        version: Version(0),
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint {
+                txid: to_spend.compute_txid(),
+                vout: 0,
+            },
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ZERO,
+            witness: Witness::new(),
+        }],
+        output: vec![TxOut {
+            value: Amount::ZERO,
+            script_pubkey: Builder::new().push_opcode(OP_RETURN).into_script(),
+        }],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        bip322_message_hash, sign_message, sign_wallet_message, verify_message, SigningTarget,
+    };
+    use crate::wallet::bitcoin::config::BitcoinNetwork;
+    use crate::wallet::bitcoin::descriptor::{
+        account_descriptors_from_mnemonic, derive_address, single_key_public_descriptor,
+        BitcoinKeychain, BitcoinScriptType,
+    };
+    use crate::wallet::bitcoin::private_key::validate_private_key;
+    use crate::wallet::security::backend::{SecretBackend, SecretBackendAdapter};
+    use crate::wallet::security::keystore::Keystore;
+    use crate::wallet::security::secret_envelope::{SecretEnvelopeError, StoredSecret};
+    use crate::wallet::security::session::SessionManager;
+    use crate::wallet::security::types::{SecurityError, SignerOperation};
+    use crate::wallet::types::{BitcoinMessageFormat, WalletInfo};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    const TEST_MNEMONIC: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    /// Key of the BIP322 test vectors.
+    const BIP322_KEY: &str = "L3VFeEujGtevx9w18HD1fhRbCH67Az2dpCymeRE1SoPK6XQtaN2k";
+
+    struct PanicKeystore;
+
+    struct MnemonicKeystore;
+
+    struct ReadySecretBackendAdapter;
+
+    impl SecretBackendAdapter for ReadySecretBackendAdapter {
+        fn probe(&self) -> Result<(), SecretEnvelopeError> {
+            Ok(())
+        }
+
+        fn initialize_empty_store(&self) -> Result<(), SecretEnvelopeError> {
+            Ok(())
+        }
+
+        fn encrypt(&self, _plaintext: &str) -> Result<StoredSecret, SecretEnvelopeError> {
+            unreachable!()
+        }
+
+        fn decrypt(
+            &self,
+            _secret_data: &str,
+            _secret_format: &str,
+        ) -> Result<String, SecretEnvelopeError> {
+            unreachable!()
+        }
+    }
+
+    impl Keystore for PanicKeystore {
+        fn load_mnemonic(&self, _address: &str) -> Result<Option<String>, SecurityError> {
+            panic!("keystore should not be called without a sign message grant");
+        }
+
+        fn load_private_key(&self, _address: &str) -> Result<Option<String>, SecurityError> {
+            panic!("keystore should not be called without a sign message grant");
+        }
+    }
+
+    impl Keystore for MnemonicKeystore {
+        fn load_mnemonic(&self, _address: &str) -> Result<Option<String>, SecurityError> {
+            Ok(Some(TEST_MNEMONIC.to_string()))
+        }
+
+        fn load_private_key(&self, _address: &str) -> Result<Option<String>, SecurityError> {
+            Ok(None)
+        }
+    }
+
+    fn ready_backend() -> SecretBackend {
+        SecretBackend::with_adapter(Arc::new(ReadySecretBackendAdapter))
+    }
+
+    fn mnemonic_wallet() -> WalletInfo {
+        WalletInfo {
+            id: "wallet-id".to_string(),
+            label: "Bitcoin Wallet".to_string(),
+            wallet_type: "mnemonic".to_string(),
+            address: "bc1ptestaddress".to_string(),
+            balance: 0.0,
+            balance_sats: Some(0),
+            created_at: "2026-04-18T00:00:00Z".to_string(),
+            updated_at: "2026-04-18T00:00:00Z".to_string(),
+        }
+    }
+
+    fn mnemonic_target(script_type: BitcoinScriptType, index: u32) -> SigningTarget {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let descriptors = account_descriptors_from_mnemonic(
+            TEST_MNEMONIC,
+            script_type,
+            BitcoinNetwork::Mainnet,
+            &secp,
+        )
+        .unwrap();
+        SigningTarget {
+            address: derive_address(&descriptors.public.external, index, BitcoinNetwork::Mainnet)
+                .unwrap(),
+            keychain: BitcoinKeychain::External,
+            index,
+            script_type,
+            network: BitcoinNetwork::Mainnet,
+        }
+    }
+
+    #[test]
+    fn bip322_message_hash_matches_spec_vectors() {
+        assert_eq!(
+            hex::encode(bip322_message_hash("")),
+            "c90c269c4f8fcbe6880f72a721ddfbf1914268a794cbb21cfafee13770ae19f1"
+        );
+        assert_eq!(
+            hex::encode(bip322_message_hash("Hello World")),
+            "f0eb03b1a75ac6d9847f55c624a99169b5dccba2a31f5b23bea77ba270de0a7a"
+        );
+    }
+
+    #[test]
+    fn bip322_spec_signatures_verify() {
+        let p2wpkh = verify_message(
+            "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l",
+            "",
+            "AkcwRAIgM2gBAQqvZX15ZiysmKmQpDrG83avLIT492QBzLnQIxYCIBaTpOaD20qRlEylyxFSeEA2ba9YOixpX8z46TSDtS40ASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=",
+        )
+        .unwrap();
+        assert!(p2wpkh.valid);
+        assert_eq!(p2wpkh.format, BitcoinMessageFormat::Bip322Simple);
+
+        let p2tr = verify_message(
+            "bc1ppv609nr0vr25u07u95waq5lucwfm6tde4nydujnu8npg4q75mr5sxq8lt3",
+            "Hello World",
+            "AUHd69PrJQEv+oKTfZ8l+WROBHuy9HKrbFCJu7U1iK2iiEy1vMU5EfMtjc+VSHM7aU0SDbak5IUZRVno2P5mjSafAQ==",
+        )
+        .unwrap();
+        assert!(p2tr.valid);
+
+        let tampered = verify_message(
+            "bc1ppv609nr0vr25u07u95waq5lucwfm6tde4nydujnu8npg4q75mr5sxq8lt3",
+            "Hello World!",
+            "AUHd69PrJQEv+oKTfZ8l+WROBHuy9HKrbFCJu7U1iK2iiEy1vMU5EfMtjc+VSHM7aU0SDbak5IUZRVno2P5mjSafAQ==",
+        )
+        .unwrap();
+        assert!(!tampered.valid);
+    }
+
+    #[test]
+    fn signatures_round_trip_for_every_script_type() {
+        let (secret_key, public_key) = validate_private_key(BIP322_KEY).unwrap();
+
+        for script_type in [
+            BitcoinScriptType::P2pkh,
+            BitcoinScriptType::P2shP2wpkh,
+            BitcoinScriptType::P2wpkh,
+            BitcoinScriptType::P2tr,
+        ] {
+            let descriptor = single_key_public_descriptor(&public_key, script_type);
+            let address =
+                &derive_address(&descriptor.external, 0, BitcoinNetwork::Mainnet).unwrap();
+            let signed = sign_message(&secret_key, script_type, address, "deposit proof").unwrap();
+            let expected_format = if script_type == BitcoinScriptType::P2tr {
+                BitcoinMessageFormat::Bip322Simple
+            } else {
+                BitcoinMessageFormat::Legacy
+            };
+            assert_eq!(signed.format, expected_format);
+
+            let verified = verify_message(address, "deposit proof", &signed.signature).unwrap();
+            assert!(verified.valid, "{} signature should verify", address);
+            assert_eq!(verified.format, expected_format);
+            assert!(
+                !verify_message(address, "other message", &signed.signature)
+                    .unwrap()
+                    .valid
+            );
+        }
+    }
+
+    #[test]
+    fn malformed_signature_is_invalid_not_an_error() {
+        let response = verify_message(
+            "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l",
+            "message",
+            "not base64!",
+        )
+        .unwrap();
+        assert!(!response.valid);
+        assert!(verify_message("not-an-address", "message", "AA==").is_err());
+    }
+
+    #[test]
+    fn signing_requires_sign_message_reauth() {
+        let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
+        let target = mnemonic_target(BitcoinScriptType::P2tr, 0);
+
+        let locked = sign_wallet_message(
+            &mnemonic_wallet(),
+            &target,
+            "message",
+            &ready_backend(),
+            &PanicKeystore,
+            &session,
+        );
+        assert_eq!(locked, Err("locked".to_string()));
+
+        session
+            .authorize_verified_operation(SignerOperation::Send)
+            .unwrap();
+        let send_grant = sign_wallet_message(
+            &mnemonic_wallet(),
+            &target,
+            "message",
+            &ready_backend(),
+            &PanicKeystore,
+            &session,
+        );
+        assert_eq!(send_grant, Err("reauth_required".to_string()));
+    }
+
+    #[test]
+    fn mnemonic_wallet_signs_for_derived_addresses() {
+        let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
+        session
+            .authorize_verified_operation(SignerOperation::SignMessage)
+            .unwrap();
+
+        for target in [
+            mnemonic_target(BitcoinScriptType::P2tr, 0),
+            mnemonic_target(BitcoinScriptType::P2wpkh, 3),
+            mnemonic_target(BitcoinScriptType::P2shP2wpkh, 1),
+        ] {
+            let signed = sign_wallet_message(
+                &mnemonic_wallet(),
+                &target,
+                "I control this address",
+                &ready_backend(),
+                &MnemonicKeystore,
+                &session,
+            )
+            .unwrap();
+
+            assert_eq!(signed.address, target.address);
+            assert!(
+                verify_message(&target.address, "I control this address", &signed.signature)
+                    .unwrap()
+                    .valid
+            );
+        }
+    }
+}
@@ -14,6 +14,7 @@ fn map_security_error(error: SecurityError) -> String {
         SecurityError::OperationNotAllowed => "operation_not_allowed".to_string(),
         SecurityError::UnknownWallet => "unknown_wallet".to_string(),
         SecurityError::SecretBackendUnavailable => "secret_backend_unavailable".to_string(),
+        SecurityError::WatchOnly => "watch_only".to_string(),
     }
 }
 
@@ -1,6 +1,22 @@
+pub mod amount;
+pub mod backend;
 pub mod balance;
+pub mod batch;
+pub mod bip21;
 pub mod commands;
+pub mod config;
+pub mod descriptor;
+pub mod discovery;
+pub mod fees;
+pub mod labels;
+pub mod local_state;
+pub mod message;
 pub mod mnemonic;
+pub mod multisig;
+pub mod preview;
 pub mod private_key;
+pub mod psbt;
 pub mod transaction;
+pub mod utxo;
 pub mod wallet;
+pub mod wallet_store;
@@ -0,0 +1,379 @@
+use crate::wallet::bitcoin::config::BitcoinNetwork;
+use crate::wallet::bitcoin::descriptor::{derive_address, BitcoinScriptType, WalletDescriptors};
+use crate::wallet::security::mnemonic_secret::MnemonicSecret;
+use bdk::bitcoin::psbt::PartiallySignedTransaction;
+use bitcoin::bip32::{DerivationPath, Xpriv, Xpub};
+use bitcoin::NetworkKind;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::str::FromStr;
+
+/// Most keys miniscript allows in a `sortedmulti` script.
+const MAX_SORTEDMULTI_KEYS: usize = 20;
+
+/// BIP341 NUMS point. As the Taproot internal key it has no known private key,
+/// so coins can only move through the `multi_a` script path.
+const UNSPENDABLE_INTERNAL_KEY: &str =
+    "50929b74c1a04954b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac0";
+
+/// Output script a multisig wallet locks its coins with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MultisigScriptType {
+    /// `wsh(sortedmulti(...))` (`bc1q...`), keys derived at BIP48 `m/48'/coin'/0'/2'`.
+    #[default]
+    P2wsh,
+    /// `tr(NUMS,multi_a(...))` (`bc1p...`), keys derived at BIP87 `m/87'/coin'/0'`.
+    P2tr,
+}
+
+impl MultisigScriptType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::P2wsh => "p2wsh",
+            Self::P2tr => "p2tr",
+        }
+    }
+
+    pub fn from_str(value: &str) -> Self {
+        match value {
+            "p2tr" => Self::P2tr,
+            _ => Self::P2wsh,
+        }
+    }
+
+    /// The single-key script type whose addresses share this one's witness
+    /// version, recorded as the wallet's script type.
+    pub fn wallet_script_type(&self) -> BitcoinScriptType {
+        match self {
+            Self::P2wsh => BitcoinScriptType::P2wpkh,
+            Self::P2tr => BitcoinScriptType::P2tr,
+        }
+    }
+
+    pub fn account_path(&self, network: BitcoinNetwork) -> String {
+        match self {
+            Self::P2wsh => format!("m/48'/{}'/0'/2'", network.coin_type()),
+            Self::P2tr => format!("m/87'/{}'/0'", network.coin_type()),
+        }
+    }
+
+    /// `sortedmulti` orders keys itself; `multi_a` keeps the order given, so
+    /// every cosigner must list the keys the same way.
+    fn wrap_keys(&self, threshold: u32, keys: &str) -> String {
+        match self {
+            Self::P2wsh => format!("wsh(sortedmulti({},{}))", threshold, keys),
+            Self::P2tr => format!(
+                "tr({},multi_a({},{}))",
+                UNSPENDABLE_INTERNAL_KEY, threshold, keys
+            ),
+        }
+    }
+}
+
+/// How many of a wallet's cosigners must sign, and the script they sign for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultisigPolicy {
+    pub threshold: u32,
+    pub cosigners: u32,
+    pub script_type: MultisigScriptType,
+}
+
+/// This device's cosigner key as `[fingerprint/path]xpub`, and the matching
+/// xprv that stands in for it in signing descriptors.
+pub(crate) struct LocalCosigner {
+    pub(crate) public: String,
+    signing: String,
+}
+
+pub(crate) fn local_cosigner(
+    mnemonic_secret: &str,
+    script_type: MultisigScriptType,
+    network: BitcoinNetwork,
+    secp: &bitcoin::secp256k1::Secp256k1<bitcoin::secp256k1::All>,
+) -> Result<LocalCosigner, String> {
+    let seed = MnemonicSecret::decode(mnemonic_secret).to_seed()?;
+    let master_xprv = Xpriv::new_master(network.network(), &seed)
+        .map_err(|e| format!("Failed to create master key: {}", e))?;
+
+    let account_path = DerivationPath::from_str(&script_type.account_path(network))
+        .map_err(|e| format!("Invalid derivation path: {}", e))?;
+    let account_xprv = master_xprv
+        .derive_priv(secp, &account_path)
+        .map_err(|e| format!("Failed to derive account key: {}", e))?;
+    let account_xpub = Xpub::from_priv(secp, &account_xprv);
+
+    let origin = format!("[{}/{}]", master_xprv.fingerprint(secp), account_path);
+    Ok(LocalCosigner {
+        public: format!("{}{}", origin, account_xpub),
+        signing: format!("{}{}", origin, account_xprv),
+    })
+}
+
+/// Receive/change descriptors requiring `threshold` of `cosigner_keys`. Keys
+/// are account xpubs (or tpubs), optionally with a `[fingerprint/path]` origin.
+pub(crate) fn multisig_descriptors(
+    cosigner_keys: &[String],
+    threshold: u32,
+    script_type: MultisigScriptType,
+    network: BitcoinNetwork,
+) -> Result<WalletDescriptors, String> {
+    let keys = cosigner_keys
+        .iter()
+        .map(|key| cosigner_key(key, network))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if keys.len() < 2 {
+        return Err("A multisig wallet needs at least two cosigners".to_string());
+    }
+    if script_type == MultisigScriptType::P2wsh && keys.len() > MAX_SORTEDMULTI_KEYS {
+        return Err(format!(
+            "P2WSH multisig supports at most {} cosigners",
+            MAX_SORTEDMULTI_KEYS
+        ));
+    }
+    if threshold == 0 || threshold as usize > keys.len() {
+        return Err(format!(
+            "Threshold must be between 1 and {} cosigners",
+            keys.len()
+        ));
+    }
+    let mut seen = HashSet::new();
+    if !keys.iter().all(|(_, xpub)| seen.insert(*xpub)) {
+        return Err("Each cosigner key can only appear once".to_string());
+    }
+
+    let descriptor = |chain: u32| {
+        let keys = keys
+            .iter()
+            .map(|(key, _)| format!("{}/{}/*", key, chain))
+            .collect::<Vec<_>>()
+            .join(",");
+        script_type.wrap_keys(threshold, &keys)
+    };
+    let descriptors = WalletDescriptors {
+        external: descriptor(0),
+        internal: Some(descriptor(1)),
+    };
+
+    derive_address(&descriptors.external, 0, network)?;
+    if let Some(internal) = &descriptors.internal {
+        derive_address(internal, 0, network)?;
+    }
+
+    Ok(descriptors)
+}
+
+/// Normalizes a cosigner key to `[origin]xpub` with the account xpub parsed,
+/// dropping a trailing `/0/*` or `/<0;1>/*` branch.
+fn cosigner_key(key: &str, network: BitcoinNetwork) -> Result<(String, Xpub), String> {
+    let key = key.trim();
+    let (origin, key) = match key.strip_prefix('[') {
+        Some(rest) => {
+            let end = rest
+                .find(']')
+                .ok_or_else(|| "Invalid cosigner key origin".to_string())?;
+            (&key[..end + 2], &rest[end + 1..])
+        }
+        None => ("", key),
+    };
+    let key = key
+        .strip_suffix("/<0;1>/*")
+        .or_else(|| key.strip_suffix("/0/*"))
+        .unwrap_or(key);
+
+    let xpub = Xpub::from_str(key).map_err(|e| format!("Invalid cosigner key: {}", e))?;
+    if xpub.network != NetworkKind::from(network.network()) {
+        return Err(format!(
+            "Cosigner key does not belong to {}",
+            network.as_str()
+        ));
+    }
+
+    Ok((format!("{}{}", origin, xpub), xpub))
+}
+
+/// Swaps this device's xpub for its xprv so BDK can sign its share of inputs.
+pub(crate) fn multisig_signing_descriptors(
+    public: &WalletDescriptors,
+    local: &LocalCosigner,
+) -> Result<WalletDescriptors, String> {
+    if !public.external.contains(&local.public) {
+        return Err("This wallet's key is not one of the multisig cosigners".to_string());
+    }
+
+    Ok(WalletDescriptors {
+        external: public.external.replace(&local.public, &local.signing),
+        internal: public
+            .internal
+            .as_ref()
+            .map(|internal| internal.replace(&local.public, &local.signing)),
+    })
+}
+
+/// Signatures collected so far, counted on the input with the fewest. A
+/// finalized input counts as fully signed.
+pub(crate) fn signatures_present(
+    psbt: &PartiallySignedTransaction,
+    policy: &MultisigPolicy,
+) -> u32 {
+    psbt.inputs
+        .iter()
+        .map(|input| {
+            if input.final_script_witness.is_some() || input.final_script_sig.is_some() {
+                return policy.threshold;
+            }
+            let signatures = match policy.script_type {
+                MultisigScriptType::P2wsh => input.partial_sigs.len(),
+                MultisigScriptType::P2tr => input.tap_script_sigs.len(),
+            };
+            signatures as u32
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        local_cosigner, multisig_descriptors, multisig_signing_descriptors, MultisigScriptType,
+    };
+    use crate::wallet::bitcoin::config::BitcoinNetwork;
+    use crate::wallet::bitcoin::descriptor::derive_address;
+
+    const TEST_MNEMONICS: [&str; 3] = [
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        "legal winner thank year wave sausage worth useful legal winner thank yellow",
+        "letter advice cage absurd amount doctor acoustic avoid letter advice cage above",
+    ];
+
+    fn cosigner_keys(script_type: MultisigScriptType, network: BitcoinNetwork) -> Vec<String> {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        TEST_MNEMONICS
+            .iter()
+            .map(|mnemonic| {
+                local_cosigner(mnemonic, script_type, network, &secp)
+                    .unwrap()
+                    .public
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sortedmulti_descriptors_ignore_cosigner_order() {
+        let keys = cosigner_keys(MultisigScriptType::P2wsh, BitcoinNetwork::Mainnet);
+        let descriptors =
+            multisig_descriptors(&keys, 2, MultisigScriptType::P2wsh, BitcoinNetwork::Mainnet)
+                .unwrap();
+        let mut reversed = keys.clone();
+        reversed.reverse();
+        let reordered = multisig_descriptors(
+            &reversed,
+            2,
+            MultisigScriptType::P2wsh,
+            BitcoinNetwork::Mainnet,
+        )
+        .unwrap();
+
+        assert!(descriptors
+            .external
+            .starts_with("wsh(sortedmulti(2,[73c5da0a/48'/0'/0'/2']xpub"));
+        assert!(descriptors.internal.as_deref().unwrap().contains("/1/*"));
+        let address = derive_address(&descriptors.external, 0, BitcoinNetwork::Mainnet).unwrap();
+        assert!(address.starts_with("bc1q") && address.len() == 62);
+        assert_eq!(
+            derive_address(&reordered.external, 0, BitcoinNetwork::Mainnet).unwrap(),
+            address
+        );
+    }
+
+    #[test]
+    fn taproot_multisig_spends_only_through_multi_a() {
+        let keys = cosigner_keys(MultisigScriptType::P2tr, BitcoinNetwork::Testnet);
+        let descriptors =
+            multisig_descriptors(&keys, 2, MultisigScriptType::P2tr, BitcoinNetwork::Testnet)
+                .unwrap();
+
+        assert!(descriptors.external.starts_with(
+            "tr(50929b74c1a04954b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac0,multi_a(2,[73c5da0a/87'/1'/0']tpub"
+        ));
+        assert!(
+            derive_address(&descriptors.external, 0, BitcoinNetwork::Testnet)
+                .unwrap()
+                .starts_with("tb1p")
+        );
+    }
+
+    #[test]
+    fn multisig_descriptors_reject_invalid_policies() {
+        let keys = cosigner_keys(MultisigScriptType::P2wsh, BitcoinNetwork::Mainnet);
+        let create = |keys: &[String], threshold| {
+            multisig_descriptors(
+                keys,
+                threshold,
+                MultisigScriptType::P2wsh,
+                BitcoinNetwork::Mainnet,
+            )
+            .unwrap_err()
+        };
+
+        assert_eq!(
+            create(&keys[..1], 1),
+            "A multisig wallet needs at least two cosigners"
+        );
+        assert_eq!(
+            create(&keys, 0),
+            "Threshold must be between 1 and 3 cosigners"
+        );
+        assert_eq!(
+            create(&keys, 4),
+            "Threshold must be between 1 and 3 cosigners"
+        );
+        assert_eq!(
+            create(&[keys[0].clone(), format!("{}/0/*", keys[0])], 1),
+            "Each cosigner key can only appear once"
+        );
+        assert_eq!(
+            multisig_descriptors(&keys, 2, MultisigScriptType::P2wsh, BitcoinNetwork::Testnet)
+                .unwrap_err(),
+            "Cosigner key does not belong to testnet"
+        );
+    }
+
+    #[test]
+    fn signing_descriptors_replace_only_the_local_key() {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let keys = cosigner_keys(MultisigScriptType::P2wsh, BitcoinNetwork::Mainnet);
+        let public = multisig_descriptors(
+            &keys[1..],
+            2,
+            MultisigScriptType::P2wsh,
+            BitcoinNetwork::Mainnet,
+        )
+        .unwrap();
+        let local = local_cosigner(
+            TEST_MNEMONICS[1],
+            MultisigScriptType::P2wsh,
+            BitcoinNetwork::Mainnet,
+            &secp,
+        )
+        .unwrap();
+
+        let signing = multisig_signing_descriptors(&public, &local).unwrap();
+
+        assert_eq!(signing.external.matches("xprv").count(), 1);
+        assert_eq!(signing.external.matches("xpub").count(), 1);
+
+        let outsider = local_cosigner(
+            TEST_MNEMONICS[0],
+            MultisigScriptType::P2wsh,
+            BitcoinNetwork::Mainnet,
+            &secp,
+        )
+        .unwrap();
+        assert_eq!(
+            multisig_signing_descriptors(&public, &outsider).unwrap_err(),
+            "This wallet's key is not one of the multisig cosigners"
+        );
+    }
+}
@@ -0,0 +1,663 @@
+use crate::wallet::bitcoin::amount::sats_to_btc;
+use crate::wallet::bitcoin::backend::connect_blockchain;
+use crate::wallet::bitcoin::batch::{batch_outputs, build_batch_psbt, validate_outputs};
+use crate::wallet::bitcoin::config::BitcoinNetwork;
+use crate::wallet::bitcoin::fees::{resolve_fee_rate, resolve_send_fee_rate};
+use crate::wallet::bitcoin::local_state::open_public_wallet;
+use crate::wallet::bitcoin::psbt::load_wallet_info;
+use crate::wallet::bitcoin::transaction::{
+    build_send_psbt, load_coin_control, plan_send, sync_signing_wallet,
+};
+use crate::wallet::bitcoin::utxo::normalize_outpoint;
+use crate::wallet::transaction_types::{
+    BitcoinBatchOutput, BitcoinPreviewInput, BitcoinPreviewOutput, BitcoinSendPreview,
+    SendBitcoinBatchRequest, SendBitcoinRequest,
+};
+use crate::wallet::types::WalletInfo;
+use bdk::bitcoin::psbt::PartiallySignedTransaction;
+use bdk::bitcoin::{Address, Script, ScriptBuf, Transaction, Txid, Witness};
+use bdk::blockchain::AnyBlockchain;
+use bdk::database::BatchDatabase;
+use bdk::Wallet;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a preview stays sendable before the send must be previewed again
+const PREVIEW_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Error of a preview for a wallet without watch-only descriptors. A send
+/// attempt stores them, after which the send can be previewed.
+pub(crate) const PREVIEW_UNAVAILABLE: &str = "preview_unavailable";
+
+/// Previewed sends awaiting approval, by template txid
+static PENDING_PREVIEWS: Lazy<Mutex<HashMap<Txid, PendingPreview>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+struct PendingPreview {
+    wallet_id: String,
+    terms: SendTerms,
+    psbt: PartiallySignedTransaction,
+    created_at: Instant,
+}
+
+/// What a send asks for, as far as it shapes the built transaction. A
+/// single send has one output; a batch has one per recipient.
+#[derive(Debug, PartialEq)]
+struct SendTerms {
+    outputs: Vec<(String, u64)>,
+    fee_rate: Option<f64>,
+    target_blocks: Option<u32>,
+    send_all: bool,
+    include_outpoints: Option<Vec<String>>,
+    exclude_outpoints: Vec<String>,
+}
+
+impl SendTerms {
+    fn of(request: &SendBitcoinRequest) -> Self {
+        Self {
+            outputs: vec![(request.to_address.trim().to_string(), request.amount_sats)],
+            fee_rate: request.fee_rate,
+            target_blocks: request.target_blocks,
+            send_all: request.send_all.unwrap_or(false),
+            include_outpoints: request
+                .include_outpoints
+                .as_deref()
+                .map(normalized_outpoints),
+            exclude_outpoints: normalized_outpoints(
+                request.exclude_outpoints.as_deref().unwrap_or_default(),
+            ),
+        }
+    }
+
+    fn of_batch(request: &SendBitcoinBatchRequest, outputs: &[BitcoinBatchOutput]) -> Self {
+        Self {
+            outputs: outputs
+                .iter()
+                .map(|output| (output.address.trim().to_string(), output.amount_sats))
+                .collect(),
+            fee_rate: request.fee_rate,
+            target_blocks: request.target_blocks,
+            send_all: false,
+            include_outpoints: request
+                .include_outpoints
+                .as_deref()
+                .map(normalized_outpoints),
+            exclude_outpoints: normalized_outpoints(
+                request.exclude_outpoints.as_deref().unwrap_or_default(),
+            ),
+        }
+    }
+}
+
+fn normalized_outpoints(outpoints: &[String]) -> Vec<String> {
+    let mut outpoints: Vec<String> = outpoints
+        .iter()
+        .map(|outpoint| {
+            normalize_outpoint(outpoint).unwrap_or_else(|_| outpoint.trim().to_string())
+        })
+        .collect();
+    outpoints.sort();
+    outpoints.dedup();
+    outpoints
+}
+
+/// Build a send from the wallet's watch-only descriptors and describe it,
+/// without signing or broadcasting it
+pub async fn preview_bitcoin_send(
+    request: SendBitcoinRequest,
+) -> Result<BitcoinSendPreview, String> {
+    let wallet_info = load_wallet_info(&request.wallet_id)?;
+    preview_bitcoin_send_resolved(request, wallet_info, connect_blockchain).await
+}
+
+async fn preview_bitcoin_send_resolved<F>(
+    mut request: SendBitcoinRequest,
+    wallet_info: WalletInfo,
+    connect_blockchain: F,
+) -> Result<BitcoinSendPreview, String>
+where
+    F: FnOnce(BitcoinNetwork, &str) -> Result<AnyBlockchain, String> + Send + 'static,
+{
+    let (wallet, network) =
+        open_public_wallet(&wallet_info)?.ok_or_else(|| PREVIEW_UNAVAILABLE.to_string())?;
+    let terms = SendTerms::of(&request);
+    let plan = plan_send(&wallet_info.id, &request, network)?;
+
+    let (wallet, _) =
+        sync_signing_wallet(wallet, network, &wallet_info.id, connect_blockchain).await?;
+
+    let (fee_rate, _) = resolve_send_fee_rate(&request, network).await?;
+    request.fee_rate = Some(fee_rate);
+    let psbt = build_send_psbt(&wallet, &request, &plan)?;
+    let preview = describe_preview(&wallet, &psbt, &[plan.recipient.script_pubkey()], network)?;
+    crate::safe_log!(
+        "[INFO] Previewed send {} for wallet {}",
+        preview.txid,
+        wallet_info.id
+    );
+
+    remember_preview(wallet_info.id, terms, psbt);
+    Ok(preview)
+}
+
+/// Build a batch payment from the wallet's watch-only descriptors and
+/// describe it, without signing or broadcasting it
+pub async fn preview_bitcoin_batch(
+    request: SendBitcoinBatchRequest,
+) -> Result<BitcoinSendPreview, String> {
+    let wallet_info = load_wallet_info(&request.wallet_id)?;
+    preview_bitcoin_batch_resolved(request, wallet_info, connect_blockchain).await
+}
+
+async fn preview_bitcoin_batch_resolved<F>(
+    request: SendBitcoinBatchRequest,
+    wallet_info: WalletInfo,
+    connect_blockchain: F,
+) -> Result<BitcoinSendPreview, String>
+where
+    F: FnOnce(BitcoinNetwork, &str) -> Result<AnyBlockchain, String> + Send + 'static,
+{
+    let (wallet, network) =
+        open_public_wallet(&wallet_info)?.ok_or_else(|| PREVIEW_UNAVAILABLE.to_string())?;
+    let outputs = batch_outputs(&request)?;
+    let recipients = validate_outputs(&outputs, network)?;
+    let terms = SendTerms::of_batch(&request, &outputs);
+    let coin_control = load_coin_control(
+        &wallet_info.id,
+        request.include_outpoints.as_deref(),
+        request.exclude_outpoints.as_deref(),
+    )?;
+
+    let (wallet, _) =
+        sync_signing_wallet(wallet, network, &wallet_info.id, connect_blockchain).await?;
+
+    let (fee_rate, _) = resolve_fee_rate(request.fee_rate, request.target_blocks, network).await?;
+    let psbt = build_batch_psbt(&wallet, &recipients, &coin_control, fee_rate)?;
+    let recipient_scripts: Vec<ScriptBuf> = recipients
+        .iter()
+        .map(|(address, _)| address.payload.script_pubkey())
+        .collect();
+    let preview = describe_preview(&wallet, &psbt, &recipient_scripts, network)?;
+    crate::safe_log!(
+        "[INFO] Previewed batch {} for wallet {}",
+        preview.txid,
+        wallet_info.id
+    );
+
+    remember_preview(wallet_info.id, terms, psbt);
+    Ok(preview)
+}
+
+fn remember_preview(wallet_id: String, terms: SendTerms, psbt: PartiallySignedTransaction) {
+    PENDING_PREVIEWS.lock().unwrap().insert(
+        psbt.unsigned_tx.txid(),
+        PendingPreview {
+            wallet_id,
+            terms,
+            psbt,
+            created_at: Instant::now(),
+        },
+    );
+}
+
+/// Inputs, outputs and signed size of an unsigned send. Input sizes come from
+/// the descriptors, so no key is needed to know the final fee rate.
+fn describe_preview<D: BatchDatabase>(
+    wallet: &Wallet<D>,
+    psbt: &PartiallySignedTransaction,
+    recipients: &[ScriptBuf],
+    network: BitcoinNetwork,
+) -> Result<BitcoinSendPreview, String> {
+    let tx = &psbt.unsigned_tx;
+    let address_of = |script: &Script| {
+        Address::from_script(script, network.bdk_network())
+            .ok()
+            .map(|address| address.to_string())
+    };
+
+    let mut weight = tx.weight().to_wu();
+    let mut segwit = false;
+    let mut inputs = Vec::new();
+    for input in &tx.input {
+        let utxo = wallet
+            .get_utxo(input.previous_output)
+            .map_err(|e| format!("Failed to look up input: {}", e))?
+            .ok_or_else(|| {
+                format!(
+                    "Input {} is not a coin of this wallet",
+                    input.previous_output
+                )
+            })?;
+        let descriptor = wallet.get_descriptor_for_keychain(utxo.keychain);
+        weight += descriptor
+            .max_weight_to_satisfy()
+            .map_err(|e| format!("Failed to size input: {}", e))? as u64;
+        segwit |= descriptor.desc_type().segwit_version().is_some();
+        inputs.push(BitcoinPreviewInput {
+            outpoint: input.previous_output.to_string(),
+            address: address_of(&utxo.txout.script_pubkey),
+            amount_sats: utxo.txout.value,
+        });
+    }
+    // Segwit transactions add a marker and flag; without them, legacy inputs
+    // drop the witness item count that input sizing assumes.
+    if segwit {
+        weight += 2;
+    } else {
+        weight -= tx.input.len() as u64;
+    }
+    let vsize = weight.div_ceil(4);
+
+    let mut outputs = Vec::new();
+    for output in &tx.output {
+        let is_mine = wallet
+            .is_mine(&output.script_pubkey)
+            .map_err(|e| format!("Failed to check output: {}", e))?;
+        outputs.push(BitcoinPreviewOutput {
+            address: address_of(&output.script_pubkey),
+            amount_sats: output.value,
+            is_change: is_mine && !recipients.contains(&output.script_pubkey),
+        });
+    }
+
+    let input_sats: u64 = inputs.iter().map(|input| input.amount_sats).sum();
+    let output_sats: u64 = outputs.iter().map(|output| output.amount_sats).sum();
+    let fee_sats = input_sats
+        .checked_sub(output_sats)
+        .ok_or_else(|| "Transaction outputs exceed its inputs".to_string())?;
+
+    Ok(BitcoinSendPreview {
+        txid: template_txid(tx).to_string(),
+        inputs,
+        outputs,
+        vsize,
+        fee_sats,
+        fee: sats_to_btc(fee_sats),
+        fee_rate: fee_sats as f64 / vsize as f64,
+        rbf: tx.is_explicitly_rbf(),
+    })
+}
+
+/// The unsigned transaction of a previewed send, as long as the send still
+/// asks for what was previewed
+pub(super) fn pending_preview(
+    wallet_id: &str,
+    request: &SendBitcoinRequest,
+    preview_txid: &str,
+) -> Result<PartiallySignedTransaction, String> {
+    take_pending(wallet_id, SendTerms::of(request), preview_txid)
+}
+
+/// The unsigned transaction of a previewed batch, as long as the batch still
+/// pays what was previewed
+pub(super) fn pending_batch_preview(
+    wallet_id: &str,
+    request: &SendBitcoinBatchRequest,
+    outputs: &[BitcoinBatchOutput],
+    preview_txid: &str,
+) -> Result<PartiallySignedTransaction, String> {
+    take_pending(
+        wallet_id,
+        SendTerms::of_batch(request, outputs),
+        preview_txid,
+    )
+}
+
+fn take_pending(
+    wallet_id: &str,
+    terms: SendTerms,
+    preview_txid: &str,
+) -> Result<PartiallySignedTransaction, String> {
+    let txid =
+        Txid::from_str(preview_txid.trim()).map_err(|e| format!("Invalid preview txid: {}", e))?;
+    let mut previews = PENDING_PREVIEWS.lock().unwrap();
+    previews.retain(|_, preview| preview.created_at.elapsed() < PREVIEW_TTL);
+
+    let preview = previews
+        .get(&txid)
+        .ok_or_else(|| "Send preview expired or not found; preview the send again".to_string())?;
+    if preview.wallet_id != wallet_id || preview.terms != terms {
+        return Err("Send does not match its preview; preview the send again".to_string());
+    }
+    Ok(preview.psbt.clone())
+}
+
+/// Refuses a signed transaction that is not the previewed one
+pub(super) fn ensure_matches_preview(tx: &Transaction, preview_txid: &str) -> Result<(), String> {
+    let template = template_txid(tx).to_string();
+    if template != preview_txid.trim() {
+        return Err(format!(
+            "Signed transaction {} does not match preview {}",
+            template, preview_txid
+        ));
+    }
+    Ok(())
+}
+
+/// Drops a preview once its transaction is broadcast
+pub(super) fn forget_preview(preview_txid: &str) {
+    if let Ok(txid) = Txid::from_str(preview_txid.trim()) {
+        PENDING_PREVIEWS.lock().unwrap().remove(&txid);
+    }
+}
+
+/// Txid of `tx` with its scriptSigs and witnesses cleared. Signing only fills
+/// those in, so a signed transaction keeps the template txid of its preview.
+pub(crate) fn template_txid(tx: &Transaction) -> Txid {
+    let mut template = tx.clone();
+    for input in &mut template.input {
+        input.script_sig = ScriptBuf::new();
+        input.witness = Witness::new();
+    }
+    template.txid()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        describe_preview, ensure_matches_preview, pending_batch_preview, pending_preview,
+        preview_bitcoin_send_resolved, template_txid, PendingPreview, SendTerms, PENDING_PREVIEWS,
+        PREVIEW_TTL, PREVIEW_UNAVAILABLE,
+    };
+    use crate::wallet::bitcoin::config::BitcoinNetwork;
+    use crate::wallet::bitcoin::descriptor::{
+        account_descriptors_from_mnemonic, derive_address, BitcoinScriptType,
+    };
+    use crate::wallet::security::secret_envelope::{StoredSecret, SECRET_FORMAT_PLAINTEXT_V0};
+    use crate::wallet::transaction_types::{
+        BitcoinBatchOutput, SendBitcoinBatchRequest, SendBitcoinRequest,
+    };
+    use crate::DB;
+    use bdk::bitcoin::absolute::LockTime;
+    use bdk::bitcoin::psbt::PartiallySignedTransaction;
+    use bdk::bitcoin::{Address, OutPoint, ScriptBuf, Transaction, TxIn, TxOut, Witness};
+    use bdk::database::{BatchOperations, MemoryDatabase};
+    use bdk::{BlockTime, KeychainKind, LocalUtxo, SignOptions, TransactionDetails, Wallet};
+    use std::str::FromStr;
+    use std::time::Instant;
+    use uuid::Uuid;
+
+    const TEST_MNEMONIC: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    const RECIPIENT: &str = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+
+    /// A wallet over the test mnemonic holding one made-up 100k sat coin
+    fn funded_wallet(script_type: BitcoinScriptType, signing: bool) -> Wallet<MemoryDatabase> {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let descriptors = account_descriptors_from_mnemonic(
+            TEST_MNEMONIC,
+            script_type,
+            BitcoinNetwork::Mainnet,
+            &secp,
+        )
+        .unwrap();
+        let address =
+            derive_address(&descriptors.public.external, 0, BitcoinNetwork::Mainnet).unwrap();
+        let script_pubkey = Address::from_str(&address)
+            .unwrap()
+            .assume_checked()
+            .script_pubkey();
+
+        let funding = Transaction {
+            version: 2,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn::default()],
+            output: vec![TxOut {
+                value: 100_000,
+                script_pubkey,
+            }],
+        };
+        let mut database = MemoryDatabase::new();
+        database
+            .set_script_pubkey(&funding.output[0].script_pubkey, KeychainKind::External, 0)
+            .unwrap();
+        database
+            .set_utxo(&LocalUtxo {
+                outpoint: OutPoint::new(funding.txid(), 0),
+                txout: funding.output[0].clone(),
+                keychain: KeychainKind::External,
+                is_spent: false,
+            })
+            .unwrap();
+        database
+            .set_tx(&TransactionDetails {
+                txid: funding.txid(),
+                received: 100_000,
+                sent: 0,
+                fee: None,
+                confirmation_time: Some(BlockTime {
+                    height: 100,
+                    timestamp: 1_700_000_000,
+                }),
+                transaction: Some(funding),
+            })
+            .unwrap();
+
+        let used = if signing {
+            descriptors.signing
+        } else {
+            descriptors.public
+        };
+        Wallet::new(
+            used.external.as_str(),
+            used.internal.as_deref(),
+            bdk::bitcoin::Network::Bitcoin,
+            database,
+        )
+        .unwrap()
+    }
+
+    fn build_send(wallet: &Wallet<MemoryDatabase>) -> PartiallySignedTransaction {
+        let mut builder = wallet.build_tx();
+        builder
+            .add_recipient(
+                Address::from_str(RECIPIENT)
+                    .unwrap()
+                    .assume_checked()
+                    .script_pubkey(),
+                40_000,
+            )
+            .enable_rbf()
+            .fee_rate(bdk::FeeRate::from_sat_per_vb(5.0));
+        builder.finish().unwrap().0
+    }
+
+    fn send_request(wallet_id: &str) -> SendBitcoinRequest {
+        SendBitcoinRequest {
+            wallet_id: wallet_id.to_string(),
+            to_address: RECIPIENT.to_string(),
+            amount_sats: 40_000,
+            fee_rate: None,
+            target_blocks: None,
+            send_all: None,
+            include_outpoints: None,
+            exclude_outpoints: None,
+            preview_txid: None,
+        }
+    }
+
+    #[test]
+    fn preview_describes_the_signed_transaction() {
+        for script_type in [BitcoinScriptType::P2wpkh, BitcoinScriptType::P2tr] {
+            let wallet = funded_wallet(script_type, true);
+            let psbt = build_send(&wallet);
+            let recipient = Address::from_str(RECIPIENT)
+                .unwrap()
+                .assume_checked()
+                .script_pubkey();
+
+            let preview =
+                describe_preview(&wallet, &psbt, &[recipient], BitcoinNetwork::Mainnet).unwrap();
+            assert_eq!(preview.inputs.len(), 1);
+            assert_eq!(preview.inputs[0].amount_sats, 100_000);
+            assert_eq!(preview.outputs.len(), 2);
+            assert_eq!(
+                preview
+                    .outputs
+                    .iter()
+                    .filter(|output| output.is_change)
+                    .count(),
+                1
+            );
+            assert_eq!(
+                preview.fee_sats,
+                100_000 - preview.outputs.iter().map(|o| o.amount_sats).sum::<u64>()
+            );
+            assert!(preview.rbf);
+
+            let mut signed = psbt.clone();
+            assert!(wallet.sign(&mut signed, SignOptions::default()).unwrap());
+            let tx = signed.extract_tx();
+            // Sizing assumes maximum-size signatures, so it never undershoots
+            assert!(preview.vsize >= tx.vsize() as u64);
+            assert!(preview.vsize - tx.vsize() as u64 <= 1);
+            assert!(preview.fee_rate >= 4.9);
+            ensure_matches_preview(&tx, &preview.txid).unwrap();
+        }
+    }
+
+    #[test]
+    fn template_txid_ignores_signatures_but_not_the_spend() {
+        let wallet = funded_wallet(BitcoinScriptType::P2wpkh, false);
+        let psbt = build_send(&wallet);
+        let template = template_txid(&psbt.unsigned_tx);
+
+        let mut signed = psbt.unsigned_tx.clone();
+        signed.input[0].script_sig = ScriptBuf::from_bytes(vec![0x51]);
+        signed.input[0].witness = Witness::from_slice(&[vec![1u8; 72]]);
+        assert_eq!(template_txid(&signed), template);
+
+        let mut altered = psbt.unsigned_tx.clone();
+        altered.output[0].value -= 1;
+        assert!(ensure_matches_preview(&altered, &template.to_string()).is_err());
+    }
+
+    #[test]
+    fn pending_preview_must_match_the_send_and_be_fresh() {
+        let wallet = funded_wallet(BitcoinScriptType::P2wpkh, false);
+        let psbt = build_send(&wallet);
+        let txid = psbt.unsigned_tx.txid();
+        PENDING_PREVIEWS.lock().unwrap().insert(
+            txid,
+            PendingPreview {
+                wallet_id: "preview-wallet".to_string(),
+                terms: SendTerms::of(&send_request("preview-wallet")),
+                psbt: psbt.clone(),
+                created_at: Instant::now(),
+            },
+        );
+
+        let request = send_request("preview-wallet");
+        let pending = pending_preview("preview-wallet", &request, &txid.to_string()).unwrap();
+        assert_eq!(pending.unsigned_tx, psbt.unsigned_tx);
+
+        let mut changed = send_request("preview-wallet");
+        changed.amount_sats = 50_000;
+        assert_eq!(
+            pending_preview("preview-wallet", &changed, &txid.to_string()).unwrap_err(),
+            "Send does not match its preview; preview the send again"
+        );
+        assert!(pending_preview("other-wallet", &request, &txid.to_string()).is_err());
+
+        let outpoint = format!("{}:0", "11".repeat(32));
+        let changes: [fn(&mut SendBitcoinRequest, &str); 4] = [
+            |request, _| request.fee_rate = Some(5.0),
+            |request, _| request.send_all = Some(true),
+            |request, outpoint| request.include_outpoints = Some(vec![outpoint.to_string()]),
+            |request, outpoint| request.exclude_outpoints = Some(vec![outpoint.to_string()]),
+        ];
+        for change in changes {
+            let mut changed = send_request("preview-wallet");
+            change(&mut changed, &outpoint);
+            assert!(pending_preview("preview-wallet", &changed, &txid.to_string()).is_err());
+        }
+        let mut unchanged = send_request("preview-wallet");
+        unchanged.send_all = Some(false);
+        unchanged.exclude_outpoints = Some(Vec::new());
+        assert!(pending_preview("preview-wallet", &unchanged, &txid.to_string()).is_ok());
+
+        PENDING_PREVIEWS
+            .lock()
+            .unwrap()
+            .get_mut(&txid)
+            .unwrap()
+            .created_at = Instant::now() - PREVIEW_TTL;
+        assert!(pending_preview("preview-wallet", &request, &txid.to_string()).is_err());
+        assert!(!PENDING_PREVIEWS.lock().unwrap().contains_key(&txid));
+    }
+
+    #[test]
+    fn pending_batch_preview_must_pay_the_previewed_outputs() {
+        let wallet = funded_wallet(BitcoinScriptType::P2wpkh, false);
+        let psbt = build_send(&wallet);
+        let txid = psbt.unsigned_tx.txid();
+        let request = SendBitcoinBatchRequest {
+            wallet_id: "batch-preview-wallet".to_string(),
+            outputs: Vec::new(),
+            csv: None,
+            csv_path: None,
+            fee_rate: Some(5.0),
+            target_blocks: None,
+            include_outpoints: None,
+            exclude_outpoints: None,
+            preview_txid: None,
+        };
+        let output = |amount_sats: u64| BitcoinBatchOutput {
+            address: RECIPIENT.to_string(),
+            amount_sats,
+        };
+        let outputs = vec![output(40_000), output(10_000)];
+        PENDING_PREVIEWS.lock().unwrap().insert(
+            txid,
+            PendingPreview {
+                wallet_id: "batch-preview-wallet".to_string(),
+                terms: SendTerms::of_batch(&request, &outputs),
+                psbt: psbt.clone(),
+                created_at: Instant::now(),
+            },
+        );
+        let pending = |outputs: &[BitcoinBatchOutput]| {
+            pending_batch_preview("batch-preview-wallet", &request, outputs, &txid.to_string())
+        };
+
+        assert!(pending(&outputs).is_ok());
+        assert!(pending(&[output(40_000)]).is_err());
+        assert!(pending(&[output(10_000), output(40_000)]).is_err());
+        assert!(pending(&[output(40_000), output(10_001)]).is_err());
+
+        PENDING_PREVIEWS.lock().unwrap().remove(&txid);
+    }
+
+    #[tokio::test]
+    async fn preview_needs_watch_only_descriptors() {
+        let unique = Uuid::new_v4().simple().to_string();
+        let wallet_info = DB
+            .lock()
+            .unwrap()
+            .insert_bitcoin_wallet_with_secret(
+                format!("BTC preview {unique}"),
+                "mnemonic".to_string(),
+                format!("btc-preview-{unique}"),
+                StoredSecret {
+                    secret_data: TEST_MNEMONIC.to_string(),
+                    secret_format: SECRET_FORMAT_PLAINTEXT_V0.to_string(),
+                },
+                "mnemonic".to_string(),
+            )
+            .unwrap();
+        let wallet_id = wallet_info.id.clone();
+
+        let result =
+            preview_bitcoin_send_resolved(send_request(&wallet_id), wallet_info, |_, _| {
+                panic!("preview must not connect without descriptors")
+            })
+            .await;
+        let _ = DB.lock().unwrap().delete_bitcoin_wallet(&wallet_id);
+
+        let error = result.unwrap_err();
+        assert_eq!(error, PREVIEW_UNAVAILABLE);
+    }
+}
@@ -1,12 +1,17 @@
+use crate::db::BitcoinWalletSetup;
+use crate::wallet::bitcoin::config::BitcoinNetwork;
+use crate::wallet::bitcoin::descriptor::{
+    derive_address, single_key_public_descriptor, BitcoinScriptType,
+};
 use crate::wallet::security::backend::SecretBackend;
 use crate::wallet::security::commands::ensure_local_password_configured;
 use crate::wallet::security::commands::AppSecurity;
 use crate::wallet::security::keystore::Keystore;
+use crate::wallet::security::mnemonic_secret::MnemonicSecret;
 use crate::wallet::security::session::SessionManager;
 use crate::wallet::security::types::{SecurityError, SignerOperation};
 use crate::wallet::types::CreateWalletResponse;
 use crate::DB;
-use bitcoin::{Address, Network};
 use std::str::FromStr;
 
 pub(crate) fn map_security_error(error: SecurityError) -> String {
@@ -20,6 +25,7 @@ pub(crate) fn map_security_error(error: SecurityError) -> String {
         SecurityError::OperationNotAllowed => "operation_not_allowed".to_string(),
         SecurityError::UnknownWallet => "unknown_wallet".to_string(),
         SecurityError::SecretBackendUnavailable => "secret_backend_unavailable".to_string(),
+        SecurityError::WatchOnly => "watch_only".to_string(),
     }
 }
 
@@ -29,8 +35,11 @@ fn export_mnemonic_inner(
     secret_backend: &SecretBackend,
     keystore: &(dyn Keystore + Send + Sync),
     session_manager: &SessionManager,
-) -> Result<String, String> {
-    if wallet_type != "mnemonic" {
+) -> Result<MnemonicSecret, String> {
+    if wallet_type == "watch-only" {
+        return Err(map_security_error(SecurityError::WatchOnly));
+    }
+    if wallet_type != "mnemonic" && wallet_type != "multisig" {
         return Err("This wallet was imported from a private key, not a mnemonic.".to_string());
     }
 
@@ -42,11 +51,14 @@ fn export_mnemonic_inner(
         SignerOperation::ExportMnemonic,
     )
     .map_err(map_security_error)?
+    .map(|secret_data| MnemonicSecret::decode(&secret_data))
     .ok_or_else(|| "Wallet secret not found".to_string())
 }
 
 fn export_private_key_inner(
     wallet_type: &str,
+    script_type: BitcoinScriptType,
+    network: BitcoinNetwork,
     address: &str,
     secret_backend: &SecretBackend,
     keystore: &(dyn Keystore + Send + Sync),
@@ -72,8 +84,10 @@ fn export_private_key_inner(
             )
             .map_err(map_security_error)?
             .ok_or_else(|| "Wallet secret not found".to_string())?;
-            derive_private_key_from_mnemonic(&mnemonic)
+            derive_private_key_from_mnemonic(&mnemonic, script_type, network)
         }
+        "watch-only" => Err(map_security_error(SecurityError::WatchOnly)),
+        "multisig" => Err("Multisig wallets have no single private key to export".to_string()),
         _ => Err("Unknown wallet type".to_string()),
     }
 }
@@ -103,7 +117,7 @@ pub(crate) fn load_authorized_private_key(
 }
 
 /// Validates a private key string (WIF or hex format)
-fn validate_private_key(
+pub(crate) fn validate_private_key(
     key_str: &str,
 ) -> Result<(bitcoin::secp256k1::SecretKey, bitcoin::key::PublicKey), String> {
     let trimmed = key_str.trim();
@@ -145,6 +159,8 @@ pub fn bitcoin_create_wallet_from_private_key(
     private_key: String,
     wallet_label: Option<String>,
     reveal_secret: Option<bool>,
+    script_type: Option<BitcoinScriptType>,
+    network: Option<BitcoinNetwork>,
     state: tauri::State<'_, AppSecurity>,
 ) -> Result<CreateWalletResponse, String> {
     ensure_local_password_configured().map_err(map_security_error)?;
@@ -152,14 +168,11 @@ pub fn bitcoin_create_wallet_from_private_key(
     // Validate and parse private key
     let (_secret, public_key) = validate_private_key(&private_key)?;
 
-    let secp = bitcoin::secp256k1::Secp256k1::new();
-
-    // Convert to XOnlyPublicKey for Taproot (P2TR)
-    let xonly_public_key = bitcoin::key::XOnlyPublicKey::from(public_key);
-
-    // Generate Taproot address (bc1p...)
-    let address = Address::p2tr(&secp, xonly_public_key, None, Network::Bitcoin);
-    let address_str = address.to_string();
+    // Generate the address for the chosen script type (Taproot unless specified)
+    let script_type = script_type.unwrap_or_default();
+    let network = network.unwrap_or_default();
+    let descriptors = single_key_public_descriptor(&public_key, script_type);
+    let address_str = derive_address(&descriptors.external, 0, network)?;
     let label = wallet_label.unwrap_or_else(|| "Bitcoin Wallet".to_string());
     let has_existing_secrets = {
         let db = DB.lock().unwrap();
@@ -177,15 +190,22 @@ pub fn bitcoin_create_wallet_from_private_key(
         .prepare_encrypted_secret(&private_key)
         .map_err(map_security_error)?;
 
+    let setup = BitcoinWalletSetup {
+        script_type,
+        network,
+        descriptors,
+        multisig: None,
+    };
+
     // Store wallet in database
     let db = DB.lock().unwrap();
     let wallet = db
-        .insert_bitcoin_wallet_with_secret(
+        .insert_bitcoin_wallet_with_setup(
             label,
             "private-key".to_string(),
             address_str,
-            stored_secret,
-            "private-key".to_string(),
+            Some((stored_secret, "private-key".to_string())),
+            &setup,
         )
         .map_err(|e| format!("Failed to save wallet: {}", e))?;
 
@@ -206,7 +226,7 @@ pub fn bitcoin_create_wallet_from_private_key(
 pub fn bitcoin_export_mnemonic(
     wallet_id: String,
     state: tauri::State<'_, AppSecurity>,
-) -> Result<String, String> {
+) -> Result<MnemonicSecret, String> {
     let db = DB.lock().unwrap();
 
     // Get wallet to verify it exists
@@ -243,10 +263,18 @@ pub fn bitcoin_export_private_key(
 
     let address = wallet.address.clone();
     let wallet_type = wallet.wallet_type.clone();
+    let script_type = db
+        .get_bitcoin_wallet_script_type(&wallet_id)
+        .map_err(|e| format!("Failed to get wallet script type: {}", e))?;
+    let network = db
+        .get_bitcoin_wallet_network(&wallet_id)
+        .map_err(|e| format!("Failed to get wallet network: {}", e))?;
     drop(db);
 
     export_private_key_inner(
         &wallet_type,
+        script_type,
+        network,
         &address,
         state.secret_backend(),
         state.keystore(),
@@ -254,27 +282,27 @@ pub fn bitcoin_export_private_key(
     )
 }
 
-/// Derive the private key from a mnemonic phrase
-fn derive_private_key_from_mnemonic(mnemonic_str: &str) -> Result<String, String> {
-    use bip39::{Language, Mnemonic};
+/// Derive the private key of the wallet's primary address from a mnemonic phrase
+fn derive_private_key_from_mnemonic(
+    mnemonic_str: &str,
+    script_type: BitcoinScriptType,
+    network: BitcoinNetwork,
+) -> Result<String, String> {
     use bitcoin::bip32::{DerivationPath, Xpriv};
     use std::str::FromStr;
 
-    // Parse mnemonic
-    let mnemonic = Mnemonic::parse_in_normalized(Language::English, mnemonic_str)
-        .map_err(|e| format!("Invalid mnemonic: {}", e))?;
-
-    // Generate seed
-    let seed = mnemonic.to_seed("");
+    // Generate seed under the stored passphrase, if any
+    let seed = MnemonicSecret::decode(mnemonic_str).to_seed()?;
 
     // Create master private key
     let secp = bitcoin::secp256k1::Secp256k1::new();
-    let master_xprv = Xpriv::new_master(Network::Bitcoin, &seed)
+    let master_xprv = Xpriv::new_master(network.network(), &seed)
         .map_err(|e| format!("Failed to create master key: {}", e))?;
 
-    // Standard BIP44 derivation path: m/44'/0'/0'/0/0
-    let derivation_path = DerivationPath::from_str("m/44'/0'/0'/0/0")
-        .map_err(|e| format!("Invalid derivation path: {}", e))?;
+    // First receive address of the script type's account, e.g. m/86'/0'/0'/0/0
+    let derivation_path =
+        DerivationPath::from_str(&format!("{}/0/0", script_type.account_path(network)))
+            .map_err(|e| format!("Invalid derivation path: {}", e))?;
 
     let child_xprv = master_xprv
         .derive_priv(&secp, &derivation_path)
@@ -289,11 +317,17 @@ fn derive_private_key_from_mnemonic(mnemonic_str: &str) -> Result<String, String
 #[cfg(test)]
 mod tests {
     use super::{
-        export_mnemonic_inner, export_private_key_inner, load_authorized_mnemonic,
-        load_authorized_private_key,
+        derive_private_key_from_mnemonic, export_mnemonic_inner, export_private_key_inner,
+        load_authorized_mnemonic, load_authorized_private_key, validate_private_key,
+    };
+    use crate::wallet::bitcoin::config::BitcoinNetwork;
+    use crate::wallet::bitcoin::descriptor::{
+        account_descriptors_from_mnemonic, derive_address, single_key_public_descriptor,
+        BitcoinScriptType,
     };
     use crate::wallet::security::backend::{SecretBackend, SecretBackendAdapter};
     use crate::wallet::security::keystore::Keystore;
+    use crate::wallet::security::mnemonic_secret::MnemonicSecret;
     use crate::wallet::security::secret_envelope::{SecretEnvelopeError, StoredSecret};
     use crate::wallet::security::session::SessionManager;
     use crate::wallet::security::types::{SecurityError, SignerOperation};
@@ -304,6 +338,11 @@ mod tests {
 
     struct StubKeystore;
 
+    struct PassphraseKeystore;
+
+    const TEST_MNEMONIC: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
     struct ReadySecretBackendAdapter;
 
     struct UnavailableSecretBackendAdapter;
@@ -380,6 +419,18 @@ mod tests {
         }
     }
 
+    impl Keystore for PassphraseKeystore {
+        fn load_mnemonic(&self, _address: &str) -> Result<Option<String>, SecurityError> {
+            Ok(Some(
+                MnemonicSecret::new(TEST_MNEMONIC, Some("TREZOR")).encode(),
+            ))
+        }
+
+        fn load_private_key(&self, _address: &str) -> Result<Option<String>, SecurityError> {
+            panic!("mnemonic wallets have no stored private key");
+        }
+    }
+
     #[test]
     fn export_mnemonic_returns_locked_without_keystore_access() {
         let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
@@ -414,6 +465,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn export_rejects_watch_only_wallet_without_keystore_access() {
+        let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
+        let backend = ready_backend();
+
+        assert_eq!(
+            export_mnemonic_inner(
+                "watch-only",
+                "bc1ptestaddress",
+                &backend,
+                &PanicKeystore,
+                &session
+            ),
+            Err("watch_only".to_string())
+        );
+        assert_eq!(
+            export_private_key_inner(
+                "watch-only",
+                BitcoinScriptType::P2wpkh,
+                BitcoinNetwork::Mainnet,
+                "bc1ptestaddress",
+                &backend,
+                &PanicKeystore,
+                &session
+            ),
+            Err("watch_only".to_string())
+        );
+    }
+
     #[test]
     fn load_authorized_mnemonic_returns_secret_after_send_reauth() {
         let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
@@ -502,6 +582,8 @@ mod tests {
         assert_eq!(
             export_private_key_inner(
                 "private-key",
+                BitcoinScriptType::P2tr,
+                BitcoinNetwork::Mainnet,
                 "bc1ptestaddress",
                 &backend,
                 &PanicKeystore,
@@ -510,4 +592,87 @@ mod tests {
             Err("reauth_required".to_string())
         );
     }
+
+    #[test]
+    fn exported_mnemonic_key_controls_primary_address_for_each_script_type() {
+        let mnemonic =
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let expected = [
+            (
+                BitcoinScriptType::P2pkh,
+                "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA",
+            ),
+            (
+                BitcoinScriptType::P2shP2wpkh,
+                "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf",
+            ),
+            (
+                BitcoinScriptType::P2wpkh,
+                "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            ),
+            (
+                BitcoinScriptType::P2tr,
+                "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+            ),
+        ];
+
+        for (script_type, address) in expected {
+            let wif =
+                derive_private_key_from_mnemonic(mnemonic, script_type, BitcoinNetwork::Mainnet)
+                    .unwrap();
+            let (_, public_key) = validate_private_key(&wif).unwrap();
+            let descriptors = single_key_public_descriptor(&public_key, script_type);
+
+            assert_eq!(
+                derive_address(&descriptors.external, 0, BitcoinNetwork::Mainnet).unwrap(),
+                address
+            );
+        }
+    }
+
+    #[test]
+    fn passphrase_mnemonic_exports_phrase_and_derives_passphrase_keys() {
+        let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
+        let backend = ready_backend();
+        session
+            .authorize_verified_operation(SignerOperation::ExportMnemonic)
+            .unwrap();
+
+        assert_eq!(
+            export_mnemonic_inner(
+                "mnemonic",
+                "bc1qtestaddress",
+                &backend,
+                &PassphraseKeystore,
+                &session
+            ),
+            Ok(MnemonicSecret::new(TEST_MNEMONIC, Some("TREZOR")))
+        );
+
+        let secret = MnemonicSecret::new(TEST_MNEMONIC, Some("TREZOR")).encode();
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let descriptors = account_descriptors_from_mnemonic(
+            &secret,
+            BitcoinScriptType::P2wpkh,
+            BitcoinNetwork::Mainnet,
+            &secp,
+        )
+        .unwrap();
+        let primary =
+            derive_address(&descriptors.public.external, 0, BitcoinNetwork::Mainnet).unwrap();
+        let wif = derive_private_key_from_mnemonic(
+            &secret,
+            BitcoinScriptType::P2wpkh,
+            BitcoinNetwork::Mainnet,
+        )
+        .unwrap();
+        let (_, public_key) = validate_private_key(&wif).unwrap();
+        let exported = single_key_public_descriptor(&public_key, BitcoinScriptType::P2wpkh);
+
+        assert_ne!(primary, "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu");
+        assert_eq!(
+            derive_address(&exported.external, 0, BitcoinNetwork::Mainnet).unwrap(),
+            primary
+        );
+    }
 }
@@ -0,0 +1,902 @@
+use crate::wallet::bitcoin::amount::sats_to_btc;
+use crate::wallet::bitcoin::backend::connect_blockchain;
+use crate::wallet::bitcoin::config::BitcoinNetwork;
+use crate::wallet::bitcoin::fees::resolve_send_fee_rate;
+use crate::wallet::bitcoin::local_state::open_public_wallet;
+use crate::wallet::bitcoin::multisig::{signatures_present, MultisigPolicy};
+use crate::wallet::bitcoin::private_key::map_security_error;
+use crate::wallet::bitcoin::transaction::{
+    broadcast_record, broadcast_transaction, build_send_psbt, load_signing_secret,
+    open_signing_wallet, plan_send, sync_signing_wallet, HistoryTxo,
+};
+use crate::wallet::security::backend::SecretBackend;
+use crate::wallet::security::keystore::Keystore;
+use crate::wallet::security::session::SessionManager;
+use crate::wallet::transaction_types::{
+    BitcoinCombinePsbtRequest, BitcoinPsbtRequest, BitcoinPsbtResponse, SendBitcoinRequest,
+    SendTransactionResponse,
+};
+use crate::wallet::types::WalletInfo;
+use crate::DB;
+use bdk::bitcoin::psbt::PartiallySignedTransaction;
+use bdk::bitcoin::TxOut;
+use bdk::blockchain::AnyBlockchain;
+use bdk::database::{BatchDatabase, SqliteDatabase};
+use bdk::psbt::PsbtUtils;
+use bdk::{SignOptions, Wallet};
+use std::collections::HashSet;
+use std::str::FromStr;
+
+/// BIP174 magic bytes that open every binary PSBT
+const PSBT_MAGIC: &[u8] = b"psbt\xff";
+
+/// Addresses cached before offline signing so inputs can be matched to our keys
+const OFFLINE_ADDRESS_CACHE: u32 = 100;
+
+/// Build an unsigned PSBT for a send, without signing or broadcasting it
+pub async fn create_unsigned_psbt(
+    request: SendBitcoinRequest,
+    output_path: Option<String>,
+    secret_backend: &SecretBackend,
+    keystore: &(dyn Keystore + Send + Sync),
+    session_manager: &SessionManager,
+) -> Result<BitcoinPsbtResponse, String> {
+    let wallet_info = load_wallet_info(&request.wallet_id)?;
+
+    create_unsigned_psbt_resolved(
+        request,
+        wallet_info,
+        output_path,
+        secret_backend,
+        keystore,
+        session_manager,
+        connect_blockchain,
+    )
+    .await
+}
+
+async fn create_unsigned_psbt_resolved<F>(
+    mut request: SendBitcoinRequest,
+    wallet_info: WalletInfo,
+    output_path: Option<String>,
+    secret_backend: &SecretBackend,
+    keystore: &(dyn Keystore + Send + Sync),
+    session_manager: &SessionManager,
+    connect_blockchain: F,
+) -> Result<BitcoinPsbtResponse, String>
+where
+    F: FnOnce(BitcoinNetwork, &str) -> Result<AnyBlockchain, String> + Send + 'static,
+{
+    let (wallet, network) =
+        open_building_wallet(&wallet_info, secret_backend, keystore, session_manager)?;
+    let plan = plan_send(&wallet_info.id, &request, network)?;
+
+    let (wallet, _) =
+        sync_signing_wallet(wallet, network, &wallet_info.id, connect_blockchain).await?;
+
+    let (fee_rate, _) = resolve_send_fee_rate(&request, network).await?;
+    request.fee_rate = Some(fee_rate);
+    let psbt = build_send_psbt(&wallet, &request, &plan)?;
+    crate::safe_log!(
+        "[INFO] Built unsigned PSBT {} for wallet {}",
+        psbt.unsigned_tx.txid(),
+        wallet_info.id
+    );
+
+    let policy = load_multisig_policy(&wallet_info.id)?;
+    to_psbt_response(psbt, output_path.as_deref(), policy.as_ref())
+}
+
+/// Opens a wallet to build an unsigned transaction. Building only needs
+/// public descriptors; wallets created before those were stored fall back to
+/// the signer, which also backfills them.
+pub(super) fn open_building_wallet(
+    wallet_info: &WalletInfo,
+    secret_backend: &SecretBackend,
+    keystore: &(dyn Keystore + Send + Sync),
+    session_manager: &SessionManager,
+) -> Result<(Wallet<SqliteDatabase>, BitcoinNetwork), String> {
+    if let Some(opened) = open_public_wallet(wallet_info)? {
+        return Ok(opened);
+    }
+    let signing_secret =
+        load_signing_secret(wallet_info, secret_backend, keystore, session_manager)
+            .map_err(map_security_error)?
+            .ok_or_else(|| "Wallet secret not found".to_string())?;
+    open_signing_wallet(wallet_info, signing_secret)
+}
+
+/// Sign a PSBT built elsewhere with this wallet's key. Needs no network access,
+/// so it also works on an air-gapped machine.
+pub fn sign_psbt(
+    request: BitcoinPsbtRequest,
+    secret_backend: &SecretBackend,
+    keystore: &(dyn Keystore + Send + Sync),
+    session_manager: &SessionManager,
+) -> Result<BitcoinPsbtResponse, String> {
+    let wallet_info = load_wallet_info(&request.wallet_id)?;
+    let mut psbt = load_psbt(request.psbt.as_deref(), request.psbt_path.as_deref())?;
+
+    let signing_secret =
+        load_signing_secret(&wallet_info, secret_backend, keystore, session_manager)
+            .map_err(map_security_error)?
+            .ok_or_else(|| "Wallet secret not found".to_string())?;
+    let (wallet, _) = open_signing_wallet(&wallet_info, signing_secret)?;
+    wallet
+        .ensure_addresses_cached(OFFLINE_ADDRESS_CACHE)
+        .map_err(|e| format!("Failed to derive wallet addresses: {}", e))?;
+
+    let unsigned = psbt.clone();
+    wallet
+        .sign(&mut psbt, SignOptions::default())
+        .map_err(|e| {
+            crate::safe_log!("[ERROR] Failed to sign PSBT: {}", e);
+            format!("Failed to sign PSBT: {}", e)
+        })?;
+    if psbt == unsigned {
+        return Err("PSBT has no inputs this wallet can sign".to_string());
+    }
+    crate::safe_log!("[INFO] Signed PSBT {}", psbt.unsigned_tx.txid());
+
+    let policy = load_multisig_policy(&wallet_info.id)?;
+    to_psbt_response(psbt, request.output_path.as_deref(), policy.as_ref())
+}
+
+/// Merge the signatures cosigners added to separate copies of one PSBT
+/// (the BIP174 combiner), finalizing it once the threshold is met.
+pub fn combine_psbts(request: BitcoinCombinePsbtRequest) -> Result<BitcoinPsbtResponse, String> {
+    let wallet_info = load_wallet_info(&request.wallet_id)?;
+    let mut psbts = request
+        .psbts
+        .iter()
+        .map(|psbt| decode_psbt(psbt.as_bytes()))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter();
+    let mut combined = psbts
+        .next()
+        .ok_or_else(|| "Provide at least one PSBT".to_string())?;
+
+    for psbt in psbts {
+        if psbt.unsigned_tx.txid() != combined.unsigned_tx.txid() {
+            return Err("PSBTs spend different transactions".to_string());
+        }
+        combined
+            .combine(psbt)
+            .map_err(|e| format!("Failed to combine PSBTs: {}", e))?;
+    }
+
+    if let Some((wallet, _)) = open_public_wallet(&wallet_info)? {
+        wallet
+            .ensure_addresses_cached(OFFLINE_ADDRESS_CACHE)
+            .map_err(|e| format!("Failed to derive wallet addresses: {}", e))?;
+        if !is_finalized(&combined) {
+            wallet
+                .finalize_psbt(&mut combined, SignOptions::default())
+                .map_err(|e| format!("Failed to finalize PSBT: {}", e))?;
+        }
+    }
+    crate::safe_log!("[INFO] Combined PSBT {}", combined.unsigned_tx.txid());
+
+    let policy = load_multisig_policy(&wallet_info.id)?;
+    to_psbt_response(combined, request.output_path.as_deref(), policy.as_ref())
+}
+
+/// Finalize a fully signed PSBT, broadcast it and record it against the wallet
+pub async fn broadcast_psbt(
+    request: BitcoinPsbtRequest,
+) -> Result<SendTransactionResponse, String> {
+    let wallet_info = load_wallet_info(&request.wallet_id)?;
+    let psbt = load_psbt(request.psbt.as_deref(), request.psbt_path.as_deref())?;
+
+    broadcast_psbt_resolved(wallet_info, psbt, connect_blockchain).await
+}
+
+async fn broadcast_psbt_resolved<F>(
+    wallet_info: WalletInfo,
+    mut psbt: PartiallySignedTransaction,
+    connect_blockchain: F,
+) -> Result<SendTransactionResponse, String>
+where
+    F: FnOnce(BitcoinNetwork, &str) -> Result<AnyBlockchain, String> + Send + 'static,
+{
+    let network = {
+        let db = DB.lock().unwrap();
+        db.get_bitcoin_wallet_network(&wallet_info.id)
+            .map_err(|e| format!("Failed to get wallet network: {}", e))?
+    };
+    let public_wallet = open_public_wallet(&wallet_info)?.map(|(wallet, _)| wallet);
+
+    if let Some(wallet) = &public_wallet {
+        wallet
+            .ensure_addresses_cached(OFFLINE_ADDRESS_CACHE)
+            .map_err(|e| format!("Failed to derive wallet addresses: {}", e))?;
+        if !is_finalized(&psbt) {
+            wallet
+                .finalize_psbt(&mut psbt, SignOptions::default())
+                .map_err(|e| format!("Failed to finalize PSBT: {}", e))?;
+        }
+    }
+    if !is_finalized(&psbt) {
+        return Err(match load_multisig_policy(&wallet_info.id)? {
+            Some(policy) => format!(
+                "PSBT has {} of {} required signatures",
+                signatures_present(&psbt, &policy),
+                policy.threshold
+            ),
+            None => "PSBT is not fully signed".to_string(),
+        });
+    }
+
+    let fee_sats = psbt.fee_amount();
+    let tx_hash = psbt.unsigned_tx.txid().to_string();
+
+    // Without descriptors the wallet's own outputs are recognised by the
+    // addresses it already knows.
+    let known_addresses = match &public_wallet {
+        Some(_) => HashSet::new(),
+        None => {
+            let db = DB.lock().unwrap();
+            let mut addresses: HashSet<String> = db
+                .get_bitcoin_wallet_addresses(&wallet_info.id)
+                .map_err(|e| format!("Failed to get wallet addresses: {}", e))?
+                .into_iter()
+                .map(|info| info.address)
+                .collect();
+            addresses.insert(wallet_info.address.clone());
+            addresses
+        }
+    };
+    let (inputs, outputs) = psbt_txos(&psbt, network, public_wallet.as_ref(), &known_addresses)?;
+    let tx_record = broadcast_record(
+        &wallet_info.id,
+        &wallet_info.address,
+        &tx_hash,
+        inputs,
+        outputs,
+        fee_sats.unwrap_or(0),
+    )?;
+    let tx = psbt.extract_tx();
+
+    let wallet_id = wallet_info.id.clone();
+    let blockchain = tokio::task::spawn_blocking(move || connect_blockchain(network, &wallet_id))
+        .await
+        .map_err(|e| format!("Backend connection task failed: {}", e))??;
+    crate::safe_log!("[INFO] Broadcasting PSBT transaction {}...", tx_hash);
+    broadcast_transaction(blockchain, &tx).await?;
+
+    {
+        let db = DB.lock().unwrap();
+        db.add_bitcoin_transaction(&tx_record)
+            .map_err(|e| format!("Failed to save transaction: {}", e))?;
+    }
+
+    Ok(SendTransactionResponse {
+        tx_hash,
+        message: "Transaction sent successfully".to_string(),
+    })
+}
+
+/// The PSBT's inputs and outputs as history txos, marking those of this
+/// wallet. An input whose spent output the PSBT does not carry counts as foreign.
+fn psbt_txos<D: BatchDatabase>(
+    psbt: &PartiallySignedTransaction,
+    network: BitcoinNetwork,
+    public_wallet: Option<&Wallet<D>>,
+    known_addresses: &HashSet<String>,
+) -> Result<(Vec<HistoryTxo>, Vec<HistoryTxo>), String> {
+    let txo = |output: &TxOut| -> Result<HistoryTxo, String> {
+        let address =
+            bdk::bitcoin::Address::from_script(&output.script_pubkey, network.bdk_network())
+                .ok()
+                .map(|address| address.to_string());
+        let is_mine = match public_wallet {
+            Some(wallet) => wallet
+                .is_mine(&output.script_pubkey)
+                .map_err(|e| format!("Failed to inspect outputs: {}", e))?,
+            None => address
+                .as_ref()
+                .is_some_and(|address| known_addresses.contains(address)),
+        };
+        Ok(HistoryTxo {
+            address,
+            value: output.value,
+            is_mine,
+        })
+    };
+
+    let inputs = psbt
+        .unsigned_tx
+        .input
+        .iter()
+        .zip(&psbt.inputs)
+        .map(|(txin, input)| {
+            let spent = input.witness_utxo.clone().or_else(|| {
+                input.non_witness_utxo.as_ref().and_then(|parent| {
+                    parent
+                        .output
+                        .get(txin.previous_output.vout as usize)
+                        .cloned()
+                })
+            });
+            match spent {
+                Some(spent) => txo(&spent),
+                None => Ok(HistoryTxo {
+                    address: None,
+                    value: 0,
+                    is_mine: false,
+                }),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let outputs = psbt
+        .unsigned_tx
+        .output
+        .iter()
+        .map(txo)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((inputs, outputs))
+}
+
+fn load_multisig_policy(wallet_id: &str) -> Result<Option<MultisigPolicy>, String> {
+    let db = DB.lock().unwrap();
+    db.get_bitcoin_wallet_multisig(wallet_id)
+        .map_err(|e| format!("Failed to get multisig policy: {}", e))
+}
+
+pub(super) fn load_wallet_info(wallet_id: &str) -> Result<WalletInfo, String> {
+    let db = DB.lock().unwrap();
+    db.get_bitcoin_wallet(wallet_id)
+        .map_err(|e| format!("Failed to get wallet info: {}", e))?
+        .ok_or_else(|| "Wallet not found".to_string())
+}
+
+/// Reads a PSBT from base64 text or from a file holding either the BIP174
+/// binary encoding or base64 text. Exactly one source must be given.
+fn load_psbt(
+    psbt: Option<&str>,
+    psbt_path: Option<&str>,
+) -> Result<PartiallySignedTransaction, String> {
+    match (psbt, psbt_path) {
+        (Some(text), None) => decode_psbt(text.as_bytes()),
+        (None, Some(path)) => {
+            let bytes =
+                std::fs::read(path).map_err(|e| format!("Failed to read PSBT file: {}", e))?;
+            decode_psbt(&bytes)
+        }
+        _ => Err("Provide either a base64 PSBT or a PSBT file".to_string()),
+    }
+}
+
+fn decode_psbt(bytes: &[u8]) -> Result<PartiallySignedTransaction, String> {
+    if bytes.starts_with(PSBT_MAGIC) {
+        return PartiallySignedTransaction::deserialize(bytes)
+            .map_err(|e| format!("Invalid PSBT: {}", e));
+    }
+
+    let text = std::str::from_utf8(bytes).map_err(|_| "Invalid PSBT encoding".to_string())?;
+    PartiallySignedTransaction::from_str(text.trim()).map_err(|e| format!("Invalid PSBT: {}", e))
+}
+
+fn to_psbt_response(
+    psbt: PartiallySignedTransaction,
+    output_path: Option<&str>,
+    policy: Option<&MultisigPolicy>,
+) -> Result<BitcoinPsbtResponse, String> {
+    // Binary is the BIP174 file format that other signers import.
+    if let Some(path) = output_path {
+        std::fs::write(path, psbt.serialize())
+            .map_err(|e| format!("Failed to write PSBT file: {}", e))?;
+    }
+
+    let fee_sats = psbt.fee_amount();
+    Ok(BitcoinPsbtResponse {
+        txid: psbt.unsigned_tx.txid().to_string(),
+        fee_sats,
+        fee: fee_sats.map(sats_to_btc),
+        is_finalized: is_finalized(&psbt),
+        file_path: output_path.map(str::to_string),
+        signatures_present: policy.map(|policy| signatures_present(&psbt, policy)),
+        signatures_required: policy.map(|policy| policy.threshold),
+        psbt: psbt.to_string(),
+    })
+}
+
+fn is_finalized(psbt: &PartiallySignedTransaction) -> bool {
+    !psbt.inputs.is_empty()
+        && psbt
+            .inputs
+            .iter()
+            .all(|input| input.final_script_sig.is_some() || input.final_script_witness.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        broadcast_psbt_resolved, combine_psbts, decode_psbt, load_psbt, psbt_txos, sign_psbt,
+    };
+    use crate::wallet::bitcoin::config::BitcoinNetwork;
+    use crate::wallet::bitcoin::descriptor::{
+        account_descriptors_from_mnemonic, derive_address, BitcoinScriptType,
+    };
+    use crate::wallet::bitcoin::multisig::{
+        local_cosigner, multisig_descriptors, MultisigPolicy, MultisigScriptType,
+    };
+    use crate::wallet::bitcoin::transaction::broadcast_record;
+    use crate::wallet::security::backend::{SecretBackend, SecretBackendAdapter};
+    use crate::wallet::security::keystore::Keystore;
+    use crate::wallet::security::secret_envelope::{
+        decrypt_secret, encrypt_secret, SecretEnvelopeError, StoredSecret,
+        SECRET_FORMAT_PLAINTEXT_V0,
+    };
+    use crate::wallet::security::session::SessionManager;
+    use crate::wallet::security::types::{SecurityError, SignerOperation};
+    use crate::wallet::transaction_types::{
+        BitcoinCombinePsbtRequest, BitcoinPsbtRequest, TransactionType,
+    };
+    use crate::wallet::types::WalletInfo;
+    use crate::DB;
+    use bdk::bitcoin::absolute::LockTime;
+    use bdk::bitcoin::psbt::PartiallySignedTransaction;
+    use bdk::bitcoin::{Address, OutPoint, Transaction, TxIn, TxOut};
+    use bdk::database::MemoryDatabase;
+    use bdk::psbt::PsbtUtils;
+    use std::collections::HashSet;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    const TEST_MNEMONIC: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    const COSIGNER_MNEMONICS: [&str; 3] = [
+        TEST_MNEMONIC,
+        "legal winner thank year wave sausage worth useful legal winner thank yellow",
+        "letter advice cage absurd amount doctor acoustic avoid letter advice cage above",
+    ];
+
+    struct TestSecretBackendAdapter;
+
+    impl SecretBackendAdapter for TestSecretBackendAdapter {
+        fn probe(&self) -> Result<(), SecretEnvelopeError> {
+            Ok(())
+        }
+
+        fn initialize_empty_store(&self) -> Result<(), SecretEnvelopeError> {
+            Ok(())
+        }
+
+        fn encrypt(&self, plaintext: &str) -> Result<StoredSecret, SecretEnvelopeError> {
+            encrypt_secret(plaintext)
+        }
+
+        fn decrypt(
+            &self,
+            secret_data: &str,
+            secret_format: &str,
+        ) -> Result<String, SecretEnvelopeError> {
+            decrypt_secret(secret_data, secret_format)
+        }
+    }
+
+    struct MnemonicKeystore;
+
+    impl Keystore for MnemonicKeystore {
+        fn load_mnemonic(&self, _address: &str) -> Result<Option<String>, SecurityError> {
+            Ok(Some(TEST_MNEMONIC.to_string()))
+        }
+
+        fn load_private_key(&self, _address: &str) -> Result<Option<String>, SecurityError> {
+            Ok(None)
+        }
+    }
+
+    struct CosignerKeystore(&'static str);
+
+    impl Keystore for CosignerKeystore {
+        fn load_mnemonic(&self, _address: &str) -> Result<Option<String>, SecurityError> {
+            Ok(Some(self.0.to_string()))
+        }
+
+        fn load_private_key(&self, _address: &str) -> Result<Option<String>, SecurityError> {
+            Ok(None)
+        }
+    }
+
+    struct PanicKeystore;
+
+    impl Keystore for PanicKeystore {
+        fn load_mnemonic(&self, _address: &str) -> Result<Option<String>, SecurityError> {
+            panic!("keystore should not be called while session is locked");
+        }
+
+        fn load_private_key(&self, _address: &str) -> Result<Option<String>, SecurityError> {
+            panic!("keystore should not be called while session is locked");
+        }
+    }
+
+    fn ready_secret_backend() -> SecretBackend {
+        SecretBackend::with_adapter(Arc::new(TestSecretBackendAdapter))
+    }
+
+    fn insert_global_mnemonic_wallet() -> WalletInfo {
+        let unique = Uuid::new_v4().simple().to_string();
+        let db = DB.lock().unwrap();
+        db.insert_bitcoin_wallet_with_secret(
+            format!("BTC PSBT {unique}"),
+            "mnemonic".to_string(),
+            format!("btc-psbt-{unique}"),
+            StoredSecret {
+                secret_data: TEST_MNEMONIC.to_string(),
+                secret_format: SECRET_FORMAT_PLAINTEXT_V0.to_string(),
+            },
+            "mnemonic".to_string(),
+        )
+        .unwrap()
+    }
+
+    /// A 2-of-3 multisig wallet over `COSIGNER_MNEMONICS` whose own key is `local_mnemonic`
+    fn insert_global_multisig_wallet(
+        local_mnemonic: &str,
+        script_type: MultisigScriptType,
+    ) -> WalletInfo {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let keys = COSIGNER_MNEMONICS
+            .iter()
+            .map(|mnemonic| {
+                local_cosigner(mnemonic, script_type, BitcoinNetwork::Mainnet, &secp)
+                    .unwrap()
+                    .public
+            })
+            .collect::<Vec<_>>();
+        let descriptors =
+            multisig_descriptors(&keys, 2, script_type, BitcoinNetwork::Mainnet).unwrap();
+
+        let unique = Uuid::new_v4().simple().to_string();
+        let db = DB.lock().unwrap();
+        let wallet = db
+            .insert_bitcoin_wallet_with_secret(
+                format!("BTC multisig {unique}"),
+                "multisig".to_string(),
+                format!("btc-multisig-{unique}"),
+                StoredSecret {
+                    secret_data: local_mnemonic.to_string(),
+                    secret_format: SECRET_FORMAT_PLAINTEXT_V0.to_string(),
+                },
+                "mnemonic".to_string(),
+            )
+            .unwrap();
+        db.set_bitcoin_wallet_descriptors(&wallet.id, &descriptors)
+            .unwrap();
+        db.set_bitcoin_wallet_multisig(
+            &wallet.id,
+            &MultisigPolicy {
+                threshold: 2,
+                cosigners: 3,
+                script_type,
+            },
+        )
+        .unwrap();
+        wallet
+    }
+
+    fn cleanup_global_bitcoin_wallet(wallet_id: &str) {
+        let db = DB.lock().unwrap();
+        let _ = db.delete_bitcoin_wallet(wallet_id);
+    }
+
+    /// A PSBT spending a made-up coin held by the first receive address of the test mnemonic
+    fn psbt_spending_own_coin() -> PartiallySignedTransaction {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let descriptors = account_descriptors_from_mnemonic(
+            TEST_MNEMONIC,
+            BitcoinScriptType::P2tr,
+            BitcoinNetwork::Mainnet,
+            &secp,
+        )
+        .unwrap();
+        let address =
+            derive_address(&descriptors.public.external, 0, BitcoinNetwork::Mainnet).unwrap();
+        let script_pubkey = Address::from_str(&address)
+            .unwrap()
+            .assume_checked()
+            .script_pubkey();
+
+        let unsigned_tx = Transaction {
+            version: 2,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::from_str(&format!("{}:0", "aa".repeat(32))).unwrap(),
+                ..Default::default()
+            }],
+            output: vec![TxOut {
+                value: 90_000,
+                script_pubkey: script_pubkey.clone(),
+            }],
+        };
+        let mut psbt = PartiallySignedTransaction::from_unsigned_tx(unsigned_tx).unwrap();
+        psbt.inputs[0].witness_utxo = Some(TxOut {
+            value: 100_000,
+            script_pubkey,
+        });
+        psbt
+    }
+
+    #[test]
+    fn broadcast_rows_are_classified_by_known_addresses_without_descriptors() {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let public = account_descriptors_from_mnemonic(
+            TEST_MNEMONIC,
+            BitcoinScriptType::P2tr,
+            BitcoinNetwork::Mainnet,
+            &secp,
+        )
+        .unwrap()
+        .public;
+        let own = derive_address(&public.external, 0, BitcoinNetwork::Mainnet).unwrap();
+        let change = derive_address(&public.internal.unwrap(), 0, BitcoinNetwork::Mainnet).unwrap();
+        let foreign = "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq";
+        let output = |address: &str, value: u64| TxOut {
+            value,
+            script_pubkey: Address::from_str(address)
+                .unwrap()
+                .assume_checked()
+                .script_pubkey(),
+        };
+        let psbt = |spent: TxOut, outputs: Vec<TxOut>| {
+            let unsigned_tx = Transaction {
+                version: 2,
+                lock_time: LockTime::ZERO,
+                input: vec![TxIn {
+                    previous_output: OutPoint::from_str(&format!("{}:0", "aa".repeat(32))).unwrap(),
+                    ..Default::default()
+                }],
+                output: outputs,
+            };
+            let mut psbt = PartiallySignedTransaction::from_unsigned_tx(unsigned_tx).unwrap();
+            psbt.inputs[0].witness_utxo = Some(spent);
+            psbt
+        };
+        let known_addresses = HashSet::from([own.clone(), change.clone()]);
+        let record = |psbt: &PartiallySignedTransaction| {
+            let (inputs, outputs) =
+                psbt_txos::<MemoryDatabase>(psbt, BitcoinNetwork::Mainnet, None, &known_addresses)
+                    .unwrap();
+            broadcast_record(
+                "wallet",
+                &own,
+                "tx",
+                inputs,
+                outputs,
+                psbt.fee_amount().unwrap_or(0),
+            )
+        };
+
+        let send = record(&psbt(
+            output(&own, 100_000),
+            vec![output(&change, 40_000), output(foreign, 50_000)],
+        ))
+        .unwrap();
+        assert_eq!(send.tx_type, TransactionType::Send);
+        assert_eq!((send.amount_sats, send.fee_sats), (50_000, 10_000));
+        assert_eq!(send.to_address, foreign);
+
+        // Everything returns to the wallet: not a send to nobody.
+        let to_self = record(&psbt(output(&own, 100_000), vec![output(&change, 99_000)])).unwrap();
+        assert_eq!(to_self.tx_type, TransactionType::SelfTransfer);
+        assert_eq!(to_self.to_address, change);
+
+        // Nothing of this wallet is spent, so nothing is recorded.
+        assert!(record(&psbt(output(foreign, 100_000), vec![output(&own, 99_000)])).is_err());
+    }
+
+    /// A PSBT spending a made-up coin paid to the first multisig receive address.
+    /// The funding transaction travels along, as segwit v0 signers require.
+    fn psbt_spending_multisig_coin(wallet_id: &str) -> PartiallySignedTransaction {
+        let descriptors = {
+            let db = DB.lock().unwrap();
+            db.get_bitcoin_wallet_descriptors(wallet_id)
+                .unwrap()
+                .unwrap()
+        };
+        let address = derive_address(&descriptors.external, 0, BitcoinNetwork::Mainnet).unwrap();
+        let funding_output = TxOut {
+            value: 100_000,
+            script_pubkey: Address::from_str(&address)
+                .unwrap()
+                .assume_checked()
+                .script_pubkey(),
+        };
+        let funding_tx = Transaction {
+            version: 2,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::from_str(&format!("{}:0", "bb".repeat(32))).unwrap(),
+                ..Default::default()
+            }],
+            output: vec![funding_output.clone()],
+        };
+
+        let unsigned_tx = Transaction {
+            version: 2,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::new(funding_tx.txid(), 0),
+                ..Default::default()
+            }],
+            output: vec![TxOut {
+                value: 90_000,
+                script_pubkey: funding_output.script_pubkey.clone(),
+            }],
+        };
+        let mut psbt = PartiallySignedTransaction::from_unsigned_tx(unsigned_tx).unwrap();
+        psbt.inputs[0].witness_utxo = Some(funding_output);
+        psbt.inputs[0].non_witness_utxo = Some(funding_tx);
+        psbt
+    }
+
+    fn psbt_request(wallet_id: &str, psbt: String) -> BitcoinPsbtRequest {
+        BitcoinPsbtRequest {
+            wallet_id: wallet_id.to_string(),
+            psbt: Some(psbt),
+            psbt_path: None,
+            output_path: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn multisig_cosigners_reach_threshold_by_combining_psbts() {
+        for script_type in [MultisigScriptType::P2wsh, MultisigScriptType::P2tr] {
+            let first = insert_global_multisig_wallet(COSIGNER_MNEMONICS[0], script_type);
+            let second = insert_global_multisig_wallet(COSIGNER_MNEMONICS[1], script_type);
+            let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
+            session
+                .authorize_verified_operation(SignerOperation::Send)
+                .unwrap();
+            let backend = ready_secret_backend();
+            let psbt = psbt_spending_multisig_coin(&first.id).to_string();
+
+            let signed_first = sign_psbt(
+                psbt_request(&first.id, psbt.clone()),
+                &backend,
+                &CosignerKeystore(COSIGNER_MNEMONICS[0]),
+                &session,
+            );
+            let signed_second = sign_psbt(
+                psbt_request(&second.id, psbt.clone()),
+                &backend,
+                &CosignerKeystore(COSIGNER_MNEMONICS[1]),
+                &session,
+            );
+            let partial = match &signed_first {
+                Ok(signed) => {
+                    broadcast_psbt_resolved(
+                        first.clone(),
+                        decode_psbt(signed.psbt.as_bytes()).unwrap(),
+                        |_, _| Err("blockchain should not be reached".to_string()),
+                    )
+                    .await
+                }
+                Err(error) => Err(error.clone()),
+            };
+            let combined = match (&signed_first, &signed_second) {
+                (Ok(first_signed), Ok(second_signed)) => combine_psbts(BitcoinCombinePsbtRequest {
+                    wallet_id: first.id.clone(),
+                    psbts: vec![first_signed.psbt.clone(), second_signed.psbt.clone()],
+                    output_path: None,
+                }),
+                _ => Err("cosigner failed to sign".to_string()),
+            };
+
+            cleanup_global_bitcoin_wallet(&first.id);
+            cleanup_global_bitcoin_wallet(&second.id);
+
+            let signed_first = signed_first.unwrap();
+            assert!(!signed_first.is_finalized);
+            assert_eq!(signed_first.signatures_present, Some(1));
+            assert_eq!(signed_first.signatures_required, Some(2));
+            assert_eq!(signed_second.unwrap().signatures_present, Some(1));
+            assert_eq!(partial.unwrap_err(), "PSBT has 1 of 2 required signatures");
+
+            let combined = combined.unwrap();
+            assert!(combined.is_finalized, "{:?}", script_type);
+            assert_eq!(combined.signatures_present, Some(2));
+            assert_eq!(combined.fee_sats, Some(10_000));
+        }
+    }
+
+    #[test]
+    fn combine_rejects_psbts_of_different_transactions() {
+        let wallet = insert_global_mnemonic_wallet();
+        let mut other = psbt_spending_own_coin();
+        other.unsigned_tx.output[0].value = 80_000;
+
+        let result = combine_psbts(BitcoinCombinePsbtRequest {
+            wallet_id: wallet.id.clone(),
+            psbts: vec![psbt_spending_own_coin().to_string(), other.to_string()],
+            output_path: None,
+        });
+
+        cleanup_global_bitcoin_wallet(&wallet.id);
+
+        assert_eq!(result.unwrap_err(), "PSBTs spend different transactions");
+    }
+
+    #[test]
+    fn psbt_decodes_from_base64_and_binary() {
+        let psbt = psbt_spending_own_coin();
+
+        assert_eq!(decode_psbt(psbt.to_string().as_bytes()).unwrap(), psbt);
+        assert_eq!(decode_psbt(&psbt.serialize()).unwrap(), psbt);
+        assert!(decode_psbt(b"not a psbt").is_err());
+        assert_eq!(
+            load_psbt(Some("cHNidP8="), Some("/tmp/tx.psbt")).unwrap_err(),
+            "Provide either a base64 PSBT or a PSBT file"
+        );
+    }
+
+    #[test]
+    fn sign_psbt_signs_offline_and_finalizes_taproot_input() {
+        let wallet = insert_global_mnemonic_wallet();
+        let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
+        session
+            .authorize_verified_operation(SignerOperation::Send)
+            .unwrap();
+        let psbt = psbt_spending_own_coin();
+
+        let result = sign_psbt(
+            BitcoinPsbtRequest {
+                wallet_id: wallet.id.clone(),
+                psbt: Some(psbt.to_string()),
+                psbt_path: None,
+                output_path: None,
+            },
+            &ready_secret_backend(),
+            &MnemonicKeystore,
+            &session,
+        );
+
+        cleanup_global_bitcoin_wallet(&wallet.id);
+
+        let signed = result.unwrap();
+        assert!(signed.is_finalized);
+        assert_eq!(signed.txid, psbt.unsigned_tx.txid().to_string());
+        assert_eq!(signed.fee, Some(0.0001));
+    }
+
+    #[test]
+    fn sign_psbt_requires_send_reauth_without_keystore_access() {
+        let wallet = insert_global_mnemonic_wallet();
+        let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
+
+        let result = sign_psbt(
+            BitcoinPsbtRequest {
+                wallet_id: wallet.id.clone(),
+                psbt: Some(psbt_spending_own_coin().to_string()),
+                psbt_path: None,
+                output_path: None,
+            },
+            &ready_secret_backend(),
+            &PanicKeystore,
+            &session,
+        );
+
+        cleanup_global_bitcoin_wallet(&wallet.id);
+
+        assert_eq!(result.unwrap_err(), "locked");
+    }
+
+    #[tokio::test]
+    async fn broadcast_rejects_unsigned_psbt_before_network_io() {
+        let wallet = insert_global_mnemonic_wallet();
+
+        let result = broadcast_psbt_resolved(wallet.clone(), psbt_spending_own_coin(), |_, _| {
+            Err("blockchain should not be reached".to_string())
+        })
+        .await;
+
+        cleanup_global_bitcoin_wallet(&wallet.id);
+
+        assert_eq!(result.unwrap_err(), "PSBT is not fully signed");
+    }
+}
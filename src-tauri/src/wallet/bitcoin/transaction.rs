@@ -1,125 +1,48 @@
+use crate::wallet::bitcoin::amount::sats_to_btc;
+use crate::wallet::bitcoin::backend::{self, connect_blockchain, BlockstreamTx};
+use crate::wallet::bitcoin::config::BitcoinNetwork;
+use crate::wallet::bitcoin::descriptor::{
+    account_descriptors_from_mnemonic, single_key_public_descriptor, AccountDescriptors,
+    BitcoinScriptType, WalletDescriptors,
+};
+use crate::wallet::bitcoin::local_state;
+use crate::wallet::bitcoin::multisig::{local_cosigner, multisig_signing_descriptors};
+use crate::wallet::bitcoin::preview::{ensure_matches_preview, forget_preview, pending_preview};
 use crate::wallet::bitcoin::private_key::{
-    load_authorized_mnemonic, load_authorized_private_key, map_security_error,
+    load_authorized_mnemonic, load_authorized_private_key, map_security_error, validate_private_key,
 };
+use crate::wallet::bitcoin::utxo::CoinControl;
+use crate::wallet::bitcoin::wallet_store;
 use crate::wallet::security::backend::SecretBackend;
 use crate::wallet::security::keystore::Keystore;
 use crate::wallet::security::session::SessionManager;
 use crate::wallet::security::types::{SecurityError, SignerOperation};
 use crate::wallet::sync::types::BITCOIN_MIN_CONFIRMATIONS;
 use crate::wallet::transaction_types::{
-    BitcoinFeeEstimationResponse, BitcoinTransaction, SendBitcoinRequest, SendTransactionResponse,
-    TransactionStatus, TransactionType,
+    BitcoinTransaction, SendBitcoinRequest, SendTransactionResponse, TransactionStatus,
+    TransactionType,
 };
 use crate::wallet::types::WalletInfo;
 use crate::DB;
-use bdk::bitcoin::Network as BdkNetwork;
-use bdk::blockchain::{Blockchain, ElectrumBlockchain};
-use bdk::database::MemoryDatabase;
-use bdk::electrum_client::Client;
+use bdk::bitcoin::psbt::PartiallySignedTransaction;
+use bdk::bitcoin::{OutPoint, ScriptBuf, Transaction, Txid};
+use bdk::blockchain::{AnyBlockchain, Blockchain};
+use bdk::database::{BatchDatabase, SqliteDatabase};
 use bdk::psbt::PsbtUtils;
-use bdk::{FeeRate, SignOptions, SyncOptions, Wallet};
-use bip39::{Language, Mnemonic};
-use bitcoin::bip32::{DerivationPath, Xpriv};
-use bitcoin::Network;
+use bdk::wallet::coin_selection::DefaultCoinSelectionAlgorithm;
+use bdk::wallet::tx_builder::{CreateTx, TxBuilder};
+use bdk::wallet::AddressIndex;
+use bdk::{FeeRate, LocalUtxo, SignOptions, SyncOptions, TransactionDetails, Wallet};
 use chrono::Utc;
-use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
-use std::time::Duration;
 use uuid::Uuid;
 
-const REQUEST_TIMEOUT_SECS: u64 = 10;
-
-enum BitcoinSigningSecret {
+pub(super) enum BitcoinSigningSecret {
     Mnemonic(String),
     PrivateKey(String),
 }
 
-#[derive(Debug, Deserialize)]
-struct BlockstreamTx {
-    txid: String,
-    version: i32,
-    locktime: u64,
-    vin: Vec<BlockstreamVin>,
-    vout: Vec<BlockstreamVout>,
-    size: u64,
-    weight: u64,
-    fee: u64,
-    status: BlockstreamTxStatus,
-}
-
-#[derive(Debug, Deserialize)]
-struct BlockstreamVin {
-    txid: String,
-    vout: u32,
-    prevout: Option<BlockstreamPrevout>,
-    scriptsig: String,
-    scriptsig_asm: String,
-    #[serde(default)]
-    witness: Vec<String>,
-    is_coinbase: bool,
-    sequence: u64,
-}
-
-#[derive(Debug, Deserialize)]
-struct BlockstreamPrevout {
-    scriptpubkey: String,
-    scriptpubkey_asm: String,
-    scriptpubkey_type: String,
-    scriptpubkey_address: Option<String>,
-    value: u64,
-}
-
-#[derive(Debug, Deserialize)]
-struct BlockstreamVout {
-    scriptpubkey: String,
-    scriptpubkey_asm: String,
-    scriptpubkey_type: String,
-    scriptpubkey_address: Option<String>,
-    value: u64,
-}
-
-#[derive(Debug, Deserialize)]
-struct BlockstreamTxStatus {
-    confirmed: bool,
-    block_height: Option<u32>,
-    block_hash: Option<String>,
-    block_time: Option<u64>,
-}
-
-/// Fetch Bitcoin transaction history from Blockstream API
-async fn fetch_transactions_from_blockstream(address: &str) -> Result<Vec<BlockstreamTx>, String> {
-    let url = format!("https://blockstream.info/api/address/{}/txs", address);
-
-    crate::safe_log!("[INFO] Fetching transactions from Blockstream API: {}", url);
-
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
-
-    let response = client
-        .get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("HTTP request failed: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()));
-    }
-
-    let transactions: Vec<BlockstreamTx> = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
-
-    crate::safe_log!(
-        "[INFO] Successfully fetched {} transactions from Blockstream",
-        transactions.len()
-    );
-
-    Ok(transactions)
-}
-
 /// Fetch Bitcoin transaction history from the blockchain
 pub async fn fetch_bitcoin_transaction_history(
     wallet_id: String,
@@ -131,8 +54,79 @@ pub async fn fetch_bitcoin_transaction_history(
     );
     crate::safe_log!("[INFO] Address: {}", address);
 
-    // Fetch transactions from Blockstream API
-    let blockstream_txs = fetch_transactions_from_blockstream(&address).await?;
+    let network = {
+        let db = DB.lock().unwrap();
+        db.get_bitcoin_wallet_network(&wallet_id)
+            .map_err(|e| format!("Failed to get wallet network: {}", e))?
+    };
+
+    // Descriptor wallets are served from the local BDK state, which covers
+    // every address of the wallet; others still ask the configured backends.
+    if let Some(snapshot) = local_state::load_local_wallet(&wallet_id, false).await? {
+        crate::safe_log!(
+            "[INFO] Found {} transactions in local wallet state",
+            snapshot.transactions.len()
+        );
+
+        let owned: HashSet<String> = snapshot
+            .addresses
+            .iter()
+            .map(|row| row.address.clone())
+            .collect();
+        let parents: HashMap<Txid, &Transaction> = snapshot
+            .transactions
+            .iter()
+            .filter_map(|tx| tx.transaction.as_ref().map(|raw| (tx.txid, raw)))
+            .collect();
+
+        let mut result = Vec::new();
+        let mut lookups = 0;
+        for tx in &snapshot.transactions {
+            let entry = local_history_entry(tx, &snapshot, &parents, &owned);
+            let mut tx_record = history_record(&wallet_id, &address, &entry, snapshot.tip_height);
+            let mut resolved = false;
+            if tx_record.tx_type == TransactionType::Receive
+                && tx_record.from_address == UNKNOWN_ADDRESS
+            {
+                if let Some(sender) = stored_sender(&tx_record.tx_hash) {
+                    tx_record.from_address = sender;
+                } else if lookups < SENDER_LOOKUPS_PER_REFRESH
+                    && !sender_resolved(&wallet_id, &tx_record.tx_hash)?
+                {
+                    lookups += 1;
+                    match lookup_sender(&tx_record.tx_hash, &owned, network).await {
+                        Ok(sender) => {
+                            if let Some(sender) = sender {
+                                tx_record.from_address = sender;
+                            }
+                            resolved = true;
+                        }
+                        Err(e) => crate::safe_log!(
+                            "[WARN] Failed to look up the sender of {}: {}",
+                            tx_record.tx_hash,
+                            e
+                        ),
+                    }
+                }
+            }
+            save_history_record(&tx_record)?;
+            if resolved {
+                let db = DB.lock().unwrap();
+                db.mark_bitcoin_sender_resolved(&wallet_id, &tx_record.tx_hash)
+                    .map_err(|e| format!("Failed to save transaction: {}", e))?;
+            }
+            result.push(tx_record);
+        }
+
+        crate::safe_log!(
+            "[SUCCESS] Loaded and saved {} Bitcoin transactions",
+            result.len()
+        );
+        return Ok(result);
+    }
+
+    // Fetch transactions from the configured backends
+    let blockstream_txs = backend::address_transactions(&address, network).await?;
 
     crate::safe_log!(
         "[INFO] Found {} transactions from API",
@@ -141,221 +135,371 @@ pub async fn fetch_bitcoin_transaction_history(
 
     let mut result = Vec::new();
 
-    // Get current block height from Blockstream API for calculating confirmations
-    let current_height = get_current_block_height().await.unwrap_or(0);
+    // Get current block height for calculating confirmations
+    let current_height = backend::tip_height(network).await.unwrap_or(0);
     crate::safe_log!("[INFO] Current block height: {}", current_height);
 
     for (index, tx) in blockstream_txs.iter().enumerate() {
-        let tx_hash = tx.txid.clone();
         crate::safe_log!(
             "[INFO] Processing transaction {}/{}: {}",
             index + 1,
             blockstream_txs.len(),
-            tx_hash
+            tx.txid
         );
 
-        // Calculate total input and output values for this address
-        let mut received: u64 = 0;
-        let mut sent: u64 = 0;
-
-        // Check outputs (vout) - money received to this address
-        for vout in &tx.vout {
-            if let Some(ref addr) = vout.scriptpubkey_address {
-                if addr == &address {
-                    received += vout.value;
-                    crate::safe_log!(
-                        "[INFO] Found output to our address: {} satoshis",
-                        vout.value
-                    );
-                }
-            }
-        }
+        let entry = explorer_history_entry(tx, &address);
+        let tx_record = history_record(&wallet_id, &address, &entry, current_height);
 
-        // Check inputs (vin) - money sent from this address
-        for vin in &tx.vin {
-            if let Some(ref prevout) = vin.prevout {
-                if let Some(ref addr) = prevout.scriptpubkey_address {
-                    if addr == &address {
-                        sent += prevout.value;
-                        crate::safe_log!(
-                            "[INFO] Found input from our address: {} satoshis",
-                            prevout.value
-                        );
-                    }
-                }
-            }
-        }
+        save_history_record(&tx_record)?;
+        result.push(tx_record);
+    }
 
-        // Determine transaction type
-        let (tx_type, from_address, to_address) = if received > sent {
-            // Receiving transaction
-            crate::safe_log!(
-                "[INFO] Transaction type: Receive (received: {}, sent: {})",
-                received,
-                sent
-            );
-            (
-                TransactionType::Receive,
-                "Unknown".to_string(),
-                address.clone(),
-            )
-        } else {
-            // Sending transaction
+    crate::safe_log!(
+        "[SUCCESS] Fetched and saved {} Bitcoin transactions",
+        result.len()
+    );
+    Ok(result)
+}
+
+fn save_history_record(tx_record: &BitcoinTransaction) -> Result<(), String> {
+    {
+        let db = DB.lock().unwrap();
+        db.add_bitcoin_transaction(tx_record).map_err(|e| {
             crate::safe_log!(
-                "[INFO] Transaction type: Send (received: {}, sent: {})",
-                received,
-                sent
+                "[ERROR] Failed to save transaction {}: {}",
+                tx_record.tx_hash,
+                e
             );
-            (
-                TransactionType::Send,
-                address.clone(),
-                "Unknown".to_string(),
-            )
-        };
-
-        // Calculate net amount
-        let amount = if tx_type == TransactionType::Receive {
-            (received as f64) / 100_000_000.0
-        } else {
-            (sent as f64) / 100_000_000.0
-        };
-
-        let fee = (tx.fee as f64) / 100_000_000.0;
+            format!("Failed to save transaction: {}", e)
+        })?;
+    }
 
-        let confirmations = if let Some(block_height) = tx.status.block_height {
-            if current_height > 0 {
-                current_height.saturating_sub(block_height)
-            } else {
-                0
-            }
-        } else {
-            0
-        };
+    crate::safe_log!(
+        "[SUCCESS] Transaction {} saved to database",
+        tx_record.tx_hash
+    );
+    Ok(())
+}
 
-        let status =
-            TransactionStatus::from_bitcoin_confirmations(confirmations, BITCOIN_MIN_CONFIRMATIONS);
+/// Counterparty placeholder when the other side of a transaction is not known.
+const UNKNOWN_ADDRESS: &str = "Unknown";
 
-        let timestamp = if let Some(block_time) = tx.status.block_time {
-            chrono::DateTime::from_timestamp(block_time as i64, 0)
-                .unwrap_or_else(|| Utc::now())
-                .to_rfc3339()
-        } else {
-            Utc::now().to_rfc3339()
-        };
+/// An output of a history transaction, or the output an input spends.
+pub(super) struct HistoryTxo {
+    pub(super) address: Option<String>,
+    pub(super) value: u64,
+    pub(super) is_mine: bool,
+}
 
-        crate::safe_log!(
-            "[INFO] Transaction details - Amount: {} BTC, Fee: {} BTC, Status: {:?}, Confirmations: {}",
-            amount,
-            fee,
-            status,
-            confirmations
-        );
+/// What the wallet saw of one transaction, in satoshis.
+struct HistoryEntry {
+    tx_hash: String,
+    received: u64,
+    sent: u64,
+    fee_sats: u64,
+    block_height: Option<u32>,
+    block_time: Option<u64>,
+    /// Spent outputs in input order; inputs with an unknown prevout have no address.
+    inputs: Vec<HistoryTxo>,
+    outputs: Vec<HistoryTxo>,
+}
 
-        let tx_record = BitcoinTransaction {
-            id: Uuid::new_v4().to_string(),
-            wallet_id: wallet_id.clone(),
-            tx_hash: tx_hash.clone(),
-            tx_type,
-            from_address,
-            to_address,
-            amount,
-            fee,
-            status,
-            confirmations,
-            block_height: tx.status.block_height,
-            timestamp: timestamp.clone(),
-            created_at: timestamp,
-        };
+/// A history entry from an explorer transaction, seen from a single address.
+fn explorer_history_entry(tx: &BlockstreamTx, address: &str) -> HistoryEntry {
+    let txo = |vout: &backend::BlockstreamVout| HistoryTxo {
+        address: vout.scriptpubkey_address.clone(),
+        value: vout.value,
+        is_mine: vout.scriptpubkey_address.as_deref() == Some(address),
+    };
+    let inputs: Vec<HistoryTxo> = tx
+        .vin
+        .iter()
+        .map(|vin| match &vin.prevout {
+            Some(prevout) => txo(prevout),
+            None => HistoryTxo {
+                address: None,
+                value: 0,
+                is_mine: false,
+            },
+        })
+        .collect();
+    let outputs: Vec<HistoryTxo> = tx.vout.iter().map(txo).collect();
+
+    HistoryEntry {
+        tx_hash: tx.txid.clone(),
+        received: outputs.iter().filter(|o| o.is_mine).map(|o| o.value).sum(),
+        sent: inputs.iter().filter(|i| i.is_mine).map(|i| i.value).sum(),
+        fee_sats: tx.fee,
+        block_height: tx.status.block_height,
+        block_time: tx.status.block_time,
+        inputs,
+        outputs,
+    }
+}
 
-        // Save to database
-        {
-            let db = DB.lock().unwrap();
-            db.add_bitcoin_transaction(&tx_record).map_err(|e| {
-                crate::safe_log!("[ERROR] Failed to save transaction {}: {}", tx_hash, e);
-                format!("Failed to save transaction: {}", e)
-            })?;
+/// A history entry from the local wallet state. Prevouts are only known for
+/// inputs spending wallet transactions, which covers every input the wallet
+/// signed itself.
+fn local_history_entry(
+    tx: &TransactionDetails,
+    snapshot: &local_state::LocalWalletSnapshot,
+    parents: &HashMap<Txid, &Transaction>,
+    owned: &HashSet<String>,
+) -> HistoryEntry {
+    let txo = |script: &ScriptBuf, value: u64| {
+        let address = snapshot.address_of(script);
+        let is_mine = address
+            .as_ref()
+            .is_some_and(|address| owned.contains(address));
+        HistoryTxo {
+            address,
+            value,
+            is_mine,
         }
+    };
+    let (inputs, outputs) = match &tx.transaction {
+        Some(raw) => (
+            raw.input
+                .iter()
+                .map(|input| {
+                    let previous = &input.previous_output;
+                    match parents
+                        .get(&previous.txid)
+                        .and_then(|parent| parent.output.get(previous.vout as usize))
+                    {
+                        Some(output) => txo(&output.script_pubkey, output.value),
+                        None => HistoryTxo {
+                            address: None,
+                            value: 0,
+                            is_mine: false,
+                        },
+                    }
+                })
+                .collect(),
+            raw.output
+                .iter()
+                .map(|output| txo(&output.script_pubkey, output.value))
+                .collect(),
+        ),
+        None => (Vec::new(), Vec::new()),
+    };
 
-        crate::safe_log!("[SUCCESS] Transaction {} saved to database", tx_hash);
-        result.push(tx_record);
+    HistoryEntry {
+        tx_hash: tx.txid.to_string(),
+        received: tx.received,
+        sent: tx.sent,
+        fee_sats: tx.fee.unwrap_or(0),
+        block_height: tx.confirmation_time.as_ref().map(|time| time.height),
+        block_time: tx.confirmation_time.as_ref().map(|time| time.timestamp),
+        inputs,
+        outputs,
     }
+}
 
-    crate::safe_log!(
-        "[SUCCESS] Fetched and saved {} Bitcoin transactions",
-        result.len()
-    );
-    Ok(result)
+/// Backend lookups of unknown senders a single history refresh makes; the
+/// rest wait for the next refresh.
+const SENDER_LOOKUPS_PER_REFRESH: usize = 10;
+
+/// Sender of an incoming transaction as already stored by any wallet.
+fn stored_sender(tx_hash: &str) -> Option<String> {
+    let db = DB.lock().unwrap();
+    db.get_bitcoin_transaction_by_hash(tx_hash)
+        .ok()
+        .flatten()
+        .filter(|stored| {
+            stored.tx_type == TransactionType::Receive && stored.from_address != UNKNOWN_ADDRESS
+        })
+        .map(|stored| stored.from_address)
 }
 
-/// Get current block height from Blockstream API
-async fn get_current_block_height() -> Result<u32, String> {
-    let url = "https://blockstream.info/api/blocks/tip/height";
+fn sender_resolved(wallet_id: &str, tx_hash: &str) -> Result<bool, String> {
+    let db = DB.lock().unwrap();
+    db.is_bitcoin_sender_resolved(wallet_id, tx_hash)
+        .map_err(|e| format!("Failed to get transaction: {}", e))
+}
 
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+/// Sender of an incoming transaction whose prevouts the local state lacks,
+/// as the backends report it. `None` when no input pays from a foreign
+/// address, which is worth remembering as much as a sender is.
+async fn lookup_sender(
+    tx_hash: &str,
+    owned: &HashSet<String>,
+    network: BitcoinNetwork,
+) -> Result<Option<String>, String> {
+    let tx = backend::transaction(tx_hash, network).await?;
+    Ok(joined_addresses(
+        tx.vin
+            .iter()
+            .filter_map(|vin| vin.prevout.as_ref())
+            .filter_map(|prevout| prevout.scriptpubkey_address.as_deref())
+            .filter(|address| !owned.contains(*address)),
+    ))
+}
 
-    let response = client
-        .get(url)
-        .send()
-        .await
-        .map_err(|e| format!("HTTP request failed: {}", e))?;
+/// Distinct addresses in order of appearance, comma separated.
+fn joined_addresses<'a>(addresses: impl Iterator<Item = &'a str>) -> Option<String> {
+    let mut distinct: Vec<&str> = Vec::new();
+    for address in addresses {
+        if !distinct.contains(&address) {
+            distinct.push(address);
+        }
+    }
+    (!distinct.is_empty()).then(|| distinct.join(", "))
+}
 
-    if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()));
+/// How a transaction moved the wallet's coins, with its amount and the fee the
+/// wallet paid. Receives carry the net value received and no fee; sends the
+/// value that left the wallet, fee excluded. Transactions paying only the
+/// wallet itself are self-transfers, or consolidations when several inputs
+/// merge into a single output; their amount is the value moved.
+fn classify_history_entry(entry: &HistoryEntry) -> (TransactionType, u64, u64) {
+    if entry.sent == 0 || entry.received > entry.sent {
+        return (TransactionType::Receive, entry.received - entry.sent, 0);
     }
 
-    let height: u32 = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?
-        .trim()
-        .parse()
-        .map_err(|e| format!("Failed to parse block height: {}", e))?;
+    // Zero-value outputs (OP_RETURN data) pay no one.
+    let paid: Vec<&HistoryTxo> = entry.outputs.iter().filter(|o| o.value > 0).collect();
+    if !paid.is_empty() && paid.iter().all(|o| o.is_mine) {
+        let tx_type = if entry.inputs.len() > 1 && paid.len() == 1 {
+            TransactionType::Consolidation
+        } else {
+            TransactionType::SelfTransfer
+        };
+        return (tx_type, entry.received, entry.fee_sats);
+    }
 
-    Ok(height)
+    let amount_sats = (entry.sent - entry.received).saturating_sub(entry.fee_sats);
+    (TransactionType::Send, amount_sats, entry.fee_sats)
 }
 
-#[derive(Debug, Deserialize)]
-struct MempoolFees {
-    #[serde(rename = "fastestFee")]
-    fastest_fee: f32,
-    #[serde(rename = "halfHourFee")]
-    half_hour_fee: f32,
-    #[serde(rename = "hourFee")]
-    hour_fee: f32,
-}
+/// Builds the history row of one wallet transaction, with the counterparty
+/// addresses read from its inputs and outputs.
+fn history_record(
+    wallet_id: &str,
+    address: &str,
+    entry: &HistoryEntry,
+    current_height: u32,
+) -> BitcoinTransaction {
+    let HistoryEntry {
+        received,
+        sent,
+        block_height,
+        block_time,
+        ..
+    } = *entry;
+
+    let (tx_type, amount_sats, fee_sats) = classify_history_entry(entry);
+    crate::safe_log!(
+        "[INFO] Transaction type: {} (received: {}, sent: {})",
+        tx_type.as_str(),
+        received,
+        sent
+    );
+
+    let addresses = |txos: &[HistoryTxo], mine: bool| {
+        joined_addresses(
+            txos.iter()
+                .filter(|txo| txo.is_mine == mine)
+                .filter_map(|txo| txo.address.as_deref()),
+        )
+    };
+    let (from_address, to_address) = match tx_type {
+        TransactionType::Receive => (
+            addresses(&entry.inputs, false).unwrap_or_else(|| UNKNOWN_ADDRESS.to_string()),
+            addresses(&entry.outputs, true).unwrap_or_else(|| address.to_string()),
+        ),
+        TransactionType::Send => (
+            addresses(&entry.inputs, true).unwrap_or_else(|| address.to_string()),
+            addresses(&entry.outputs, false).unwrap_or_else(|| UNKNOWN_ADDRESS.to_string()),
+        ),
+        _ => (
+            addresses(&entry.inputs, true).unwrap_or_else(|| address.to_string()),
+            addresses(&entry.outputs, true).unwrap_or_else(|| address.to_string()),
+        ),
+    };
+
+    let confirmations = if let Some(block_height) = block_height {
+        if current_height > 0 {
+            current_height.saturating_sub(block_height)
+        } else {
+            0
+        }
+    } else {
+        0
+    };
 
-/// Estimate Bitcoin fees from mempool.space
-pub async fn estimate_bitcoin_fees() -> Result<BitcoinFeeEstimationResponse, String> {
-    let url = "https://mempool.space/api/v1/fees/recommended";
+    let status =
+        TransactionStatus::from_bitcoin_confirmations(confirmations, BITCOIN_MIN_CONFIRMATIONS);
 
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let timestamp = if let Some(block_time) = block_time {
+        chrono::DateTime::from_timestamp(block_time as i64, 0)
+            .unwrap_or_else(|| Utc::now())
+            .to_rfc3339()
+    } else {
+        Utc::now().to_rfc3339()
+    };
 
-    let response = client
-        .get(url)
-        .send()
-        .await
-        .map_err(|e| format!("HTTP request failed: {}", e))?;
+    crate::safe_log!(
+        "[INFO] Transaction details - Amount: {} sats, Fee: {} sats, Status: {:?}, Confirmations: {}",
+        amount_sats,
+        fee_sats,
+        status,
+        confirmations
+    );
 
-    if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()));
+    BitcoinTransaction {
+        id: Uuid::new_v4().to_string(),
+        wallet_id: wallet_id.to_string(),
+        tx_hash: entry.tx_hash.clone(),
+        tx_type,
+        from_address,
+        to_address,
+        amount_sats,
+        fee_sats,
+        amount: sats_to_btc(amount_sats),
+        fee: sats_to_btc(fee_sats),
+        status,
+        confirmations,
+        block_height,
+        timestamp: timestamp.clone(),
+        created_at: timestamp,
+        replaces_tx_hash: None,
+        replaced_by_tx_hash: None,
+        parent_tx_hash: None,
+        batch_id: None,
+        output_index: 0,
+        label: None,
     }
+}
 
-    let fees: MempoolFees = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse fee estimates: {}", e))?;
+/// The history row of a transaction this wallet is about to broadcast,
+/// classified from its inputs and outputs the way history sync does. A
+/// transaction spending none of the wallet's coins is not the wallet's to record.
+pub(super) fn broadcast_record(
+    wallet_id: &str,
+    address: &str,
+    tx_hash: &str,
+    inputs: Vec<HistoryTxo>,
+    outputs: Vec<HistoryTxo>,
+    fee_sats: u64,
+) -> Result<BitcoinTransaction, String> {
+    let entry = HistoryEntry {
+        tx_hash: tx_hash.to_string(),
+        received: outputs.iter().filter(|o| o.is_mine).map(|o| o.value).sum(),
+        sent: inputs.iter().filter(|i| i.is_mine).map(|i| i.value).sum(),
+        fee_sats,
+        block_height: None,
+        block_time: None,
+        inputs,
+        outputs,
+    };
+    if entry.sent == 0 {
+        return Err("Transaction spends no coins of this wallet".to_string());
+    }
 
-    Ok(BitcoinFeeEstimationResponse {
-        fast: fees.fastest_fee,
-        half_hour: fees.half_hour_fee,
-        hour: fees.hour_fee,
-    })
+    let mut record = history_record(wallet_id, address, &entry, 0);
+    record.status = TransactionStatus::after_broadcast();
+    Ok(record)
 }
 
 /// Send Bitcoin transaction
@@ -370,9 +514,9 @@ pub async fn send_bitcoin_transaction(
         request.wallet_id
     );
     crate::safe_log!(
-        "[INFO] Recipient: {}, Amount: {} BTC",
+        "[INFO] Recipient: {}, Amount: {} sats",
         request.to_address,
-        request.amount
+        request.amount_sats
     );
 
     // Get wallet info
@@ -395,21 +539,11 @@ pub async fn send_bitcoin_transaction(
         secret_backend,
         keystore,
         session_manager,
-        connect_electrum_blockchain,
+        connect_blockchain,
     )
     .await
 }
 
-fn connect_electrum_blockchain() -> Result<ElectrumBlockchain, String> {
-    crate::safe_log!("[INFO] Connecting to Electrum server...");
-    let client = Client::new("ssl://electrum.blockstream.info:50002").map_err(|e| {
-        crate::safe_log!("[ERROR] Failed to connect to Electrum: {}", e);
-        format!("Failed to connect to Electrum: {}", e)
-    })?;
-    crate::safe_log!("[INFO] Connected to Electrum server");
-    Ok(ElectrumBlockchain::from(client))
-}
-
 async fn send_bitcoin_transaction_resolved<F>(
     request: SendBitcoinRequest,
     wallet_info: WalletInfo,
@@ -419,35 +553,150 @@ async fn send_bitcoin_transaction_resolved<F>(
     connect_blockchain: F,
 ) -> Result<SendTransactionResponse, String>
 where
-    F: FnOnce() -> Result<ElectrumBlockchain, String>,
+    F: FnOnce(BitcoinNetwork, &str) -> Result<AnyBlockchain, String> + Send + 'static,
 {
     let signing_secret =
         load_signing_secret(&wallet_info, secret_backend, keystore, session_manager)
             .map_err(map_security_error)?
             .ok_or_else(|| "Wallet secret not found".to_string())?;
 
-    // Reconstruct the private key and descriptor
-    let secp = bitcoin::secp256k1::Secp256k1::new();
-    let descriptor = descriptor_from_signing_secret(signing_secret, &secp)?;
-
-    crate::safe_log!("[INFO] Creating wallet with descriptor: tr(SECRET)");
-    let bdk_network = match Network::Bitcoin {
-        Network::Bitcoin => BdkNetwork::Bitcoin,
-        Network::Testnet => BdkNetwork::Testnet,
-        Network::Regtest => BdkNetwork::Regtest,
-        Network::Signet => BdkNetwork::Signet,
-        _ => BdkNetwork::Bitcoin,
+    // Opening the signing wallet stores its public descriptors, so a wallet
+    // sent from for the first time can be previewed right after.
+    let (wallet, network) = open_signing_wallet(&wallet_info, signing_secret)?;
+    let plan = plan_send(&wallet_info.id, &request, network)?;
+    // Every send signs exactly the transaction that was reviewed.
+    let preview_txid = request
+        .preview_txid
+        .clone()
+        .ok_or_else(|| "Preview the send before sending it".to_string())?;
+    let psbt = pending_preview(&wallet_info.id, &request, &preview_txid)?;
+
+    let (wallet, blockchain) =
+        sync_signing_wallet(wallet, network, &wallet_info.id, connect_blockchain).await?;
+
+    // Calculate fee before extracting tx (psbt moves after extract_tx)
+    let fee_sats = psbt.fee_amount().unwrap_or(0);
+    crate::safe_log!("[INFO] Transaction fee: {} sats", fee_sats);
+
+    let tx = sign_transaction(&wallet, psbt)?;
+    ensure_matches_preview(&tx, &preview_txid)?;
+    broadcast_transaction(blockchain, &tx).await?;
+    forget_preview(&preview_txid);
+    let tx_hash = tx.txid().to_string();
+    // A drained send pays whatever is left after the fee, so record what
+    // the recipient output actually carries.
+    let recipient_script = plan.recipient.payload.script_pubkey();
+    let amount_sats: u64 = tx
+        .output
+        .iter()
+        .filter(|output| output.script_pubkey == recipient_script)
+        .map(|output| output.value)
+        .sum();
+
+    // Save transaction to database
+    crate::safe_log!("[INFO] Saving transaction to database...");
+    let now = Utc::now().to_rfc3339();
+    let tx_record = BitcoinTransaction {
+        id: Uuid::new_v4().to_string(),
+        wallet_id: request.wallet_id.clone(),
+        tx_hash: tx_hash.clone(),
+        tx_type: TransactionType::Send,
+        from_address: wallet_info.address.clone(),
+        to_address: request.to_address,
+        amount_sats,
+        fee_sats,
+        amount: sats_to_btc(amount_sats),
+        fee: sats_to_btc(fee_sats),
+        status: TransactionStatus::after_broadcast(),
+        confirmations: 0,
+        block_height: None,
+        timestamp: now.clone(),
+        created_at: now,
+        replaces_tx_hash: None,
+        replaced_by_tx_hash: None,
+        parent_tx_hash: None,
+        batch_id: None,
+        output_index: 0,
+        label: None,
     };
 
+    {
+        let db = DB.lock().unwrap();
+        db.add_bitcoin_transaction(&tx_record).map_err(|e| {
+            crate::safe_log!("[ERROR] Failed to save transaction: {}", e);
+            format!("Failed to save transaction: {}", e)
+        })?;
+    }
+
+    crate::safe_log!("[SUCCESS] Transaction saved to database");
+    crate::safe_log!("[SUCCESS] Bitcoin transaction completed: {}", tx_hash);
+
+    Ok(SendTransactionResponse {
+        tx_hash,
+        message: "Transaction sent successfully".to_string(),
+    })
+}
+
+/// Recipient and coin selection of a send, validated before any network IO.
+pub(super) struct SendPlan {
+    pub(super) recipient: bdk::bitcoin::Address,
+    coin_control: CoinControl,
+}
+
+pub(super) fn plan_send(
+    wallet_id: &str,
+    request: &SendBitcoinRequest,
+    network: BitcoinNetwork,
+) -> Result<SendPlan, String> {
+    let coin_control = load_coin_control(
+        wallet_id,
+        request.include_outpoints.as_deref(),
+        request.exclude_outpoints.as_deref(),
+    )?;
+
     // Parse recipient address before network IO so mismatch fails closed locally.
-    let to_address = request.to_address.trim();
+    let recipient = parse_recipient_address(&request.to_address, network)?;
+
+    Ok(SendPlan {
+        recipient,
+        coin_control,
+    })
+}
+
+/// Coin control for a send from `wallet_id`, honouring its frozen UTXOs.
+pub(super) fn load_coin_control(
+    wallet_id: &str,
+    include_outpoints: Option<&[String]>,
+    exclude_outpoints: Option<&[String]>,
+) -> Result<CoinControl, String> {
+    let frozen = {
+        let db = DB.lock().unwrap();
+        db.get_frozen_bitcoin_utxos(wallet_id)
+            .map_err(|e| format!("Failed to get frozen UTXOs: {}", e))?
+    };
+    CoinControl::new(
+        include_outpoints.unwrap_or_default(),
+        exclude_outpoints.unwrap_or_default(),
+        &frozen,
+    )
+}
+
+/// Parses a recipient address and checks it belongs to the wallet's network.
+pub(crate) fn parse_recipient_address(
+    to_address: &str,
+    network: BitcoinNetwork,
+) -> Result<bdk::bitcoin::Address, String> {
+    let bdk_network = network.bdk_network();
+    let to_address = to_address.trim();
     crate::safe_log!("[INFO] Parsing recipient address: {}", to_address);
     let recipient = bdk::bitcoin::Address::from_str(to_address).map_err(|e| {
         crate::safe_log!("[ERROR] Invalid recipient address '{}': {}", to_address, e);
         format!("Invalid recipient address: {}", e)
     })?;
 
-    if recipient.network != bdk_network {
+    // Testnet, signet and regtest share base58 prefixes, so compare by validity
+    // rather than by the parsed network.
+    if !recipient.is_valid_for_network(bdk_network) {
         crate::safe_log!(
             "[ERROR] Network mismatch: expected {:?}, got {:?}",
             bdk_network,
@@ -459,41 +708,26 @@ where
         ));
     }
 
-    let wallet =
-        Wallet::new(&descriptor, None, bdk_network, MemoryDatabase::default()).map_err(|e| {
-            crate::safe_log!("[ERROR] Failed to create wallet: {}", e);
-            format!("Failed to create wallet: {}", e)
-        })?;
-
-    let blockchain = connect_blockchain()?;
-
-    // Sync wallet
-    crate::safe_log!("[INFO] Syncing wallet...");
-    wallet
-        .sync(&blockchain, SyncOptions::default())
-        .map_err(|e| {
-            crate::safe_log!("[ERROR] Failed to sync wallet: {}", e);
-            format!("Failed to sync wallet: {}", e)
-        })?;
-    crate::safe_log!("[INFO] Wallet synced successfully");
+    Ok(recipient.assume_checked())
+}
 
-    // Check balance
-    let balance = wallet
-        .get_balance()
-        .map_err(|e| format!("Failed to get balance: {}", e))?;
-    let total_balance = balance.get_total();
-    crate::safe_log!("[INFO] Wallet total balance: {} satoshis", total_balance);
+/// Builds the unsigned send PSBT from a synced wallet.
+pub(super) fn build_send_psbt(
+    wallet: &Wallet<SqliteDatabase>,
+    request: &SendBitcoinRequest,
+    plan: &SendPlan,
+) -> Result<bdk::bitcoin::psbt::PartiallySignedTransaction, String> {
+    let coin_control = &plan.coin_control;
+    let recipient = &plan.recipient;
+    let total_balance = spendable_balance(wallet, coin_control)?;
 
-    // Convert BTC to satoshis using floor to avoid rounding up sub-satoshi values
-    let amount_satoshis = (request.amount * 100_000_000.0).floor() as u64;
-    crate::safe_log!(
-        "[INFO] Requested amount in satoshis (truncated): {}",
-        amount_satoshis
-    );
+    let amount_satoshis = request.amount_sats;
+    crate::safe_log!("[INFO] Requested amount in satoshis: {}", amount_satoshis);
 
     // Build transaction
     crate::safe_log!("[INFO] Building transaction...");
     let mut tx_builder = wallet.build_tx();
+    apply_coin_control(&mut tx_builder, coin_control)?;
 
     // Auto-drain if amount is >= total balance
     let should_drain = request.send_all.unwrap_or(false)
@@ -501,89 +735,672 @@ where
 
     if should_drain {
         crate::safe_log!("[INFO] Using drain_wallet() to send all available funds");
-        tx_builder
-            .drain_wallet()
-            .drain_to(recipient.payload.script_pubkey());
+        if !coin_control.is_manual() {
+            tx_builder.drain_wallet();
+        }
+        tx_builder.drain_to(recipient.payload.script_pubkey());
     } else {
         tx_builder.add_recipient(recipient.payload.script_pubkey(), amount_satoshis);
     }
 
+    // Signal BIP125 replaceability so a stuck send can be fee-bumped later
+    tx_builder.enable_rbf();
+
     // Set fee rate if provided
     if let Some(fee_rate) = request.fee_rate {
         crate::safe_log!("[INFO] Using custom fee rate: {} sat/vB", fee_rate);
         tx_builder.fee_rate(FeeRate::from_sat_per_vb(fee_rate as f32));
     }
 
-    let (mut psbt, _) = tx_builder.finish().map_err(|e| {
+    let (psbt, _) = tx_builder.finish().map_err(|e| {
         crate::safe_log!("[ERROR] Failed to build transaction: {}", e);
         format!("Failed to build transaction: {}", e)
     })?;
     crate::safe_log!("[INFO] Transaction built successfully");
 
-    // Calculate fee before extracting tx (psbt moves after extract_tx)
-    let fee = if let Some(fee_satoshis) = psbt.fee_amount() {
-        (fee_satoshis as f64) / 100_000_000.0
-    } else {
-        0.0
-    };
-    crate::safe_log!("[INFO] Transaction fee: {} BTC", fee);
-
-    // Sign transaction
-    crate::safe_log!("[INFO] Signing transaction...");
-    wallet
-        .sign(&mut psbt, SignOptions::default())
-        .map_err(|e| {
-            crate::safe_log!("[ERROR] Failed to sign transaction: {}", e);
-            format!("Failed to sign transaction: {}", e)
-        })?;
-    crate::safe_log!("[INFO] Transaction signed successfully");
-
-    // Extract and broadcast transaction
-    let tx = psbt.extract_tx();
-    let tx_hash = tx.txid().to_string();
-    crate::safe_log!("[INFO] Transaction hash: {}", tx_hash);
+    Ok(psbt)
+}
 
-    crate::safe_log!("[INFO] Broadcasting transaction...");
-    blockchain.broadcast(&tx).map_err(|e| {
-        crate::safe_log!("[ERROR] Failed to broadcast transaction: {}", e);
-        format!("Failed to broadcast transaction: {}", e)
-    })?;
-    crate::safe_log!("[SUCCESS] Transaction broadcasted successfully");
+/// Balance of the coins a send is allowed to use. Fails when coin control
+/// names a coin the wallet cannot spend.
+pub(super) fn spendable_balance(
+    wallet: &Wallet<SqliteDatabase>,
+    coin_control: &CoinControl,
+) -> Result<u64, String> {
+    let unspent = wallet
+        .list_unspent()
+        .map_err(|e| format!("Failed to list unspent outputs: {}", e))?;
+    for outpoint in coin_control.include() {
+        if !unspent.iter().any(|utxo| utxo.outpoint == *outpoint) {
+            return Err(format!("UTXO {} is not spendable by this wallet", outpoint));
+        }
+    }
+    let total_balance: u64 = unspent
+        .iter()
+        .filter(|utxo| coin_control.allows(&utxo.outpoint))
+        .map(|utxo| utxo.txout.value)
+        .sum();
+    crate::safe_log!(
+        "[INFO] Wallet spendable balance: {} satoshis",
+        total_balance
+    );
 
-    // Save transaction to database
-    crate::safe_log!("[INFO] Saving transaction to database...");
-    let now = Utc::now().to_rfc3339();
-    let tx_record = BitcoinTransaction {
-        id: Uuid::new_v4().to_string(),
-        wallet_id: request.wallet_id.clone(),
-        tx_hash: tx_hash.clone(),
-        tx_type: TransactionType::Send,
-        from_address: wallet_info.address.clone(),
-        to_address: request.to_address,
-        amount: request.amount,
-        fee,
-        status: TransactionStatus::after_broadcast(),
-        confirmations: 0,
-        block_height: None,
-        timestamp: now.clone(),
-        created_at: now,
-    };
+    Ok(total_balance)
+}
 
-    {
-        let db = DB.lock().unwrap();
-        db.add_bitcoin_transaction(&tx_record).map_err(|e| {
-            crate::safe_log!("[ERROR] Failed to save transaction: {}", e);
-            format!("Failed to save transaction: {}", e)
-        })?;
+/// Restricts coin selection to the coins coin control allows.
+pub(super) fn apply_coin_control(
+    tx_builder: &mut TxBuilder<'_, SqliteDatabase, DefaultCoinSelectionAlgorithm, CreateTx>,
+    coin_control: &CoinControl,
+) -> Result<(), String> {
+    if coin_control.is_manual() {
+        tx_builder
+            .add_utxos(coin_control.include())
+            .map_err(|e| format!("Failed to select UTXOs: {}", e))?
+            .manually_selected_only();
+    } else {
+        tx_builder.unspendable(coin_control.unspendable());
     }
 
-    crate::safe_log!("[SUCCESS] Transaction saved to database");
-    crate::safe_log!("[SUCCESS] Bitcoin transaction completed: {}", tx_hash);
+    Ok(())
+}
+
+/// Builds an RBF replacement of `txid` that never pulls in a coin coin control rules out.
+fn build_fee_bump_psbt<D: BatchDatabase>(
+    wallet: &Wallet<D>,
+    txid: Txid,
+    new_fee_rate: f64,
+    coin_control: &CoinControl,
+) -> Result<(PartiallySignedTransaction, TransactionDetails), String> {
+    let mut tx_builder = wallet.build_fee_bump(txid).map_err(|e| {
+        crate::safe_log!("[ERROR] Failed to start fee bump: {}", e);
+        format!("Failed to start fee bump: {}", e)
+    })?;
+    tx_builder
+        .fee_rate(FeeRate::from_sat_per_vb(new_fee_rate as f32))
+        .unspendable(coin_control.unspendable())
+        .enable_rbf();
+
+    tx_builder.finish().map_err(|e| {
+        crate::safe_log!("[ERROR] Failed to build replacement transaction: {}", e);
+        format!("Failed to build replacement transaction: {}", e)
+    })
+}
+
+/// Replace a stuck, unconfirmed send with a higher-fee version (BIP125 RBF)
+pub async fn bump_bitcoin_transaction_fee(
+    tx_hash: String,
+    new_fee_rate: f64,
+    secret_backend: &SecretBackend,
+    keystore: &(dyn Keystore + Send + Sync),
+    session_manager: &SessionManager,
+) -> Result<SendTransactionResponse, String> {
+    bump_bitcoin_transaction_fee_resolved(
+        tx_hash,
+        new_fee_rate,
+        secret_backend,
+        keystore,
+        session_manager,
+        connect_blockchain,
+    )
+    .await
+}
+
+async fn bump_bitcoin_transaction_fee_resolved<F>(
+    tx_hash: String,
+    new_fee_rate: f64,
+    secret_backend: &SecretBackend,
+    keystore: &(dyn Keystore + Send + Sync),
+    session_manager: &SessionManager,
+    connect_blockchain: F,
+) -> Result<SendTransactionResponse, String>
+where
+    F: FnOnce(BitcoinNetwork, &str) -> Result<AnyBlockchain, String> + Send + 'static,
+{
+    crate::safe_log!(
+        "[INFO] Bumping fee of Bitcoin transaction {} to {} sat/vB",
+        tx_hash,
+        new_fee_rate
+    );
+
+    if !new_fee_rate.is_finite() || new_fee_rate <= 0.0 {
+        return Err("Fee rate must be greater than zero".to_string());
+    }
+
+    let (original, wallet_info) = {
+        let db = DB.lock().unwrap();
+        let original = db
+            .get_bitcoin_transaction_by_hash(tx_hash.trim())
+            .map_err(|e| format!("Failed to get transaction: {}", e))?
+            .ok_or_else(|| "Transaction not found".to_string())?;
+        let wallet_info = db
+            .get_bitcoin_wallet(&original.wallet_id)
+            .map_err(|e| format!("Failed to get wallet info: {}", e))?
+            .ok_or_else(|| "Wallet not found".to_string())?;
+        (original, wallet_info)
+    };
+
+    if !matches!(
+        original.tx_type,
+        TransactionType::Send | TransactionType::SelfTransfer | TransactionType::Consolidation
+    ) {
+        return Err("Only outgoing transactions can be fee-bumped".to_string());
+    }
+    if original.status.is_terminal() {
+        return Err(format!(
+            "Transaction is already {}",
+            original.status.as_str()
+        ));
+    }
+    let txid = bdk::bitcoin::Txid::from_str(&original.tx_hash)
+        .map_err(|e| format!("Invalid transaction hash: {}", e))?;
+
+    let signing_secret =
+        load_signing_secret(&wallet_info, secret_backend, keystore, session_manager)
+            .map_err(map_security_error)?
+            .ok_or_else(|| "Wallet secret not found".to_string())?;
+    let (wallet, network) = open_signing_wallet(&wallet_info, signing_secret)?;
+
+    let (wallet, blockchain) =
+        sync_signing_wallet(wallet, network, &wallet_info.id, connect_blockchain).await?;
+
+    // BDK adds inputs on its own when the bump needs more funds
+    let coin_control = load_coin_control(&original.wallet_id, None, None)?;
+
+    crate::safe_log!("[INFO] Building replacement transaction...");
+    let (psbt, details) = build_fee_bump_psbt(&wallet, txid, new_fee_rate, &coin_control)?;
+
+    // The replacement may shrink change or, for drains, the recipient output
+    let fee_satoshis = details.fee.unwrap_or(0);
+    let amount_satoshis = if original.tx_type == TransactionType::Send {
+        details
+            .sent
+            .saturating_sub(details.received)
+            .saturating_sub(fee_satoshis)
+    } else {
+        details.received
+    };
+    // BDK reorders the replacement's outputs, so batch rows are matched to
+    // their new vouts by address and amount.
+    let replacement_outputs: Vec<(Option<String>, u64)> = psbt
+        .unsigned_tx
+        .output
+        .iter()
+        .map(|output| {
+            let address =
+                bdk::bitcoin::Address::from_script(&output.script_pubkey, network.bdk_network())
+                    .ok()
+                    .map(|address| address.to_string());
+            (address, output.value)
+        })
+        .collect();
+
+    let tx = sign_transaction(&wallet, psbt)?;
+    broadcast_transaction(blockchain, &tx).await?;
+    let replacement_hash = tx.txid().to_string();
+
+    let batch_rows = match original.batch_id {
+        Some(_) => {
+            let db = DB.lock().unwrap();
+            db.get_bitcoin_transactions(&original.wallet_id)
+                .map_err(|e| format!("Failed to get transactions: {}", e))?
+                .into_iter()
+                .filter(|row| row.tx_hash == original.tx_hash && row.batch_id.is_some())
+                .collect()
+        }
+        None => Vec::new(),
+    };
+    let tx_records = fee_bump_records(
+        &original,
+        &batch_rows,
+        &replacement_outputs,
+        &replacement_hash,
+        amount_satoshis,
+        fee_satoshis,
+    );
+
+    {
+        let db = DB.lock().unwrap();
+        db.record_bitcoin_replacement(&original.tx_hash, &tx_records)
+            .map_err(|e| format!("Failed to save transaction: {}", e))?;
+    }
+
+    crate::safe_log!(
+        "[SUCCESS] Transaction {} replaced by {}",
+        original.tx_hash,
+        replacement_hash
+    );
+
+    Ok(SendTransactionResponse {
+        tx_hash: replacement_hash,
+        message: "Fee bump sent successfully".to_string(),
+    })
+}
+
+/// History rows for a fee bump. A batch keeps one row per recipient, since a
+/// bump only shrinks change and leaves the recipient outputs as they were;
+/// each row takes the vout of its output in the replacement.
+fn fee_bump_records(
+    original: &BitcoinTransaction,
+    batch_rows: &[BitcoinTransaction],
+    replacement_outputs: &[(Option<String>, u64)],
+    replacement_hash: &str,
+    amount_sats: u64,
+    fee_sats: u64,
+) -> Vec<BitcoinTransaction> {
+    let now = Utc::now().to_rfc3339();
+    let replacement =
+        |row: &BitcoinTransaction, output_index: u32, amount_sats: u64, fee_sats: u64| {
+            BitcoinTransaction {
+                id: Uuid::new_v4().to_string(),
+                wallet_id: row.wallet_id.clone(),
+                tx_hash: replacement_hash.to_string(),
+                tx_type: row.tx_type.clone(),
+                from_address: row.from_address.clone(),
+                to_address: row.to_address.clone(),
+                amount_sats,
+                fee_sats,
+                amount: sats_to_btc(amount_sats),
+                fee: sats_to_btc(fee_sats),
+                status: TransactionStatus::after_broadcast(),
+                confirmations: 0,
+                block_height: None,
+                timestamp: now.clone(),
+                created_at: now.clone(),
+                replaces_tx_hash: Some(row.tx_hash.clone()),
+                replaced_by_tx_hash: None,
+                parent_tx_hash: None,
+                batch_id: row.batch_id.clone(),
+                output_index,
+                label: None,
+            }
+        };
+
+    if batch_rows.is_empty() {
+        return vec![replacement(
+            original,
+            original.output_index,
+            amount_sats,
+            fee_sats,
+        )];
+    }
+
+    let mut rows: Vec<&BitcoinTransaction> = batch_rows.iter().collect();
+    rows.sort_by_key(|row| row.output_index);
+    let mut used = vec![false; replacement_outputs.len()];
+    rows.iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let vout = (0..replacement_outputs.len()).find(|&vout| {
+                let (address, value) = &replacement_outputs[vout];
+                !used[vout]
+                    && *value == row.amount_sats
+                    && address.as_deref() == Some(row.to_address.as_str())
+            });
+            if let Some(vout) = vout {
+                used[vout] = true;
+            }
+            replacement(
+                row,
+                vout.map_or(row.output_index, |vout| vout as u32),
+                row.amount_sats,
+                if index == 0 { fee_sats } else { 0 },
+            )
+        })
+        .collect()
+}
+
+/// Accelerate an unconfirmed incoming transaction by spending our outputs of
+/// it back to ourselves with a high-fee child (CPFP)
+pub async fn accelerate_incoming_transaction(
+    wallet_id: String,
+    parent_tx_hash: String,
+    target_fee_rate: f64,
+    secret_backend: &SecretBackend,
+    keystore: &(dyn Keystore + Send + Sync),
+    session_manager: &SessionManager,
+) -> Result<SendTransactionResponse, String> {
+    let (wallet_info, network) = {
+        let db = DB.lock().unwrap();
+        let wallet_info = db
+            .get_bitcoin_wallet(&wallet_id)
+            .map_err(|e| format!("Failed to get wallet info: {}", e))?
+            .ok_or_else(|| "Wallet not found".to_string())?;
+        let network = db
+            .get_bitcoin_wallet_network(&wallet_id)
+            .map_err(|e| format!("Failed to get wallet network: {}", e))?;
+        (wallet_info, network)
+    };
+
+    let parent = backend::transaction(parent_tx_hash.trim(), network).await?;
+
+    accelerate_incoming_transaction_resolved(
+        wallet_info,
+        parent,
+        target_fee_rate,
+        secret_backend,
+        keystore,
+        session_manager,
+        connect_blockchain,
+    )
+    .await
+}
+
+async fn accelerate_incoming_transaction_resolved<F>(
+    wallet_info: WalletInfo,
+    parent: BlockstreamTx,
+    target_fee_rate: f64,
+    secret_backend: &SecretBackend,
+    keystore: &(dyn Keystore + Send + Sync),
+    session_manager: &SessionManager,
+    connect_blockchain: F,
+) -> Result<SendTransactionResponse, String>
+where
+    F: FnOnce(BitcoinNetwork, &str) -> Result<AnyBlockchain, String> + Send + 'static,
+{
+    crate::safe_log!(
+        "[INFO] Accelerating transaction {} to {} sat/vB",
+        parent.txid,
+        target_fee_rate
+    );
+
+    if !target_fee_rate.is_finite() || target_fee_rate <= 0.0 {
+        return Err("Fee rate must be greater than zero".to_string());
+    }
+    if parent.status.confirmed {
+        return Err("Transaction is already confirmed".to_string());
+    }
+
+    let parent_vsize = parent.weight.div_ceil(4);
+    if parent.fee as f64 >= target_fee_rate * parent_vsize as f64 {
+        return Err(format!(
+            "Transaction already pays at least {} sat/vB",
+            target_fee_rate
+        ));
+    }
+    let parent_txid = bdk::bitcoin::Txid::from_str(&parent.txid)
+        .map_err(|e| format!("Invalid transaction hash: {}", e))?;
+
+    let signing_secret =
+        load_signing_secret(&wallet_info, secret_backend, keystore, session_manager)
+            .map_err(map_security_error)?
+            .ok_or_else(|| "Wallet secret not found".to_string())?;
+    let (wallet, network) = open_signing_wallet(&wallet_info, signing_secret)?;
+
+    let (wallet, blockchain) =
+        sync_signing_wallet(wallet, network, &wallet_info.id, connect_blockchain).await?;
+
+    let coin_control = load_coin_control(&wallet_info.id, None, None)?;
+    let unspent = wallet
+        .list_unspent()
+        .map_err(|e| format!("Failed to list wallet outputs: {}", e))?;
+    let parent_outputs = cpfp_parent_outputs(&unspent, parent_txid, &coin_control)?;
+
+    let destination = wallet
+        .get_internal_address(AddressIndex::New)
+        .map_err(|e| format!("Failed to derive change address: {}", e))?;
+
+    // The builder borrows the wallet, so keep it out of the broadcast await.
+    let (psbt, details, child_fee) = {
+        // First pass at the target rate only sizes the child; the second pass
+        // pays for the whole package.
+        let build_child = |fee: Option<u64>| {
+            let mut tx_builder = wallet.build_tx();
+            tx_builder
+                .add_utxos(&parent_outputs)
+                .map_err(|e| format!("Failed to select parent outputs: {}", e))?
+                .manually_selected_only()
+                .drain_to(destination.script_pubkey())
+                .enable_rbf();
+            match fee {
+                Some(fee) => tx_builder.fee_absolute(fee),
+                None => tx_builder.fee_rate(FeeRate::from_sat_per_vb(target_fee_rate as f32)),
+            };
+            tx_builder.finish().map_err(|e| {
+                crate::safe_log!("[ERROR] Failed to build child transaction: {}", e);
+                format!("Failed to build child transaction: {}", e)
+            })
+        };
+
+        let (_, sizing) = build_child(None)?;
+        let child_vsize = (sizing.fee.unwrap_or(0) as f64 / target_fee_rate).ceil() as u64;
+        let child_fee = cpfp_child_fee(target_fee_rate, parent_vsize, parent.fee, child_vsize)
+            .max(sizing.fee.unwrap_or(0));
+        crate::safe_log!(
+            "[INFO] CPFP child fee: {} sats (parent {} vB / {} sats, child {} vB)",
+            child_fee,
+            parent_vsize,
+            parent.fee,
+            child_vsize
+        );
+
+        let (psbt, details) = build_child(Some(child_fee))?;
+        (psbt, details, child_fee)
+    };
+    let amount_satoshis = details.received;
+
+    let tx = sign_transaction(&wallet, psbt)?;
+    broadcast_transaction(blockchain, &tx).await?;
+    let tx_hash = tx.txid().to_string();
+
+    let tx_record = cpfp_child_record(
+        &wallet_info,
+        &tx_hash,
+        &parent.txid,
+        destination.to_string(),
+        amount_satoshis,
+        child_fee,
+    );
+
+    {
+        let db = DB.lock().unwrap();
+        db.add_bitcoin_transaction(&tx_record)
+            .map_err(|e| format!("Failed to save transaction: {}", e))?;
+    }
+
+    crate::safe_log!(
+        "[SUCCESS] Transaction {} accelerated by child {}",
+        parent.txid,
+        tx_hash
+    );
 
     Ok(SendTransactionResponse {
         tx_hash,
-        message: "Transaction sent successfully".to_string(),
+        message: "Acceleration sent successfully".to_string(),
+    })
+}
+
+/// Our unspent outputs of the parent that the child may sweep. Frozen coins
+/// are never spent automatically, so they are left out.
+fn cpfp_parent_outputs(
+    unspent: &[LocalUtxo],
+    parent_txid: Txid,
+    coin_control: &CoinControl,
+) -> Result<Vec<OutPoint>, String> {
+    let owned: Vec<OutPoint> = unspent
+        .iter()
+        .filter(|utxo| utxo.outpoint.txid == parent_txid)
+        .map(|utxo| utxo.outpoint)
+        .collect();
+    if owned.is_empty() {
+        return Err("Transaction has no unspent outputs belonging to this wallet".to_string());
+    }
+
+    let spendable: Vec<OutPoint> = owned
+        .into_iter()
+        .filter(|outpoint| coin_control.allows(outpoint))
+        .collect();
+    if spendable.is_empty() {
+        return Err("All of this wallet's outputs of the transaction are frozen".to_string());
+    }
+    Ok(spendable)
+}
+
+/// The row of a CPFP child. It only moves the parent's outputs back into the
+/// wallet, so it is a self-transfer rather than a send.
+fn cpfp_child_record(
+    wallet_info: &WalletInfo,
+    tx_hash: &str,
+    parent_tx_hash: &str,
+    destination: String,
+    amount_sats: u64,
+    fee_sats: u64,
+) -> BitcoinTransaction {
+    let now = Utc::now().to_rfc3339();
+    BitcoinTransaction {
+        id: Uuid::new_v4().to_string(),
+        wallet_id: wallet_info.id.clone(),
+        tx_hash: tx_hash.to_string(),
+        tx_type: TransactionType::SelfTransfer,
+        from_address: wallet_info.address.clone(),
+        to_address: destination,
+        amount_sats,
+        fee_sats,
+        amount: sats_to_btc(amount_sats),
+        fee: sats_to_btc(fee_sats),
+        status: TransactionStatus::after_broadcast(),
+        confirmations: 0,
+        block_height: None,
+        timestamp: now.clone(),
+        created_at: now,
+        replaces_tx_hash: None,
+        replaced_by_tx_hash: None,
+        parent_tx_hash: Some(parent_tx_hash.to_string()),
+        batch_id: None,
+        output_index: 0,
+        label: None,
+    }
+}
+
+/// Fee the child must pay so parent and child together average
+/// `target_fee_rate` sat/vB.
+fn cpfp_child_fee(
+    target_fee_rate: f64,
+    parent_vsize: u64,
+    parent_fee: u64,
+    child_vsize: u64,
+) -> u64 {
+    let package_fee = (target_fee_rate * (parent_vsize + child_vsize) as f64).ceil() as u64;
+    package_fee.saturating_sub(parent_fee)
+}
+
+/// Builds the BDK signing wallet for a Bitcoin wallet row over its locally
+/// stored state, using its stored script type and network.
+pub(super) fn open_signing_wallet(
+    wallet_info: &WalletInfo,
+    signing_secret: BitcoinSigningSecret,
+) -> Result<(Wallet<SqliteDatabase>, BitcoinNetwork), String> {
+    // Reconstruct the receive/change descriptors for the wallet's script type and network
+    let (script_type, network) = {
+        let db = DB.lock().unwrap();
+        let script_type = db
+            .get_bitcoin_wallet_script_type(&wallet_info.id)
+            .map_err(|e| format!("Failed to get wallet script type: {}", e))?;
+        let network = db
+            .get_bitcoin_wallet_network(&wallet_info.id)
+            .map_err(|e| format!("Failed to get wallet network: {}", e))?;
+        (script_type, network)
+    };
+    let secp = bitcoin::secp256k1::Secp256k1::new();
+    let signing = if wallet_info.wallet_type == "multisig" {
+        crate::safe_log!("[INFO] Creating multisig wallet with this device's cosigner key");
+        multisig_descriptors_from_signing_secret(&wallet_info.id, signing_secret, network, &secp)?
+    } else {
+        let descriptors =
+            descriptors_from_signing_secret(signing_secret, script_type, network, &secp)?;
+        backfill_public_descriptors(&wallet_info.id, &descriptors.public);
+        crate::safe_log!(
+            "[INFO] Creating wallet with descriptor: {}",
+            script_type.wrap_key("SECRET")
+        );
+        descriptors.signing
+    };
+
+    let wallet = wallet_store::open_wallet(
+        &wallet_info.id,
+        signing.external.as_str(),
+        signing.internal.as_deref(),
+        network.bdk_network(),
+    )
+    .map_err(|e| {
+        crate::safe_log!("[ERROR] Failed to create wallet: {}", e);
+        format!("Failed to create wallet: {}", e)
+    })?;
+
+    Ok((wallet, network))
+}
+
+/// Connects to the network's backends and syncs the wallet. Both block on
+/// network I/O, so the wallet moves to the blocking pool and comes back with
+/// the connected blockchain.
+pub(super) async fn sync_signing_wallet<F>(
+    wallet: Wallet<SqliteDatabase>,
+    network: BitcoinNetwork,
+    wallet_id: &str,
+    connect_blockchain: F,
+) -> Result<(Wallet<SqliteDatabase>, AnyBlockchain), String>
+where
+    F: FnOnce(BitcoinNetwork, &str) -> Result<AnyBlockchain, String> + Send + 'static,
+{
+    let wallet_id = wallet_id.to_string();
+    tokio::task::spawn_blocking(move || {
+        let blockchain = connect_blockchain(network, &wallet_id)?;
+        crate::safe_log!("[INFO] Syncing wallet...");
+        local_state::with_sync_lock(&wallet_id, || {
+            wallet.sync(&blockchain, SyncOptions::default())
+        })
+        .map_err(|e| {
+            crate::safe_log!("[ERROR] Failed to sync wallet: {}", e);
+            format!("Failed to sync wallet: {}", e)
+        })?;
+        crate::safe_log!("[INFO] Wallet synced successfully");
+        Ok((wallet, blockchain))
+    })
+    .await
+    .map_err(|e| format!("Wallet sync task failed: {}", e))?
+}
+
+pub(super) fn sign_transaction(
+    wallet: &Wallet<SqliteDatabase>,
+    mut psbt: bdk::bitcoin::psbt::PartiallySignedTransaction,
+) -> Result<bdk::bitcoin::Transaction, String> {
+    // Sign transaction
+    crate::safe_log!("[INFO] Signing transaction...");
+    let finalized = wallet
+        .sign(&mut psbt, SignOptions::default())
+        .map_err(|e| {
+            crate::safe_log!("[ERROR] Failed to sign transaction: {}", e);
+            format!("Failed to sign transaction: {}", e)
+        })?;
+    // A multisig wallet above 1-of-n cannot complete a spend on its own.
+    if !finalized {
+        return Err(
+            "Transaction needs more cosigner signatures; send it as a PSBT instead".to_string(),
+        );
+    }
+    crate::safe_log!("[INFO] Transaction signed successfully");
+
+    let tx = psbt.extract_tx();
+    crate::safe_log!("[INFO] Transaction hash: {}", tx.txid());
+    Ok(tx)
+}
+
+pub(super) async fn broadcast_transaction(
+    blockchain: AnyBlockchain,
+    tx: &bdk::bitcoin::Transaction,
+) -> Result<(), String> {
+    let tx = tx.clone();
+    tokio::task::spawn_blocking(move || {
+        crate::safe_log!("[INFO] Broadcasting transaction...");
+        blockchain.broadcast(&tx).map_err(|e| {
+            crate::safe_log!("[ERROR] Failed to broadcast transaction: {}", e);
+            format!("Failed to broadcast transaction: {}", e)
+        })?;
+        crate::safe_log!("[SUCCESS] Transaction broadcasted successfully");
+        Ok(())
     })
+    .await
+    .map_err(|e| format!("Broadcast task failed: {}", e))?
 }
 
 #[cfg(test)]
@@ -595,7 +1412,7 @@ async fn send_bitcoin_transaction_with_blockchain_factory<F>(
     connect_blockchain: F,
 ) -> Result<SendTransactionResponse, String>
 where
-    F: FnOnce() -> Result<ElectrumBlockchain, String>,
+    F: FnOnce(BitcoinNetwork, &str) -> Result<AnyBlockchain, String> + Send + 'static,
 {
     let wallet_info = {
         let db = DB.lock().unwrap();
@@ -615,19 +1432,37 @@ where
     .await
 }
 
-fn load_signing_secret(
+pub(super) fn load_signing_secret(
+    wallet_info: &WalletInfo,
+    secret_backend: &SecretBackend,
+    keystore: &(dyn Keystore + Send + Sync),
+    session_manager: &SessionManager,
+) -> Result<Option<BitcoinSigningSecret>, SecurityError> {
+    load_signing_secret_for(
+        wallet_info,
+        secret_backend,
+        keystore,
+        session_manager,
+        SignerOperation::Send,
+    )
+}
+
+/// Loads the wallet's signing secret once `operation` is authorized
+pub(super) fn load_signing_secret_for(
     wallet_info: &WalletInfo,
     secret_backend: &SecretBackend,
     keystore: &(dyn Keystore + Send + Sync),
     session_manager: &SessionManager,
+    operation: SignerOperation,
 ) -> Result<Option<BitcoinSigningSecret>, SecurityError> {
     match wallet_info.wallet_type.as_str() {
-        "mnemonic" => Ok(load_authorized_mnemonic(
+        "watch-only" => Err(SecurityError::WatchOnly),
+        "mnemonic" | "multisig" => Ok(load_authorized_mnemonic(
             &wallet_info.address,
             secret_backend,
             keystore,
             session_manager,
-            SignerOperation::Send,
+            operation,
         )?
         .map(BitcoinSigningSecret::Mnemonic)),
         "private-key" | "private_key" => Ok(load_authorized_private_key(
@@ -635,44 +1470,92 @@ fn load_signing_secret(
             secret_backend,
             keystore,
             session_manager,
-            SignerOperation::Send,
+            operation,
         )?
         .map(BitcoinSigningSecret::PrivateKey)),
         _ => Ok(None),
     }
 }
 
-fn descriptor_from_signing_secret(
+fn descriptors_from_signing_secret(
     signing_secret: BitcoinSigningSecret,
+    script_type: BitcoinScriptType,
+    network: BitcoinNetwork,
     secp: &bitcoin::secp256k1::Secp256k1<bitcoin::secp256k1::All>,
-) -> Result<String, String> {
+) -> Result<AccountDescriptors, String> {
     match signing_secret {
         BitcoinSigningSecret::Mnemonic(secret_data) => {
-            let mnemonic = Mnemonic::parse_in_normalized(Language::English, &secret_data)
-                .map_err(|e| format!("Invalid mnemonic: {}", e))?;
-            let seed = mnemonic.to_seed("");
-            let master_xprv = Xpriv::new_master(Network::Bitcoin, &seed)
-                .map_err(|e| format!("Failed to create master key: {}", e))?;
-
-            let derivation_path = DerivationPath::from_str("m/86'/0'/0'/0/0")
-                .map_err(|e| format!("Invalid derivation path: {}", e))?;
-            let child_xprv = master_xprv
-                .derive_priv(secp, &derivation_path)
-                .map_err(|e| format!("Failed to derive child key: {}", e))?;
-
-            let private_key = child_xprv.to_priv();
-            Ok(format!("tr({})", private_key))
+            account_descriptors_from_mnemonic(&secret_data, script_type, network, secp)
+        }
+        BitcoinSigningSecret::PrivateKey(secret_data) => {
+            let (secret_key, public_key) = validate_private_key(&secret_data)?;
+            let private_key = bitcoin::PrivateKey::new(secret_key, network.network());
+
+            Ok(AccountDescriptors {
+                signing: WalletDescriptors {
+                    external: script_type.wrap_key(&private_key.to_wif()),
+                    internal: None,
+                },
+                public: single_key_public_descriptor(&public_key, script_type),
+            })
+        }
+    }
+}
+
+/// Signing descriptors of a multisig wallet: its stored public descriptors with
+/// this device's cosigner xpub swapped for the xprv.
+fn multisig_descriptors_from_signing_secret(
+    wallet_id: &str,
+    signing_secret: BitcoinSigningSecret,
+    network: BitcoinNetwork,
+    secp: &bitcoin::secp256k1::Secp256k1<bitcoin::secp256k1::All>,
+) -> Result<WalletDescriptors, String> {
+    let BitcoinSigningSecret::Mnemonic(secret_data) = signing_secret else {
+        return Err("Multisig wallets sign with a mnemonic".to_string());
+    };
+    let (policy, descriptors) = {
+        let db = DB.lock().unwrap();
+        let policy = db
+            .get_bitcoin_wallet_multisig(wallet_id)
+            .map_err(|e| format!("Failed to get multisig policy: {}", e))?;
+        let descriptors = db
+            .get_bitcoin_wallet_descriptors(wallet_id)
+            .map_err(|e| format!("Failed to get wallet descriptors: {}", e))?;
+        (policy, descriptors)
+    };
+    let policy = policy.ok_or_else(|| "Multisig policy not found".to_string())?;
+    let descriptors = descriptors.ok_or_else(|| "Wallet descriptors not found".to_string())?;
+
+    let local = local_cosigner(&secret_data, policy.script_type, network, secp)?;
+    multisig_signing_descriptors(&descriptors, &local)
+}
+
+/// Stores the public descriptors of wallets created before descriptors were
+/// persisted, so address discovery can run without unlocking the secret.
+fn backfill_public_descriptors(wallet_id: &str, descriptors: &WalletDescriptors) {
+    let db = DB.lock().unwrap();
+    match db.get_bitcoin_wallet_descriptors(wallet_id) {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            if let Err(e) = db.set_bitcoin_wallet_descriptors(wallet_id, descriptors) {
+                crate::safe_log!("[WARN] Failed to backfill wallet descriptors: {}", e);
+            }
         }
-        BitcoinSigningSecret::PrivateKey(secret_data) => Ok(format!("tr({})", secret_data)),
+        Err(e) => crate::safe_log!("[WARN] Failed to read wallet descriptors: {}", e),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        load_signing_secret, send_bitcoin_transaction_with_blockchain_factory, BitcoinSigningSecret,
+        accelerate_incoming_transaction_resolved, build_fee_bump_psbt,
+        bump_bitcoin_transaction_fee_resolved, cpfp_child_fee, cpfp_child_record,
+        cpfp_parent_outputs, explorer_history_entry, fee_bump_records, history_record,
+        load_signing_secret, send_bitcoin_transaction_with_blockchain_factory,
+        BitcoinSigningSecret, BlockstreamTx,
     };
     use crate::db::Database;
+    use crate::wallet::bitcoin::config::BitcoinNetwork;
     use crate::wallet::security::backend::{SecretBackend, SecretBackendAdapter};
     use crate::wallet::security::keystore::{Keystore, SqliteKeystore};
     use crate::wallet::security::log_sanitize::take_test_log_lines;
@@ -682,7 +1565,9 @@ mod tests {
     };
     use crate::wallet::security::session::SessionManager;
     use crate::wallet::security::types::{SecurityError, SignerOperation};
-    use crate::wallet::transaction_types::SendBitcoinRequest;
+    use crate::wallet::transaction_types::{
+        BitcoinTransaction, SendBitcoinRequest, TransactionStatus, TransactionType,
+    };
     use crate::wallet::types::WalletInfo;
     use crate::DB;
     use std::sync::Arc;
@@ -830,6 +1715,7 @@ mod tests {
             wallet_type: wallet_type.to_string(),
             address: "bc1ptestaddress".to_string(),
             balance: 0.0,
+            balance_sats: Some(0),
             created_at: "2026-04-18T00:00:00Z".to_string(),
             updated_at: "2026-04-18T00:00:00Z".to_string(),
         }
@@ -851,6 +1737,24 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn send_signing_rejects_watch_only_wallet_without_keystore_access() {
+        let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
+        session
+            .authorize_verified_operation(SignerOperation::Send)
+            .unwrap();
+
+        assert!(matches!(
+            load_signing_secret(
+                &test_wallet("watch-only"),
+                &ready_secret_backend(),
+                &PanicKeystore,
+                &session
+            ),
+            Err(SecurityError::WatchOnly)
+        ));
+    }
+
     #[test]
     fn send_signing_returns_expired_without_keystore_access() {
         let session = SessionManager::new(Duration::from_millis(1), Duration::from_secs(90));
@@ -916,7 +1820,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn send_command_path_reads_plaintext_secret_row_before_injected_blockchain_failure() {
+    async fn send_command_path_reads_plaintext_secret_row_before_requiring_a_preview() {
         let wallet = insert_global_bitcoin_wallet_with_secret(StoredSecret {
             secret_data: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_string(),
             secret_format: SECRET_FORMAT_PLAINTEXT_V0.to_string(),
@@ -934,24 +1838,28 @@ mod tests {
             SendBitcoinRequest {
                 wallet_id: wallet.id.clone(),
                 to_address: "1BoatSLRHtKNngkdXEeobR76b53LETtpyT".to_string(),
-                amount: 0.0001,
+                amount_sats: 10_000,
                 fee_rate: None,
+                target_blocks: None,
                 send_all: None,
+                include_outpoints: None,
+                exclude_outpoints: None,
+                preview_txid: None,
             },
             &ready_secret_backend(),
             &keystore,
             &session,
-            || Err("injected electrum failure".to_string()),
+            |_, _| Err("blockchain should not be reached".to_string()),
         )
         .await;
 
         cleanup_global_bitcoin_wallet(&wallet.id);
 
-        assert!(matches!(result, Err(message) if message == "injected electrum failure"));
+        assert_eq!(result.unwrap_err(), "Preview the send before sending it");
     }
 
     #[tokio::test]
-    async fn send_command_path_reads_migrated_secret_row_before_injected_blockchain_failure() {
+    async fn send_command_path_reads_migrated_secret_row_before_requiring_a_preview() {
         let wallet = insert_global_bitcoin_wallet_with_secret(
             encrypt_secret(
                 "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
@@ -971,20 +1879,24 @@ mod tests {
             SendBitcoinRequest {
                 wallet_id: wallet.id.clone(),
                 to_address: "1BoatSLRHtKNngkdXEeobR76b53LETtpyT".to_string(),
-                amount: 0.0001,
+                amount_sats: 10_000,
                 fee_rate: None,
+                target_blocks: None,
                 send_all: None,
+                include_outpoints: None,
+                exclude_outpoints: None,
+                preview_txid: None,
             },
             &ready_secret_backend(),
             &keystore,
             &session,
-            || Err("injected electrum failure".to_string()),
+            |_, _| Err("blockchain should not be reached".to_string()),
         )
         .await;
 
         cleanup_global_bitcoin_wallet(&wallet.id);
 
-        assert!(matches!(result, Err(message) if message == "injected electrum failure"));
+        assert_eq!(result.unwrap_err(), "Preview the send before sending it");
     }
 
     #[tokio::test]
@@ -1009,14 +1921,18 @@ mod tests {
             SendBitcoinRequest {
                 wallet_id: wallet.id.clone(),
                 to_address: "mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn".to_string(),
-                amount: 0.0001,
+                amount_sats: 10_000,
                 fee_rate: None,
+                target_blocks: None,
                 send_all: None,
+                include_outpoints: None,
+                exclude_outpoints: None,
+                preview_txid: None,
             },
             &secret_backend,
             &keystore,
             &session,
-            || Err("blockchain should not be reached".to_string()),
+            |_, _| Err("blockchain should not be reached".to_string()),
         )
         .await;
 
@@ -1025,18 +1941,714 @@ mod tests {
         assert!(matches!(result, Err(message) if message.contains("Address network mismatch")));
     }
 
-    #[test]
-    fn history_fetch_log_harness_redacts_mnemonic_shape() {
-        let _ = take_test_log_lines();
-        let mnemonic_shape =
-            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
-        crate::safe_log!(
-            "[INFO] Fetching Bitcoin transaction history for wallet: {}",
-            mnemonic_shape
-        );
-        let output = take_test_log_lines().join("\n");
-
-        assert!(output.contains("[REDACTED_MNEMONIC]"));
+    #[tokio::test]
+    async fn send_command_path_rejects_unknown_preview_before_blockchain_connection() {
+        let wallet = insert_global_bitcoin_wallet_with_secret(StoredSecret {
+            secret_data: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_string(),
+            secret_format: SECRET_FORMAT_PLAINTEXT_V0.to_string(),
+        });
+        let secret_backend = ready_secret_backend();
+        let keystore = SqliteKeystore::new(
+            &DB,
+            Arc::new(SecretBackend::with_adapter(Arc::new(
+                TestSecretBackendAdapter,
+            ))),
+        );
+        let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
+        session
+            .authorize_verified_operation(SignerOperation::Send)
+            .unwrap();
+
+        let result = send_bitcoin_transaction_with_blockchain_factory(
+            SendBitcoinRequest {
+                wallet_id: wallet.id.clone(),
+                to_address: "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".to_string(),
+                amount_sats: 10_000,
+                fee_rate: None,
+                target_blocks: None,
+                send_all: None,
+                include_outpoints: None,
+                exclude_outpoints: None,
+                preview_txid: Some("ab".repeat(32)),
+            },
+            &secret_backend,
+            &keystore,
+            &session,
+            |_, _| Err("blockchain should not be reached".to_string()),
+        )
+        .await;
+
+        cleanup_global_bitcoin_wallet(&wallet.id);
+
+        assert!(matches!(result, Err(message) if message.contains("preview the send again")));
+    }
+
+    #[tokio::test]
+    async fn send_command_path_rejects_an_unpreviewed_send() {
+        let wallet = insert_global_bitcoin_wallet_with_secret(StoredSecret {
+            secret_data: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_string(),
+            secret_format: SECRET_FORMAT_PLAINTEXT_V0.to_string(),
+        });
+        let secret_backend = ready_secret_backend();
+        let keystore = SqliteKeystore::new(
+            &DB,
+            Arc::new(SecretBackend::with_adapter(Arc::new(
+                TestSecretBackendAdapter,
+            ))),
+        );
+        let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
+        session
+            .authorize_verified_operation(SignerOperation::Send)
+            .unwrap();
+
+        let result = send_bitcoin_transaction_with_blockchain_factory(
+            SendBitcoinRequest {
+                wallet_id: wallet.id.clone(),
+                to_address: "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".to_string(),
+                amount_sats: 10_000,
+                fee_rate: None,
+                target_blocks: None,
+                send_all: None,
+                include_outpoints: None,
+                exclude_outpoints: None,
+                preview_txid: None,
+            },
+            &secret_backend,
+            &keystore,
+            &session,
+            |_, _| Err("blockchain should not be reached".to_string()),
+        )
+        .await;
+
+        // The refused send still stores the descriptors a preview needs.
+        let descriptors = DB
+            .lock()
+            .unwrap()
+            .get_bitcoin_wallet_descriptors(&wallet.id)
+            .unwrap();
+        cleanup_global_bitcoin_wallet(&wallet.id);
+
+        assert_eq!(result.unwrap_err(), "Preview the send before sending it");
+        assert!(descriptors.is_some());
+    }
+
+    #[tokio::test]
+    async fn regtest_wallet_accepts_test_network_recipient() {
+        let wallet = insert_global_bitcoin_wallet_with_secret(StoredSecret {
+            secret_data: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_string(),
+            secret_format: SECRET_FORMAT_PLAINTEXT_V0.to_string(),
+        });
+        {
+            let db = DB.lock().unwrap();
+            db.set_bitcoin_wallet_network(&wallet.id, BitcoinNetwork::Regtest)
+                .unwrap();
+        }
+        let secret_backend = ready_secret_backend();
+        let keystore = SqliteKeystore::new(
+            &DB,
+            Arc::new(SecretBackend::with_adapter(Arc::new(
+                TestSecretBackendAdapter,
+            ))),
+        );
+        let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
+        session
+            .authorize_verified_operation(SignerOperation::Send)
+            .unwrap();
+
+        let result = send_bitcoin_transaction_with_blockchain_factory(
+            SendBitcoinRequest {
+                wallet_id: wallet.id.clone(),
+                to_address: "mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn".to_string(),
+                amount_sats: 10_000,
+                fee_rate: None,
+                target_blocks: None,
+                send_all: None,
+                include_outpoints: None,
+                exclude_outpoints: None,
+                preview_txid: None,
+            },
+            &secret_backend,
+            &keystore,
+            &session,
+            |_, _| Err("blockchain should not be reached".to_string()),
+        )
+        .await;
+
+        cleanup_global_bitcoin_wallet(&wallet.id);
+
+        // Past the address checks, so only the missing preview stops it.
+        assert_eq!(result.unwrap_err(), "Preview the send before sending it");
+    }
+
+    fn insert_global_bitcoin_send(wallet: &WalletInfo, status: TransactionStatus) -> String {
+        let tx_hash = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let db = DB.lock().unwrap();
+        db.add_bitcoin_transaction(&BitcoinTransaction {
+            id: Uuid::new_v4().to_string(),
+            wallet_id: wallet.id.clone(),
+            tx_hash: tx_hash.clone(),
+            tx_type: TransactionType::Send,
+            from_address: wallet.address.clone(),
+            to_address: "1BoatSLRHtKNngkdXEeobR76b53LETtpyT".to_string(),
+            amount_sats: 10_000,
+            fee_sats: 100,
+            amount: 0.0001,
+            fee: 0.000001,
+            status,
+            confirmations: 0,
+            block_height: None,
+            timestamp: "2026-04-18T00:00:00Z".to_string(),
+            created_at: "2026-04-18T00:00:00Z".to_string(),
+            replaces_tx_hash: None,
+            replaced_by_tx_hash: None,
+            parent_tx_hash: None,
+            batch_id: None,
+            output_index: 0,
+            label: None,
+        })
+        .unwrap();
+
+        tx_hash
+    }
+
+    #[tokio::test]
+    async fn bump_fee_rejects_confirmed_transaction_without_keystore_access() {
+        let wallet = insert_global_bitcoin_wallet_with_secret(StoredSecret {
+            secret_data: "seed words".to_string(),
+            secret_format: SECRET_FORMAT_PLAINTEXT_V0.to_string(),
+        });
+        let tx_hash = insert_global_bitcoin_send(&wallet, TransactionStatus::Confirmed);
+        let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
+        session
+            .authorize_verified_operation(SignerOperation::Send)
+            .unwrap();
+
+        let result = bump_bitcoin_transaction_fee_resolved(
+            tx_hash,
+            20.0,
+            &ready_secret_backend(),
+            &PanicKeystore,
+            &session,
+            |_, _| Err("blockchain should not be reached".to_string()),
+        )
+        .await;
+
+        cleanup_global_bitcoin_wallet(&wallet.id);
+
+        assert_eq!(result.unwrap_err(), "Transaction is already confirmed");
+    }
+
+    #[tokio::test]
+    async fn bump_fee_requires_send_reauth_without_keystore_access() {
+        let wallet = insert_global_bitcoin_wallet_with_secret(StoredSecret {
+            secret_data: "seed words".to_string(),
+            secret_format: SECRET_FORMAT_PLAINTEXT_V0.to_string(),
+        });
+        let tx_hash = insert_global_bitcoin_send(&wallet, TransactionStatus::Pending);
+        let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
+
+        let result = bump_bitcoin_transaction_fee_resolved(
+            tx_hash,
+            20.0,
+            &ready_secret_backend(),
+            &PanicKeystore,
+            &session,
+            |_, _| Err("blockchain should not be reached".to_string()),
+        )
+        .await;
+
+        cleanup_global_bitcoin_wallet(&wallet.id);
+
+        assert_eq!(result.unwrap_err(), "locked");
+    }
+
+    #[tokio::test]
+    async fn bump_fee_loads_signer_before_injected_blockchain_failure() {
+        let wallet = insert_global_bitcoin_wallet_with_secret(StoredSecret {
+            secret_data: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_string(),
+            secret_format: SECRET_FORMAT_PLAINTEXT_V0.to_string(),
+        });
+        let tx_hash = insert_global_bitcoin_send(&wallet, TransactionStatus::Broadcasted);
+        let keystore = SqliteKeystore::new(
+            &DB,
+            Arc::new(SecretBackend::with_adapter(Arc::new(
+                TestSecretBackendAdapter,
+            ))),
+        );
+        let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
+        session
+            .authorize_verified_operation(SignerOperation::Send)
+            .unwrap();
+
+        let result = bump_bitcoin_transaction_fee_resolved(
+            tx_hash.clone(),
+            20.0,
+            &ready_secret_backend(),
+            &keystore,
+            &session,
+            |_, _| Err("injected electrum failure".to_string()),
+        )
+        .await;
+
+        let original = {
+            let db = DB.lock().unwrap();
+            db.get_bitcoin_transaction_by_hash(&tx_hash)
+                .unwrap()
+                .unwrap()
+        };
+        cleanup_global_bitcoin_wallet(&wallet.id);
+
+        assert_eq!(result.unwrap_err(), "injected electrum failure");
+        assert_eq!(original.status, TransactionStatus::Broadcasted);
+        assert_eq!(original.replaced_by_tx_hash, None);
+    }
+
+    fn blockstream_parent(confirmed: bool, weight: u64, fee: u64) -> BlockstreamTx {
+        serde_json::from_value(serde_json::json!({
+            "txid": "f".repeat(64),
+            "version": 2,
+            "locktime": 0,
+            "vin": [],
+            "vout": [],
+            "size": weight / 4,
+            "weight": weight,
+            "fee": fee,
+            "status": {
+                "confirmed": confirmed,
+                "block_height": null,
+                "block_hash": null,
+                "block_time": null
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn cpfp_child_fee_covers_parent_shortfall() {
+        // 200 vB parent paying 1 sat/vB, 150 vB child, package target 10 sat/vB.
+        assert_eq!(cpfp_child_fee(10.0, 200, 200, 150), 3_300);
+        // Fractional targets round the package fee up.
+        assert_eq!(cpfp_child_fee(2.5, 141, 0, 1), 355);
+        // A parent that already overpays never yields a negative fee.
+        assert_eq!(cpfp_child_fee(1.0, 200, 10_000, 150), 0);
+    }
+
+    #[test]
+    fn cpfp_child_is_recorded_as_a_self_transfer() {
+        let wallet = test_wallet("mnemonic");
+        let record = cpfp_child_record(
+            &wallet,
+            "child",
+            "parent",
+            "bc1pdestination".to_string(),
+            9_000,
+            1_000,
+        );
+
+        assert_eq!(record.tx_type, TransactionType::SelfTransfer);
+        assert_eq!(record.parent_tx_hash.as_deref(), Some("parent"));
+        assert_eq!((record.amount_sats, record.fee_sats), (9_000, 1_000));
+    }
+
+    #[test]
+    fn cpfp_never_sweeps_a_frozen_parent_output() {
+        use crate::wallet::bitcoin::utxo::CoinControl;
+        use bdk::bitcoin::{OutPoint, ScriptBuf, TxOut, Txid};
+        use bdk::{KeychainKind, LocalUtxo};
+        use std::str::FromStr;
+
+        let parent_txid = Txid::from_str(&"ab".repeat(32)).unwrap();
+        let utxo = |txid: Txid, vout: u32| LocalUtxo {
+            outpoint: OutPoint::new(txid, vout),
+            txout: TxOut {
+                value: 10_000,
+                script_pubkey: ScriptBuf::new(),
+            },
+            keychain: KeychainKind::External,
+            is_spent: false,
+        };
+        let other = Txid::from_str(&"cd".repeat(32)).unwrap();
+        let unspent = vec![utxo(parent_txid, 0), utxo(parent_txid, 1), utxo(other, 0)];
+        let (allowed, frozen) = (OutPoint::new(parent_txid, 0), OutPoint::new(parent_txid, 1));
+
+        let coin_control = CoinControl::new(&[], &[], &[frozen.to_string()]).unwrap();
+        assert_eq!(
+            cpfp_parent_outputs(&unspent, parent_txid, &coin_control).unwrap(),
+            vec![allowed]
+        );
+
+        let all_frozen =
+            CoinControl::new(&[], &[], &[allowed.to_string(), frozen.to_string()]).unwrap();
+        assert!(cpfp_parent_outputs(&unspent, parent_txid, &all_frozen).is_err());
+        assert!(cpfp_parent_outputs(&unspent[2..], parent_txid, &coin_control).is_err());
+    }
+
+    #[tokio::test]
+    async fn accelerate_rejects_confirmed_parent_without_keystore_access() {
+        let wallet = insert_global_bitcoin_wallet_with_secret(StoredSecret {
+            secret_data: "seed words".to_string(),
+            secret_format: SECRET_FORMAT_PLAINTEXT_V0.to_string(),
+        });
+        let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
+        session
+            .authorize_verified_operation(SignerOperation::Send)
+            .unwrap();
+
+        let result = accelerate_incoming_transaction_resolved(
+            wallet.clone(),
+            blockstream_parent(true, 800, 200),
+            20.0,
+            &ready_secret_backend(),
+            &PanicKeystore,
+            &session,
+            |_, _| Err("blockchain should not be reached".to_string()),
+        )
+        .await;
+
+        cleanup_global_bitcoin_wallet(&wallet.id);
+
+        assert_eq!(result.unwrap_err(), "Transaction is already confirmed");
+    }
+
+    #[tokio::test]
+    async fn accelerate_rejects_parent_already_at_target_rate() {
+        let wallet = insert_global_bitcoin_wallet_with_secret(StoredSecret {
+            secret_data: "seed words".to_string(),
+            secret_format: SECRET_FORMAT_PLAINTEXT_V0.to_string(),
+        });
+        let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
+        session
+            .authorize_verified_operation(SignerOperation::Send)
+            .unwrap();
+
+        // 200 vB paying 4,000 sats is already 20 sat/vB.
+        let result = accelerate_incoming_transaction_resolved(
+            wallet.clone(),
+            blockstream_parent(false, 800, 4_000),
+            20.0,
+            &ready_secret_backend(),
+            &PanicKeystore,
+            &session,
+            |_, _| Err("blockchain should not be reached".to_string()),
+        )
+        .await;
+
+        cleanup_global_bitcoin_wallet(&wallet.id);
+
+        assert_eq!(
+            result.unwrap_err(),
+            "Transaction already pays at least 20 sat/vB"
+        );
+    }
+
+    #[tokio::test]
+    async fn accelerate_requires_send_reauth_without_keystore_access() {
+        let wallet = insert_global_bitcoin_wallet_with_secret(StoredSecret {
+            secret_data: "seed words".to_string(),
+            secret_format: SECRET_FORMAT_PLAINTEXT_V0.to_string(),
+        });
+        let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
+
+        let result = accelerate_incoming_transaction_resolved(
+            wallet.clone(),
+            blockstream_parent(false, 800, 200),
+            20.0,
+            &ready_secret_backend(),
+            &PanicKeystore,
+            &session,
+            |_, _| Err("blockchain should not be reached".to_string()),
+        )
+        .await;
+
+        cleanup_global_bitcoin_wallet(&wallet.id);
+
+        assert_eq!(result.unwrap_err(), "locked");
+    }
+
+    const OWN_ADDRESS: &str = "bc1qown";
+
+    /// An explorer transaction spending `inputs` into `outputs`, both as
+    /// (address, value) pairs.
+    fn explorer_tx(inputs: &[(&str, u64)], outputs: &[(&str, u64)], fee: u64) -> BlockstreamTx {
+        let txo = |(address, value): &(&str, u64)| serde_json::json!({ "scriptpubkey_address": address, "value": value });
+        serde_json::from_value(serde_json::json!({
+            "txid": "a".repeat(64),
+            "vin": inputs.iter().map(|input| serde_json::json!({ "prevout": txo(input) })).collect::<Vec<_>>(),
+            "vout": outputs.iter().map(txo).collect::<Vec<_>>(),
+            "weight": 800,
+            "fee": fee,
+            "status": { "confirmed": true, "block_height": 100, "block_time": 1_700_000_000 },
+        }))
+        .unwrap()
+    }
+
+    fn explorer_record(tx: &BlockstreamTx) -> BitcoinTransaction {
+        let entry = explorer_history_entry(tx, OWN_ADDRESS);
+        history_record("wallet-1", OWN_ADDRESS, &entry, 105)
+    }
+
+    #[test]
+    fn fee_bump_of_a_batch_keeps_one_row_per_recipient() {
+        let output = |output_index: u32, to_address: &str, amount_sats: u64| BitcoinTransaction {
+            to_address: to_address.to_string(),
+            amount_sats,
+            fee_sats: if output_index == 0 { 500 } else { 0 },
+            batch_id: Some("batch-1".to_string()),
+            output_index,
+            ..explorer_record(&explorer_tx(&[(OWN_ADDRESS, 100_000)], &[], 500))
+        };
+        let batch_rows = vec![output(1, "bc1qbob", 20_000), output(0, "bc1qalice", 30_000)];
+
+        // The replacement put change first and swapped the recipients.
+        let replacement_outputs = vec![
+            (Some("bc1qchange".to_string()), 40_000),
+            (Some("bc1qbob".to_string()), 20_000),
+            (Some("bc1qalice".to_string()), 30_000),
+        ];
+
+        let records = fee_bump_records(
+            &batch_rows[1],
+            &batch_rows,
+            &replacement_outputs,
+            "replacement",
+            50_000,
+            2_000,
+        );
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].to_address, "bc1qalice");
+        assert_eq!(records[0].amount_sats, 30_000);
+        assert_eq!(records[0].fee_sats, 2_000);
+        assert_eq!(records[1].to_address, "bc1qbob");
+        assert_eq!(records[1].amount_sats, 20_000);
+        assert_eq!(records[1].fee_sats, 0);
+        assert_eq!((records[0].output_index, records[1].output_index), (2, 1));
+        for record in &records {
+            assert_eq!(record.tx_hash, "replacement");
+            assert_eq!(record.batch_id.as_deref(), Some("batch-1"));
+            assert_eq!(record.replaces_tx_hash, Some(batch_rows[1].tx_hash.clone()));
+        }
+
+        let single = fee_bump_records(&batch_rows[1], &[], &[], "replacement", 50_000, 2_000);
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].amount_sats, 50_000);
+    }
+
+    #[test]
+    fn history_send_records_net_amount_and_recipients() {
+        let tx = explorer_tx(
+            &[(OWN_ADDRESS, 100_000)],
+            &[
+                ("bc1qalice", 30_000),
+                ("bc1qbob", 20_000),
+                (OWN_ADDRESS, 49_000),
+            ],
+            1_000,
+        );
+
+        let record = explorer_record(&tx);
+
+        assert_eq!(record.tx_type, TransactionType::Send);
+        assert_eq!(record.amount_sats, 50_000);
+        assert_eq!(record.fee_sats, 1_000);
+        assert_eq!(record.from_address, OWN_ADDRESS);
+        assert_eq!(record.to_address, "bc1qalice, bc1qbob");
+    }
+
+    #[test]
+    fn history_receive_records_sender_and_no_fee() {
+        let tx = explorer_tx(
+            &[("bc1qsender", 80_000), ("bc1qsender", 20_000)],
+            &[(OWN_ADDRESS, 60_000), ("bc1qsender", 39_500)],
+            500,
+        );
+
+        let record = explorer_record(&tx);
+
+        assert_eq!(record.tx_type, TransactionType::Receive);
+        assert_eq!(record.amount_sats, 60_000);
+        assert_eq!(record.fee_sats, 0);
+        assert_eq!(record.from_address, "bc1qsender");
+        assert_eq!(record.to_address, OWN_ADDRESS);
+    }
+
+    #[test]
+    fn history_detects_self_transfers_and_consolidations() {
+        let self_transfer = explorer_tx(&[(OWN_ADDRESS, 50_000)], &[(OWN_ADDRESS, 49_800)], 200);
+        let record = explorer_record(&self_transfer);
+        assert_eq!(record.tx_type, TransactionType::SelfTransfer);
+        assert_eq!(record.amount_sats, 49_800);
+        assert_eq!(record.fee_sats, 200);
+        assert_eq!(record.to_address, OWN_ADDRESS);
+
+        let consolidation = explorer_tx(
+            &[
+                (OWN_ADDRESS, 10_000),
+                (OWN_ADDRESS, 20_000),
+                (OWN_ADDRESS, 30_000),
+            ],
+            &[(OWN_ADDRESS, 59_400)],
+            600,
+        );
+        let record = explorer_record(&consolidation);
+        assert_eq!(record.tx_type, TransactionType::Consolidation);
+        assert_eq!(record.amount_sats, 59_400);
+        assert_eq!(record.fee_sats, 600);
+    }
+
+    #[test]
+    fn history_fetch_log_harness_redacts_mnemonic_shape() {
+        let _ = take_test_log_lines();
+        let mnemonic_shape =
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        crate::safe_log!(
+            "[INFO] Fetching Bitcoin transaction history for wallet: {}",
+            mnemonic_shape
+        );
+        let output = take_test_log_lines().join("\n");
+
+        assert!(output.contains("[REDACTED_MNEMONIC]"));
         assert!(!output.contains(mnemonic_shape));
     }
+
+    #[test]
+    fn fee_bump_never_selects_a_frozen_coin() {
+        use crate::wallet::bitcoin::descriptor::{
+            account_descriptors_from_mnemonic, BitcoinScriptType,
+        };
+        use crate::wallet::bitcoin::utxo::CoinControl;
+        use bdk::bitcoin::absolute::LockTime;
+        use bdk::bitcoin::{
+            OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Txid, Witness,
+        };
+        use bdk::database::{BatchOperations, Database as _, MemoryDatabase};
+        use bdk::wallet::AddressIndex;
+        use bdk::{BlockTime, FeeRate, KeychainKind, LocalUtxo, TransactionDetails, Wallet};
+        use std::str::FromStr;
+
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let descriptors = account_descriptors_from_mnemonic(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            BitcoinScriptType::P2wpkh,
+            BitcoinNetwork::Testnet,
+            &secp,
+        )
+        .unwrap()
+        .public;
+        let open_wallet = |database: MemoryDatabase| {
+            Wallet::new(
+                descriptors.external.as_str(),
+                descriptors.internal.as_deref(),
+                BitcoinNetwork::Testnet.bdk_network(),
+                database,
+            )
+            .unwrap()
+        };
+        let peek = |index: u32| {
+            open_wallet(MemoryDatabase::default())
+                .get_address(AddressIndex::Peek(index))
+                .unwrap()
+                .address
+        };
+        let (receive, recipient) = (peek(0), peek(50));
+
+        // Confirmed coins of 20k (spent by the original), 50k (frozen) and 30k
+        let funding: Vec<Transaction> = [20_000, 50_000, 30_000]
+            .into_iter()
+            .enumerate()
+            .map(|(vout, value)| Transaction {
+                version: 2,
+                lock_time: LockTime::ZERO,
+                input: vec![TxIn {
+                    previous_output: OutPoint::new(
+                        Txid::from_str(&"11".repeat(32)).unwrap(),
+                        vout as u32,
+                    ),
+                    script_sig: ScriptBuf::new(),
+                    sequence: Sequence::MAX,
+                    witness: Witness::new(),
+                }],
+                output: vec![TxOut {
+                    value,
+                    script_pubkey: receive.script_pubkey(),
+                }],
+            })
+            .collect();
+        let outpoints: Vec<OutPoint> = funding
+            .iter()
+            .map(|tx| OutPoint::new(tx.txid(), 0))
+            .collect();
+        let (spent, frozen, allowed) = (outpoints[0], outpoints[1], outpoints[2]);
+        let funded_database = || {
+            let mut database = MemoryDatabase::default();
+            for tx in &funding {
+                database
+                    .set_tx(&TransactionDetails {
+                        transaction: Some(tx.clone()),
+                        txid: tx.txid(),
+                        received: tx.output[0].value,
+                        sent: 0,
+                        fee: Some(0),
+                        confirmation_time: Some(BlockTime {
+                            height: 100,
+                            timestamp: 1_700_000_000,
+                        }),
+                    })
+                    .unwrap();
+                database
+                    .set_utxo(&LocalUtxo {
+                        outpoint: OutPoint::new(tx.txid(), 0),
+                        txout: tx.output[0].clone(),
+                        keychain: KeychainKind::External,
+                        is_spent: false,
+                    })
+                    .unwrap();
+            }
+            database
+        };
+
+        // The original send spends only the 20k coin and pays 15k out
+        let (psbt, details) = {
+            let wallet = open_wallet(funded_database());
+            let mut tx_builder = wallet.build_tx();
+            tx_builder
+                .add_recipient(recipient.script_pubkey(), 15_000)
+                .add_utxo(spent)
+                .unwrap()
+                .manually_selected_only()
+                .fee_rate(FeeRate::from_sat_per_vb(1.0))
+                .enable_rbf();
+            tx_builder.finish().unwrap()
+        };
+        let original = psbt.unsigned_tx;
+        let mut database = funded_database();
+        database
+            .set_tx(&TransactionDetails {
+                transaction: Some(original.clone()),
+                ..details
+            })
+            .unwrap();
+        let mut spent_utxo = database.get_utxo(&spent).unwrap().unwrap();
+        spent_utxo.is_spent = true;
+        database.set_utxo(&spent_utxo).unwrap();
+        let wallet = open_wallet(database);
+        wallet.get_address(AddressIndex::New).unwrap();
+        wallet.get_internal_address(AddressIndex::New).unwrap();
+
+        let coin_control = CoinControl::new(&[], &[], &[frozen.to_string()]).unwrap();
+        let (psbt, _) = build_fee_bump_psbt(&wallet, original.txid(), 60.0, &coin_control).unwrap();
+        let inputs: Vec<OutPoint> = psbt
+            .unsigned_tx
+            .input
+            .iter()
+            .map(|input| input.previous_output)
+            .collect();
+        assert!(inputs.contains(&spent));
+        assert!(inputs.contains(&allowed));
+        assert!(!inputs.contains(&frozen));
+
+        let only_frozen_left =
+            CoinControl::new(&[], &[allowed.to_string()], &[frozen.to_string()]).unwrap();
+        assert!(build_fee_bump_psbt(&wallet, original.txid(), 60.0, &only_frozen_left).is_err());
+    }
 }
@@ -0,0 +1,373 @@
+use crate::wallet::bitcoin::backend::{self, EsploraUtxo};
+use crate::wallet::bitcoin::config::BitcoinNetwork;
+use crate::wallet::bitcoin::local_state::{self, LocalWalletSnapshot};
+use crate::wallet::types::{
+    BitcoinAddressInfo, BitcoinLabelType, BitcoinUtxo, BitcoinUtxoMetadata, WalletInfo,
+};
+use crate::DB;
+use bdk::bitcoin::OutPoint;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+/// Lists the unspent outputs of a wallet, merged with the labels and freeze
+/// flags stored locally. Descriptor wallets are read from the local BDK state;
+/// others query every known address on the configured backends.
+pub async fn list_wallet_utxos(wallet: &WalletInfo) -> Result<Vec<BitcoinUtxo>, String> {
+    let (network, addresses, metadata, labels) = {
+        let db = DB.lock().unwrap();
+        let network = db
+            .get_bitcoin_wallet_network(&wallet.id)
+            .map_err(|e| format!("Failed to get wallet network: {}", e))?;
+        let addresses = db
+            .get_bitcoin_wallet_addresses(&wallet.id)
+            .map_err(|e| format!("Failed to get wallet addresses: {}", e))?;
+        let metadata = db
+            .get_bitcoin_utxo_metadata(&wallet.id)
+            .map_err(|e| format!("Failed to get UTXO metadata: {}", e))?;
+        let labels = db
+            .get_bitcoin_labels(&wallet.id)
+            .map_err(|e| format!("Failed to get wallet labels: {}", e))?;
+        (network, addresses, metadata, labels)
+    };
+
+    let metadata: HashMap<String, BitcoinUtxoMetadata> = metadata
+        .into_iter()
+        .map(|row| (row.outpoint.clone(), row))
+        .collect();
+
+    let mut utxos = match local_state::load_local_wallet(&wallet.id, false).await? {
+        Some(snapshot) => local_wallet_utxos(&snapshot, &metadata),
+        None => backend_utxos(wallet, network, addresses, &metadata).await?,
+    };
+    utxos.sort_by_key(|utxo| std::cmp::Reverse(utxo.value_sats));
+
+    let address_labels: HashMap<String, String> = labels
+        .into_iter()
+        .filter(|label| label.label_type == BitcoinLabelType::Addr)
+        .map(|label| (label.reference, label.label))
+        .collect();
+    for utxo in &mut utxos {
+        utxo.address_label = address_labels.get(&utxo.address).cloned();
+    }
+
+    crate::safe_log!(
+        "[INFO] Listed {} UTXOs for wallet {}",
+        utxos.len(),
+        wallet.id
+    );
+
+    Ok(utxos)
+}
+
+async fn backend_utxos(
+    wallet: &WalletInfo,
+    network: BitcoinNetwork,
+    addresses: Vec<BitcoinAddressInfo>,
+    metadata: &HashMap<String, BitcoinUtxoMetadata>,
+) -> Result<Vec<BitcoinUtxo>, String> {
+    // Addresses never seen on chain cannot hold coins.
+    let mut candidates = vec![wallet.address.clone()];
+    for address in addresses {
+        if address.tx_count > 0 && !candidates.contains(&address.address) {
+            candidates.push(address.address);
+        }
+    }
+
+    let tip_height = backend::tip_height(network).await?;
+    let mut utxos = Vec::new();
+    for address in candidates {
+        let entries = backend::address_utxos(&address, network).await?;
+        utxos.extend(to_wallet_utxos(&address, entries, tip_height, metadata));
+    }
+
+    Ok(utxos)
+}
+
+fn local_wallet_utxos(
+    snapshot: &LocalWalletSnapshot,
+    metadata: &HashMap<String, BitcoinUtxoMetadata>,
+) -> Vec<BitcoinUtxo> {
+    snapshot
+        .utxos
+        .iter()
+        .map(|utxo| {
+            let confirmations = snapshot
+                .confirmation_height(&utxo.outpoint.txid)
+                .map_or(0, |height| snapshot.tip_height.saturating_sub(height) + 1);
+            let address = snapshot
+                .address_of(&utxo.txout.script_pubkey)
+                .unwrap_or_default();
+
+            with_metadata(
+                utxo.outpoint.to_string(),
+                utxo.txout.value,
+                confirmations,
+                address,
+                metadata,
+            )
+        })
+        .collect()
+}
+
+fn to_wallet_utxos(
+    address: &str,
+    entries: Vec<EsploraUtxo>,
+    tip_height: u32,
+    metadata: &HashMap<String, BitcoinUtxoMetadata>,
+) -> Vec<BitcoinUtxo> {
+    entries
+        .into_iter()
+        .map(|entry| {
+            let outpoint = format!("{}:{}", entry.txid, entry.vout);
+            let confirmations = match (entry.status.confirmed, entry.status.block_height) {
+                (true, Some(height)) => tip_height.saturating_sub(height) + 1,
+                _ => 0,
+            };
+
+            with_metadata(
+                outpoint,
+                entry.value,
+                confirmations,
+                address.to_string(),
+                metadata,
+            )
+        })
+        .collect()
+}
+
+fn with_metadata(
+    outpoint: String,
+    value_sats: u64,
+    confirmations: u32,
+    address: String,
+    metadata: &HashMap<String, BitcoinUtxoMetadata>,
+) -> BitcoinUtxo {
+    let meta = metadata.get(&outpoint);
+
+    BitcoinUtxo {
+        value_sats,
+        confirmations,
+        address,
+        label: meta.and_then(|meta| meta.label.clone()),
+        address_label: None,
+        frozen: meta.is_some_and(|meta| meta.frozen),
+        outpoint,
+    }
+}
+
+/// Parses `txid:vout` into its canonical form.
+pub fn normalize_outpoint(outpoint: &str) -> Result<String, String> {
+    parse_outpoint(outpoint).map(|outpoint| outpoint.to_string())
+}
+
+fn parse_outpoint(outpoint: &str) -> Result<OutPoint, String> {
+    OutPoint::from_str(outpoint.trim())
+        .map_err(|e| format!("Invalid outpoint '{}': {}", outpoint, e))
+}
+
+/// Which coins a send may spend: an explicit include list, or everything that
+/// is neither excluded by the request nor frozen.
+#[derive(Debug)]
+pub(crate) struct CoinControl {
+    include: Vec<OutPoint>,
+    unspendable: HashSet<OutPoint>,
+}
+
+impl CoinControl {
+    /// Explicitly included coins are spent even when frozen, since the user chose them.
+    pub(crate) fn new(
+        include: &[String],
+        exclude: &[String],
+        frozen: &[String],
+    ) -> Result<Self, String> {
+        let include = include
+            .iter()
+            .map(|outpoint| parse_outpoint(outpoint))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut unspendable = exclude
+            .iter()
+            .map(|outpoint| parse_outpoint(outpoint))
+            .collect::<Result<HashSet<_>, _>>()?;
+
+        if let Some(conflict) = include
+            .iter()
+            .find(|outpoint| unspendable.contains(outpoint))
+        {
+            return Err(format!(
+                "Outpoint {} is both included and excluded",
+                conflict
+            ));
+        }
+
+        for outpoint in frozen {
+            // A malformed stored row must not make the whole wallet unspendable.
+            if let Ok(outpoint) = parse_outpoint(outpoint) {
+                if !include.contains(&outpoint) {
+                    unspendable.insert(outpoint);
+                }
+            }
+        }
+
+        Ok(Self {
+            include,
+            unspendable,
+        })
+    }
+
+    pub(crate) fn include(&self) -> &[OutPoint] {
+        &self.include
+    }
+
+    pub(crate) fn is_manual(&self) -> bool {
+        !self.include.is_empty()
+    }
+
+    pub(crate) fn unspendable(&self) -> Vec<OutPoint> {
+        self.unspendable.iter().copied().collect()
+    }
+
+    pub(crate) fn allows(&self, outpoint: &OutPoint) -> bool {
+        if self.is_manual() {
+            self.include.contains(outpoint)
+        } else {
+            !self.unspendable.contains(outpoint)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{local_wallet_utxos, normalize_outpoint, to_wallet_utxos, CoinControl};
+    use crate::wallet::bitcoin::backend::EsploraUtxo;
+    use crate::wallet::bitcoin::config::BitcoinNetwork;
+    use crate::wallet::bitcoin::local_state::LocalWalletSnapshot;
+    use crate::wallet::types::BitcoinUtxoMetadata;
+    use bdk::bitcoin::{Address, OutPoint, ScriptBuf, TxOut, Txid};
+    use bdk::{BlockTime, KeychainKind, LocalUtxo, TransactionDetails};
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    fn outpoint(byte: &str, vout: u32) -> String {
+        format!("{}:{}", byte.repeat(32), vout)
+    }
+
+    #[test]
+    fn utxos_merge_confirmations_and_local_metadata() {
+        let entries: Vec<EsploraUtxo> = serde_json::from_value(serde_json::json!([
+            {
+                "txid": "aa".repeat(32),
+                "vout": 0,
+                "value": 50_000,
+                "status": { "confirmed": true, "block_height": 100 }
+            },
+            {
+                "txid": "bb".repeat(32),
+                "vout": 1,
+                "value": 1_000,
+                "status": { "confirmed": false }
+            }
+        ]))
+        .unwrap();
+        let metadata = HashMap::from([(
+            outpoint("aa", 0),
+            BitcoinUtxoMetadata {
+                wallet_id: "wallet-1".to_string(),
+                outpoint: outpoint("aa", 0),
+                label: Some("exchange withdrawal".to_string()),
+                origin: None,
+                frozen: true,
+                updated_at: "2026-04-18T00:00:00Z".to_string(),
+            },
+        )]);
+
+        let utxos = to_wallet_utxos("bc1qexample", entries, 105, &metadata);
+
+        assert_eq!(utxos[0].confirmations, 6);
+        assert_eq!(utxos[0].label.as_deref(), Some("exchange withdrawal"));
+        assert!(utxos[0].frozen);
+        assert_eq!(utxos[1].outpoint, outpoint("bb", 1));
+        assert_eq!(utxos[1].confirmations, 0);
+        assert!(!utxos[1].frozen);
+    }
+
+    #[test]
+    fn local_utxos_use_synced_tip_and_wallet_addresses() {
+        let address = Address::p2wsh(
+            &ScriptBuf::from(vec![0x51]),
+            BitcoinNetwork::Regtest.bdk_network(),
+        );
+        let txid = Txid::from_str(&"cc".repeat(32)).unwrap();
+        let snapshot = LocalWalletSnapshot {
+            network: BitcoinNetwork::Regtest,
+            tip_height: 209,
+            transactions: vec![TransactionDetails {
+                transaction: None,
+                txid,
+                received: 7_500,
+                sent: 0,
+                fee: None,
+                confirmation_time: Some(BlockTime {
+                    height: 200,
+                    timestamp: 1_700_000_000,
+                }),
+            }],
+            utxos: vec![LocalUtxo {
+                outpoint: OutPoint::new(txid, 1),
+                txout: TxOut {
+                    value: 7_500,
+                    script_pubkey: address.script_pubkey(),
+                },
+                keychain: KeychainKind::External,
+                is_spent: false,
+            }],
+            addresses: Vec::new(),
+            sync_error: None,
+        };
+
+        let utxos = local_wallet_utxos(&snapshot, &HashMap::new());
+
+        assert_eq!(utxos.len(), 1);
+        assert_eq!(utxos[0].outpoint, outpoint("cc", 1));
+        assert_eq!(utxos[0].address, address.to_string());
+        assert_eq!(utxos[0].confirmations, 10);
+        assert_eq!(snapshot.balance_sats(), 7_500);
+    }
+
+    #[test]
+    fn coin_control_skips_frozen_and_excluded_coins() {
+        let control = CoinControl::new(&[], &[outpoint("aa", 0)], &[outpoint("bb", 0)]).unwrap();
+
+        assert!(!control.is_manual());
+        assert!(!control.allows(&OutPoint::from_str(&outpoint("aa", 0)).unwrap()));
+        assert!(!control.allows(&OutPoint::from_str(&outpoint("bb", 0)).unwrap()));
+        assert!(control.allows(&OutPoint::from_str(&outpoint("cc", 0)).unwrap()));
+        assert_eq!(control.unspendable().len(), 2);
+    }
+
+    #[test]
+    fn coin_control_include_list_overrides_freeze() {
+        let control = CoinControl::new(&[outpoint("bb", 0)], &[], &[outpoint("bb", 0)]).unwrap();
+
+        assert!(control.is_manual());
+        assert!(control.allows(&OutPoint::from_str(&outpoint("bb", 0)).unwrap()));
+        assert!(!control.allows(&OutPoint::from_str(&outpoint("cc", 0)).unwrap()));
+        assert!(control.unspendable().is_empty());
+    }
+
+    #[test]
+    fn coin_control_rejects_conflicting_and_malformed_outpoints() {
+        assert_eq!(
+            CoinControl::new(&[outpoint("aa", 0)], &[outpoint("aa", 0)], &[]).unwrap_err(),
+            format!(
+                "Outpoint {} is both included and excluded",
+                outpoint("aa", 0)
+            )
+        );
+        assert!(CoinControl::new(&["not-an-outpoint".to_string()], &[], &[]).is_err());
+        assert!(normalize_outpoint(" nope ").is_err());
+        assert_eq!(
+            normalize_outpoint(&format!(" {} ", outpoint("AB", 2))).unwrap(),
+            outpoint("ab", 2)
+        );
+    }
+}
@@ -1,14 +1,18 @@
+use crate::db::BitcoinWalletSetup;
+use crate::wallet::bitcoin::config::BitcoinNetwork;
+use crate::wallet::bitcoin::descriptor::{
+    account_descriptors_from_mnemonic, derive_address, watch_only_descriptors, BitcoinScriptType,
+};
+use crate::wallet::bitcoin::multisig::{local_cosigner, multisig_descriptors, MultisigPolicy};
 use crate::wallet::bitcoin::private_key::map_security_error;
 use crate::wallet::security::backend::SecretBackend;
 use crate::wallet::security::commands::ensure_local_password_configured;
 use crate::wallet::security::commands::AppSecurity;
+use crate::wallet::security::mnemonic_secret::MnemonicSecret;
 use crate::wallet::security::secret_envelope::StoredSecret;
-use crate::wallet::types::CreateWalletResponse;
+use crate::wallet::types::{CreateBitcoinMultisigWalletRequest, CreateWalletResponse, WalletInfo};
 use crate::DB;
 use bip39::{Language, Mnemonic};
-use bitcoin::bip32::{DerivationPath, Xpriv};
-use bitcoin::Network;
-use std::str::FromStr;
 
 fn prepare_mnemonic_secret(
     secret_backend: &SecretBackend,
@@ -22,8 +26,11 @@ fn prepare_mnemonic_secret(
 #[tauri::command]
 pub fn bitcoin_create_wallet_from_mnemonic(
     mnemonic_phrase: String,
+    passphrase: Option<String>,
     wallet_label: Option<String>,
     reveal_secret: Option<bool>,
+    script_type: Option<BitcoinScriptType>,
+    network: Option<BitcoinNetwork>,
     state: tauri::State<'_, AppSecurity>,
 ) -> Result<CreateWalletResponse, String> {
     ensure_local_password_configured().map_err(map_security_error)?;
@@ -31,33 +38,18 @@ pub fn bitcoin_create_wallet_from_mnemonic(
     // Validate and parse mnemonic
     let mnemonic = Mnemonic::parse_in_normalized(Language::English, &mnemonic_phrase)
         .map_err(|e| format!("Invalid mnemonic: {}", e))?;
-
-    // Generate seed from mnemonic
-    let seed = mnemonic.to_seed("");
-
-    // Create master private key from seed
+    // The optional BIP39 passphrase is kept with the phrase in the same secret
+    let mnemonic_secret = MnemonicSecret::new(&mnemonic.to_string(), passphrase.as_deref());
+    let encoded_secret = mnemonic_secret.encode();
+
+    // Account descriptors for the chosen script type (BIP86 Taproot unless specified);
+    // the primary address is the first receive index, e.g. m/86'/0'/0'/0/0
+    let script_type = script_type.unwrap_or_default();
+    let network = network.unwrap_or_default();
     let secp = bitcoin::secp256k1::Secp256k1::new();
-    let master_xprv = Xpriv::new_master(Network::Bitcoin, &seed)
-        .map_err(|e| format!("Failed to create master key: {}", e))?;
-
-    // Standard BIP86 derivation path for Taproot: m/86'/0'/0'/0/0
-    let derivation_path = DerivationPath::from_str("m/86'/0'/0'/0/0")
-        .map_err(|e| format!("Invalid derivation path: {}", e))?;
-
-    let child_xprv = master_xprv
-        .derive_priv(&secp, &derivation_path)
-        .map_err(|e| format!("Failed to derive child key: {}", e))?;
-
-    let private_key = child_xprv.to_priv();
-    let public_key = private_key.public_key(&secp);
-
-    // Convert to XOnlyPublicKey for Taproot (P2TR)
-    let xonly_public_key = bitcoin::key::XOnlyPublicKey::from(public_key);
-
-    // Generate Taproot address (bc1p...)
-    let address = bitcoin::Address::p2tr(&secp, xonly_public_key, None, Network::Bitcoin);
-
-    let address_str = address.to_string();
+    let descriptors =
+        account_descriptors_from_mnemonic(&encoded_secret, script_type, network, &secp)?;
+    let address_str = derive_address(&descriptors.public.external, 0, network)?;
     let label = wallet_label.unwrap_or_else(|| "Bitcoin Wallet".to_string());
     let has_existing_secrets = {
         let db = DB.lock().unwrap();
@@ -70,17 +62,24 @@ pub fn bitcoin_create_wallet_from_mnemonic(
             .initialize_for_empty_store()
             .map_err(map_security_error)?;
     }
-    let stored_secret = prepare_mnemonic_secret(state.secret_backend(), &mnemonic.to_string())?;
+    let stored_secret = prepare_mnemonic_secret(state.secret_backend(), &encoded_secret)?;
+
+    let setup = BitcoinWalletSetup {
+        script_type,
+        network,
+        descriptors: descriptors.public,
+        multisig: None,
+    };
 
     // Store wallet in database
     let db = DB.lock().unwrap();
     let wallet = db
-        .insert_bitcoin_wallet_with_secret(
+        .insert_bitcoin_wallet_with_setup(
             label,
             "mnemonic".to_string(),
             address_str,
-            stored_secret,
-            "mnemonic".to_string(),
+            Some((stored_secret, "mnemonic".to_string())),
+            &setup,
         )
         .map_err(|e| format!("Failed to save wallet: {}", e))?;
 
@@ -89,7 +88,7 @@ pub fn bitcoin_create_wallet_from_mnemonic(
     if reveal_secret.unwrap_or(false) {
         Ok(CreateWalletResponse::with_revealed_secret(
             wallet,
-            mnemonic.to_string(),
+            mnemonic_secret.phrase,
             "mnemonic",
         ))
     } else {
@@ -97,6 +96,119 @@ pub fn bitcoin_create_wallet_from_mnemonic(
     }
 }
 
+/// Import an xpub/ypub/zpub or output descriptor as a wallet that can track
+/// balances and history but never sign.
+#[tauri::command]
+pub fn bitcoin_import_watch_only_wallet(
+    source: String,
+    wallet_label: Option<String>,
+    script_type: Option<BitcoinScriptType>,
+    network: Option<BitcoinNetwork>,
+) -> Result<WalletInfo, String> {
+    let network = network.unwrap_or_default();
+    let (descriptors, script_type) = watch_only_descriptors(&source, script_type, network)?;
+    let address_str = derive_address(&descriptors.external, 0, network)?;
+    let label = wallet_label.unwrap_or_else(|| "Watch-only Wallet".to_string());
+
+    let setup = BitcoinWalletSetup {
+        script_type,
+        network,
+        descriptors,
+        multisig: None,
+    };
+
+    let db = DB.lock().unwrap();
+    db.insert_bitcoin_wallet_with_setup(label, "watch-only".to_string(), address_str, None, &setup)
+        .map_err(|e| format!("Failed to save wallet: {}", e))
+}
+
+/// Create a multisig wallet from cosigner account xpubs. With a mnemonic this
+/// device holds one of the keys and can co-sign; without one the wallet is
+/// watch-only and only builds, combines and broadcasts PSBTs.
+#[tauri::command]
+pub fn bitcoin_create_multisig_wallet(
+    request: CreateBitcoinMultisigWalletRequest,
+    state: tauri::State<'_, AppSecurity>,
+) -> Result<WalletInfo, String> {
+    let CreateBitcoinMultisigWalletRequest {
+        cosigner_keys,
+        threshold,
+        mnemonic_phrase,
+        passphrase,
+        wallet_label,
+        script_type,
+        network,
+    } = request;
+    let script_type = script_type.unwrap_or_default();
+    let network = network.unwrap_or_default();
+    let label = wallet_label.unwrap_or_else(|| "Multisig Wallet".to_string());
+
+    let local_secret = match mnemonic_phrase {
+        Some(mnemonic_phrase) => {
+            ensure_local_password_configured().map_err(map_security_error)?;
+            let mnemonic = Mnemonic::parse_in_normalized(Language::English, &mnemonic_phrase)
+                .map_err(|e| format!("Invalid mnemonic: {}", e))?;
+            Some(MnemonicSecret::new(&mnemonic.to_string(), passphrase.as_deref()).encode())
+        }
+        None => None,
+    };
+
+    let mut keys = cosigner_keys;
+    if let Some(secret) = &local_secret {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        keys.push(local_cosigner(secret, script_type, network, &secp)?.public);
+    }
+    let descriptors = multisig_descriptors(&keys, threshold, script_type, network)?;
+    let policy = MultisigPolicy {
+        threshold,
+        cosigners: keys.len() as u32,
+        script_type,
+    };
+    let address_str = derive_address(&descriptors.external, 0, network)?;
+
+    let stored_secret = match local_secret {
+        Some(secret) => {
+            let has_existing_secrets = {
+                let db = DB.lock().unwrap();
+                db.has_any_wallet_secret_rows()
+                    .map_err(|e| format!("Failed to inspect existing wallet secrets: {}", e))?
+            };
+            if !has_existing_secrets {
+                state
+                    .secret_backend()
+                    .initialize_for_empty_store()
+                    .map_err(map_security_error)?;
+            }
+            Some(prepare_mnemonic_secret(state.secret_backend(), &secret)?)
+        }
+        None => None,
+    };
+    let wallet_type = if stored_secret.is_some() {
+        "multisig"
+    } else {
+        "watch-only"
+    };
+    let setup = BitcoinWalletSetup {
+        script_type: script_type.wallet_script_type(),
+        network,
+        descriptors,
+        multisig: Some(policy),
+    };
+
+    let db = DB.lock().unwrap();
+    let wallet = db
+        .insert_bitcoin_wallet_with_setup(
+            label,
+            wallet_type.to_string(),
+            address_str,
+            stored_secret.map(|secret| (secret, "mnemonic".to_string())),
+            &setup,
+        )
+        .map_err(|e| format!("Failed to save wallet: {}", e))?;
+
+    Ok(wallet)
+}
+
 #[cfg(test)]
 mod tests {
     use super::prepare_mnemonic_secret;
@@ -0,0 +1,105 @@
+use bdk::database::SqliteDatabase;
+use bdk::{Error, Wallet};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+/// Directory holding one BDK SQLite file per Bitcoin wallet, so syncs are
+/// incremental across sends and app restarts.
+#[cfg(not(test))]
+fn store_dir() -> PathBuf {
+    crate::bdk_store_dir()
+}
+
+#[cfg(test)]
+fn store_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("aiigo-bdk-test-{}", std::process::id()))
+}
+
+fn wallet_path(wallet_id: &str) -> PathBuf {
+    store_dir().join(format!("{}.db", wallet_id))
+}
+
+fn remove_if_exists(result: std::io::Result<()>) -> std::io::Result<()> {
+    match result {
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+/// Drops the stored BDK state of one wallet, e.g. when it is deleted or its
+/// descriptors no longer match what was synced.
+pub fn clear_wallet_state(wallet_id: &str) -> Result<(), String> {
+    let path = wallet_path(wallet_id);
+    // SQLite keeps uncheckpointed WAL writes next to the database file.
+    for suffix in ["", "-wal", "-shm"] {
+        let mut file = path.clone().into_os_string();
+        file.push(suffix);
+        remove_if_exists(fs::remove_file(file))
+            .map_err(|e| format!("Failed to clear wallet state: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Drops the stored BDK state of every wallet.
+pub fn clear_all_wallet_state() -> Result<(), String> {
+    remove_if_exists(fs::remove_dir_all(store_dir()))
+        .map_err(|e| format!("Failed to clear wallet state: {}", e))
+}
+
+fn open_database(wallet_id: &str) -> Result<SqliteDatabase, Error> {
+    fs::create_dir_all(store_dir())
+        .map_err(|e| Error::Generic(format!("Failed to create wallet store: {}", e)))?;
+    Ok(SqliteDatabase::new(wallet_path(wallet_id)))
+}
+
+/// Opens a BDK wallet over its stored state. State synced for different
+/// descriptors is dropped and rebuilt by the next sync.
+pub fn open_wallet(
+    wallet_id: &str,
+    external: &str,
+    internal: Option<&str>,
+    network: bdk::bitcoin::Network,
+) -> Result<Wallet<SqliteDatabase>, Error> {
+    match Wallet::new(external, internal, network, open_database(wallet_id)?) {
+        Err(Error::ChecksumMismatch) => {
+            crate::safe_log!(
+                "[WARN] Descriptors of wallet {} changed, resetting its local state",
+                wallet_id
+            );
+            clear_wallet_state(wallet_id).map_err(Error::Generic)?;
+            Wallet::new(external, internal, network, open_database(wallet_id)?)
+        }
+        result => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clear_wallet_state, open_wallet};
+    use bdk::bitcoin::Network;
+    use bdk::wallet::AddressIndex;
+
+    const EXTERNAL: &str = "wpkh(tpubD6NzVbkrYhZ4XgiXtGrdW5XDAPFCL9h7we1vwNCpn8tGbBcgfVYjXyhWo4E1xkh56hjod1RhGjxbaTLV3X4FyWuejifB9jusQ46QzG87VKp/0/*)";
+    const OTHER: &str = "wpkh(tpubD6NzVbkrYhZ4XgiXtGrdW5XDAPFCL9h7we1vwNCpn8tGbBcgfVYjXyhWo4E1xkh56hjod1RhGjxbaTLV3X4FyWuejifB9jusQ46QzG87VKp/1/*)";
+
+    #[test]
+    fn wallet_store_resets_state_synced_for_other_descriptors() {
+        let wallet_id = format!("store-{}", uuid::Uuid::new_v4().simple());
+
+        let wallet = open_wallet(&wallet_id, EXTERNAL, None, Network::Regtest).unwrap();
+        wallet.get_address(AddressIndex::New).unwrap();
+        wallet.get_address(AddressIndex::New).unwrap();
+        drop(wallet);
+
+        let reopened = open_wallet(&wallet_id, EXTERNAL, None, Network::Regtest).unwrap();
+        assert_eq!(reopened.get_address(AddressIndex::New).unwrap().index, 2);
+        drop(reopened);
+
+        let replaced = open_wallet(&wallet_id, OTHER, None, Network::Regtest).unwrap();
+        assert_eq!(replaced.get_address(AddressIndex::New).unwrap().index, 0);
+        drop(replaced);
+
+        clear_wallet_state(&wallet_id).unwrap();
+    }
+}
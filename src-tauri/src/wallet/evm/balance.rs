@@ -1,97 +1,80 @@
-use crate::wallet::chain::traits::{ChainAdapter, ChainAssetBalanceSnapshot, ChainBalanceSnapshot};
+use crate::wallet::chain::traits::{ChainAssetBalanceSnapshot, ChainBalanceSnapshot};
 use crate::wallet::evm::config::EvmChainConfig;
 use crate::wallet::evm::provider::{HybridProvider, ProviderError, ProviderRegistry};
+use crate::wallet::evm::tokens;
 use crate::wallet::security::sanitize;
+use crate::wallet::types::EvmAsset;
+use ethers::abi::{self, ParamType, Token};
 use ethers::prelude::*;
 use ethers::types::transaction::eip2718::TypedTransaction;
-use std::future::Future;
-use std::pin::Pin;
-use std::sync::Arc;
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::task::JoinSet;
 
 const RETRY_ATTEMPTS: u32 = 3;
+/// Times a snapshot is taken again at a fresh block when some read never
+/// found the pinned one.
+const SNAPSHOT_ATTEMPTS: u32 = 2;
 const INITIAL_RETRY_DELAY_MS: u64 = 500;
 
-pub struct EvmChainAdapter {
-    chain_config: EvmChainConfig,
-    wallet_address: String,
+/// Multicall3 is deployed at the same address on nearly every EVM chain.
+const MULTICALL3_ADDRESS: &str = "0xcA11bde05977b3631167028862bE2a173976CA11";
+/// aggregate3((address,bool,bytes)[])
+const AGGREGATE3_SELECTOR: [u8; 4] = [0x82, 0xad, 0x56, 0xcb];
+/// getEthBalance(address)
+const GET_ETH_BALANCE_SELECTOR: [u8; 4] = [0x4d, 0x23, 0x01, 0xcc];
+/// balanceOf(address)
+const BALANCE_OF_SELECTOR: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
+/// Reads per aggregate3 call, kept well under public RPC gas and size caps.
+const MULTICALL_BATCH_SIZE: usize = 250;
+
+/// Chains where Multicall3 turned out not to be deployed; they are read with
+/// one call per balance for the rest of the session.
+static MULTICALL_UNAVAILABLE: Lazy<Mutex<HashSet<u64>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// One balance read: a wallet's holding of one tracked asset.
+struct BalanceRead {
+    wallet_index: usize,
+    wallet: Address,
+    asset: EvmAsset,
 }
 
-impl EvmChainAdapter {
-    pub fn new(chain_config: EvmChainConfig, wallet_address: impl Into<String>) -> Self {
-        Self {
-            chain_config,
-            wallet_address: wallet_address.into(),
+impl BalanceRead {
+    /// The Multicall3 sub-call: `getEthBalance` on Multicall3 itself for the
+    /// native asset, `balanceOf` on the token otherwise.
+    fn call(&self, multicall: Address) -> Result<(Address, Bytes), String> {
+        match &self.asset.contract_address {
+            Some(contract_address) => {
+                let token: Address = contract_address
+                    .parse()
+                    .map_err(|_| format!("Invalid token address: {}", contract_address))?;
+                Ok((token, encode_address_call(BALANCE_OF_SELECTOR, self.wallet)))
+            }
+            None => Ok((
+                multicall,
+                encode_address_call(GET_ETH_BALANCE_SELECTOR, self.wallet),
+            )),
         }
     }
 }
 
-impl ChainAdapter for EvmChainAdapter {
-    fn chain_family(&self) -> &'static str {
-        "evm"
-    }
-
-    fn chain_name(&self) -> &str {
-        self.chain_config.name()
-    }
-
-    fn chain_id(&self) -> Option<String> {
-        Some(self.chain_config.chain_id().to_string())
-    }
-
-    fn wallet_address(&self) -> &str {
-        &self.wallet_address
-    }
-
-    fn fetch_balances<'a>(
-        &'a self,
-    ) -> Pin<Box<dyn Future<Output = Result<ChainBalanceSnapshot, String>> + Send + 'a>> {
-        Box::pin(async move {
-            let assets = self.chain_config.assets();
-            let balances = get_chain_balances(self.chain_config, &self.wallet_address).await?;
-
-            let normalized_assets = balances
-                .into_iter()
-                .map(|(symbol, raw_amount, display_amount)| {
-                    let configured_asset = assets
-                        .iter()
-                        .find(|asset| asset.symbol == symbol)
-                        .cloned()
-                        .unwrap_or_else(|| {
-                            crate::wallet::types::EvmAsset::new(&symbol, &symbol, 18, None)
-                        });
-
-                    ChainAssetBalanceSnapshot {
-                        symbol,
-                        name: configured_asset.name,
-                        contract_address: configured_asset.contract_address,
-                        raw_amount,
-                        display_amount,
-                        decimals: configured_asset.decimals,
-                    }
-                })
-                .collect();
-
-            Ok(ChainBalanceSnapshot {
-                chain_family: self.chain_family(),
-                chain_name: self.chain_name().to_string(),
-                chain_id: self.chain_id(),
-                wallet_address: self.wallet_address.clone(),
-                assets: normalized_assets,
-            })
+/// Balance snapshots of several wallets on one chain, in the order of
+/// `wallet_addresses`. All reads are pinned to one block and batched through
+/// Multicall3 where it is deployed.
+pub async fn fetch_chain_snapshots(
+    chain_config: &EvmChainConfig,
+    wallet_addresses: &[String],
+) -> Result<Vec<ChainBalanceSnapshot>, String> {
+    let wallets = wallet_addresses
+        .iter()
+        .map(|address| {
+            address
+                .parse::<Address>()
+                .map_err(|_| "Invalid wallet address".to_string())
         })
-    }
-}
-
-/// Get all balances for a wallet on a specific chain.
-pub async fn get_chain_balances(
-    chain_config: EvmChainConfig,
-    wallet_address: &str,
-) -> Result<Vec<(String, String, f64)>, String> {
-    let wallet_addr: Address = wallet_address
-        .parse()
-        .map_err(|_| "Invalid wallet address".to_string())?;
+        .collect::<Result<Vec<_>, _>>()?;
 
     let provider = ProviderRegistry::get_or_init(chain_config)
         .await
@@ -103,59 +86,230 @@ pub async fn get_chain_balances(
             )
         })?;
 
-    let mut set = JoinSet::new();
+    let assets = tokens::tracked_assets(chain_config);
+    let reads = wallets
+        .iter()
+        .enumerate()
+        .flat_map(|(wallet_index, wallet)| {
+            assets.iter().map(move |asset| BalanceRead {
+                wallet_index,
+                wallet: *wallet,
+                asset: asset.clone(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // A read that never sees the pinned block is not answered at another
+    // one; the whole snapshot is taken again at a fresh block instead.
+    let mut attempt = 1;
+    let results = loop {
+        let block = query_block_number_with_retry(&provider).await?;
+        let results = query_balances(chain_config, &provider, &reads, block).await;
+        let missed_block = results
+            .iter()
+            .any(|result| matches!(result, Err(err) if is_unknown_block(err)));
+        if !missed_block || attempt >= SNAPSHOT_ATTEMPTS {
+            break results;
+        }
+        tracing::warn!(
+            chain = %sanitize(chain_config.name()),
+            block = %sanitize(&format!("{}", block)),
+            "Pinned block unknown to the RPC, retaking the balance snapshot"
+        );
+        attempt += 1;
+    };
 
-    for asset in chain_config.assets() {
-        let provider = provider.clone();
-        let symbol = asset.symbol.clone();
-        let decimals = asset.decimals;
-        if let Some(contract_address) = asset.contract_address.clone() {
-            set.spawn(async move {
-                let token_addr: Address = contract_address
-                    .parse()
-                    .map_err(|_| format!("Invalid token address: {}", contract_address))?;
+    let mut snapshots = wallet_addresses
+        .iter()
+        .map(|wallet_address| ChainBalanceSnapshot {
+            chain_family: "evm",
+            chain_name: chain_config.name().to_string(),
+            chain_id: Some(chain_config.chain_id().to_string()),
+            wallet_address: wallet_address.clone(),
+            assets: Vec::new(),
+        })
+        .collect::<Vec<_>>();
+
+    for (read, result) in reads.into_iter().zip(results) {
+        match result {
+            Ok(balance) => {
+                let (raw_amount, display_amount) = convert_balance(balance, read.asset.decimals);
+                snapshots[read.wallet_index]
+                    .assets
+                    .push(ChainAssetBalanceSnapshot {
+                        symbol: read.asset.symbol,
+                        name: read.asset.name,
+                        contract_address: read.asset.contract_address,
+                        raw_amount,
+                        display_amount,
+                        decimals: read.asset.decimals,
+                    });
+            }
+            Err(err) => tracing::warn!(
+                chain = %sanitize(chain_config.name()),
+                symbol = %sanitize(&read.asset.symbol),
+                error = %sanitize(&err),
+                "Asset balance query failed"
+            ),
+        }
+    }
+
+    Ok(snapshots)
+}
 
-                query_erc20_with_retry(provider, token_addr, wallet_addr, decimals)
-                    .await
-                    .map(|(balance_str, balance_float)| (symbol, balance_str, balance_float))
-            });
-        } else {
-            set.spawn(async move {
-                query_native_with_retry(provider, wallet_addr, decimals)
-                    .await
-                    .map(|(balance_str, balance_float)| (symbol, balance_str, balance_float))
-            });
+/// Reads every balance at `block`, through Multicall3 when possible and one
+/// call per balance otherwise.
+async fn query_balances(
+    chain_config: &EvmChainConfig,
+    provider: &Arc<HybridProvider>,
+    reads: &[BalanceRead],
+    block: U64,
+) -> Vec<Result<U256, String>> {
+    let chain_id = chain_config.chain_id();
+    let multicall_unavailable = MULTICALL_UNAVAILABLE.lock().unwrap().contains(&chain_id);
+
+    if !multicall_unavailable {
+        match query_multicall(provider, reads, block).await {
+            Ok(Some(results)) => return results,
+            Ok(None) => {
+                tracing::info!(
+                    chain = %sanitize(chain_config.name()),
+                    "Multicall3 not deployed, reading balances individually"
+                );
+                MULTICALL_UNAVAILABLE.lock().unwrap().insert(chain_id);
+            }
+            // Individual reads would miss the same block; let the caller
+            // retake the snapshot.
+            Err(err) if is_unknown_block(&err) => {
+                return reads.iter().map(|_| Err(err.clone())).collect();
+            }
+            Err(err) => tracing::warn!(
+                chain = %sanitize(chain_config.name()),
+                error = %sanitize(&err),
+                "Multicall3 balance query failed, reading balances individually"
+            ),
+        }
+    }
+
+    query_individually(provider, reads, block).await
+}
+
+/// `None` when Multicall3 is not deployed on the chain, which shows as empty
+/// output for the first chunk.
+async fn query_multicall(
+    provider: &HybridProvider,
+    reads: &[BalanceRead],
+    block: U64,
+) -> Result<Option<Vec<Result<U256, String>>>, String> {
+    let multicall: Address = MULTICALL3_ADDRESS
+        .parse()
+        .map_err(|_| "Invalid Multicall3 address".to_string())?;
+    let mut results = Vec::with_capacity(reads.len());
+
+    for chunk in reads.chunks(MULTICALL_BATCH_SIZE) {
+        let calls = chunk
+            .iter()
+            .map(|read| read.call(multicall))
+            .collect::<Result<Vec<_>, _>>()?;
+        let tx: TypedTransaction = TransactionRequest::new()
+            .to(multicall)
+            .data(encode_aggregate3_call(&calls))
+            .into();
+
+        let response = query_contract_with_retry(provider, &tx, block, "multicall3").await?;
+        // Calls to an address without code succeed with empty output
+        if response.is_empty() && results.is_empty() {
+            return Ok(None);
+        }
+        let chunk_results = decode_aggregate3_result(response.as_ref())
+            .ok_or_else(|| "Invalid Multicall3 response".to_string())?;
+        if chunk_results.len() != chunk.len() {
+            return Err(format!(
+                "Multicall3 returned {} results for {} calls",
+                chunk_results.len(),
+                chunk.len()
+            ));
         }
+        results.extend(chunk_results);
+    }
+
+    Ok(Some(results))
+}
+
+async fn query_individually(
+    provider: &Arc<HybridProvider>,
+    reads: &[BalanceRead],
+    block: U64,
+) -> Vec<Result<U256, String>> {
+    let mut set = JoinSet::new();
+
+    for (index, read) in reads.iter().enumerate() {
+        let provider = provider.clone();
+        let wallet = read.wallet;
+        let contract_address = read.asset.contract_address.clone();
+        set.spawn(async move {
+            let result = match contract_address {
+                Some(contract_address) => match contract_address.parse::<Address>() {
+                    Ok(token) => query_erc20_with_retry(provider, token, wallet, block).await,
+                    Err(_) => Err(format!("Invalid token address: {}", contract_address)),
+                },
+                None => query_native_with_retry(provider, wallet, block).await,
+            };
+            (index, result)
+        });
     }
 
-    let mut balances = Vec::new();
+    let mut results = (0..reads.len())
+        .map(|_| Err("Asset balance task panicked".to_string()))
+        .collect::<Vec<_>>();
     while let Some(res) = set.join_next().await {
         match res {
-            Ok(Ok(tuple)) => balances.push(tuple),
-            Ok(Err(err)) => tracing::warn!(
-                chain = %sanitize(&format!("{}", chain_config.name())),
-                error = %sanitize(&format!("{}", err)),
-                "Asset balance query failed"
-            ),
+            Ok((index, result)) => results[index] = result,
             Err(join_err) => tracing::error!(
-                chain = %sanitize(&format!("{}", chain_config.name())),
                 join_err = %sanitize(&format!("{:?}", join_err)),
                 "Asset balance task panicked"
             ),
         }
     }
 
-    Ok(balances)
+    results
+}
+
+async fn query_block_number_with_retry(provider: &HybridProvider) -> Result<U64, String> {
+    for attempt in 1..=RETRY_ATTEMPTS {
+        match provider.get_block_number().await {
+            Ok(block) => return Ok(block),
+            Err(err) => handle_retry("block number", attempt, err).await?,
+        }
+    }
+
+    Err("Failed to query block number after retries".to_string())
+}
+
+async fn query_contract_with_retry(
+    provider: &HybridProvider,
+    tx: &TypedTransaction,
+    block: U64,
+    label: &str,
+) -> Result<Bytes, String> {
+    for attempt in 1..=RETRY_ATTEMPTS {
+        match provider.call_contract(tx, Some(block.into())).await {
+            Ok(result) => return Ok(result),
+            Err(err) => handle_retry(label, attempt, err).await?,
+        }
+    }
+
+    Err(format!("Failed to query {} balance after retries", label))
 }
 
 async fn query_native_with_retry(
     provider: Arc<HybridProvider>,
     wallet_addr: Address,
-    decimals: u8,
-) -> Result<(String, f64), String> {
+    block: U64,
+) -> Result<U256, String> {
     for attempt in 1..=RETRY_ATTEMPTS {
-        match provider.get_balance(wallet_addr).await {
-            Ok(balance) => return Ok(convert_balance(balance, decimals)),
+        match provider.get_balance(wallet_addr, Some(block.into())).await {
+            Ok(balance) => return Ok(balance),
             Err(err) => handle_retry("native", attempt, err).await?,
         }
     }
@@ -167,25 +321,24 @@ async fn query_erc20_with_retry(
     provider: Arc<HybridProvider>,
     token_addr: Address,
     wallet_addr: Address,
-    decimals: u8,
-) -> Result<(String, f64), String> {
-    for attempt in 1..=RETRY_ATTEMPTS {
-        let call_data = encode_balance_of_call(wallet_addr);
-        let tx: TypedTransaction = TransactionRequest::new()
-            .to(token_addr)
-            .data(call_data)
-            .into();
-
-        match provider.call_contract(&tx).await {
-            Ok(result) => {
-                let balance = U256::from_big_endian(result.as_ref());
-                return Ok(convert_balance(balance, decimals));
-            }
-            Err(err) => handle_retry("erc20", attempt, err).await?,
-        }
-    }
+    block: U64,
+) -> Result<U256, String> {
+    let tx: TypedTransaction = TransactionRequest::new()
+        .to(token_addr)
+        .data(encode_address_call(BALANCE_OF_SELECTOR, wallet_addr))
+        .into();
+    let result = query_contract_with_retry(&provider, &tx, block, "erc20").await?;
+    decode_balance(result.as_ref()).ok_or_else(|| "Invalid ERC20 balance response".to_string())
+}
 
-    Err("Failed to query ERC20 balance after retries".to_string())
+/// Load-balanced RPCs can route a read to a node that has not seen the
+/// pinned block yet. Such a read is retried at the same block like any other
+/// failure; this recognises it once the retries run out.
+fn is_unknown_block(error: &str) -> bool {
+    let message = error.to_lowercase();
+    ["header not found", "block not found", "unknown block"]
+        .iter()
+        .any(|pattern| message.contains(pattern))
 }
 
 async fn handle_retry(label: &str, attempt: u32, err: ProviderError) -> Result<(), String> {
@@ -205,54 +358,144 @@ async fn handle_retry(label: &str, attempt: u32, err: ProviderError) -> Result<(
     Ok(())
 }
 
-/// Encode a balanceOf(address) call for ERC20 tokens.
-fn encode_balance_of_call(address: Address) -> Bytes {
-    let mut data = vec![0x70, 0xa0, 0x82, 0x31];
+/// Encode a call to a function taking a single address, such as balanceOf.
+fn encode_address_call(selector: [u8; 4], address: Address) -> Bytes {
+    let mut data = selector.to_vec();
     let mut addr_bytes = [0u8; 32];
     addr_bytes[12..].copy_from_slice(&address.to_fixed_bytes());
     data.extend_from_slice(&addr_bytes);
     Bytes::from(data)
 }
 
-fn convert_balance(balance: U256, decimals: u8) -> (String, f64) {
-    let balance_str = balance.to_string();
+/// Encode an aggregate3 call where every sub-call may fail on its own.
+fn encode_aggregate3_call(calls: &[(Address, Bytes)]) -> Bytes {
+    let calls = calls
+        .iter()
+        .map(|(target, data)| {
+            Token::Tuple(vec![
+                Token::Address(*target),
+                Token::Bool(true),
+                Token::Bytes(data.to_vec()),
+            ])
+        })
+        .collect();
+    let mut data = AGGREGATE3_SELECTOR.to_vec();
+    data.extend(abi::encode(&[Token::Array(calls)]));
+    Bytes::from(data)
+}
 
-    if decimals == 0 {
-        return (balance_str, balance.as_u64() as f64);
-    }
+/// Decode aggregate3's `(bool success, bytes returnData)[]` into balances.
+fn decode_aggregate3_result(data: &[u8]) -> Option<Vec<Result<U256, String>>> {
+    let result_type = ParamType::Array(Box::new(ParamType::Tuple(vec![
+        ParamType::Bool,
+        ParamType::Bytes,
+    ])));
+    let Token::Array(results) = abi::decode(&[result_type], data).ok()?.pop()? else {
+        return None;
+    };
 
-    let divisor = U256::from(10u64).pow(U256::from(decimals));
-    if divisor.is_zero() {
-        return (balance_str, 0.0);
-    }
+    results
+        .into_iter()
+        .map(|result| match result {
+            Token::Tuple(fields) => match fields.as_slice() {
+                [Token::Bool(true), Token::Bytes(data)] => {
+                    Some(decode_balance(data).ok_or_else(|| "Invalid balance response".to_string()))
+                }
+                [Token::Bool(false), Token::Bytes(_)] => {
+                    Some(Err("Balance call reverted".to_string()))
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
 
-    let whole = balance / divisor;
-    let remainder = balance % divisor;
+fn decode_balance(data: &[u8]) -> Option<U256> {
+    (data.len() >= 32).then(|| U256::from_big_endian(&data[..32]))
+}
 
-    let remainder_divisor = divisor.as_u64();
-    let remainder_f64 = if remainder_divisor > 0 {
-        remainder.as_u64() as f64 / remainder_divisor as f64
-    } else {
-        0.0
-    };
+fn convert_balance(balance: U256, decimals: u8) -> (String, f64) {
+    (balance.to_string(), units_to_f64(balance, decimals as u32))
+}
 
-    let balance_float = whole.as_u64() as f64 + remainder_f64;
-    (balance_str, balance_float)
+/// An amount in whole units for display. Custom tokens can have more decimals,
+/// or larger amounts, than fit a u128.
+pub(crate) fn units_to_f64(amount: U256, decimals: u32) -> f64 {
+    ethers::utils::format_units(amount, decimals)
+        .ok()
+        .and_then(|formatted| formatted.parse::<f64>().ok())
+        .unwrap_or(0.0)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::EvmChainAdapter;
-    use crate::wallet::chain::traits::ChainAdapter;
-    use crate::wallet::evm::config::EvmChainConfig;
+    use super::{
+        convert_balance, decode_aggregate3_result, encode_aggregate3_call, is_unknown_block,
+        AGGREGATE3_SELECTOR, BALANCE_OF_SELECTOR, GET_ETH_BALANCE_SELECTOR,
+    };
+
+    #[test]
+    fn unknown_block_errors_are_recognised() {
+        assert!(is_unknown_block(
+            "multicall3 balance query failed: (code: -32000, message: header not found, data: None)"
+        ));
+        assert!(is_unknown_block(
+            "native balance query failed: Block not found"
+        ));
+        assert!(!is_unknown_block(
+            "erc20 balance query failed: execution reverted"
+        ));
+    }
+
+    #[test]
+    fn convert_balance_handles_tokens_with_more_than_nineteen_decimals() {
+        let balance = ethers::types::U256::exp10(24) * 3 / 2;
+
+        assert_eq!(
+            convert_balance(balance, 24),
+            ("1500000000000000000000000".to_string(), 1.5)
+        );
+        assert_eq!(
+            convert_balance(ethers::types::U256::from(1_500_000), 6).1,
+            1.5
+        );
+    }
 
     #[test]
-    fn evm_chain_adapter_reports_expected_identity() {
-        let adapter = EvmChainAdapter::new(EvmChainConfig::Ethereum, "0xabc");
+    fn multicall_selectors_match_their_signatures() {
+        use ethers::utils::id;
+
+        assert_eq!(
+            AGGREGATE3_SELECTOR,
+            id("aggregate3((address,bool,bytes)[])")
+        );
+        assert_eq!(GET_ETH_BALANCE_SELECTOR, id("getEthBalance(address)"));
+        assert_eq!(BALANCE_OF_SELECTOR, id("balanceOf(address)"));
+    }
 
-        assert_eq!(adapter.chain_family(), "evm");
-        assert_eq!(adapter.chain_name(), "ethereum");
-        assert_eq!(adapter.chain_id(), Some("1".to_string()));
-        assert_eq!(adapter.wallet_address(), "0xabc");
+    #[test]
+    fn aggregate3_results_keep_per_call_failures() {
+        use ethers::abi::{encode, Token};
+        use ethers::types::{Address, Bytes, U256};
+
+        let call = encode_aggregate3_call(&[(Address::zero(), Bytes::from(vec![1, 2, 3]))]);
+        assert_eq!(call[..4], AGGREGATE3_SELECTOR);
+
+        let mut balance = [0u8; 32];
+        U256::from(42).to_big_endian(&mut balance);
+        let response = encode(&[Token::Array(vec![
+            Token::Tuple(vec![Token::Bool(true), Token::Bytes(balance.to_vec())]),
+            Token::Tuple(vec![Token::Bool(false), Token::Bytes(Vec::new())]),
+            Token::Tuple(vec![Token::Bool(true), Token::Bytes(Vec::new())]),
+        ])]);
+
+        let results = decode_aggregate3_result(&response).unwrap();
+        assert_eq!(results[0], Ok(U256::from(42)));
+        assert!(results[1].is_err());
+        assert!(results[2].is_err());
+
+        // An address without code answers with empty output
+        assert!(decode_aggregate3_result(&[]).is_none());
     }
 }
@@ -1,9 +1,12 @@
+use crate::wallet::evm::config::is_testnet_chain_id;
+use crate::wallet::evm::networks;
+use crate::wallet::evm::tokens;
 use crate::wallet::state::types::{FreshnessMetadata, FreshnessStatus};
 use crate::wallet::sync::engine;
 use crate::wallet::sync::types::SyncReason;
 use crate::wallet::types::{
-    EvmAsset, EvmAssetBalance, EvmChainAssets, EvmWalletBalancesResponse, EvmWalletInfo,
-    ValuationStatus, WalletInfo,
+    EvmAsset, EvmAssetBalance, EvmChainAssets, EvmCustomToken, EvmNetwork,
+    EvmWalletBalancesResponse, EvmWalletInfo, SaveEvmNetworkRequest, ValuationStatus, WalletInfo,
 };
 use crate::DB;
 
@@ -144,12 +147,12 @@ fn query_evm_wallet_balances_inner(wallet_id: &str) -> Result<EvmWalletBalancesR
 
     let total_balance_usd = chain_views
         .iter()
-        .filter(|chain| chain.chain_id != 11155111)
+        .filter(|chain| !is_testnet_chain_id(chain.chain_id))
         .map(|chain| chain.total_balance_usd)
         .sum();
     let unpriced_asset_count = chain_views
         .iter()
-        .filter(|chain| chain.chain_id != 11155111)
+        .filter(|chain| !is_testnet_chain_id(chain.chain_id))
         .map(|chain| chain.unpriced_asset_count)
         .sum();
 
@@ -209,6 +212,46 @@ pub async fn refresh_evm_wallet_balances(
         .map(|(wallet, sync)| EvmWalletBalancesResponse { wallet, sync })
 }
 
+#[tauri::command]
+pub async fn evm_add_custom_token(
+    chain_id: u64,
+    contract_address: String,
+) -> Result<EvmCustomToken, String> {
+    tokens::add_custom_token(chain_id, &contract_address).await
+}
+
+#[tauri::command]
+pub fn evm_remove_custom_token(chain_id: u64, contract_address: String) -> Result<bool, String> {
+    let db = DB.lock().unwrap();
+    db.delete_evm_custom_token(chain_id, &contract_address)
+        .map_err(|e| format!("Failed to remove custom token: {}", e))
+}
+
+#[tauri::command]
+pub fn evm_get_custom_tokens(chain_id: Option<u64>) -> Result<Vec<EvmCustomToken>, String> {
+    let db = DB.lock().unwrap();
+    db.get_evm_custom_tokens(chain_id)
+        .map_err(|e| format!("Failed to get custom tokens: {}", e))
+}
+
+#[tauri::command]
+pub fn evm_get_networks() -> Result<Vec<EvmNetwork>, String> {
+    let db = DB.lock().unwrap();
+    db.get_evm_networks()
+        .map_err(|e| format!("Failed to get EVM networks: {}", e))
+}
+
+/// Adds or updates a network once its RPC answers with the same chain id.
+#[tauri::command]
+pub async fn evm_save_network(request: SaveEvmNetworkRequest) -> Result<EvmNetwork, String> {
+    networks::save_network(request).await
+}
+
+#[tauri::command]
+pub async fn evm_delete_network(chain_id: u64) -> Result<bool, String> {
+    networks::delete_network(chain_id).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::{evm_get_wallet_with_balances, query_chain_freshness, query_sync_outcome};
@@ -1,5 +1,12 @@
 use crate::wallet::evm::provider::ProviderConfig;
-use crate::wallet::types::EvmAsset;
+use crate::wallet::types::{EvmAsset, EvmNetwork};
+use once_cell::sync::Lazy;
+use std::sync::{Arc, RwLock};
+
+/// User-defined networks, loaded from the database at startup and after each
+/// change. An entry is only replaced when its network is edited; chain
+/// configs still holding the old one keep it alive until they are dropped.
+static CUSTOM_NETWORKS: Lazy<RwLock<Vec<Arc<EvmNetwork>>>> = Lazy::new(|| RwLock::new(Vec::new()));
 
 fn env_with_fallback(keys: &[&str], fallback: &str) -> String {
     for key in keys {
@@ -48,7 +55,7 @@ fn env_u32(var: &str, default: u32) -> u32 {
 }
 
 /// Chain configuration
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum EvmChainConfig {
     Ethereum,
     Arbitrum,
@@ -56,6 +63,7 @@ pub enum EvmChainConfig {
     Polygon,
     BinanceSmartChain,
     EthereumSepolia,
+    Custom(Arc<EvmNetwork>),
 }
 
 impl EvmChainConfig {
@@ -67,6 +75,7 @@ impl EvmChainConfig {
             Self::Polygon => 137,
             Self::BinanceSmartChain => 56,
             Self::EthereumSepolia => 11155111,
+            Self::Custom(network) => network.chain_id,
         }
     }
 
@@ -78,6 +87,7 @@ impl EvmChainConfig {
             Self::Polygon => "polygon",
             Self::BinanceSmartChain => "bsc",
             Self::EthereumSepolia => "sepolia",
+            Self::Custom(network) => &network.name,
         }
     }
 
@@ -90,11 +100,37 @@ impl EvmChainConfig {
             Self::Polygon => "Polygon",
             Self::BinanceSmartChain => "Binance Smart Chain",
             Self::EthereumSepolia => "Ethereum Sepolia",
+            Self::Custom(network) => &network.display_name,
+        }
+    }
+
+    pub fn is_testnet(&self) -> bool {
+        match self {
+            Self::EthereumSepolia => true,
+            Self::Custom(network) => network.is_testnet,
+            _ => false,
         }
     }
 
     pub fn supports_history_sync(&self) -> bool {
-        !matches!(self, Self::EthereumSepolia)
+        match self {
+            Self::EthereumSepolia => false,
+            Self::Custom(network) => network.explorer_api_url.is_some(),
+            _ => true,
+        }
+    }
+
+    /// Etherscan-compatible API used for history sync.
+    pub fn explorer_api_url(&self) -> Option<String> {
+        match self {
+            Self::Ethereum => Some("https://api.etherscan.io/v2/api".to_string()),
+            Self::BinanceSmartChain => Some("https://api.bscscan.com/v2/api".to_string()),
+            Self::Polygon => Some("https://api.polygonscan.com/v2/api".to_string()),
+            Self::Arbitrum => Some("https://api.arbiscan.io/v2/api".to_string()),
+            Self::Optimism => Some("https://api-optimistic.etherscan.io/v2/api".to_string()),
+            Self::EthereumSepolia => None,
+            Self::Custom(network) => network.explorer_api_url.clone(),
+        }
     }
 
     #[allow(dead_code)]
@@ -124,6 +160,7 @@ impl EvmChainConfig {
                 &["ETHEREUM_SEPOLIA_HTTP_URL", "EVM_RPC_SEPOLIA_URL"],
                 "https://ethereum-sepolia-rpc.publicnode.com",
             ),
+            Self::Custom(network) => network.rpc_url.clone(),
         }
     }
 
@@ -135,6 +172,7 @@ impl EvmChainConfig {
             Self::Polygon => env_optional("POLYGON_WSS_URL"),
             Self::BinanceSmartChain => env_optional("BSC_WSS_URL"),
             Self::EthereumSepolia => env_optional("ETHEREUM_SEPOLIA_WSS_URL"),
+            Self::Custom(network) => network.wss_url.clone(),
         }
     }
 
@@ -243,12 +281,17 @@ impl EvmChainConfig {
                     Some("0xE50d86c6dE38F9754f6777d2925377564Bf79482"),
                 ),
             ],
+            Self::Custom(network) => vec![EvmAsset::new(
+                &network.native_symbol,
+                &network.native_symbol,
+                network.native_decimals,
+                None,
+            )],
         }
     }
 }
 
-/// Get all supported chains
-pub fn get_all_chains() -> Vec<EvmChainConfig> {
+fn builtin_chains() -> Vec<EvmChainConfig> {
     vec![
         EvmChainConfig::Ethereum,
         EvmChainConfig::Arbitrum,
@@ -259,6 +302,51 @@ pub fn get_all_chains() -> Vec<EvmChainConfig> {
     ]
 }
 
+fn custom_chains() -> Vec<EvmChainConfig> {
+    CUSTOM_NETWORKS
+        .read()
+        .unwrap()
+        .iter()
+        .map(|network| EvmChainConfig::Custom(network.clone()))
+        .collect()
+}
+
+/// Replaces the registered user-defined networks. Networks that did not
+/// change keep their entry, so their cached providers stay in use.
+pub fn set_custom_networks(networks: Vec<EvmNetwork>) {
+    let mut registered = CUSTOM_NETWORKS.write().unwrap();
+    let next = networks
+        .into_iter()
+        .map(|network| {
+            registered
+                .iter()
+                .find(|existing| ***existing == network)
+                .cloned()
+                .unwrap_or_else(|| Arc::new(network))
+        })
+        .collect();
+    *registered = next;
+}
+
+/// Whether a built-in chain already uses this chain id or name.
+pub fn is_builtin_chain(chain_id: u64, name: &str) -> bool {
+    builtin_chains()
+        .iter()
+        .any(|chain| chain.chain_id() == chain_id || chain.name().eq_ignore_ascii_case(name))
+}
+
+/// Testnet balances are listed but left out of portfolio totals.
+pub fn is_testnet_chain_id(chain_id: u64) -> bool {
+    get_chain_by_id(chain_id).is_some_and(|chain| chain.is_testnet())
+}
+
+/// Get all supported chains, built-in first
+pub fn get_all_chains() -> Vec<EvmChainConfig> {
+    let mut chains = builtin_chains();
+    chains.extend(custom_chains());
+    chains
+}
+
 pub fn get_history_sync_supported_chains() -> Vec<EvmChainConfig> {
     get_all_chains()
         .into_iter()
@@ -281,7 +369,9 @@ pub fn get_chain_by_name(name: &str) -> Option<EvmChainConfig> {
         "polygon" => Some(EvmChainConfig::Polygon),
         "bsc" => Some(EvmChainConfig::BinanceSmartChain),
         "sepolia" => Some(EvmChainConfig::EthereumSepolia),
-        _ => None,
+        _ => custom_chains()
+            .into_iter()
+            .find(|chain| chain.name() == name),
     }
 }
 
@@ -295,7 +385,9 @@ pub fn get_chain_by_id(chain_id: u64) -> Option<EvmChainConfig> {
         137 => Some(EvmChainConfig::Polygon),
         56 => Some(EvmChainConfig::BinanceSmartChain),
         11155111 => Some(EvmChainConfig::EthereumSepolia),
-        _ => None,
+        _ => custom_chains()
+            .into_iter()
+            .find(|chain| chain.chain_id() == chain_id),
     }
 }
 
@@ -405,4 +497,35 @@ mod tests {
         );
         assert_eq!(get_chain_by_id(999999), None);
     }
+
+    #[test]
+    fn test_custom_network_config() {
+        let network = Arc::new(EvmNetwork {
+            chain_id: 31337,
+            name: "anvil".to_string(),
+            display_name: "Anvil".to_string(),
+            native_symbol: "ETH".to_string(),
+            native_decimals: 18,
+            rpc_url: "http://127.0.0.1:8545".to_string(),
+            wss_url: None,
+            explorer_api_url: None,
+            is_testnet: true,
+            created_at: "2026-05-01T00:00:00Z".to_string(),
+            updated_at: "2026-05-01T00:00:00Z".to_string(),
+        });
+        let chain = EvmChainConfig::Custom(network);
+
+        assert_eq!(chain.chain_id(), 31337);
+        assert_eq!(chain.name(), "anvil");
+        assert_eq!(chain.rpc_url(), "http://127.0.0.1:8545");
+        assert!(chain.is_testnet());
+        assert!(!chain.supports_history_sync());
+        assert_eq!(chain.assets().len(), 1);
+        assert_eq!(chain.assets()[0].contract_address, None);
+        assert!(is_builtin_chain(1, "anvil"));
+        assert!(is_builtin_chain(31337, "Polygon"));
+        assert!(!is_builtin_chain(31337, "anvil"));
+        assert!(is_testnet_chain_id(11155111));
+        assert!(!is_testnet_chain_id(1));
+    }
 }
@@ -0,0 +1,570 @@
+use crate::wallet::evm::balance::units_to_f64;
+use crate::wallet::transaction_types::{EvmFeeSpeed, EvmFeeTier};
+use ethers::providers::Middleware;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{
+    BlockNumber, Eip1559TransactionRequest, FeeHistory, Transaction, TransactionReceipt,
+    TransactionRequest, U256,
+};
+
+/// Recent blocks whose priority fees the tiers are drawn from.
+const FEE_HISTORY_BLOCKS: u64 = 10;
+/// Priority fee percentiles of the slow, normal and fast tiers.
+const REWARD_PERCENTILES: [f64; 3] = [10.0, 50.0, 90.0];
+/// The base fee rises at most 12.5% per block, so twice the next base fee
+/// keeps a transaction valid through about six full blocks in a row.
+const BASE_FEE_MULTIPLIER: u64 = 2;
+/// Nodes only accept a same-nonce replacement that raises its fees by 10%.
+const REPLACEMENT_BUMP_PERCENT: u64 = 10;
+
+/// Fee conditions of a chain: EIP-1559 with per-tier priority fees, or a
+/// single gas price on chains that have not activated London.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EvmFeeMarket {
+    Eip1559 {
+        base_fee_per_gas: U256,
+        /// Slow, normal and fast
+        priority_fees: [U256; 3],
+    },
+    Legacy {
+        gas_price: U256,
+    },
+}
+
+/// The fee fields a transaction is signed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EvmFeeParams {
+    Eip1559 {
+        max_fee_per_gas: U256,
+        max_priority_fee_per_gas: U256,
+    },
+    Legacy {
+        gas_price: U256,
+    },
+}
+
+impl EvmFeeMarket {
+    pub(crate) fn base_fee_per_gas(&self) -> Option<U256> {
+        match self {
+            Self::Eip1559 {
+                base_fee_per_gas, ..
+            } => Some(*base_fee_per_gas),
+            Self::Legacy { .. } => None,
+        }
+    }
+
+    pub(crate) fn params(&self, speed: EvmFeeSpeed) -> EvmFeeParams {
+        match *self {
+            Self::Eip1559 {
+                base_fee_per_gas,
+                priority_fees,
+            } => {
+                let priority = priority_fees[speed_index(speed)];
+                EvmFeeParams::Eip1559 {
+                    max_fee_per_gas: base_fee_per_gas
+                        .saturating_mul(U256::from(BASE_FEE_MULTIPLIER))
+                        .saturating_add(priority),
+                    max_priority_fee_per_gas: priority,
+                }
+            }
+            Self::Legacy { gas_price } => EvmFeeParams::Legacy { gas_price },
+        }
+    }
+
+    /// Fee params under a per-gas cap: the cap is the max fee on EIP-1559
+    /// chains and the gas price elsewhere.
+    pub(crate) fn capped_params(&self, cap: U256, speed: EvmFeeSpeed) -> EvmFeeParams {
+        match self.params(speed) {
+            EvmFeeParams::Eip1559 {
+                max_priority_fee_per_gas,
+                ..
+            } => EvmFeeParams::Eip1559 {
+                max_fee_per_gas: cap,
+                max_priority_fee_per_gas: max_priority_fee_per_gas.min(cap),
+            },
+            EvmFeeParams::Legacy { .. } => EvmFeeParams::Legacy { gas_price: cap },
+        }
+    }
+
+    pub(crate) fn tier(&self, speed: EvmFeeSpeed) -> Option<EvmFeeTier> {
+        match self.params(speed) {
+            EvmFeeParams::Eip1559 {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            } => Some(EvmFeeTier {
+                max_fee_per_gas: max_fee_per_gas.to_string(),
+                max_priority_fee_per_gas: max_priority_fee_per_gas.to_string(),
+            }),
+            EvmFeeParams::Legacy { .. } => None,
+        }
+    }
+}
+
+impl EvmFeeParams {
+    /// The price per gas a transaction is expected to pay, used for the fee
+    /// snapshot stored until its receipt reports the effective gas price.
+    pub(crate) fn expected_gas_price(&self, market: &EvmFeeMarket) -> U256 {
+        match *self {
+            Self::Eip1559 {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            } => market
+                .base_fee_per_gas()
+                .unwrap_or_default()
+                .saturating_add(max_priority_fee_per_gas)
+                .min(max_fee_per_gas),
+            Self::Legacy { gas_price } => gas_price,
+        }
+    }
+
+    /// Converts a request to a type-2 transaction, or sets its gas price.
+    pub(crate) fn apply(&self, tx: TransactionRequest) -> TypedTransaction {
+        match *self {
+            Self::Eip1559 {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            } => {
+                let mut request = Eip1559TransactionRequest::new()
+                    .max_fee_per_gas(max_fee_per_gas)
+                    .max_priority_fee_per_gas(max_priority_fee_per_gas);
+                request.from = tx.from;
+                request.to = tx.to;
+                request.gas = tx.gas;
+                request.value = tx.value;
+                request.data = tx.data;
+                request.nonce = tx.nonce;
+                request.chain_id = tx.chain_id;
+                request.into()
+            }
+            Self::Legacy { gas_price } => tx.gas_price(gas_price).into(),
+        }
+    }
+}
+
+/// Fees a sent transaction was signed with, as reported by the node.
+pub(crate) fn sent_fee_params(tx: &Transaction) -> Option<EvmFeeParams> {
+    match (tx.max_fee_per_gas, tx.max_priority_fee_per_gas) {
+        (Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) => Some(EvmFeeParams::Eip1559 {
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        }),
+        _ => tx
+            .gas_price
+            .map(|gas_price| EvmFeeParams::Legacy { gas_price }),
+    }
+}
+
+/// Fees for re-signing a nonce: the original fees bumped enough for nodes to
+/// accept the replacement, and at least the current fast tier. Without the
+/// original fees the fast tier is used as is.
+pub(crate) fn replacement_params(
+    original: Option<EvmFeeParams>,
+    market: &EvmFeeMarket,
+) -> EvmFeeParams {
+    let fast = market.params(EvmFeeSpeed::Fast);
+    let (fast_max_fee, fast_priority) = match fast {
+        EvmFeeParams::Eip1559 {
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        } => (max_fee_per_gas, max_priority_fee_per_gas),
+        EvmFeeParams::Legacy { gas_price } => (gas_price, gas_price),
+    };
+
+    match original {
+        Some(EvmFeeParams::Eip1559 {
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        }) => {
+            let priority = bumped(max_priority_fee_per_gas).max(fast_priority);
+            EvmFeeParams::Eip1559 {
+                max_fee_per_gas: bumped(max_fee_per_gas).max(fast_max_fee).max(priority),
+                max_priority_fee_per_gas: priority,
+            }
+        }
+        Some(EvmFeeParams::Legacy { gas_price }) => EvmFeeParams::Legacy {
+            gas_price: bumped(gas_price).max(fast_max_fee),
+        },
+        None => fast,
+    }
+}
+
+fn bumped(value: U256) -> U256 {
+    value
+        .saturating_add(value * U256::from(REPLACEMENT_BUMP_PERCENT) / U256::from(100))
+        .saturating_add(U256::one())
+}
+
+/// Fee params of a send: explicit EIP-1559 caps, else a per-gas cap, else
+/// the tier of `speed`. Values are wei as decimal strings.
+pub(crate) fn send_fee_params(
+    market: &EvmFeeMarket,
+    gas_price: Option<&str>,
+    max_fee_per_gas: Option<&str>,
+    max_priority_fee_per_gas: Option<&str>,
+    speed: EvmFeeSpeed,
+) -> Result<EvmFeeParams, String> {
+    if let Some(max_fee_per_gas) = max_fee_per_gas {
+        let EvmFeeParams::Eip1559 {
+            max_priority_fee_per_gas: tier_priority,
+            ..
+        } = market.params(speed)
+        else {
+            return Err("This chain does not support EIP-1559 fees".to_string());
+        };
+        let max_fee_per_gas = parse_wei(max_fee_per_gas, "max fee per gas")?;
+        let max_priority_fee_per_gas = match max_priority_fee_per_gas {
+            Some(priority) => parse_wei(priority, "max priority fee per gas")?,
+            None => tier_priority,
+        };
+        if max_priority_fee_per_gas > max_fee_per_gas {
+            return Err("Max priority fee cannot exceed the max fee".to_string());
+        }
+        return Ok(EvmFeeParams::Eip1559 {
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        });
+    }
+
+    match gas_price {
+        Some(gas_price) => Ok(market.capped_params(parse_wei(gas_price, "gas price")?, speed)),
+        None => Ok(market.params(speed)),
+    }
+}
+
+fn parse_wei(value: &str, name: &str) -> Result<U256, String> {
+    U256::from_dec_str(value.trim()).map_err(|e| format!("Invalid {}: {}", name, e))
+}
+
+/// Reads the fee market of a chain. Chains whose latest block has no base fee
+/// get the node's legacy gas price; nodes without `eth_feeHistory` fall back
+/// to the gas price minus the base fee as the priority fee of every tier.
+pub(crate) async fn fee_market<M: Middleware>(provider: &M) -> Result<EvmFeeMarket, String> {
+    let latest = provider
+        .get_block(BlockNumber::Latest)
+        .await
+        .map_err(|e| format!("Failed to get latest block: {}", e))?
+        .ok_or_else(|| "Latest block not found".to_string())?;
+
+    let Some(latest_base_fee) = latest.base_fee_per_gas else {
+        let gas_price = provider
+            .get_gas_price()
+            .await
+            .map_err(|e| format!("Failed to get gas price: {}", e))?;
+        return Ok(EvmFeeMarket::Legacy { gas_price });
+    };
+
+    let market = match provider
+        .fee_history(FEE_HISTORY_BLOCKS, BlockNumber::Latest, &REWARD_PERCENTILES)
+        .await
+    {
+        Ok(history) => market_from_history(&history),
+        Err(_) => None,
+    };
+    if let Some(market) = market {
+        return Ok(market);
+    }
+
+    let gas_price = provider
+        .get_gas_price()
+        .await
+        .map_err(|e| format!("Failed to get gas price: {}", e))?;
+    let priority = gas_price.saturating_sub(latest_base_fee);
+    Ok(EvmFeeMarket::Eip1559 {
+        base_fee_per_gas: latest_base_fee,
+        priority_fees: [priority; 3],
+    })
+}
+
+/// Each tier's priority fee is the median of its percentile across the
+/// sampled blocks, raised so that faster tiers never pay less.
+fn market_from_history(history: &FeeHistory) -> Option<EvmFeeMarket> {
+    // The last entry is the base fee of the block after the newest sampled one.
+    let base_fee_per_gas = *history.base_fee_per_gas.last()?;
+
+    let mut priority_fees = [U256::zero(); 3];
+    for (index, priority_fee) in priority_fees.iter_mut().enumerate() {
+        let mut rewards: Vec<U256> = history
+            .reward
+            .iter()
+            .filter_map(|block| block.get(index).copied())
+            .collect();
+        if rewards.is_empty() {
+            return None;
+        }
+        rewards.sort();
+        *priority_fee = rewards[rewards.len() / 2];
+    }
+    priority_fees[1] = priority_fees[1].max(priority_fees[0]);
+    priority_fees[2] = priority_fees[2].max(priority_fees[1]);
+
+    Some(EvmFeeMarket::Eip1559 {
+        base_fee_per_gas,
+        priority_fees,
+    })
+}
+
+fn speed_index(speed: EvmFeeSpeed) -> usize {
+    match speed {
+        EvmFeeSpeed::Slow => 0,
+        EvmFeeSpeed::Normal => 1,
+        EvmFeeSpeed::Fast => 2,
+    }
+}
+
+/// What a mined transaction actually paid, read from its receipt.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct EvmReceiptFee {
+    pub gas_used: String,
+    pub effective_gas_price: String,
+    /// In native units
+    pub fee: f64,
+}
+
+pub(crate) fn receipt_fee(receipt: &TransactionReceipt) -> Option<EvmReceiptFee> {
+    let gas_used = receipt.gas_used?;
+    let effective_gas_price = receipt.effective_gas_price?;
+    let fee_wei = gas_used.saturating_mul(effective_gas_price);
+
+    Some(EvmReceiptFee {
+        gas_used: gas_used.to_string(),
+        effective_gas_price: effective_gas_price.to_string(),
+        fee: units_to_f64(fee_wei, 18),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::{Address, NameOrAddress};
+
+    fn gwei(value: u64) -> U256 {
+        U256::from(value) * U256::exp10(9)
+    }
+
+    fn history(base_fees: &[u64], rewards: &[[u64; 3]]) -> FeeHistory {
+        FeeHistory {
+            base_fee_per_gas: base_fees.iter().map(|fee| gwei(*fee)).collect(),
+            gas_used_ratio: vec![0.5; rewards.len()],
+            oldest_block: U256::from(100),
+            reward: rewards
+                .iter()
+                .map(|block| block.iter().map(|fee| gwei(*fee)).collect())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn tiers_use_the_next_base_fee_and_median_rewards() {
+        let market = market_from_history(&history(
+            &[20, 22, 24, 30],
+            &[[1, 2, 5], [1, 3, 9], [2, 2, 4]],
+        ))
+        .unwrap();
+
+        assert_eq!(
+            market,
+            EvmFeeMarket::Eip1559 {
+                base_fee_per_gas: gwei(30),
+                priority_fees: [gwei(1), gwei(2), gwei(5)],
+            }
+        );
+        assert_eq!(
+            market.params(EvmFeeSpeed::Fast),
+            EvmFeeParams::Eip1559 {
+                max_fee_per_gas: gwei(65),
+                max_priority_fee_per_gas: gwei(5),
+            }
+        );
+    }
+
+    #[test]
+    fn tiers_never_decrease_with_speed() {
+        let market = market_from_history(&history(&[10, 10], &[[3, 1, 0]])).unwrap();
+
+        assert_eq!(
+            market,
+            EvmFeeMarket::Eip1559 {
+                base_fee_per_gas: gwei(10),
+                priority_fees: [gwei(3); 3],
+            }
+        );
+        assert!(market_from_history(&history(&[10], &[])).is_none());
+    }
+
+    #[test]
+    fn a_cap_becomes_the_max_fee_or_the_legacy_gas_price() {
+        let market = EvmFeeMarket::Eip1559 {
+            base_fee_per_gas: gwei(30),
+            priority_fees: [gwei(1), gwei(2), gwei(5)],
+        };
+        let params = market.capped_params(gwei(31), EvmFeeSpeed::Normal);
+
+        assert_eq!(
+            params,
+            EvmFeeParams::Eip1559 {
+                max_fee_per_gas: gwei(31),
+                max_priority_fee_per_gas: gwei(2),
+            }
+        );
+        assert_eq!(params.expected_gas_price(&market), gwei(31));
+        assert_eq!(
+            EvmFeeMarket::Legacy { gas_price: gwei(3) }.capped_params(gwei(5), EvmFeeSpeed::Fast),
+            EvmFeeParams::Legacy { gas_price: gwei(5) }
+        );
+    }
+
+    #[test]
+    fn explicit_caps_require_eip1559_and_a_consistent_priority_fee() {
+        let market = EvmFeeMarket::Eip1559 {
+            base_fee_per_gas: gwei(30),
+            priority_fees: [gwei(1), gwei(2), gwei(5)],
+        };
+
+        assert_eq!(
+            send_fee_params(&market, None, Some("50000000000"), None, EvmFeeSpeed::Fast),
+            Ok(EvmFeeParams::Eip1559 {
+                max_fee_per_gas: gwei(50),
+                max_priority_fee_per_gas: gwei(5),
+            })
+        );
+        assert!(send_fee_params(
+            &market,
+            None,
+            Some("1000000000"),
+            Some("2000000000"),
+            EvmFeeSpeed::Normal
+        )
+        .unwrap_err()
+        .contains("cannot exceed"));
+        assert!(send_fee_params(
+            &EvmFeeMarket::Legacy { gas_price: gwei(3) },
+            None,
+            Some("1000000000"),
+            None,
+            EvmFeeSpeed::Normal
+        )
+        .unwrap_err()
+        .contains("EIP-1559"));
+    }
+
+    #[test]
+    fn replacements_bump_the_original_fees_or_follow_the_fast_tier() {
+        let market = EvmFeeMarket::Eip1559 {
+            base_fee_per_gas: gwei(30),
+            priority_fees: [gwei(1), gwei(2), gwei(5)],
+        };
+
+        assert_eq!(
+            replacement_params(
+                Some(EvmFeeParams::Eip1559 {
+                    max_fee_per_gas: gwei(100),
+                    max_priority_fee_per_gas: gwei(1),
+                }),
+                &market
+            ),
+            EvmFeeParams::Eip1559 {
+                max_fee_per_gas: gwei(110) + 1,
+                max_priority_fee_per_gas: gwei(5),
+            }
+        );
+        assert_eq!(
+            replacement_params(
+                Some(EvmFeeParams::Legacy {
+                    gas_price: gwei(10)
+                }),
+                &market
+            ),
+            EvmFeeParams::Legacy {
+                gas_price: gwei(65)
+            }
+        );
+        assert_eq!(
+            replacement_params(None, &market),
+            market.params(EvmFeeSpeed::Fast)
+        );
+    }
+
+    #[test]
+    fn sent_fee_params_read_type_2_caps_before_the_gas_price() {
+        let tx = Transaction {
+            gas_price: Some(gwei(31)),
+            max_fee_per_gas: Some(gwei(60)),
+            max_priority_fee_per_gas: Some(gwei(1)),
+            ..Default::default()
+        };
+        assert_eq!(
+            sent_fee_params(&tx),
+            Some(EvmFeeParams::Eip1559 {
+                max_fee_per_gas: gwei(60),
+                max_priority_fee_per_gas: gwei(1),
+            })
+        );
+
+        let legacy = Transaction {
+            gas_price: Some(gwei(5)),
+            ..Default::default()
+        };
+        assert_eq!(
+            sent_fee_params(&legacy),
+            Some(EvmFeeParams::Legacy { gas_price: gwei(5) })
+        );
+    }
+
+    #[test]
+    fn receipt_fee_uses_the_effective_gas_price() {
+        let mut receipt = TransactionReceipt {
+            gas_used: Some(U256::from(21_000)),
+            effective_gas_price: Some(gwei(12)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            receipt_fee(&receipt),
+            Some(EvmReceiptFee {
+                gas_used: "21000".to_string(),
+                effective_gas_price: "12000000000".to_string(),
+                fee: 0.000252,
+            })
+        );
+
+        receipt.effective_gas_price = None;
+        assert_eq!(receipt_fee(&receipt), None);
+    }
+
+    #[test]
+    fn receipt_fee_beyond_u128_does_not_panic() {
+        let receipt = TransactionReceipt {
+            gas_used: Some(U256::from(u128::MAX)),
+            effective_gas_price: Some(U256::from(4)),
+            ..Default::default()
+        };
+
+        let fee = receipt_fee(&receipt).unwrap().fee;
+        assert!((fee / (u128::MAX as f64 * 4e-18) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn eip1559_params_build_type_2_transactions() {
+        let to = Address::repeat_byte(0x11);
+        let tx = EvmFeeParams::Eip1559 {
+            max_fee_per_gas: gwei(40),
+            max_priority_fee_per_gas: gwei(2),
+        }
+        .apply(
+            TransactionRequest::new()
+                .to(to)
+                .value(7)
+                .gas(21_000)
+                .chain_id(1),
+        );
+
+        let TypedTransaction::Eip1559(request) = tx else {
+            panic!("expected a type-2 transaction");
+        };
+        assert_eq!(request.to, Some(NameOrAddress::Address(to)));
+        assert_eq!(request.value, Some(U256::from(7)));
+        assert_eq!(request.gas, Some(U256::from(21_000)));
+        assert_eq!(request.max_fee_per_gas, Some(gwei(40)));
+        assert_eq!(request.max_priority_fee_per_gas, Some(gwei(2)));
+    }
+}
@@ -1,10 +1,15 @@
 pub mod balance;
 pub mod commands;
 pub mod config;
+pub mod gas;
 pub mod mnemonic;
+pub mod networks;
+pub mod nonce;
 pub mod price;
 pub mod price_manager;
 pub mod private_key;
 pub mod provider;
+pub mod replacement;
+pub mod tokens;
 pub mod transaction;
 pub mod wallet;
@@ -0,0 +1,235 @@
+use crate::wallet::evm::config::{is_builtin_chain, set_custom_networks};
+use crate::wallet::evm::provider::ProviderRegistry;
+use crate::wallet::types::{EvmNetwork, SaveEvmNetworkRequest};
+use crate::DB;
+use chrono::Utc;
+use ethers::providers::{Http, Middleware, Provider};
+
+const DEFAULT_NATIVE_DECIMALS: u8 = 18;
+/// 10^77 is the largest power of ten a U256 holds.
+const MAX_NATIVE_DECIMALS: u8 = 77;
+
+/// Registers the stored user-defined networks so chain lookups see them.
+pub fn reload_custom_networks() -> Result<(), String> {
+    let networks = {
+        let db = DB.lock().map_err(|e| e.to_string())?;
+        db.get_evm_networks()
+            .map_err(|e| format!("Failed to get EVM networks: {}", e))?
+    };
+    set_custom_networks(networks);
+    Ok(())
+}
+
+/// Adds or updates a network after checking that its RPC serves the chain id
+/// it claims.
+pub async fn save_network(request: SaveEvmNetworkRequest) -> Result<EvmNetwork, String> {
+    let request = normalize_network_request(request)?;
+    if is_builtin_chain(request.chain_id, &request.name) {
+        return Err(format!(
+            "Chain ID {} or name {} is already a built-in network",
+            request.chain_id, request.name
+        ));
+    }
+
+    let reported_chain_id = probe_chain_id(&request.rpc_url).await?;
+    if reported_chain_id != request.chain_id {
+        return Err(format!(
+            "RPC reports chain ID {} instead of {}",
+            reported_chain_id, request.chain_id
+        ));
+    }
+
+    let existing = {
+        let db = DB.lock().map_err(|e| e.to_string())?;
+        db.get_evm_networks()
+            .map_err(|e| format!("Failed to get EVM networks: {}", e))?
+    };
+    if existing.iter().any(|network| {
+        network.chain_id != request.chain_id && network.name.eq_ignore_ascii_case(&request.name)
+    }) {
+        return Err(format!("Another network is already named {}", request.name));
+    }
+
+    let now = Utc::now().to_rfc3339();
+    let network = EvmNetwork {
+        chain_id: request.chain_id,
+        display_name: request.display_name.unwrap_or_else(|| request.name.clone()),
+        name: request.name,
+        native_symbol: request.native_symbol,
+        native_decimals: request.native_decimals.unwrap_or(DEFAULT_NATIVE_DECIMALS),
+        rpc_url: request.rpc_url,
+        wss_url: request.wss_url,
+        explorer_api_url: request.explorer_api_url,
+        is_testnet: request.is_testnet,
+        created_at: existing
+            .iter()
+            .find(|network| network.chain_id == request.chain_id)
+            .map(|network| network.created_at.clone())
+            .unwrap_or_else(|| now.clone()),
+        updated_at: now,
+    };
+
+    {
+        let db = DB.lock().map_err(|e| e.to_string())?;
+        db.save_evm_network(&network)
+            .map_err(|e| format!("Failed to save EVM network: {}", e))?;
+    }
+    reload_custom_networks()?;
+    ProviderRegistry::evict(network.chain_id).await;
+
+    Ok(network)
+}
+
+pub async fn delete_network(chain_id: u64) -> Result<bool, String> {
+    let deleted = {
+        let db = DB.lock().map_err(|e| e.to_string())?;
+        db.delete_evm_network(chain_id)
+            .map_err(|e| format!("Failed to delete EVM network: {}", e))?
+    };
+    reload_custom_networks()?;
+    ProviderRegistry::evict(chain_id).await;
+    Ok(deleted)
+}
+
+/// Asks the RPC which chain it serves (`eth_chainId`).
+pub async fn probe_chain_id(rpc_url: &str) -> Result<u64, String> {
+    let provider = Provider::<Http>::try_from(rpc_url)
+        .map_err(|e| format!("Failed to create provider: {}", e))?;
+    let chain_id = provider
+        .get_chainid()
+        .await
+        .map_err(|e| format!("Failed to reach RPC: {}", e))?;
+    if chain_id > u64::MAX.into() {
+        return Err(format!("RPC reported an invalid chain ID: {}", chain_id));
+    }
+    Ok(chain_id.as_u64())
+}
+
+fn normalize_network_request(
+    mut request: SaveEvmNetworkRequest,
+) -> Result<SaveEvmNetworkRequest, String> {
+    if request.chain_id == 0 {
+        return Err("Chain ID must be greater than 0".to_string());
+    }
+
+    request.name = request.name.trim().to_lowercase();
+    if request.name.is_empty()
+        || !request
+            .name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(
+            "Network name must be letters, digits, '-' or '_', e.g. base or anvil-local"
+                .to_string(),
+        );
+    }
+    request.display_name = request
+        .display_name
+        .map(|display_name| display_name.trim().to_string())
+        .filter(|display_name| !display_name.is_empty());
+
+    request.native_symbol = request.native_symbol.trim().to_string();
+    if request.native_symbol.is_empty() {
+        return Err("Native currency symbol is required".to_string());
+    }
+    if request
+        .native_decimals
+        .is_some_and(|decimals| decimals > MAX_NATIVE_DECIMALS)
+    {
+        return Err(format!(
+            "Native currency decimals must be at most {}",
+            MAX_NATIVE_DECIMALS
+        ));
+    }
+
+    request.rpc_url = normalize_url(&request.rpc_url, &["http://", "https://"], "RPC")?;
+    request.wss_url = match request.wss_url.as_deref().map(str::trim) {
+        Some(url) if !url.is_empty() => Some(normalize_url(url, &["ws://", "wss://"], "WSS")?),
+        _ => None,
+    };
+    request.explorer_api_url = match request.explorer_api_url.as_deref().map(str::trim) {
+        Some(url) if !url.is_empty() => Some(normalize_url(
+            url,
+            &["http://", "https://"],
+            "Explorer API",
+        )?),
+        _ => None,
+    };
+
+    Ok(request)
+}
+
+fn normalize_url(url: &str, schemes: &[&str], label: &str) -> Result<String, String> {
+    let url = url.trim();
+    if !schemes.iter().any(|scheme| url.starts_with(scheme)) {
+        return Err(format!(
+            "{} URL must start with {}",
+            label,
+            schemes.join(" or ")
+        ));
+    }
+    Ok(url.trim_end_matches('/').to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> SaveEvmNetworkRequest {
+        SaveEvmNetworkRequest {
+            chain_id: 8453,
+            name: " Base ".to_string(),
+            display_name: Some("  ".to_string()),
+            native_symbol: " ETH ".to_string(),
+            native_decimals: None,
+            rpc_url: "https://mainnet.base.org/".to_string(),
+            wss_url: Some(String::new()),
+            explorer_api_url: Some("https://api.basescan.org/api".to_string()),
+            is_testnet: false,
+        }
+    }
+
+    #[test]
+    fn normalize_network_request_trims_and_drops_empty_fields() {
+        let request = normalize_network_request(request()).unwrap();
+
+        assert_eq!(request.name, "base");
+        assert_eq!(request.display_name, None);
+        assert_eq!(request.native_symbol, "ETH");
+        assert_eq!(request.rpc_url, "https://mainnet.base.org");
+        assert_eq!(request.wss_url, None);
+        assert_eq!(
+            request.explorer_api_url.as_deref(),
+            Some("https://api.basescan.org/api")
+        );
+    }
+
+    #[test]
+    fn normalize_network_request_rejects_bad_names_and_urls() {
+        for invalid in [
+            SaveEvmNetworkRequest {
+                name: "base mainnet".to_string(),
+                ..request()
+            },
+            SaveEvmNetworkRequest {
+                rpc_url: "ws://127.0.0.1:8545".to_string(),
+                ..request()
+            },
+            SaveEvmNetworkRequest {
+                wss_url: Some("http://127.0.0.1:8546".to_string()),
+                ..request()
+            },
+            SaveEvmNetworkRequest {
+                native_decimals: Some(78),
+                ..request()
+            },
+            SaveEvmNetworkRequest {
+                chain_id: 0,
+                ..request()
+            },
+        ] {
+            assert!(normalize_network_request(invalid).is_err());
+        }
+    }
+}
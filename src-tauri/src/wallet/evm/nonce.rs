@@ -0,0 +1,314 @@
+use crate::wallet::evm::config::get_chain_by_id;
+use crate::wallet::transaction_types::TransactionStatus;
+use crate::DB;
+use chrono::{DateTime, Utc};
+use ethers::providers::{Http, Middleware, Provider};
+use ethers::types::{Address as EthAddress, BlockNumber, H256, U256};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+/// How long a stored transaction the node does not know keeps its nonce.
+/// Behind a load balancer the node asked may not have seen it yet.
+const DROP_GRACE: Duration = Duration::from_secs(10 * 60);
+
+type NonceLocks = HashMap<(String, u64), Arc<AsyncMutex<()>>>;
+
+/// One lock per (address, chain id), held from picking a nonce until the
+/// transaction that uses it is stored, so concurrent sends never share one.
+static NONCE_LOCKS: Lazy<Mutex<NonceLocks>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A nonce reserved for one outgoing transaction. Keep it alive until the
+/// transaction is stored in `evm_transactions`.
+pub(crate) struct NonceReservation {
+    pub nonce: U256,
+    _guard: OwnedMutexGuard<()>,
+}
+
+/// Picks the next nonce of an address: the node's pending count, or one past
+/// the highest nonce still in flight locally that the node knows but has not
+/// counted yet. In-flight transactions the node still does not know once
+/// `DROP_GRACE` has passed were lost, so they are marked dropped and the lowest
+/// nonce they free is reused, since every later transaction waits on it.
+pub(crate) async fn reserve_nonce<M: Middleware>(
+    provider: &M,
+    address: EthAddress,
+    chain_id: u64,
+) -> Result<NonceReservation, String> {
+    let key = (format!("{:?}", address), chain_id);
+    let lock = {
+        let mut locks = NONCE_LOCKS.lock().map_err(|e| e.to_string())?;
+        locks.entry(key.clone()).or_default().clone()
+    };
+    let guard = lock.lock_owned().await;
+
+    let pending = provider
+        .get_transaction_count(address, Some(BlockNumber::Pending.into()))
+        .await
+        .map_err(|e| format!("Failed to get nonce: {}", e))?;
+    let in_flight = {
+        let db = DB.lock().map_err(|e| e.to_string())?;
+        db.get_in_flight_evm_nonces(&key.0, chain_id)
+            .map_err(|e| format!("Failed to get stored nonces: {}", e))?
+    };
+
+    let mut held = Vec::new();
+    let mut dropped = Vec::new();
+    for (tx_hash, nonce, created_at) in in_flight {
+        // Nonces below the pending count are used whatever became of them
+        if U256::from(nonce) < pending {
+            continue;
+        }
+        let Ok(hash) = H256::from_str(&tx_hash) else {
+            continue;
+        };
+        let seen = provider
+            .get_transaction(hash)
+            .await
+            .map_err(|e| format!("Failed to look up transaction: {}", e))?
+            .is_some();
+        if seen || within_drop_grace(&created_at, Utc::now()) {
+            held.push(nonce);
+        } else {
+            crate::safe_log!(
+                "[WARN] In-flight transaction {} is unknown to the node; marking it dropped",
+                tx_hash
+            );
+            let db = DB.lock().map_err(|e| e.to_string())?;
+            db.update_evm_transaction_lifecycle(
+                &tx_hash,
+                chain_id,
+                TransactionStatus::Dropped,
+                None,
+            )
+            .map_err(|e| format!("Failed to update transaction: {}", e))?;
+            dropped.push(nonce);
+        }
+    }
+
+    Ok(NonceReservation {
+        nonce: next_nonce(pending, &held, &dropped),
+        _guard: guard,
+    })
+}
+
+/// Timestamps that do not parse count as old, so a bad row cannot hold a
+/// nonce forever.
+fn within_drop_grace(created_at: &str, now: DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(created_at)
+        .map(|created_at| {
+            now.signed_duration_since(created_at)
+                .to_std()
+                .map_or(true, |age| age < DROP_GRACE)
+        })
+        .unwrap_or(false)
+}
+
+/// `held` and `dropped` are in-flight nonces at or above `pending`. A dropped
+/// nonce no other transaction still holds is a gap the node waits on.
+fn next_nonce(pending: U256, held: &[u64], dropped: &[u64]) -> U256 {
+    let gap = dropped
+        .iter()
+        .filter(|nonce| !held.contains(nonce))
+        .min()
+        .copied();
+    match (gap, held.iter().max()) {
+        (Some(nonce), _) => U256::from(nonce),
+        (None, Some(&nonce)) => pending.max(U256::from(nonce) + 1),
+        (None, None) => pending,
+    }
+}
+
+/// Settles a stored transaction against the other transactions of its nonce.
+/// Once it is mined, the speed-ups and cancels it raced are replaced. While it
+/// is unmined but its nonce is already used, it was replaced by one of its
+/// own speed-ups or cancels, or else dropped for a transaction sent elsewhere.
+pub(crate) async fn reconcile_nonce_lifecycle(
+    wallet_id: &str,
+    tx_hash: &str,
+    chain_id: u64,
+    status: TransactionStatus,
+    block_number: Option<u64>,
+) -> Result<TransactionStatus, String> {
+    let stored = {
+        let db = DB.lock().map_err(|e| e.to_string())?;
+        db.get_evm_transaction(wallet_id, tx_hash, chain_id)
+            .map_err(|e| format!("Failed to get transaction: {}", e))?
+    };
+    let Some((tx, nonce)) = stored.and_then(|tx| tx.nonce.map(|nonce| (tx, nonce))) else {
+        return Ok(status);
+    };
+
+    if block_number.is_some() {
+        let db = DB.lock().map_err(|e| e.to_string())?;
+        db.mark_evm_nonce_siblings_replaced(&tx.from_address, chain_id, nonce, tx_hash)
+            .map_err(|e| format!("Failed to reconcile replaced transactions: {}", e))?;
+        return Ok(status);
+    }
+
+    let chain_config =
+        get_chain_by_id(chain_id).ok_or_else(|| format!("Chain ID {} not supported", chain_id))?;
+    let provider = Provider::<Http>::try_from(chain_config.rpc_url())
+        .map_err(|e| format!("Failed to create provider: {}", e))?;
+    let from_address = EthAddress::from_str(&tx.from_address)
+        .map_err(|e| format!("Invalid wallet address: {}", e))?;
+    let mined_count = provider
+        .get_transaction_count(from_address, Some(BlockNumber::Latest.into()))
+        .await
+        .map_err(|e| format!("Failed to get nonce: {}", e))?;
+
+    Ok(unmined_status(
+        status,
+        nonce,
+        mined_count,
+        tx.replaced_by_tx_hash.is_some(),
+    ))
+}
+
+fn unmined_status(
+    status: TransactionStatus,
+    nonce: u64,
+    mined_count: U256,
+    has_replacement: bool,
+) -> TransactionStatus {
+    if mined_count <= U256::from(nonce) {
+        status
+    } else if has_replacement {
+        TransactionStatus::Replaced
+    } else {
+        TransactionStatus::Dropped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::security::secret_envelope::{StoredSecret, SECRET_FORMAT_PLAINTEXT_V0};
+    use crate::wallet::transaction_types::{EvmTransaction, TransactionType};
+    use ethers::types::Transaction;
+    use uuid::Uuid;
+
+    fn in_flight_transfer(wallet_id: &str, from_address: &str, nonce: u64) -> EvmTransaction {
+        EvmTransaction {
+            id: Uuid::new_v4().to_string(),
+            wallet_id: wallet_id.to_string(),
+            tx_hash: format!("{:?}", H256::random()),
+            tx_type: TransactionType::Send,
+            from_address: from_address.to_string(),
+            to_address: format!("{:?}", EthAddress::random()),
+            amount: "1".to_string(),
+            amount_float: 1.0,
+            asset_symbol: "ETH".to_string(),
+            asset_name: "Ethereum".to_string(),
+            contract_address: None,
+            chain: "sepolia".to_string(),
+            chain_id: 11155111,
+            gas_used: "21000".to_string(),
+            gas_price: "1".to_string(),
+            fee: 0.0,
+            status: TransactionStatus::Broadcasted,
+            block_number: None,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            nonce: Some(nonce),
+            replaces_tx_hash: None,
+            replaced_by_tx_hash: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn in_flight_transactions_unknown_to_the_node_are_dropped_after_the_grace() {
+        let address = EthAddress::random();
+        let from_address = format!("{:?}", address);
+        let (known, lost, unseen) = {
+            let db = DB.lock().unwrap();
+            let wallet = db
+                .insert_evm_wallet_with_secret(
+                    "Nonce".to_string(),
+                    "private-key".to_string(),
+                    from_address.clone(),
+                    StoredSecret {
+                        secret_data: "test-secret".to_string(),
+                        secret_format: SECRET_FORMAT_PLAINTEXT_V0.to_string(),
+                    },
+                    "private-key".to_string(),
+                )
+                .unwrap();
+            let known = in_flight_transfer(&wallet.id, &from_address, 5);
+            let mut lost = in_flight_transfer(&wallet.id, &from_address, 6);
+            lost.created_at = (Utc::now() - chrono::Duration::minutes(30)).to_rfc3339();
+            let unseen = in_flight_transfer(&wallet.id, &from_address, 7);
+            db.add_evm_transaction(&known).unwrap();
+            db.add_evm_transaction(&lost).unwrap();
+            db.add_evm_transaction(&unseen).unwrap();
+            (known, lost, unseen)
+        };
+
+        // Responses are served last pushed first
+        let (provider, mock) = Provider::mocked();
+        mock.push(serde_json::Value::Null).unwrap();
+        mock.push(serde_json::Value::Null).unwrap();
+        mock.push(Transaction::default()).unwrap();
+        mock.push(U256::from(5)).unwrap();
+
+        let reservation = reserve_nonce(&provider, address, 11155111).await.unwrap();
+        assert_eq!(reservation.nonce, U256::from(6));
+
+        let db = DB.lock().unwrap();
+        let status = |tx: &EvmTransaction| {
+            db.get_evm_transaction(&tx.wallet_id, &tx.tx_hash, 11155111)
+                .unwrap()
+                .unwrap()
+                .status
+        };
+        assert_eq!(status(&known), TransactionStatus::Broadcasted);
+        assert_eq!(status(&lost), TransactionStatus::Dropped);
+        assert_eq!(status(&unseen), TransactionStatus::Broadcasted);
+    }
+
+    #[test]
+    fn drop_grace_runs_from_when_the_transaction_was_stored() {
+        let now = Utc::now();
+        let stored = |minutes: i64| (now - chrono::Duration::minutes(minutes)).to_rfc3339();
+
+        assert!(within_drop_grace(&stored(1), now));
+        assert!(!within_drop_grace(&stored(30), now));
+        assert!(!within_drop_grace("not a timestamp", now));
+    }
+
+    #[test]
+    fn next_nonce_skips_past_locally_tracked_transactions() {
+        assert_eq!(next_nonce(U256::from(4), &[], &[]), U256::from(4));
+        assert_eq!(next_nonce(U256::from(4), &[5], &[]), U256::from(6));
+        assert_eq!(next_nonce(U256::from(9), &[5], &[]), U256::from(9));
+    }
+
+    #[test]
+    fn next_nonce_fills_the_lowest_dropped_gap() {
+        assert_eq!(next_nonce(U256::from(5), &[5, 7], &[6]), U256::from(6));
+        assert_eq!(next_nonce(U256::from(5), &[7], &[8, 6]), U256::from(6));
+        // A nonce still held by a replacement is not free
+        assert_eq!(next_nonce(U256::from(5), &[6], &[6]), U256::from(7));
+    }
+
+    #[test]
+    fn unmined_transactions_with_a_used_nonce_are_replaced_or_dropped() {
+        let pending = TransactionStatus::Broadcasted;
+
+        assert_eq!(
+            unmined_status(pending, 7, U256::from(7), true),
+            TransactionStatus::Broadcasted
+        );
+        assert_eq!(
+            unmined_status(pending, 7, U256::from(8), true),
+            TransactionStatus::Replaced
+        );
+        assert_eq!(
+            unmined_status(pending, 7, U256::from(8), false),
+            TransactionStatus::Dropped
+        );
+    }
+}
@@ -55,6 +55,18 @@ fn get_coingecko_id(symbol: &str) -> Option<&'static str> {
     }
 }
 
+/// CoinGecko asset platform of a chain, for pricing tokens by contract
+fn coingecko_platform(chain_id: u64) -> Option<&'static str> {
+    match chain_id {
+        1 => Some("ethereum"),
+        42161 => Some("arbitrum-one"),
+        10 => Some("optimistic-ethereum"),
+        137 => Some("polygon-pos"),
+        56 => Some("binance-smart-chain"),
+        _ => None,
+    }
+}
+
 /// Check if a symbol is a stablecoin and return its fixed price
 fn get_stablecoin_price(symbol: &str) -> Option<f64> {
     match symbol.to_uppercase().as_str() {
@@ -188,12 +200,48 @@ pub async fn fetch_prices(symbols: Vec<String>) -> Result<HashMap<String, (f64,
     Ok(result)
 }
 
+/// Fetch USD prices and 24h changes of tokens by contract address, keyed by
+/// lowercase address. Tokens CoinGecko does not list, including every token
+/// of a chain it has no platform for, are left out.
+pub async fn fetch_token_prices(
+    chain_id: u64,
+    contract_addresses: &[String],
+) -> Result<HashMap<String, (f64, f64)>, String> {
+    let Some(platform) = coingecko_platform(chain_id) else {
+        return Ok(HashMap::new());
+    };
+    if contract_addresses.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let addresses: Vec<String> = contract_addresses
+        .iter()
+        .map(|address| address.to_lowercase())
+        .collect();
+    let url = format!(
+        "https://api.coingecko.com/api/v3/simple/token_price/{}?contract_addresses={}&vs_currencies=usd&include_24hr_change=true",
+        platform,
+        addresses.join(",")
+    );
+    let prices = fetch_price_map(&url).await?;
+
+    Ok(prices
+        .into_iter()
+        .map(|(address, data)| (address.to_lowercase(), data))
+        .collect())
+}
+
 async fn try_fetch_prices(ids: &str) -> Result<HashMap<String, (f64, f64)>, String> {
     let url = format!(
         "https://api.coingecko.com/api/v3/simple/price?ids={}&vs_currencies=usd&include_24hr_change=true",
         ids
     );
+    fetch_price_map(&url).await
+}
 
+/// A CoinGecko `simple` price response: coin id or token address to price
+/// and 24h change.
+async fn fetch_price_map(url: &str) -> Result<HashMap<String, (f64, f64)>, String> {
     let client = reqwest::Client::builder()
         .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
         .user_agent("aiigo-desktop/0.1.0")
@@ -201,7 +249,7 @@ async fn try_fetch_prices(ids: &str) -> Result<HashMap<String, (f64, f64)>, Stri
         .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
 
     let response = client
-        .get(&url)
+        .get(url)
         .send()
         .await
         .map_err(|e| format!("HTTP request failed: {}", e))?;
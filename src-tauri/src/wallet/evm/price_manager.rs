@@ -98,9 +98,36 @@ impl PriceManager {
             "MATIC".to_string(),
             "BNB".to_string(),
         ];
-
         // Fetch fresh prices
-        let fresh_prices = super::price::fetch_prices(symbols).await?;
+        let mut fresh_prices = super::price::fetch_prices(symbols).await?;
+
+        // Custom tokens are priced by contract, never by their symbol
+        let custom_tokens = crate::DB
+            .lock()
+            .unwrap()
+            .get_evm_custom_tokens(None)
+            .map_err(|e| format!("Failed to get custom tokens: {}", e))?;
+        let mut contracts_by_chain: HashMap<u64, Vec<String>> = HashMap::new();
+        for token in custom_tokens {
+            contracts_by_chain
+                .entry(token.chain_id)
+                .or_default()
+                .push(token.contract_address);
+        }
+        for (chain_id, contract_addresses) in contracts_by_chain {
+            match super::price::fetch_token_prices(chain_id, &contract_addresses).await {
+                Ok(prices) => {
+                    for (contract_address, data) in prices {
+                        fresh_prices.insert(token_price_key(chain_id, &contract_address), data);
+                    }
+                }
+                Err(e) => tracing::warn!(
+                    chain_id = %sanitize(&format!("{}", chain_id)),
+                    error = %sanitize(&format!("{}", e)),
+                    "Custom token price refresh failed"
+                ),
+            }
+        }
 
         // Update cache
         let mut cache = self.cache.lock().unwrap();
@@ -132,6 +159,22 @@ impl PriceManager {
     }
 }
 
+/// Cache key of a custom token's price
+fn token_price_key(chain_id: u64, contract_address: &str) -> String {
+    format!("{}:{}", chain_id, contract_address.to_lowercase())
+}
+
+/// Cache key of an asset's price: its symbol, or its contract for a custom
+/// token so one reusing a well-known symbol is not valued as that coin.
+fn asset_price_key(chain_id: u64, symbol: &str, contract_address: Option<&str>) -> String {
+    match contract_address {
+        Some(contract_address) if super::tokens::is_custom_token(chain_id, contract_address) => {
+            token_price_key(chain_id, contract_address)
+        }
+        _ => symbol.to_string(),
+    }
+}
+
 /// Check if a symbol is a stablecoin and return its fixed price
 fn get_stablecoin_price(symbol: &str) -> Option<f64> {
     match symbol.to_uppercase().as_str() {
@@ -180,6 +223,24 @@ pub fn get_cached_24h_change(symbol: &str) -> Option<f64> {
     PRICE_MANAGER.get_cached_24h_change(symbol)
 }
 
+/// Cached price of an asset tracked on an EVM chain
+pub fn get_cached_asset_price_state(
+    chain_id: u64,
+    symbol: &str,
+    contract_address: Option<&str>,
+) -> PriceState {
+    PRICE_MANAGER.get_cached_price_state(&asset_price_key(chain_id, symbol, contract_address))
+}
+
+/// Cached 24h change of an asset tracked on an EVM chain
+pub fn get_cached_asset_24h_change(
+    chain_id: u64,
+    symbol: &str,
+    contract_address: Option<&str>,
+) -> Option<f64> {
+    PRICE_MANAGER.get_cached_24h_change(&asset_price_key(chain_id, symbol, contract_address))
+}
+
 /// Force refresh all prices (public API for manual refresh)
 pub async fn force_refresh_prices() -> Result<(), String> {
     PRICE_MANAGER.force_refresh().await
@@ -190,3 +251,28 @@ pub async fn refresh_all_prices() -> Result<String, String> {
     force_refresh_prices().await?;
     Ok("Prices refreshed successfully".to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::state::types::PriceStatus;
+
+    #[test]
+    fn custom_tokens_are_priced_by_contract_not_symbol() {
+        let builtin_usdt = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+        let lookalike_usdt = "0x00000000000000000000000000000000000000Aa";
+
+        assert_eq!(asset_price_key(1, "ETH", None), "ETH");
+        assert_eq!(asset_price_key(1, "USDT", Some(builtin_usdt)), "USDT");
+        assert_eq!(
+            asset_price_key(1, "USDT", Some(lookalike_usdt)),
+            "1:0x00000000000000000000000000000000000000aa"
+        );
+
+        // A custom token calling itself USDT is not a $1 stablecoin
+        assert!(matches!(
+            get_cached_asset_price_state(1, "USDT", Some(lookalike_usdt)).status,
+            PriceStatus::Unavailable
+        ));
+    }
+}
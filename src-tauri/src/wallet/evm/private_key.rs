@@ -2,6 +2,7 @@ use crate::wallet::security::backend::SecretBackend;
 use crate::wallet::security::commands::ensure_local_password_configured;
 use crate::wallet::security::commands::AppSecurity;
 use crate::wallet::security::keystore::Keystore;
+use crate::wallet::security::mnemonic_secret::MnemonicSecret;
 use crate::wallet::security::session::SessionManager;
 use crate::wallet::security::types::{SecurityError, SignerOperation};
 use crate::wallet::types::CreateWalletResponse;
@@ -20,6 +21,7 @@ pub(crate) fn map_security_error(error: SecurityError) -> String {
         SecurityError::OperationNotAllowed => "operation_not_allowed".to_string(),
         SecurityError::UnknownWallet => "unknown_wallet".to_string(),
         SecurityError::SecretBackendUnavailable => "secret_backend_unavailable".to_string(),
+        SecurityError::WatchOnly => "watch_only".to_string(),
     }
 }
 
@@ -29,7 +31,7 @@ fn export_mnemonic_inner(
     secret_backend: &SecretBackend,
     keystore: &(dyn Keystore + Send + Sync),
     session_manager: &SessionManager,
-) -> Result<String, String> {
+) -> Result<MnemonicSecret, String> {
     if wallet_type != "mnemonic" {
         return Err("This wallet was imported from a private key, not a mnemonic.".to_string());
     }
@@ -42,6 +44,7 @@ fn export_mnemonic_inner(
         SignerOperation::ExportMnemonic,
     )
     .map_err(map_security_error)?
+    .map(|secret_data| MnemonicSecret::decode(&secret_data))
     .ok_or_else(|| "Wallet secret not found".to_string())
 }
 
@@ -169,7 +172,7 @@ pub fn evm_create_wallet_from_private_key(
 pub fn evm_export_mnemonic(
     wallet_id: String,
     state: tauri::State<'_, AppSecurity>,
-) -> Result<String, String> {
+) -> Result<MnemonicSecret, String> {
     let db = DB.lock().unwrap();
 
     // Get wallet to verify it exists
@@ -217,18 +220,27 @@ pub fn evm_export_private_key(
     )
 }
 
-fn derive_private_key_from_mnemonic(mnemonic_str: &str) -> Result<String, String> {
+/// The wallet at m/44'/60'/0'/0/0 of a stored mnemonic secret, derived under
+/// its BIP39 passphrase when one was set.
+pub(crate) fn wallet_from_mnemonic_secret(secret_data: &str) -> Result<LocalWallet, String> {
     use ethers::signers::coins_bip39::English;
     use ethers::signers::MnemonicBuilder;
 
-    let builder = MnemonicBuilder::<English>::default()
-        .phrase(mnemonic_str)
-        .derivation_path("m/44'/60'/0'/0/0")
-        .map_err(|e| format!("Failed to set derivation path: {}", e))?;
+    let mnemonic = MnemonicSecret::decode(secret_data);
+    let mut builder = MnemonicBuilder::<English>::default().phrase(mnemonic.phrase.as_str());
+    if let Some(passphrase) = mnemonic.passphrase.as_deref() {
+        builder = builder.password(passphrase);
+    }
 
-    let wallet = builder
+    builder
+        .derivation_path("m/44'/60'/0'/0/0")
+        .map_err(|e| format!("Failed to set derivation path: {}", e))?
         .build()
-        .map_err(|e| format!("Failed to build wallet: {}", e))?;
+        .map_err(|e| format!("Failed to build wallet: {}", e))
+}
+
+fn derive_private_key_from_mnemonic(mnemonic_str: &str) -> Result<String, String> {
+    let wallet = wallet_from_mnemonic_secret(mnemonic_str)?;
 
     // Get the signing key and encode as hex
     let key_bytes = wallet.signer().to_bytes();
@@ -237,19 +249,29 @@ fn derive_private_key_from_mnemonic(mnemonic_str: &str) -> Result<String, String
 
 #[cfg(test)]
 mod tests {
-    use super::{export_mnemonic_inner, load_authorized_mnemonic, load_authorized_private_key};
+    use super::{
+        export_mnemonic_inner, load_authorized_mnemonic, load_authorized_private_key,
+        wallet_from_mnemonic_secret,
+    };
     use crate::wallet::security::backend::{SecretBackend, SecretBackendAdapter};
     use crate::wallet::security::keystore::Keystore;
+    use crate::wallet::security::mnemonic_secret::MnemonicSecret;
     use crate::wallet::security::secret_envelope::{SecretEnvelopeError, StoredSecret};
     use crate::wallet::security::session::SessionManager;
     use crate::wallet::security::types::{SecurityError, SignerOperation};
+    use ethers::signers::Signer;
     use std::sync::Arc;
     use std::time::Duration;
 
+    const TEST_MNEMONIC: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
     struct StubKeystore;
 
     struct PanicKeystore;
 
+    struct PassphraseKeystore;
+
     struct ReadySecretBackendAdapter;
 
     struct UnavailableSecretBackendAdapter;
@@ -326,6 +348,18 @@ mod tests {
         }
     }
 
+    impl Keystore for PassphraseKeystore {
+        fn load_mnemonic(&self, _address: &str) -> Result<Option<String>, SecurityError> {
+            Ok(Some(
+                MnemonicSecret::new(TEST_MNEMONIC, Some("TREZOR")).encode(),
+            ))
+        }
+
+        fn load_private_key(&self, _address: &str) -> Result<Option<String>, SecurityError> {
+            panic!("mnemonic wallets have no stored private key");
+        }
+    }
+
     #[test]
     fn export_mnemonic_returns_locked_without_keystore_access() {
         let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
@@ -411,4 +445,36 @@ mod tests {
             Err("reauth_required".to_string())
         );
     }
+
+    #[test]
+    fn passphrase_mnemonic_exports_phrase_and_derives_another_account() {
+        let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
+        let backend = ready_backend();
+        session
+            .authorize_verified_operation(SignerOperation::ExportMnemonic)
+            .unwrap();
+
+        assert_eq!(
+            export_mnemonic_inner(
+                "mnemonic",
+                "0x1234",
+                &backend,
+                &PassphraseKeystore,
+                &session
+            ),
+            Ok(MnemonicSecret::new(TEST_MNEMONIC, Some("TREZOR")))
+        );
+
+        let plain = wallet_from_mnemonic_secret(TEST_MNEMONIC).unwrap();
+        let with_passphrase = wallet_from_mnemonic_secret(
+            &MnemonicSecret::new(TEST_MNEMONIC, Some("TREZOR")).encode(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            format!("{:?}", plain.address()),
+            "0x9858effd232b4033e47d90003d41ec34ecaeda94"
+        );
+        assert_ne!(plain.address(), with_passphrase.address());
+    }
 }
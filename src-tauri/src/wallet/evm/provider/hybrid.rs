@@ -5,7 +5,7 @@ use super::types::{ProviderConfig, ProviderError};
 use crate::wallet::security::sanitize;
 use ethers::providers::{Http, Middleware, Provider};
 use ethers::types::transaction::eip2718::TypedTransaction;
-use ethers::types::{Address, Bytes, U256};
+use ethers::types::{Address, BlockId, Bytes, U256, U64};
 use std::convert::TryFrom;
 use std::sync::Arc;
 use std::time::Instant;
@@ -69,11 +69,15 @@ impl HybridProvider {
         guard.clone()
     }
 
-    pub async fn get_balance(&self, address: Address) -> Result<U256, ProviderError> {
+    pub async fn get_balance(
+        &self,
+        address: Address,
+        block: Option<BlockId>,
+    ) -> Result<U256, ProviderError> {
         if let Some(pool) = self.current_ws_pool().await {
             if let Ok(provider) = pool.acquire().await {
                 let start = Instant::now();
-                match provider.get_balance(address, None).await {
+                match provider.get_balance(address, block).await {
                     Ok(balance) => {
                         self.metrics.record_wss_query(start.elapsed(), true);
                         tracing::info!(
@@ -97,7 +101,7 @@ impl HybridProvider {
 
         let start = Instant::now();
         self.http_provider
-            .get_balance(address, None)
+            .get_balance(address, block)
             .await
             .map(|balance| {
                 self.metrics.record_http_query(start.elapsed(), true);
@@ -117,11 +121,15 @@ impl HybridProvider {
             })
     }
 
-    pub async fn call_contract(&self, tx: &TypedTransaction) -> Result<Bytes, ProviderError> {
+    pub async fn call_contract(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<Bytes, ProviderError> {
         if let Some(pool) = self.current_ws_pool().await {
             if let Ok(provider) = pool.acquire().await {
                 let start = Instant::now();
-                match provider.call(tx, None).await {
+                match provider.call(tx, block).await {
                     Ok(result) => {
                         self.metrics.record_wss_query(start.elapsed(), true);
                         tracing::info!(
@@ -145,7 +153,7 @@ impl HybridProvider {
 
         let start = Instant::now();
         self.http_provider
-            .call(tx, None)
+            .call(tx, block)
             .await
             .map(|result| {
                 self.metrics.record_http_query(start.elapsed(), true);
@@ -164,4 +172,39 @@ impl HybridProvider {
                 ))
             })
     }
+
+    pub async fn get_block_number(&self) -> Result<U64, ProviderError> {
+        if let Some(pool) = self.current_ws_pool().await {
+            if let Ok(provider) = pool.acquire().await {
+                let start = Instant::now();
+                match provider.get_block_number().await {
+                    Ok(block_number) => {
+                        self.metrics.record_wss_query(start.elapsed(), true);
+                        return Ok(block_number);
+                    }
+                    Err(err) => {
+                        self.metrics.record_wss_query(start.elapsed(), false);
+                        tracing::warn!(
+                            chain = %sanitize(&format!("{}", self.chain_name)),
+                            error = %sanitize(&format!("{}", err)),
+                            "WSS block number query failed, falling back to HTTP"
+                        );
+                    }
+                }
+            }
+        }
+
+        let start = Instant::now();
+        self.http_provider
+            .get_block_number()
+            .await
+            .inspect(|_| self.metrics.record_http_query(start.elapsed(), true))
+            .map_err(|e| {
+                self.metrics.record_http_query(start.elapsed(), false);
+                ProviderError::AllProvidersFailed(format!(
+                    "[{}] HTTP block number query failed: {}",
+                    self.chain_name, e
+                ))
+            })
+    }
 }
@@ -13,7 +13,7 @@ static PROVIDER_CACHE: Lazy<RwLock<HashMap<EvmChainConfig, Arc<HybridProvider>>>
 pub struct ProviderRegistry;
 
 impl ProviderRegistry {
-    pub async fn get_or_init(chain: EvmChainConfig) -> Result<Arc<HybridProvider>, ProviderError> {
+    pub async fn get_or_init(chain: &EvmChainConfig) -> Result<Arc<HybridProvider>, ProviderError> {
         if let Some(provider) = Self::try_get(chain).await {
             return Ok(provider);
         }
@@ -37,14 +37,21 @@ impl ProviderRegistry {
 
         let mut cache = PROVIDER_CACHE.write().await;
         Ok(cache
-            .entry(chain)
+            .entry(chain.clone())
             .or_insert_with(|| provider.clone())
             .clone())
     }
 
-    async fn try_get(chain: EvmChainConfig) -> Option<Arc<HybridProvider>> {
+    async fn try_get(chain: &EvmChainConfig) -> Option<Arc<HybridProvider>> {
         let cache = PROVIDER_CACHE.read().await;
-        cache.get(&chain).cloned()
+        cache.get(chain).cloned()
+    }
+
+    /// Drops cached providers for a chain id so the next lookup rebuilds them
+    /// from the current network settings.
+    pub async fn evict(chain_id: u64) {
+        let mut cache = PROVIDER_CACHE.write().await;
+        cache.retain(|chain, _| chain.chain_id() != chain_id);
     }
 
     #[allow(dead_code)]
@@ -0,0 +1,365 @@
+use crate::wallet::evm::balance::units_to_f64;
+use crate::wallet::evm::config::get_chain_by_id;
+use crate::wallet::evm::gas;
+use crate::wallet::evm::private_key::map_security_error;
+use crate::wallet::evm::transaction::{load_signing_secret, wallet_from_signing_secret};
+use crate::wallet::security::backend::SecretBackend;
+use crate::wallet::security::keystore::Keystore;
+use crate::wallet::security::session::SessionManager;
+use crate::wallet::security::types::SignerOperation;
+use crate::wallet::transaction_types::{
+    EvmTransaction, SendTransactionResponse, TransactionStatus, TransactionType,
+};
+use crate::DB;
+use chrono::Utc;
+use ethers::prelude::*;
+use ethers::providers::{Http, Provider};
+use ethers::types::{Address as EthAddress, H256, U256};
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Gas of a plain value transfer, which is all a cancel is.
+const CANCEL_GAS_LIMIT: u64 = 21_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Replacement {
+    /// The same call with higher fees
+    SpeedUp,
+    /// A 0-value self-send that uses up the nonce
+    Cancel,
+}
+
+/// Re-signs a pending transaction at its nonce with bumped fees.
+pub async fn speed_up_evm_transaction(
+    wallet_id: String,
+    tx_hash: String,
+    chain_id: u64,
+    secret_backend: &SecretBackend,
+    keystore: &(dyn Keystore + Send + Sync),
+    session_manager: &SessionManager,
+) -> Result<SendTransactionResponse, String> {
+    replace_evm_transaction(
+        Replacement::SpeedUp,
+        &wallet_id,
+        &tx_hash,
+        chain_id,
+        secret_backend,
+        keystore,
+        session_manager,
+    )
+    .await
+}
+
+/// Replaces a pending transaction with a 0-value self-send at its nonce.
+pub async fn cancel_evm_transaction(
+    wallet_id: String,
+    tx_hash: String,
+    chain_id: u64,
+    secret_backend: &SecretBackend,
+    keystore: &(dyn Keystore + Send + Sync),
+    session_manager: &SessionManager,
+) -> Result<SendTransactionResponse, String> {
+    replace_evm_transaction(
+        Replacement::Cancel,
+        &wallet_id,
+        &tx_hash,
+        chain_id,
+        secret_backend,
+        keystore,
+        session_manager,
+    )
+    .await
+}
+
+async fn replace_evm_transaction(
+    replacement: Replacement,
+    wallet_id: &str,
+    tx_hash: &str,
+    chain_id: u64,
+    secret_backend: &SecretBackend,
+    keystore: &(dyn Keystore + Send + Sync),
+    session_manager: &SessionManager,
+) -> Result<SendTransactionResponse, String> {
+    let (wallet_info, original) = {
+        let db = DB.lock().unwrap();
+        let wallet_info = db
+            .get_evm_wallet(wallet_id)
+            .map_err(|e| format!("Failed to get wallet info: {}", e))?
+            .ok_or_else(|| "Wallet not found".to_string())?;
+        let original = db
+            .get_evm_transaction(wallet_id, tx_hash, chain_id)
+            .map_err(|e| format!("Failed to get transaction: {}", e))?
+            .ok_or_else(|| "Transaction not found".to_string())?;
+        (wallet_info, original)
+    };
+    let nonce = replaceable_nonce(&original, wallet_id)?;
+
+    let signing_secret = load_signing_secret(
+        &wallet_info,
+        secret_backend,
+        keystore,
+        session_manager,
+        SignerOperation::Send,
+    )
+    .map_err(map_security_error)?
+    .ok_or_else(|| "Wallet secret not found".to_string())?;
+
+    let chain_config =
+        get_chain_by_id(chain_id).ok_or_else(|| format!("Chain ID {} not supported", chain_id))?;
+    let provider = Provider::<Http>::try_from(chain_config.rpc_url())
+        .map_err(|e| format!("Failed to create provider: {}", e))?;
+    let provider = Arc::new(provider);
+    let wallet = wallet_from_signing_secret(signing_secret, chain_id)?;
+    let client = SignerMiddleware::new(provider.clone(), wallet);
+
+    let hash = H256::from_str(tx_hash).map_err(|e| format!("Invalid transaction hash: {}", e))?;
+    let receipt = provider
+        .get_transaction_receipt(hash)
+        .await
+        .map_err(|e| format!("Failed to get receipt: {}", e))?;
+    if receipt.is_some() {
+        return Err("Transaction is already mined".to_string());
+    }
+    let sent = provider
+        .get_transaction(hash)
+        .await
+        .map_err(|e| format!("Failed to get transaction: {}", e))?;
+
+    let from_address = EthAddress::from_str(&wallet_info.address)
+        .map_err(|e| format!("Invalid wallet address: {}", e))?;
+    let request = match (replacement, &sent) {
+        (Replacement::SpeedUp, Some(sent)) => {
+            let mut request = TransactionRequest::new()
+                .value(sent.value)
+                .data(sent.input.clone())
+                .gas(sent.gas);
+            request.to = sent.to.map(NameOrAddress::Address);
+            request
+        }
+        (Replacement::SpeedUp, None) => {
+            return Err(
+                "The node no longer knows this transaction, so it cannot be sped up; cancel it instead"
+                    .to_string(),
+            );
+        }
+        (Replacement::Cancel, _) => TransactionRequest::new()
+            .to(from_address)
+            .value(U256::zero())
+            .gas(CANCEL_GAS_LIMIT),
+    };
+    let gas_limit = request.gas.unwrap_or_default();
+
+    let market = gas::fee_market(provider.as_ref()).await?;
+    let fee_params = gas::replacement_params(sent.as_ref().and_then(gas::sent_fee_params), &market);
+    let tx = fee_params.apply(request.nonce(nonce));
+
+    let pending_tx = client
+        .send_transaction(tx, None)
+        .await
+        .map_err(|e| format!("Failed to send replacement transaction: {}", e))?;
+    let replacement_hash = format!("{:?}", *pending_tx);
+
+    let native_symbol = chain_config
+        .assets()
+        .first()
+        .map(|a| a.symbol.clone())
+        .unwrap_or_else(|| "ETH".to_string());
+    let expected_gas_price = fee_params.expected_gas_price(&market);
+    let record = replacement_record(
+        replacement,
+        &original,
+        &replacement_hash,
+        nonce,
+        gas_limit,
+        expected_gas_price,
+        &native_symbol,
+    );
+
+    {
+        let db = DB.lock().unwrap();
+        db.record_evm_replacement(tx_hash, &record)
+            .map_err(|e| format!("Failed to save replacement transaction: {}", e))?;
+    }
+
+    crate::safe_log!(
+        "[INFO] Replaced EVM transaction {} with {} at nonce {}",
+        tx_hash,
+        replacement_hash,
+        nonce
+    );
+
+    Ok(SendTransactionResponse {
+        tx_hash: replacement_hash,
+        message: match replacement {
+            Replacement::SpeedUp => "Speed-up transaction broadcasted".to_string(),
+            Replacement::Cancel => "Cancel transaction broadcasted".to_string(),
+        },
+    })
+}
+
+/// Only the newest unmined transaction of a tracked nonce can be replaced.
+fn replaceable_nonce(tx: &EvmTransaction, wallet_id: &str) -> Result<u64, String> {
+    if tx.wallet_id != wallet_id {
+        return Err("Transaction does not belong to this wallet".to_string());
+    }
+    if !matches!(
+        tx.status,
+        TransactionStatus::Broadcasted | TransactionStatus::Pending
+    ) || tx.block_number.is_some()
+    {
+        return Err("Only pending transactions can be sped up or cancelled".to_string());
+    }
+    if let Some(replaced_by) = &tx.replaced_by_tx_hash {
+        return Err(format!(
+            "Transaction was already replaced by {}; replace that one instead",
+            replaced_by
+        ));
+    }
+    tx.nonce.ok_or_else(|| {
+        "Transaction nonce is unknown; it was sent before nonces were tracked".to_string()
+    })
+}
+
+fn replacement_record(
+    replacement: Replacement,
+    original: &EvmTransaction,
+    tx_hash: &str,
+    nonce: u64,
+    gas_limit: U256,
+    expected_gas_price: U256,
+    native_symbol: &str,
+) -> EvmTransaction {
+    let fee_wei = gas_limit.saturating_mul(expected_gas_price);
+    let now = Utc::now().to_rfc3339();
+    let base = EvmTransaction {
+        id: Uuid::new_v4().to_string(),
+        tx_hash: tx_hash.to_string(),
+        gas_used: gas_limit.to_string(),
+        gas_price: expected_gas_price.to_string(),
+        fee: units_to_f64(fee_wei, 18),
+        status: TransactionStatus::after_broadcast(),
+        block_number: None,
+        timestamp: now.clone(),
+        created_at: now,
+        nonce: Some(nonce),
+        replaces_tx_hash: Some(original.tx_hash.clone()),
+        replaced_by_tx_hash: None,
+        ..original.clone()
+    };
+
+    match replacement {
+        Replacement::SpeedUp => base,
+        Replacement::Cancel => EvmTransaction {
+            tx_type: TransactionType::Send,
+            to_address: original.from_address.clone(),
+            amount: "0".to_string(),
+            amount_float: 0.0,
+            asset_symbol: native_symbol.to_string(),
+            asset_name: native_symbol.to_string(),
+            contract_address: None,
+            ..base
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending_transfer() -> EvmTransaction {
+        EvmTransaction {
+            id: "row-1".to_string(),
+            wallet_id: "evm-wallet-1".to_string(),
+            tx_hash: "0xoriginal".to_string(),
+            tx_type: TransactionType::Send,
+            from_address: "0xfrom".to_string(),
+            to_address: "0xto".to_string(),
+            amount: "1000".to_string(),
+            amount_float: 1.0,
+            asset_symbol: "USDC".to_string(),
+            asset_name: "USD Coin".to_string(),
+            contract_address: Some("0xtoken".to_string()),
+            chain: "ethereum".to_string(),
+            chain_id: 1,
+            gas_used: "65000".to_string(),
+            gas_price: "1000000000".to_string(),
+            fee: 0.000065,
+            status: TransactionStatus::Broadcasted,
+            block_number: None,
+            timestamp: "2026-05-01T00:00:00Z".to_string(),
+            created_at: "2026-05-01T00:00:00Z".to_string(),
+            nonce: Some(7),
+            replaces_tx_hash: None,
+            replaced_by_tx_hash: None,
+        }
+    }
+
+    #[test]
+    fn only_pending_tracked_unreplaced_transactions_are_replaceable() {
+        let tx = pending_transfer();
+        assert_eq!(replaceable_nonce(&tx, "evm-wallet-1"), Ok(7));
+        assert!(replaceable_nonce(&tx, "other-wallet").is_err());
+
+        let mined = EvmTransaction {
+            status: TransactionStatus::Confirmed,
+            block_number: Some(10),
+            ..pending_transfer()
+        };
+        assert!(replaceable_nonce(&mined, "evm-wallet-1")
+            .unwrap_err()
+            .contains("Only pending"));
+
+        let replaced = EvmTransaction {
+            replaced_by_tx_hash: Some("0xspeedup".to_string()),
+            ..pending_transfer()
+        };
+        assert!(replaceable_nonce(&replaced, "evm-wallet-1")
+            .unwrap_err()
+            .contains("0xspeedup"));
+
+        let untracked = EvmTransaction {
+            nonce: None,
+            ..pending_transfer()
+        };
+        assert!(replaceable_nonce(&untracked, "evm-wallet-1")
+            .unwrap_err()
+            .contains("nonce is unknown"));
+    }
+
+    #[test]
+    fn cancel_records_a_zero_value_self_send_linked_to_the_original() {
+        let original = pending_transfer();
+        let cancel = replacement_record(
+            Replacement::Cancel,
+            &original,
+            "0xcancel",
+            7,
+            U256::from(CANCEL_GAS_LIMIT),
+            U256::from(2_000_000_000u64),
+            "ETH",
+        );
+
+        assert_eq!(cancel.tx_hash, "0xcancel");
+        assert_eq!(cancel.to_address, original.from_address);
+        assert_eq!(cancel.amount, "0");
+        assert_eq!(cancel.asset_symbol, "ETH");
+        assert_eq!(cancel.contract_address, None);
+        assert_eq!(cancel.nonce, Some(7));
+        assert_eq!(cancel.replaces_tx_hash.as_deref(), Some("0xoriginal"));
+        assert_eq!(cancel.fee, 0.000042);
+
+        let speed_up = replacement_record(
+            Replacement::SpeedUp,
+            &original,
+            "0xspeedup",
+            7,
+            U256::from(65_000),
+            U256::from(2_000_000_000u64),
+            "ETH",
+        );
+        assert_eq!(speed_up.to_address, "0xto");
+        assert_eq!(speed_up.contract_address.as_deref(), Some("0xtoken"));
+        assert_ne!(speed_up.id, original.id);
+    }
+}
@@ -0,0 +1,229 @@
+use crate::wallet::evm::config::{get_chain_by_id, EvmChainConfig};
+use crate::wallet::evm::provider::{HybridProvider, ProviderRegistry};
+use crate::wallet::security::sanitize;
+use crate::wallet::types::{EvmAsset, EvmCustomToken};
+use crate::DB;
+use chrono::Utc;
+use ethers::abi::{self, ParamType, Token};
+use ethers::prelude::*;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::utils::to_checksum;
+use uuid::Uuid;
+
+const SYMBOL_SELECTOR: [u8; 4] = [0x95, 0xd8, 0x9b, 0x41];
+const NAME_SELECTOR: [u8; 4] = [0x06, 0xfd, 0xde, 0x03];
+const DECIMALS_SELECTOR: [u8; 4] = [0x31, 0x3c, 0xe5, 0x67];
+
+/// 10^77 is the largest power of ten a U256 holds.
+const MAX_TOKEN_DECIMALS: u8 = 77;
+
+/// The built-in assets of a chain followed by the custom tokens added for it.
+pub fn tracked_assets(chain_config: &EvmChainConfig) -> Vec<EvmAsset> {
+    let mut assets = chain_config.assets();
+    let custom_tokens = {
+        let db = DB.lock().unwrap();
+        db.get_evm_custom_tokens(Some(chain_config.chain_id()))
+    };
+
+    match custom_tokens {
+        Ok(tokens) => assets.extend(tokens.iter().map(EvmCustomToken::to_asset)),
+        Err(err) => tracing::warn!(
+            chain = %sanitize(chain_config.name()),
+            error = %sanitize(&format!("{}", err)),
+            "Failed to load custom tokens"
+        ),
+    }
+
+    assets
+}
+
+/// The tracked asset of a chain with the given contract address.
+pub fn find_tracked_asset(
+    chain_config: &EvmChainConfig,
+    contract_address: &str,
+) -> Option<EvmAsset> {
+    tracked_assets(chain_config).into_iter().find(|asset| {
+        asset
+            .contract_address
+            .as_deref()
+            .is_some_and(|address| address.eq_ignore_ascii_case(contract_address))
+    })
+}
+
+/// Whether a contract is one of the tokens a chain tracks out of the box.
+fn is_builtin_token(chain_config: &EvmChainConfig, contract_address: &str) -> bool {
+    chain_config.assets().iter().any(|asset| {
+        asset
+            .contract_address
+            .as_deref()
+            .is_some_and(|address| address.eq_ignore_ascii_case(contract_address))
+    })
+}
+
+/// Whether a token contract on a chain was added by the user.
+pub fn is_custom_token(chain_id: u64, contract_address: &str) -> bool {
+    get_chain_by_id(chain_id)
+        .is_none_or(|chain_config| !is_builtin_token(&chain_config, contract_address))
+}
+
+/// Reads a token's metadata from its contract and starts tracking it.
+pub async fn add_custom_token(
+    chain_id: u64,
+    contract_address: &str,
+) -> Result<EvmCustomToken, String> {
+    let chain_config =
+        get_chain_by_id(chain_id).ok_or_else(|| format!("Chain ID {} not supported", chain_id))?;
+    let token_address: Address = contract_address
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid token address: {}", contract_address))?;
+    let contract_address = to_checksum(&token_address, None);
+
+    if is_builtin_token(&chain_config, &contract_address) {
+        return Err("Token is already tracked on this chain".to_string());
+    }
+
+    let provider = ProviderRegistry::get_or_init(&chain_config)
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to initialize provider for {}: {}",
+                chain_config.name(),
+                e
+            )
+        })?;
+    let symbol = read_text(&provider, token_address, SYMBOL_SELECTOR)
+        .await?
+        .ok_or_else(|| "Contract did not return a token symbol".to_string())?;
+    let name = read_text(&provider, token_address, NAME_SELECTOR)
+        .await?
+        .unwrap_or_else(|| symbol.clone());
+    let decimals = read_decimals(&provider, token_address).await?;
+
+    // Balances are stored per symbol, so two tracked assets cannot share one
+    if tracked_assets(&chain_config).iter().any(|asset| {
+        asset.symbol.eq_ignore_ascii_case(&symbol)
+            && !asset
+                .contract_address
+                .as_deref()
+                .is_some_and(|address| address.eq_ignore_ascii_case(&contract_address))
+    }) {
+        return Err(format!(
+            "Another asset with symbol {} is already tracked on this chain",
+            symbol
+        ));
+    }
+
+    let token = EvmCustomToken {
+        id: Uuid::new_v4().to_string(),
+        chain_id,
+        contract_address,
+        symbol,
+        name,
+        decimals,
+        created_at: Utc::now().to_rfc3339(),
+    };
+
+    let db = DB.lock().unwrap();
+    db.save_evm_custom_token(&token)
+        .map_err(|e| format!("Failed to save custom token: {}", e))?;
+    db.get_evm_custom_tokens(Some(chain_id))
+        .map_err(|e| format!("Failed to get custom tokens: {}", e))?
+        .into_iter()
+        .find(|saved| saved.contract_address == token.contract_address)
+        .ok_or_else(|| "Custom token not found".to_string())
+}
+
+async fn call_view(
+    provider: &HybridProvider,
+    token_address: Address,
+    selector: [u8; 4],
+) -> Result<Bytes, String> {
+    let tx: TypedTransaction = TransactionRequest::new()
+        .to(token_address)
+        .data(Bytes::from(selector.to_vec()))
+        .into();
+    provider
+        .call_contract(&tx, None)
+        .await
+        .map_err(|e| format!("Failed to read token metadata: {}", e))
+}
+
+async fn read_text(
+    provider: &HybridProvider,
+    token_address: Address,
+    selector: [u8; 4],
+) -> Result<Option<String>, String> {
+    let result = call_view(provider, token_address, selector).await?;
+    Ok(decode_text(result.as_ref()))
+}
+
+async fn read_decimals(provider: &HybridProvider, token_address: Address) -> Result<u8, String> {
+    let result = call_view(provider, token_address, DECIMALS_SELECTOR).await?;
+    decode_decimals(result.as_ref())
+}
+
+/// Decodes an ABI `string`, or the `bytes32` that older tokens such as MKR
+/// return instead.
+fn decode_text(data: &[u8]) -> Option<String> {
+    let text = match abi::decode(&[ParamType::String], data) {
+        Ok(tokens) => match tokens.into_iter().next() {
+            Some(Token::String(text)) => text,
+            _ => return None,
+        },
+        Err(_) if data.len() == 32 => {
+            let end = data
+                .iter()
+                .position(|byte| *byte == 0)
+                .unwrap_or(data.len());
+            String::from_utf8(data[..end].to_vec()).ok()?
+        }
+        Err(_) => return None,
+    };
+
+    let text = text.trim().to_string();
+    if text.is_empty() || text.chars().any(char::is_control) {
+        return None;
+    }
+    Some(text)
+}
+
+fn decode_decimals(data: &[u8]) -> Result<u8, String> {
+    if data.len() < 32 {
+        return Err("Contract did not return token decimals".to_string());
+    }
+    let decimals = U256::from_big_endian(&data[..32]);
+    if decimals > U256::from(MAX_TOKEN_DECIMALS) {
+        return Err(format!("Unsupported token decimals: {}", decimals));
+    }
+    Ok(decimals.as_u32() as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_text_reads_abi_strings_and_bytes32_symbols() {
+        let encoded = abi::encode(&[Token::String("Wrapped Ether".to_string())]);
+        assert_eq!(decode_text(&encoded), Some("Wrapped Ether".to_string()));
+
+        let mut bytes32 = [0u8; 32];
+        bytes32[..3].copy_from_slice(b"MKR");
+        assert_eq!(decode_text(&bytes32), Some("MKR".to_string()));
+
+        assert_eq!(decode_text(&[0u8; 32]), None);
+        assert_eq!(decode_text(&[]), None);
+    }
+
+    #[test]
+    fn decode_decimals_rejects_missing_and_oversized_values() {
+        let mut word = [0u8; 32];
+        word[31] = 6;
+        assert_eq!(decode_decimals(&word), Ok(6));
+
+        word[31] = 78;
+        assert!(decode_decimals(&word).is_err());
+        assert!(decode_decimals(&[]).is_err());
+    }
+}
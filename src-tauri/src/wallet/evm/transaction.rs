@@ -1,6 +1,11 @@
-use crate::wallet::evm::config::get_chain_by_id;
+use crate::wallet::evm::balance::units_to_f64;
+use crate::wallet::evm::config::{get_chain_by_id, EvmChainConfig};
+use crate::wallet::evm::gas::{self, EvmFeeParams};
+use crate::wallet::evm::nonce;
+use crate::wallet::evm::tokens;
 use crate::wallet::evm::private_key::{
     load_authorized_mnemonic, load_authorized_private_key, map_security_error,
+    wallet_from_mnemonic_secret,
 };
 use crate::wallet::security::backend::SecretBackend;
 use crate::wallet::security::keystore::Keystore;
@@ -8,15 +13,14 @@ use crate::wallet::security::session::SessionManager;
 use crate::wallet::security::types::{SecurityError, SignerOperation};
 use crate::wallet::sync::types::EVM_MIN_BLOCK_DEPTH;
 use crate::wallet::transaction_types::{
-    EvmTransaction, SendEvmRequest, SendTransactionResponse, TransactionStatus, TransactionType,
+    EvmFeeSpeed, EvmTransaction, SendEvmRequest, SendTransactionResponse, TransactionStatus,
+    TransactionType,
 };
 use crate::wallet::types::WalletInfo;
 use crate::DB;
 use chrono::Utc;
 use ethers::prelude::*;
 use ethers::providers::{Http, Provider};
-use ethers::signers::coins_bip39::English;
-use ethers::signers::MnemonicBuilder;
 use ethers::types::{Address as EthAddress, TransactionReceipt, H256, U256};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
@@ -61,6 +65,8 @@ struct EtherscanTransaction {
     input: String,
     #[serde(rename = "methodId", default)]
     method_id: String,
+    #[serde(default)]
+    nonce: String,
 }
 
 impl Default for EtherscanTransaction {
@@ -82,6 +88,7 @@ impl Default for EtherscanTransaction {
             token_decimal: None,
             input: String::new(),
             method_id: String::new(),
+            nonce: String::new(),
         }
     }
 }
@@ -94,19 +101,38 @@ struct EtherscanResponse {
 }
 
 fn get_etherscan_api_url(chain_id: u64) -> Option<String> {
-    match chain_id {
-        1 => Some("https://api.etherscan.io/v2/api".to_string()),
-        56 => Some("https://api.bscscan.com/v2/api".to_string()),
-        137 => Some("https://api.polygonscan.com/v2/api".to_string()),
-        42161 => Some("https://api.arbiscan.io/v2/api".to_string()),
-        10 => Some("https://api-optimistic.etherscan.io/v2/api".to_string()),
-        _ => None,
+    get_chain_by_id(chain_id).and_then(|chain| chain.explorer_api_url())
+}
+
+/// The bundled key belongs to Etherscan; user-defined explorers never see it.
+fn etherscan_api_key(chain: &EvmChainConfig) -> Option<&'static str> {
+    match chain {
+        EvmChainConfig::Custom(_) => None,
+        _ => Some(ETHERSCAN_API_KEY),
     }
 }
 
+fn etherscan_query_url(
+    api_url: &str,
+    action: &str,
+    address: &str,
+    chain_id: u64,
+    api_key: Option<&str>,
+) -> String {
+    let mut url = format!(
+        "{}?chainid={}&module=account&action={}&address={}&startblock=0&endblock=99999999&sort=desc",
+        api_url, chain_id, action, address
+    );
+    if let Some(api_key) = api_key {
+        url.push_str("&apikey=");
+        url.push_str(api_key);
+    }
+    url
+}
+
 fn estimated_fee_snapshot(gas_limit: U256, gas_price: U256) -> (String, String, f64) {
     let fee_wei = gas_limit.saturating_mul(gas_price);
-    let fee = fee_wei.as_u128() as f64 / 1e18;
+    let fee = units_to_f64(fee_wei, 18);
 
     (gas_limit.to_string(), gas_price.to_string(), fee)
 }
@@ -123,24 +149,26 @@ pub async fn fetch_evm_transaction_history(
 
     let chain_config =
         get_chain_by_id(chain_id).ok_or_else(|| format!("Chain ID {} not supported", chain_id))?;
+    let api_key = etherscan_api_key(&chain_config);
 
     let mut all_transactions = Vec::new();
 
     // Fetch normal transactions
-    let normal_txs = fetch_normal_transactions(&api_url, &address, chain_id).await?;
+    let normal_txs = fetch_normal_transactions(&api_url, &address, chain_id, api_key).await?;
     all_transactions.extend(normal_txs);
 
     // Fetch ERC20 token transfers
-    let token_txs = fetch_token_transactions(&api_url, &address, chain_id).await?;
+    let token_txs = fetch_token_transactions(&api_url, &address, chain_id, api_key).await?;
     all_transactions.extend(token_txs);
 
     // Convert to EvmTransaction and save to database
     let mut result = Vec::new();
-    let native_symbol = chain_config
-        .assets()
+    let tracked_assets = tokens::tracked_assets(&chain_config);
+    let native_symbol = tracked_assets
         .first()
         .map(|a| a.symbol.clone())
         .unwrap_or_else(|| "ETH".to_string());
+    let native_decimals = tracked_assets.first().map(|a| a.decimals).unwrap_or(18);
 
     for tx in all_transactions {
         let is_from_me = tx.from.to_lowercase() == address.to_lowercase();
@@ -174,7 +202,20 @@ pub async fn fetch_evm_transaction_history(
 
         // Determine if it's a token transfer or native transfer
         let (asset_symbol, asset_name, contract_address, decimals) =
-            if !tx.contract_address.is_empty() {
+            if let Some(asset) = tracked_assets.iter().find(|asset| {
+                asset
+                    .contract_address
+                    .as_deref()
+                    .is_some_and(|address| address.eq_ignore_ascii_case(&tx.contract_address))
+            }) {
+                // Transfer of a tracked token, labelled like its balance
+                (
+                    asset.symbol.clone(),
+                    asset.name.clone(),
+                    Some(tx.contract_address.clone()),
+                    asset.decimals,
+                )
+            } else if !tx.contract_address.is_empty() {
                 // ERC20 token transfer
                 let symbol = tx
                     .token_symbol
@@ -192,24 +233,19 @@ pub async fn fetch_evm_transaction_history(
                 (symbol, name, Some(tx.contract_address.clone()), decimals)
             } else {
                 // Native token transfer
-                (native_symbol.clone(), native_symbol.clone(), None, 18)
+                (native_symbol.clone(), native_symbol.clone(), None, native_decimals)
             };
 
         // Calculate amount
         let value_u256 = U256::from_dec_str(&tx.value).unwrap_or_default();
-        let amount_float = if decimals > 0 {
-            let divisor = 10_u128.pow(decimals as u32);
-            value_u256.as_u128() as f64 / divisor as f64
-        } else {
-            value_u256.as_u128() as f64
-        };
+        let amount_float = units_to_f64(value_u256, decimals as u32);
 
         // Calculate fee (only for transactions sent by this wallet)
         let fee = if tx_type == TransactionType::Send {
             let gas_used_u256 = U256::from_dec_str(&tx.gas_used).unwrap_or_default();
             let gas_price_u256 = U256::from_dec_str(&tx.gas_price).unwrap_or_default();
-            let fee_wei = gas_used_u256 * gas_price_u256;
-            fee_wei.as_u128() as f64 / 1e18
+            let fee_wei = gas_used_u256.saturating_mul(gas_price_u256);
+            units_to_f64(fee_wei, 18)
         } else {
             0.0
         };
@@ -235,13 +271,20 @@ pub async fn fetch_evm_transaction_history(
             block_number,
             timestamp: timestamp.clone(),
             created_at: timestamp,
+            nonce: if is_from_me { tx.nonce.parse::<u64>().ok() } else { None },
+            replaces_tx_hash: None,
+            replaced_by_tx_hash: None,
         };
 
-        // Save to database
+        // Save to database; a mined send settles the speed-ups and cancels of its nonce
         {
             let db = DB.lock().unwrap();
             db.add_evm_transaction(&evm_tx)
                 .map_err(|e| format!("Failed to save transaction: {}", e))?;
+            if let (Some(nonce), Some(_)) = (evm_tx.nonce, evm_tx.block_number) {
+                db.mark_evm_nonce_siblings_replaced(&evm_tx.from_address, chain_id, nonce, &evm_tx.tx_hash)
+                    .map_err(|e| format!("Failed to reconcile replaced transactions: {}", e))?;
+            }
         }
 
         result.push(evm_tx);
@@ -254,11 +297,9 @@ async fn fetch_normal_transactions(
     api_url: &str,
     address: &str,
     chain_id: u64,
+    api_key: Option<&str>,
 ) -> Result<Vec<EtherscanTransaction>, String> {
-    let url = format!(
-        "{}?chainid={}&module=account&action=txlist&address={}&startblock=0&endblock=99999999&sort=desc&apikey={}",
-        api_url, chain_id, address, ETHERSCAN_API_KEY
-    );
+    let url = etherscan_query_url(api_url, "txlist", address, chain_id, api_key);
 
     let client = reqwest::Client::new();
     let response = client
@@ -293,11 +334,9 @@ async fn fetch_token_transactions(
     api_url: &str,
     address: &str,
     chain_id: u64,
+    api_key: Option<&str>,
 ) -> Result<Vec<EtherscanTransaction>, String> {
-    let url = format!(
-        "{}?chainid={}&module=account&action=tokentx&address={}&startblock=0&endblock=99999999&sort=desc&apikey={}",
-        api_url, chain_id, address, ETHERSCAN_API_KEY
-    );
+    let url = etherscan_query_url(api_url, "tokentx", address, chain_id, api_key);
 
     let client = reqwest::Client::new();
     let response = client
@@ -360,7 +399,6 @@ pub async fn estimate_evm_gas(
         .map_err(|e| format!("Invalid wallet address: {}", e))?;
 
     let gas_limit: U256;
-    let gas_price: U256;
 
     // Check if it's a native token or ERC20 transfer
     if request.contract_address.is_none() {
@@ -406,14 +444,23 @@ pub async fn estimate_evm_gas(
             .map_err(|e| format!("Failed to estimate gas: {}", e))?;
     }
 
-    gas_price = provider
-        .get_gas_price()
-        .await
-        .map_err(|e| format!("Failed to get gas price: {}", e))?;
+    // On EIP-1559 chains the normal tier's max fee doubles as the legacy
+    // gas price, so callers that pass it back as a cap get the normal tier.
+    let market = gas::fee_market(&provider).await?;
+    let gas_price = match market.params(EvmFeeSpeed::Normal) {
+        EvmFeeParams::Eip1559 {
+            max_fee_per_gas, ..
+        } => max_fee_per_gas,
+        EvmFeeParams::Legacy { gas_price } => gas_price,
+    };
 
     Ok(crate::wallet::transaction_types::EvmGasEstimationResponse {
         gas_limit: gas_limit.as_u64(),
         gas_price: gas_price.to_string(),
+        base_fee_per_gas: market.base_fee_per_gas().map(|fee| fee.to_string()),
+        slow: market.tier(EvmFeeSpeed::Slow),
+        normal: market.tier(EvmFeeSpeed::Normal),
+        fast: market.tier(EvmFeeSpeed::Fast),
     })
 }
 
@@ -466,6 +513,19 @@ pub async fn send_evm_transaction(
     let from_address = EthAddress::from_str(&wallet_info.address)
         .map_err(|e| format!("Invalid wallet address: {}", e))?;
 
+    // Type-2 fee caps on EIP-1559 chains, a gas price elsewhere
+    let market = gas::fee_market(provider.as_ref()).await?;
+    let fee_params = gas::send_fee_params(
+        &market,
+        request.gas_price.as_deref(),
+        request.max_fee_per_gas.as_deref(),
+        request.max_priority_fee_per_gas.as_deref(),
+        request.speed,
+    )?;
+    let expected_gas_price = fee_params.expected_gas_price(&market);
+    let reservation =
+        nonce::reserve_nonce(provider.as_ref(), from_address, request.chain_id).await?;
+
     let tx_hash: H256;
     let gas_used_str: String;
     let gas_price_str: String;
@@ -488,20 +548,13 @@ pub async fn send_evm_transaction(
                 .await
                 .map_err(|e| format!("Failed to estimate gas: {}", e))?
         };
-        let gas_price = if let Some(gas_price) = &request.gas_price {
-            U256::from_dec_str(gas_price).map_err(|e| format!("Invalid gas price: {}", e))?
-        } else {
-            provider
-                .get_gas_price()
-                .await
-                .map_err(|e| format!("Failed to get gas price: {}", e))?
-        };
-
-        let tx = TransactionRequest::new()
-            .to(to_address)
-            .value(amount_wei)
-            .gas(gas_limit)
-            .gas_price(gas_price);
+        let tx = fee_params.apply(
+            TransactionRequest::new()
+                .to(to_address)
+                .value(amount_wei)
+                .gas(gas_limit)
+                .nonce(reservation.nonce),
+        );
 
         // Send transaction
         let pending_tx = client
@@ -510,7 +563,7 @@ pub async fn send_evm_transaction(
             .map_err(|e| format!("Failed to send transaction: {}", e))?;
 
         tx_hash = *pending_tx;
-        (gas_used_str, gas_price_str, fee) = estimated_fee_snapshot(gas_limit, gas_price);
+        (gas_used_str, gas_price_str, fee) = estimated_fee_snapshot(gas_limit, expected_gas_price);
     } else {
         // ERC20 token transfer
         let contract_address_str = request.contract_address.as_ref().unwrap();
@@ -540,69 +593,46 @@ pub async fn send_evm_transaction(
                     &TransactionRequest::new()
                         .from(from_address)
                         .to(contract_address)
-                        .data(transfer_data)
+                        .data(transfer_data.clone())
                         .into(),
                     None,
                 )
                 .await
                 .map_err(|e| format!("Failed to estimate gas: {}", e))?
         };
-        let gas_price = if let Some(gas_price) = &request.gas_price {
-            U256::from_dec_str(gas_price).map_err(|e| format!("Invalid gas price: {}", e))?
-        } else {
-            provider
-                .get_gas_price()
-                .await
-                .map_err(|e| format!("Failed to get gas price: {}", e))?
-        };
-
-        let contract = Contract::new(contract_address, abi, Arc::new(client.clone()));
-
         // Call transfer function
-        let call = contract
-            .method::<_, bool>("transfer", (to_address, amount_wei))
-            .map_err(|e| format!("Failed to create contract call: {}", e))?
-            .gas(gas_limit)
-            .gas_price(gas_price);
+        let tx = fee_params.apply(
+            TransactionRequest::new()
+                .to(contract_address)
+                .data(transfer_data)
+                .gas(gas_limit)
+                .nonce(reservation.nonce),
+        );
 
-        let pending_tx = call
-            .send()
+        let pending_tx = client
+            .send_transaction(tx, None)
             .await
             .map_err(|e| format!("Failed to send transaction: {}", e))?;
 
         tx_hash = *pending_tx;
-        (gas_used_str, gas_price_str, fee) = estimated_fee_snapshot(gas_limit, gas_price);
+        (gas_used_str, gas_price_str, fee) = estimated_fee_snapshot(gas_limit, expected_gas_price);
     }
 
-    // Find decimals for the asset
-    let decimals = if let Some(addr) = &request.contract_address {
-        chain_config
-            .assets()
-            .iter()
-            .find(|a| {
-                a.contract_address.as_ref().map(|ca| ca.to_lowercase()) == Some(addr.to_lowercase())
-            })
-            .map(|a| a.decimals)
-            .unwrap_or(18)
-    } else {
-        18
+    // Find the tracked asset, built-in or custom, for its decimals and name
+    let tracked_asset = match request.contract_address.as_deref() {
+        Some(addr) => tokens::find_tracked_asset(&chain_config, addr),
+        None => chain_config.assets().into_iter().next(),
     };
+    let decimals = tracked_asset.as_ref().map(|a| a.decimals).unwrap_or(18);
 
     // Parse amount as float for display
     let amount_float = U256::from_dec_str(&request.amount)
-        .map(|v| v.as_u128() as f64 / 10f64.powi(decimals as i32))
+        .map(|v| units_to_f64(v, decimals as u32))
         .unwrap_or(0.0);
 
     // Get asset name from config if possible
-    let asset_name = if let Some(addr) = &request.contract_address {
-        chain_config
-            .assets()
-            .iter()
-            .find(|a| {
-                a.contract_address.as_ref().map(|ca| ca.to_lowercase()) == Some(addr.to_lowercase())
-            })
-            .map(|a| a.name.clone())
-            .unwrap_or_else(|| "".to_string())
+    let asset_name = if request.contract_address.is_some() {
+        tracked_asset.map(|a| a.name).unwrap_or_default()
     } else {
         request.asset_symbol.clone() // For native token, symbol and name are often same
     };
@@ -630,6 +660,9 @@ pub async fn send_evm_transaction(
         block_number: None,
         timestamp: now.clone(),
         created_at: now,
+        nonce: Some(reservation.nonce.as_u64()),
+        replaces_tx_hash: None,
+        replaced_by_tx_hash: None,
     };
 
     {
@@ -746,9 +779,15 @@ pub async fn send_raw_evm_transaction(
     let gas_limit =
         U256::from_dec_str(&request.gas_limit).map_err(|e| format!("Invalid gas limit: {}", e))?;
 
-    // Parse gas price (in wei)
-    let gas_price =
-        U256::from_dec_str(&request.gas_price).map_err(|e| format!("Invalid gas price: {}", e))?;
+    // The quoted gas price caps the fee; EIP-1559 chains pay less when the base fee allows
+    let market = gas::fee_market(provider.as_ref()).await?;
+    let fee_params = gas::send_fee_params(
+        &market,
+        Some(&request.gas_price),
+        None,
+        None,
+        EvmFeeSpeed::Normal,
+    )?;
 
     // Parse data (hex string)
     let data = if request.data.starts_with("0x") {
@@ -757,13 +796,20 @@ pub async fn send_raw_evm_transaction(
         hex::decode(&request.data).map_err(|e| format!("Invalid data hex: {}", e))?
     };
 
+    let from_address = EthAddress::from_str(&wallet_info.address)
+        .map_err(|e| format!("Invalid wallet address: {}", e))?;
+    let reservation =
+        nonce::reserve_nonce(provider.as_ref(), from_address, request.chain_id).await?;
+
     // Build transaction
-    let tx = TransactionRequest::new()
-        .to(to_address)
-        .value(value)
-        .data(data)
-        .gas(gas_limit)
-        .gas_price(gas_price);
+    let tx = fee_params.apply(
+        TransactionRequest::new()
+            .to(to_address)
+            .value(value)
+            .data(data)
+            .gas(gas_limit)
+            .nonce(reservation.nonce),
+    );
 
     // Send transaction
     let pending_tx = client
@@ -774,6 +820,47 @@ pub async fn send_raw_evm_transaction(
     let tx_hash = *pending_tx;
     let tx_hash_str = format!("{:?}", tx_hash);
 
+    // Record the contract call so its nonce is tracked and it can be sped up or cancelled
+    let native_symbol = chain_config
+        .assets()
+        .first()
+        .map(|a| a.symbol.clone())
+        .unwrap_or_else(|| "ETH".to_string());
+    let (gas_used, gas_price, fee) =
+        estimated_fee_snapshot(gas_limit, fee_params.expected_gas_price(&market));
+    let now = Utc::now().to_rfc3339();
+    let tx_record = EvmTransaction {
+        id: Uuid::new_v4().to_string(),
+        wallet_id: request.wallet_id.clone(),
+        tx_hash: tx_hash_str.clone(),
+        tx_type: TransactionType::Contract,
+        from_address: wallet_info.address.clone(),
+        to_address: request.to.clone(),
+        amount: value.to_string(),
+        amount_float: units_to_f64(value, 18),
+        asset_symbol: native_symbol.clone(),
+        asset_name: native_symbol,
+        contract_address: None,
+        chain: chain_config.name().to_string(),
+        chain_id: request.chain_id,
+        gas_used,
+        gas_price,
+        fee,
+        status: TransactionStatus::after_broadcast(),
+        block_number: None,
+        timestamp: now.clone(),
+        created_at: now,
+        nonce: Some(reservation.nonce.as_u64()),
+        replaces_tx_hash: None,
+        replaced_by_tx_hash: None,
+    };
+
+    {
+        let db = DB.lock().unwrap();
+        db.add_evm_transaction(&tx_record)
+            .map_err(|e| format!("Failed to save transaction: {}", e))?;
+    }
+
     // Don't wait for confirmation, return immediately
     // The frontend can track the transaction status separately
 
@@ -870,11 +957,16 @@ async fn approve_erc20_token_with_wallet(
     let wallet = wallet_from_signing_secret(signing_secret, chain_id)?;
     let client = SignerMiddleware::new(provider.clone(), wallet);
 
+    let from_address = EthAddress::from_str(&wallet_info.address)
+        .map_err(|e| format!("Invalid wallet address: {}", e))?;
+    let reservation = nonce::reserve_nonce(provider.as_ref(), from_address, chain_id).await?;
+
     // Call approve function
     let contract = Contract::new(contract_address, abi, Arc::new(client.clone()));
     let call = contract
         .method::<_, bool>("approve", (spender, approve_amount))
-        .map_err(|e| format!("Failed to create approve call: {}", e))?;
+        .map_err(|e| format!("Failed to create approve call: {}", e))?
+        .nonce(reservation.nonce);
 
     let pending_tx = call
         .send()
@@ -882,6 +974,9 @@ async fn approve_erc20_token_with_wallet(
         .map_err(|e| format!("Failed to send approval transaction: {}", e))?;
 
     let tx_hash = *pending_tx;
+    // The node counts the approval as pending now; waiting for its receipt
+    // must not hold up other sends from this address.
+    drop(reservation);
     let tx_hash_str = format!("{:?}", tx_hash);
 
     // Approval refresh stays inline until the shared sync engine consumes
@@ -930,12 +1025,7 @@ pub(crate) fn wallet_from_signing_secret(
     chain_id: u64,
 ) -> Result<LocalWallet, String> {
     match signing_secret {
-        EvmSigningSecret::Mnemonic(secret_data) => MnemonicBuilder::<English>::default()
-            .phrase(secret_data.as_str())
-            .derivation_path("m/44'/60'/0'/0/0")
-            .map_err(|e| format!("Failed to set derivation path: {}", e))?
-            .build()
-            .map_err(|e| format!("Failed to build wallet: {}", e))
+        EvmSigningSecret::Mnemonic(secret_data) => wallet_from_mnemonic_secret(&secret_data)
             .map(|wallet| wallet.with_chain_id(chain_id)),
         EvmSigningSecret::PrivateKey(secret_data) => {
             let trimmed = secret_data.trim();
@@ -953,7 +1043,11 @@ pub(crate) fn wallet_from_signing_secret(
 
 #[cfg(test)]
 mod tests {
-    use super::{approve_erc20_token, load_signing_secret, EvmSigningSecret};
+    use super::{
+        approve_erc20_token, etherscan_api_key, etherscan_query_url, load_signing_secret,
+        EvmSigningSecret, ETHERSCAN_API_KEY,
+    };
+    use crate::wallet::evm::config::EvmChainConfig;
     use crate::db::Database;
     use crate::wallet::security::backend::{SecretBackend, SecretBackendAdapter};
     use crate::wallet::security::keystore::{Keystore, SqliteKeystore};
@@ -963,7 +1057,7 @@ mod tests {
     };
     use crate::wallet::security::session::SessionManager;
     use crate::wallet::security::types::{SecurityError, SignerOperation};
-    use crate::wallet::types::WalletInfo;
+    use crate::wallet::types::{EvmNetwork, WalletInfo};
     use crate::DB;
     use std::sync::Arc;
     use std::time::Duration;
@@ -1113,11 +1207,53 @@ mod tests {
             wallet_type: wallet_type.to_string(),
             address: "0x1234".to_string(),
             balance: 0.0,
+            balance_sats: None,
             created_at: "2026-04-19T00:00:00Z".to_string(),
             updated_at: "2026-04-19T00:00:00Z".to_string(),
         }
     }
 
+    #[test]
+    fn etherscan_key_is_only_sent_to_builtin_explorers() {
+        let network = Arc::new(EvmNetwork {
+            chain_id: 31337,
+            name: "anvil".to_string(),
+            display_name: "Anvil".to_string(),
+            native_symbol: "ETH".to_string(),
+            native_decimals: 18,
+            rpc_url: "http://127.0.0.1:8545".to_string(),
+            wss_url: None,
+            explorer_api_url: Some("https://explorer.example/api".to_string()),
+            is_testnet: true,
+            created_at: "2026-05-01T00:00:00Z".to_string(),
+            updated_at: "2026-05-01T00:00:00Z".to_string(),
+        });
+
+        assert_eq!(
+            etherscan_api_key(&EvmChainConfig::Ethereum),
+            Some(ETHERSCAN_API_KEY)
+        );
+        assert_eq!(etherscan_api_key(&EvmChainConfig::Custom(network)), None);
+
+        let url = etherscan_query_url(
+            "https://explorer.example/api",
+            "txlist",
+            "0xabc",
+            31337,
+            None,
+        );
+        assert!(!url.contains("apikey"));
+        let url = etherscan_query_url(
+            "https://api.etherscan.io/v2/api",
+            "tokentx",
+            "0xabc",
+            1,
+            Some("KEY"),
+        );
+        assert!(url.ends_with("&apikey=KEY"));
+        assert!(url.contains("action=tokentx"));
+    }
+
     #[test]
     fn send_signing_returns_locked_without_keystore_access() {
         let session = SessionManager::new(Duration::from_secs(30), Duration::from_secs(90));
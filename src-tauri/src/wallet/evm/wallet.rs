@@ -1,13 +1,13 @@
-use crate::wallet::evm::private_key::map_security_error;
+use crate::wallet::evm::private_key::{map_security_error, wallet_from_mnemonic_secret};
 use crate::wallet::security::backend::SecretBackend;
 use crate::wallet::security::commands::ensure_local_password_configured;
 use crate::wallet::security::commands::AppSecurity;
+use crate::wallet::security::mnemonic_secret::MnemonicSecret;
 use crate::wallet::security::secret_envelope::StoredSecret;
 use crate::wallet::types::CreateWalletResponse;
 use crate::DB;
 use bip39::{Language, Mnemonic};
-use ethers::signers::coins_bip39::English;
-use ethers::signers::{MnemonicBuilder, Signer};
+use ethers::signers::Signer;
 
 fn prepare_mnemonic_secret(
     secret_backend: &SecretBackend,
@@ -21,6 +21,7 @@ fn prepare_mnemonic_secret(
 #[tauri::command]
 pub fn evm_create_wallet_from_mnemonic(
     mnemonic_phrase: String,
+    passphrase: Option<String>,
     wallet_label: Option<String>,
     reveal_secret: Option<bool>,
     state: tauri::State<'_, AppSecurity>,
@@ -31,15 +32,12 @@ pub fn evm_create_wallet_from_mnemonic(
     let _mnemonic = Mnemonic::parse_in_normalized(Language::English, &mnemonic_phrase)
         .map_err(|e| format!("Invalid mnemonic: {}", e))?;
 
-    // Create wallet from mnemonic using ethers-rs
-    let builder = MnemonicBuilder::<English>::default()
-        .phrase(mnemonic_phrase.as_str())
-        .derivation_path("m/44'/60'/0'/0/0")
-        .map_err(|e| format!("Failed to set derivation path: {}", e))?;
+    // The optional BIP39 passphrase is kept with the phrase in the same secret
+    let mnemonic_secret = MnemonicSecret::new(&mnemonic_phrase, passphrase.as_deref());
+    let encoded_secret = mnemonic_secret.encode();
 
-    let wallet = builder
-        .build()
-        .map_err(|e| format!("Failed to build wallet: {}", e))?;
+    // Create wallet from mnemonic using ethers-rs
+    let wallet = wallet_from_mnemonic_secret(&encoded_secret)?;
 
     let address = wallet.address();
     let address_str = format!("{:?}", address);
@@ -55,7 +53,7 @@ pub fn evm_create_wallet_from_mnemonic(
             .initialize_for_empty_store()
             .map_err(map_security_error)?;
     }
-    let stored_secret = prepare_mnemonic_secret(state.secret_backend(), &mnemonic_phrase)?;
+    let stored_secret = prepare_mnemonic_secret(state.secret_backend(), &encoded_secret)?;
 
     // Store wallet in database
     let db = DB.lock().unwrap();
@@ -74,7 +72,7 @@ pub fn evm_create_wallet_from_mnemonic(
     if reveal_secret.unwrap_or(false) {
         Ok(CreateWalletResponse::with_revealed_secret(
             wallet_info,
-            mnemonic_phrase,
+            mnemonic_secret.phrase,
             "mnemonic",
         ))
     } else {
@@ -6,6 +6,7 @@ use super::session::SessionManager;
 use super::types::{
     LocalPasswordPolicy, SecretMigrationState, SecurityBackendState, SecurityError, SignerOperation,
 };
+use crate::wallet::bitcoin::wallet_store;
 use crate::DB;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -161,6 +162,7 @@ fn security_reset_local_wallet_data_inner(state: &AppSecurity) -> Result<(), Sec
     db.clear_local_wallet_data()
         .map_err(|_| SecurityError::OperationNotAllowed)?;
     drop(db);
+    wallet_store::clear_all_wallet_state().map_err(|_| SecurityError::OperationNotAllowed)?;
     reset_master_key_after_local_data_reset().map_err(|_| SecurityError::OperationNotAllowed)?;
 
     if let Ok(mut startup_state) = state.startup_state().lock() {
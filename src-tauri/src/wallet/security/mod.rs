@@ -10,6 +10,7 @@ pub mod backend;
 pub mod commands;
 pub mod keystore;
 pub mod log_sanitize;
+pub mod mnemonic_secret;
 pub mod secret_envelope;
 pub mod session;
 pub mod types;
@@ -167,7 +167,10 @@ impl SessionManager {
 }
 
 fn operation_grant_is_reusable(operation: SignerOperation) -> bool {
-    matches!(operation, SignerOperation::Send | SignerOperation::Approve)
+    matches!(
+        operation,
+        SignerOperation::Send | SignerOperation::Approve | SignerOperation::SignMessage
+    )
 }
 
 enum AuthorizationState {
@@ -227,6 +230,22 @@ mod tests {
         assert_eq!(session.authorize(SignerOperation::Approve), Ok(()));
     }
 
+    #[test]
+    fn verified_sign_message_grant_is_reusable_within_reauth_window() {
+        let (session, _) = test_session(Duration::from_secs(30), Duration::from_secs(90));
+
+        session
+            .authorize_verified_operation(SignerOperation::SignMessage)
+            .unwrap();
+
+        assert_eq!(session.authorize(SignerOperation::SignMessage), Ok(()));
+        assert_eq!(session.authorize(SignerOperation::SignMessage), Ok(()));
+        assert_eq!(
+            session.authorize(SignerOperation::Send),
+            Err(SecurityError::ReauthRequired)
+        );
+    }
+
     #[test]
     fn export_grant_remains_single_use() {
         let (session, _) = test_session(Duration::from_secs(30), Duration::from_secs(90));
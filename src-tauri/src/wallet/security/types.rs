@@ -18,6 +18,7 @@ pub enum SignerOperation {
     Approve,
     ExportMnemonic,
     ExportPrivateKey,
+    SignMessage,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -63,6 +64,7 @@ impl LocalPasswordPolicy {
                 SignerOperation::Approve,
                 SignerOperation::ExportMnemonic,
                 SignerOperation::ExportPrivateKey,
+                SignerOperation::SignMessage,
             ],
             forgot_password_mode: ForgotPasswordMode::ResetLocalDataAndRestoreWithRecoveryMaterial,
             reauth_window_seconds: LOCAL_PASSWORD_REAUTH_WINDOW_SECONDS,
@@ -83,6 +85,7 @@ pub enum SecurityError {
     OperationNotAllowed,
     UnknownWallet,
     SecretBackendUnavailable,
+    WatchOnly,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
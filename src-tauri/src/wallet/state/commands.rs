@@ -1,3 +1,5 @@
+use crate::wallet::bitcoin::amount::sats_to_btc;
+use crate::wallet::bitcoin::config::BitcoinNetwork;
 use crate::DB;
 
 use super::{
@@ -8,10 +10,13 @@ use super::{
 const BALANCE_FRESH_WITHIN_SECS: i64 = 60;
 const BALANCE_STALE_AFTER_SECS: i64 = 300;
 
-fn bitcoin_balance_state_from_parts(balance: f64, freshness: FreshnessMetadata) -> BalanceState {
+fn bitcoin_balance_state_from_parts(
+    balance_sats: u64,
+    freshness: FreshnessMetadata,
+) -> BalanceState {
     BalanceState {
-        raw_amount: balance.to_string(),
-        display_amount: balance,
+        raw_amount: balance_sats.to_string(),
+        display_amount: sats_to_btc(balance_sats),
         chain_id: None,
         freshness,
     }
@@ -47,7 +52,10 @@ pub fn state_get_bitcoin_wallet_balance_state(wallet_id: String) -> Result<Balan
         .map_err(|e| format!("Failed to get bitcoin wallet freshness: {}", e))?
         .ok_or_else(|| "Bitcoin wallet freshness not found".to_string())?;
 
-    Ok(bitcoin_balance_state_from_parts(wallet.balance, freshness))
+    Ok(bitcoin_balance_state_from_parts(
+        wallet.balance_sats.unwrap_or(0),
+        freshness,
+    ))
 }
 
 #[tauri::command]
@@ -67,6 +75,14 @@ pub fn state_get_bitcoin_portfolio_state() -> Result<PortfolioState, String> {
 
     let mut items = Vec::with_capacity(wallets.len());
     for wallet in wallets {
+        // Test networks' coins are not valued at the BTC price
+        let network = db
+            .get_bitcoin_wallet_network(&wallet.id)
+            .map_err(|e| format!("Failed to get wallet network: {}", e))?;
+        if network != BitcoinNetwork::Mainnet {
+            continue;
+        }
+
         let freshness = db
             .get_bitcoin_wallet_balance_freshness(
                 &wallet.id,
@@ -79,7 +95,7 @@ pub fn state_get_bitcoin_portfolio_state() -> Result<PortfolioState, String> {
 
         items.push((
             wallet.address,
-            bitcoin_balance_state_from_parts(wallet.balance, freshness),
+            bitcoin_balance_state_from_parts(wallet.balance_sats.unwrap_or(0), freshness),
         ));
     }
 
@@ -111,7 +127,7 @@ mod tests {
     #[test]
     fn state_command_balance_shape_uses_frozen_contract_keys() {
         let state = bitcoin_balance_state_from_parts(
-            1.25,
+            125_000_000,
             FreshnessMetadata {
                 status: FreshnessStatus::Cached,
                 updated_at: None,
@@ -143,7 +159,7 @@ mod tests {
             &[(
                 "bc1test".to_string(),
                 bitcoin_balance_state_from_parts(
-                    2.0,
+                    200_000_000,
                     FreshnessMetadata {
                         status: freshness::classify_age(None, 100, 60, 300),
                         updated_at: None,
@@ -1,9 +1,15 @@
 use crate::db::AssetBalanceData;
+use crate::wallet::bitcoin::amount::sats_to_btc;
 use crate::wallet::bitcoin::balance::BitcoinChainAdapter;
+use crate::wallet::bitcoin::discovery as bitcoin_discovery;
+use crate::wallet::bitcoin::local_state as bitcoin_local_state;
 use crate::wallet::bitcoin::transaction as bitcoin_transaction;
 use crate::wallet::chain::traits::{ChainAdapter, ChainBalanceSnapshot};
-use crate::wallet::evm::balance::EvmChainAdapter;
-use crate::wallet::evm::config::{chain_concurrency_limit, get_all_chains};
+use crate::wallet::evm::balance::fetch_chain_snapshots;
+use crate::wallet::evm::config::{
+    chain_concurrency_limit, get_all_chains, is_testnet_chain_id, EvmChainConfig,
+};
+use crate::wallet::evm::gas::{self as evm_gas, EvmReceiptFee};
 use crate::wallet::evm::transaction as evm_transaction;
 use crate::wallet::state::types::{FreshnessMetadata, FreshnessStatus};
 use crate::wallet::sync::types::{SyncOutcome, SyncReason, SyncTarget};
@@ -27,10 +33,18 @@ pub struct DashboardRefreshResult {
 }
 
 fn asset_valuation(
+    chain_id: u64,
     symbol: &str,
+    contract_address: Option<&str>,
     balance_float: f64,
 ) -> (Option<f64>, Option<f64>, ValuationStatus) {
-    match crate::wallet::evm::price_manager::get_cached_price_state(symbol).price_usd {
+    match crate::wallet::evm::price_manager::get_cached_asset_price_state(
+        chain_id,
+        symbol,
+        contract_address,
+    )
+    .price_usd
+    {
         Some(usd_price) => (
             Some(usd_price),
             Some(balance_float * usd_price),
@@ -66,26 +80,66 @@ pub async fn sync_bitcoin_wallet_balance(
     wallet_id: &str,
     reason: SyncReason,
 ) -> Result<(WalletInfo, SyncOutcome), String> {
-    let wallet = {
+    let (wallet, descriptors, network) = {
         let db = DB.lock().unwrap();
-        db.get_bitcoin_wallet(wallet_id)
+        let wallet = db
+            .get_bitcoin_wallet(wallet_id)
             .map_err(|e| format!("Failed to get wallet: {}", e))?
-            .ok_or_else(|| "Wallet not found".to_string())?
+            .ok_or_else(|| "Wallet not found".to_string())?;
+        let descriptors = db
+            .get_bitcoin_wallet_descriptors(wallet_id)
+            .map_err(|e| format!("Failed to get wallet descriptors: {}", e))?;
+        let network = db
+            .get_bitcoin_wallet_network(wallet_id)
+            .map_err(|e| format!("Failed to get wallet network: {}", e))?;
+        (wallet, descriptors, network)
     };
 
-    let adapter = BitcoinChainAdapter::new(wallet.address.clone());
-    let sync_result = adapter.fetch_balances().await;
+    // Descriptor wallets are served from the local BDK state and fall back to
+    // address discovery until it has synced once; legacy wallets only know
+    // their primary address.
+    let mut stale_error = None;
+    let sync_result = match descriptors {
+        Some(descriptors) => match bitcoin_local_state::load_local_wallet(&wallet.id, true).await {
+            Ok(Some(snapshot)) => {
+                let db = DB.lock().unwrap();
+                db.upsert_bitcoin_wallet_addresses(&snapshot.addresses)
+                    .map_err(|e| format!("Failed to save wallet addresses: {}", e))?;
+                stale_error = snapshot.sync_error.clone();
+                Ok(snapshot.balance_sats())
+            }
+            result => {
+                if let Err(error) = result {
+                    crate::safe_log!("[WARN] Local wallet state unavailable: {}", error);
+                }
+                bitcoin_discovery::sync_wallet_addresses(&wallet.id, &descriptors, network)
+                    .await
+                    .map(|addresses| {
+                        addresses
+                            .iter()
+                            .map(|address| address.balance_sats.max(0) as u64)
+                            .sum::<u64>()
+                    })
+            }
+        },
+        None => BitcoinChainAdapter::new(network, wallet.address.clone())
+            .fetch_balances()
+            .await
+            .map(|snapshot| {
+                snapshot
+                    .assets
+                    .first()
+                    .and_then(|asset| asset.raw_amount.parse::<u64>().ok())
+                    .unwrap_or(0)
+            }),
+    };
 
     let (balance, failed_sources, partial) = match sync_result {
-        Ok(snapshot) => (
-            snapshot
-                .assets
-                .first()
-                .map(|asset| asset.display_amount)
-                .unwrap_or(0.0),
-            Vec::new(),
-            false,
-        ),
+        // A failed refresh still reports the last synced state, flagged as partial.
+        Ok(balance) => match stale_error {
+            Some(error) => (balance, vec![format!("bitcoin:{}", error)], true),
+            None => (balance, Vec::new(), false),
+        },
         Err(error) => {
             let failed_sources = vec![format!("bitcoin:{}", error)];
 
@@ -117,7 +171,11 @@ pub async fn sync_bitcoin_wallet_balance(
     }
 
     Ok((
-        WalletInfo { balance, ..wallet },
+        WalletInfo {
+            balance: sats_to_btc(balance),
+            balance_sats: Some(balance),
+            ..wallet
+        },
         SyncOutcome {
             reason,
             target: SyncTarget::BitcoinWalletBalance,
@@ -132,6 +190,26 @@ pub async fn sync_evm_wallet_balances(
     wallet_id: &str,
     reason: SyncReason,
 ) -> Result<(EvmWalletInfo, SyncOutcome), String> {
+    sync_evm_wallets_balances(&[wallet_id.to_string()], reason)
+        .await
+        .pop()
+        .unwrap_or_else(|| Err("Wallet not found".to_string()))
+}
+
+/// One chain's balance snapshots, tagged with its spawn order.
+type EvmChainSnapshots = (
+    usize,
+    EvmChainConfig,
+    Result<Vec<ChainBalanceSnapshot>, String>,
+);
+
+struct EvmWalletSyncState {
+    wallet: WalletInfo,
+    previous_wallet_updated_at: Option<i64>,
+    cached_chain_assets: HashMap<String, Vec<EvmAssetBalance>>,
+}
+
+fn load_evm_wallet_sync_state(wallet_id: &str) -> Result<EvmWalletSyncState, String> {
     let (wallet, previous_wallet_updated_at) = {
         let db = DB.lock().unwrap();
         let wallet = db
@@ -150,83 +228,133 @@ pub async fn sync_evm_wallet_balances(
 
     let cached_chain_assets = cached_evm_chain_assets(&wallet.id)?;
 
+    Ok(EvmWalletSyncState {
+        wallet,
+        previous_wallet_updated_at,
+        cached_chain_assets,
+    })
+}
+
+/// Syncs several EVM wallets together: each chain is read once for all of
+/// them, from a single block. Results follow the order of `wallet_ids`.
+pub async fn sync_evm_wallets_balances(
+    wallet_ids: &[String],
+    reason: SyncReason,
+) -> Vec<Result<(EvmWalletInfo, SyncOutcome), String>> {
+    let states = wallet_ids
+        .iter()
+        .map(|wallet_id| load_evm_wallet_sync_state(wallet_id))
+        .collect::<Vec<_>>();
+    let wallet_addresses = states
+        .iter()
+        .flatten()
+        .map(|state| state.wallet.address.clone())
+        .collect::<Vec<_>>();
+
     let chains_config = get_all_chains();
     let concurrency_limit = chain_concurrency_limit();
     let semaphore = Arc::new(Semaphore::new(concurrency_limit));
     let mut set = JoinSet::new();
 
-    for (order, chain_config) in chains_config.into_iter().enumerate() {
-        let semaphore = semaphore.clone();
-        let wallet_address = wallet.address.clone();
-        let wallet_id = wallet.id.clone();
-        let cached_assets = cached_chain_assets
-            .get(chain_config.name())
-            .cloned()
-            .unwrap_or_default();
-        let previous_wallet_updated_at = previous_wallet_updated_at;
-
-        set.spawn(async move {
-            let _permit = semaphore
-                .acquire_owned()
-                .await
-                .map_err(|e| format!("Semaphore closed: {}", e))?;
-
-            let adapter = EvmChainAdapter::new(chain_config, wallet_address.clone());
-            match adapter.fetch_balances().await {
-                Ok(snapshot) => Ok::<_, String>((
-                    order,
-                    chain_snapshot_to_assets(&wallet_id, snapshot),
-                    Vec::<String>::new(),
-                )),
-                Err(error) => Ok((
-                    order,
-                    failed_chain_assets(
-                        chain_config.name(),
-                        chain_config.chain_id(),
-                        cached_assets,
-                        previous_wallet_updated_at,
-                    ),
-                    vec![chain_config.name().to_string(), error],
-                )),
-            }
-        });
+    if !wallet_addresses.is_empty() {
+        let wallet_addresses = Arc::new(wallet_addresses);
+        for (order, chain_config) in chains_config.into_iter().enumerate() {
+            let semaphore = semaphore.clone();
+            let wallet_addresses = wallet_addresses.clone();
+
+            set.spawn(async move {
+                let snapshots = match semaphore.acquire_owned().await {
+                    Ok(_permit) => fetch_chain_snapshots(&chain_config, &wallet_addresses).await,
+                    Err(e) => Err(format!("Semaphore closed: {}", e)),
+                };
+                (order, chain_config, snapshots)
+            });
+        }
     }
 
-    let mut chains_with_order = Vec::new();
-    let mut all_asset_data = Vec::new();
-    let mut total_balance_usd = 0.0;
-    let mut failed_sources = Vec::new();
-
+    let mut chain_results = Vec::new();
+    let mut task_failures = Vec::new();
     while let Some(res) = set.join_next().await {
         match res {
-            Ok(Ok((order, (chain_assets, asset_data), failures))) => {
-                if chain_assets.chain_id != 11155111
-                    && chain_assets.freshness.status != FreshnessStatus::Unavailable
-                {
-                    total_balance_usd += chain_assets.total_balance_usd;
-                }
+            Ok(chain_result) => chain_results.push(chain_result),
+            Err(join_err) => task_failures.push(format!("join:{:?}", join_err)),
+        }
+    }
+    chain_results.sort_by_key(|(order, _, _)| *order);
 
-                if failures.is_empty() {
-                    all_asset_data.extend(asset_data);
-                } else {
-                    failed_sources.extend(failures.into_iter().take(1));
-                }
+    let mut address_index = 0;
+    states
+        .into_iter()
+        .map(|state| {
+            let state = state?;
+            let index = address_index;
+            address_index += 1;
+            finish_evm_wallet_sync(state, index, &chain_results, &task_failures, reason)
+        })
+        .collect()
+}
 
-                chains_with_order.push((order, chain_assets));
+fn finish_evm_wallet_sync(
+    state: EvmWalletSyncState,
+    address_index: usize,
+    chain_results: &[EvmChainSnapshots],
+    task_failures: &[String],
+    reason: SyncReason,
+) -> Result<(EvmWalletInfo, SyncOutcome), String> {
+    let EvmWalletSyncState {
+        wallet,
+        previous_wallet_updated_at,
+        cached_chain_assets,
+    } = state;
+
+    let mut chains = Vec::new();
+    let mut all_asset_data = Vec::new();
+    let mut total_balance_usd = 0.0;
+    let mut failed_sources = task_failures.to_vec();
+
+    for (_, chain_config, snapshots) in chain_results {
+        let snapshot = snapshots
+            .as_ref()
+            .map_err(Clone::clone)
+            .and_then(|snapshots| {
+                snapshots
+                    .get(address_index)
+                    .cloned()
+                    .ok_or_else(|| "Balance snapshot missing".to_string())
+            });
+        let chain_assets = match snapshot {
+            Ok(snapshot) => {
+                let (chain_assets, asset_data) = chain_snapshot_to_assets(&wallet.id, snapshot);
+                all_asset_data.extend(asset_data);
+                chain_assets
+            }
+            Err(_) => {
+                failed_sources.push(chain_config.name().to_string());
+                let cached_assets = cached_chain_assets
+                    .get(chain_config.name())
+                    .cloned()
+                    .unwrap_or_default();
+                failed_chain_assets(
+                    chain_config.name(),
+                    chain_config.chain_id(),
+                    cached_assets,
+                    previous_wallet_updated_at,
+                )
+                .0
             }
-            Ok(Err(error)) => failed_sources.push(error),
-            Err(join_err) => failed_sources.push(format!("join:{:?}", join_err)),
+        };
+
+        if !is_testnet_chain_id(chain_assets.chain_id)
+            && chain_assets.freshness.status != FreshnessStatus::Unavailable
+        {
+            total_balance_usd += chain_assets.total_balance_usd;
         }
+        chains.push(chain_assets);
     }
 
-    chains_with_order.sort_by_key(|(order, _)| *order);
-    let chains: Vec<EvmChainAssets> = chains_with_order
-        .into_iter()
-        .map(|(_, chain)| chain)
-        .collect();
     let wallet_unpriced_asset_count = chains
         .iter()
-        .filter(|chain| chain.chain_id != 11155111)
+        .filter(|chain| !is_testnet_chain_id(chain.chain_id))
         .map(|chain| chain.unpriced_asset_count)
         .sum();
 
@@ -313,8 +441,9 @@ pub async fn refresh_dashboard(
         }
     }
 
-    for wallet_id in evm_wallet_ids {
-        match sync_evm_wallet_balances(&wallet_id, reason).await {
+    // All EVM wallets share one batched balance read per chain
+    for result in sync_evm_wallets_balances(&evm_wallet_ids, reason).await {
+        match result {
             Ok((_, outcome)) => failed_sources.extend(outcome.failed_sources),
             Err(error) => failed_sources.push(format!("evm:{}", error)),
         }
@@ -323,8 +452,10 @@ pub async fn refresh_dashboard(
     let result = {
         let db = DB.lock().map_err(|e| e.to_string())?;
 
-        let btc_wallets = db.get_bitcoin_wallets().map_err(|e| e.to_string())?;
-        let total_btc_balance: f64 = btc_wallets.iter().map(|wallet| wallet.balance).sum();
+        let total_btc_balance = sats_to_btc(
+            db.get_mainnet_bitcoin_balance_sats()
+                .map_err(|e| e.to_string())?,
+        );
         let btc_price_state = crate::wallet::evm::price_manager::get_cached_price_state("BTC");
         let btc_price = btc_price_state.price_usd.unwrap_or(0.0);
         let btc_price_unavailable = total_btc_balance > 0.0
@@ -350,7 +481,7 @@ pub async fn refresh_dashboard(
                 .get_evm_asset_balances(&wallet.id)
                 .map_err(|e| e.to_string())?;
             for asset in assets {
-                if asset.2 != 11155111 {
+                if !is_testnet_chain_id(asset.2) {
                     total_evm_usd += asset.9.unwrap_or(0.0);
                 }
                 all_assets.push(asset);
@@ -375,7 +506,11 @@ pub async fn refresh_dashboard(
         let mut evm_change_amount = 0.0;
         for asset in &all_assets {
             if let (Some(change), Some(usd_value)) = (
-                crate::wallet::evm::price_manager::get_cached_24h_change(&asset.1),
+                crate::wallet::evm::price_manager::get_cached_asset_24h_change(
+                    asset.2,
+                    &asset.1,
+                    asset.4.as_deref(),
+                ),
                 asset.9,
             ) {
                 evm_change_amount += usd_value * (change / 100.0);
@@ -470,11 +605,20 @@ pub async fn refresh_evm_transaction_receipt_status(
     tx_hash: String,
     chain_id: u64,
     reason: SyncReason,
-) -> Result<(TransactionStatus, Option<u64>, SyncOutcome), String> {
+) -> Result<
+    (
+        TransactionStatus,
+        Option<u64>,
+        Option<EvmReceiptFee>,
+        SyncOutcome,
+    ),
+    String,
+> {
     let receipt = evm_transaction::get_transaction_receipt(tx_hash, chain_id).await?;
     let block_number = receipt
         .as_ref()
         .and_then(|value| value.block_number.map(|number| number.as_u64()));
+    let receipt_fee = receipt.as_ref().and_then(evm_gas::receipt_fee);
     let status = match receipt {
         Some(receipt) => TransactionStatus::from_evm_receipt(
             receipt.status.map(|value| value.as_u64() == 1),
@@ -487,6 +631,7 @@ pub async fn refresh_evm_transaction_receipt_status(
     Ok((
         status,
         block_number,
+        receipt_fee,
         SyncOutcome {
             reason,
             target: SyncTarget::TransactionLifecycle,
@@ -513,8 +658,12 @@ fn chain_snapshot_to_assets(
     let mut unpriced_asset_count = 0;
 
     for asset in snapshot.assets {
-        let (usd_price, usd_value, valuation_status) =
-            asset_valuation(&asset.symbol, asset.display_amount);
+        let (usd_price, usd_value, valuation_status) = asset_valuation(
+            chain_id,
+            &asset.symbol,
+            asset.contract_address.as_deref(),
+            asset.display_amount,
+        );
         if let Some(usd_value) = usd_value {
             chain_total_usd += usd_value;
         } else if asset.display_amount > 0.0 {
@@ -1,11 +1,22 @@
+use crate::wallet::bitcoin::batch as bitcoin_batch;
+use crate::wallet::bitcoin::bip21 as bitcoin_bip21;
+use crate::wallet::bitcoin::config::BitcoinNetwork;
+use crate::wallet::bitcoin::fees as bitcoin_fees;
+use crate::wallet::bitcoin::preview as bitcoin_preview;
+use crate::wallet::bitcoin::psbt as bitcoin_psbt;
 use crate::wallet::bitcoin::transaction as bitcoin_transaction;
+use crate::wallet::evm::nonce;
+use crate::wallet::evm::replacement as evm_replacement;
 use crate::wallet::evm::transaction as evm_transaction;
 use crate::wallet::security::commands::AppSecurity;
 use crate::wallet::sync::engine;
 use crate::wallet::sync::types::SyncReason;
 use crate::wallet::transaction_types::TransactionStatus;
 use crate::wallet::transaction_types::{
-    BitcoinFeeEstimationResponse, BitcoinTransaction, EvmTransaction, SendBitcoinRequest,
+    BitcoinCombinePsbtRequest, BitcoinFeeEstimate, BitcoinFeeEstimationResponse,
+    BitcoinFeePreview, BitcoinPaymentUri, BitcoinPaymentUriResponse, BitcoinPsbtRequest,
+    BitcoinPsbtResponse, BitcoinSendPreview, BitcoinTransaction, CreateBitcoinPaymentUriRequest,
+    EvmTransaction, SendBitcoinBatchRequest, SendBitcoinBatchResponse, SendBitcoinRequest,
     SendEvmRequest, SendTransactionResponse,
 };
 use crate::DB;
@@ -42,8 +53,152 @@ pub async fn send_bitcoin(
 }
 
 #[tauri::command]
-pub async fn bitcoin_estimate_fees() -> Result<BitcoinFeeEstimationResponse, String> {
-    bitcoin_transaction::estimate_bitcoin_fees().await
+pub async fn send_bitcoin_batch(
+    request: SendBitcoinBatchRequest,
+    state: tauri::State<'_, AppSecurity>,
+) -> Result<SendBitcoinBatchResponse, String> {
+    bitcoin_batch::send_bitcoin_batch(
+        request,
+        state.secret_backend(),
+        state.keystore(),
+        state.session_manager(),
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn bitcoin_bump_fee(
+    tx_hash: String,
+    new_fee_rate: f64,
+    state: tauri::State<'_, AppSecurity>,
+) -> Result<SendTransactionResponse, String> {
+    bitcoin_transaction::bump_bitcoin_transaction_fee(
+        tx_hash,
+        new_fee_rate,
+        state.secret_backend(),
+        state.keystore(),
+        state.session_manager(),
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn bitcoin_accelerate_transaction(
+    wallet_id: String,
+    parent_tx_hash: String,
+    target_fee_rate: f64,
+    state: tauri::State<'_, AppSecurity>,
+) -> Result<SendTransactionResponse, String> {
+    bitcoin_transaction::accelerate_incoming_transaction(
+        wallet_id,
+        parent_tx_hash,
+        target_fee_rate,
+        state.secret_backend(),
+        state.keystore(),
+        state.session_manager(),
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn bitcoin_preview_send(
+    request: SendBitcoinRequest,
+) -> Result<BitcoinSendPreview, String> {
+    bitcoin_preview::preview_bitcoin_send(request).await
+}
+
+#[tauri::command]
+pub async fn bitcoin_preview_batch(
+    request: SendBitcoinBatchRequest,
+) -> Result<BitcoinSendPreview, String> {
+    bitcoin_preview::preview_bitcoin_batch(request).await
+}
+
+#[tauri::command]
+pub async fn bitcoin_create_psbt(
+    request: SendBitcoinRequest,
+    output_path: Option<String>,
+    state: tauri::State<'_, AppSecurity>,
+) -> Result<BitcoinPsbtResponse, String> {
+    bitcoin_psbt::create_unsigned_psbt(
+        request,
+        output_path,
+        state.secret_backend(),
+        state.keystore(),
+        state.session_manager(),
+    )
+    .await
+}
+
+#[tauri::command]
+pub fn bitcoin_sign_psbt(
+    request: BitcoinPsbtRequest,
+    state: tauri::State<'_, AppSecurity>,
+) -> Result<BitcoinPsbtResponse, String> {
+    bitcoin_psbt::sign_psbt(
+        request,
+        state.secret_backend(),
+        state.keystore(),
+        state.session_manager(),
+    )
+}
+
+#[tauri::command]
+pub fn bitcoin_combine_psbts(
+    request: BitcoinCombinePsbtRequest,
+) -> Result<BitcoinPsbtResponse, String> {
+    bitcoin_psbt::combine_psbts(request)
+}
+
+#[tauri::command]
+pub async fn bitcoin_broadcast_psbt(
+    request: BitcoinPsbtRequest,
+) -> Result<SendTransactionResponse, String> {
+    bitcoin_psbt::broadcast_psbt(request).await
+}
+
+#[tauri::command]
+pub fn bitcoin_parse_payment_uri(
+    wallet_id: String,
+    uri: String,
+) -> Result<BitcoinPaymentUri, String> {
+    bitcoin_bip21::parse_bitcoin_payment_uri(&wallet_id, &uri)
+}
+
+#[tauri::command]
+pub async fn bitcoin_create_payment_uri(
+    request: CreateBitcoinPaymentUriRequest,
+) -> Result<BitcoinPaymentUriResponse, String> {
+    bitcoin_bip21::create_bitcoin_payment_uri(request).await
+}
+
+#[tauri::command]
+pub async fn bitcoin_estimate_fees(
+    network: Option<BitcoinNetwork>,
+) -> Result<BitcoinFeeEstimationResponse, String> {
+    bitcoin_fees::estimate_bitcoin_fees(network.unwrap_or_default()).await
+}
+
+#[tauri::command]
+pub async fn bitcoin_estimate_fee_for_target(
+    network: Option<BitcoinNetwork>,
+    target_blocks: u32,
+) -> Result<BitcoinFeeEstimate, String> {
+    bitcoin_fees::estimate_fee_for_target(network.unwrap_or_default(), target_blocks).await
+}
+
+#[tauri::command]
+pub async fn bitcoin_preview_send_fee(
+    request: SendBitcoinRequest,
+    state: tauri::State<'_, AppSecurity>,
+) -> Result<BitcoinFeePreview, String> {
+    bitcoin_fees::preview_send_fee(
+        request,
+        state.secret_backend(),
+        state.keystore(),
+        state.session_manager(),
+    )
+    .await
 }
 
 #[tauri::command]
@@ -216,17 +371,33 @@ pub async fn evm_send_transaction(
 
 #[tauri::command]
 pub async fn refresh_evm_transaction_lifecycle(
+    wallet_id: String,
     tx_hash: String,
     chain_id: u64,
 ) -> Result<EvmTransactionLifecycleUpdate, String> {
-    let (status, block_number, _) =
+    let (status, block_number, receipt_fee, _) =
         engine::refresh_evm_transaction_receipt_status(tx_hash.clone(), chain_id, SyncReason::AfterBroadcast)
             .await?;
+    // An unmined transaction whose nonce is already used lost to a replacement
+    let status =
+        nonce::reconcile_nonce_lifecycle(&wallet_id, &tx_hash, chain_id, status, block_number)
+            .await?;
 
     {
         let db = DB.lock().unwrap();
         db.update_evm_transaction_lifecycle(&tx_hash, chain_id, status, block_number)
             .map_err(|e| format!("Failed to persist EVM transaction lifecycle: {}", e))?;
+        // Replace the send-time fee snapshot with what the transaction actually paid
+        if let Some(receipt_fee) = receipt_fee {
+            db.update_evm_transaction_fee(
+                &tx_hash,
+                chain_id,
+                &receipt_fee.gas_used,
+                &receipt_fee.effective_gas_price,
+                receipt_fee.fee,
+            )
+            .map_err(|e| format!("Failed to persist EVM transaction fee: {}", e))?;
+        }
     }
 
     Ok(EvmTransactionLifecycleUpdate {
@@ -258,3 +429,39 @@ pub async fn evm_approve_token(
     .await?;
     Ok(response.tx_hash)
 }
+
+#[tauri::command]
+pub async fn evm_speed_up_transaction(
+    wallet_id: String,
+    tx_hash: String,
+    chain_id: u64,
+    state: tauri::State<'_, AppSecurity>,
+) -> Result<SendTransactionResponse, String> {
+    evm_replacement::speed_up_evm_transaction(
+        wallet_id,
+        tx_hash,
+        chain_id,
+        state.secret_backend(),
+        state.keystore(),
+        state.session_manager(),
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn evm_cancel_transaction(
+    wallet_id: String,
+    tx_hash: String,
+    chain_id: u64,
+    state: tauri::State<'_, AppSecurity>,
+) -> Result<SendTransactionResponse, String> {
+    evm_replacement::cancel_evm_transaction(
+        wallet_id,
+        tx_hash,
+        chain_id,
+        state.secret_backend(),
+        state.keystore(),
+        state.session_manager(),
+    )
+    .await
+}
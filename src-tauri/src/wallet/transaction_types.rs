@@ -1,6 +1,7 @@
 pub use crate::wallet::sync::types::LifecycleStatus as TransactionStatus;
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Transaction type (send or receive)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -10,6 +11,11 @@ pub enum TransactionType {
     Receive,
     Approve,
     Contract,
+    /// Bitcoin transaction paying only the wallet's own addresses
+    #[serde(rename = "self_transfer")]
+    SelfTransfer,
+    /// Bitcoin self-transfer merging several inputs into one output
+    Consolidation,
 }
 
 impl TransactionType {
@@ -19,6 +25,8 @@ impl TransactionType {
             TransactionType::Receive => "receive",
             TransactionType::Approve => "approve",
             TransactionType::Contract => "contract",
+            TransactionType::SelfTransfer => "self_transfer",
+            TransactionType::Consolidation => "consolidation",
         }
     }
 
@@ -28,6 +36,8 @@ impl TransactionType {
             "receive" => TransactionType::Receive,
             "approve" => TransactionType::Approve,
             "contract" => TransactionType::Contract,
+            "self_transfer" => TransactionType::SelfTransfer,
+            "consolidation" => TransactionType::Consolidation,
             _ => TransactionType::Receive,
         }
     }
@@ -42,13 +52,33 @@ pub struct BitcoinTransaction {
     pub tx_type: TransactionType,
     pub from_address: String,
     pub to_address: String,
-    pub amount: f64, // in BTC
-    pub fee: f64,    // in BTC
+    pub amount_sats: u64,
+    pub fee_sats: u64,
+    pub amount: f64, // in BTC, derived from amount_sats for display
+    pub fee: f64,    // in BTC, derived from fee_sats for display
     pub status: TransactionStatus,
     pub confirmations: u32,
     pub block_height: Option<u32>,
     pub timestamp: String,
     pub created_at: String,
+    /// Original transaction this one replaced through RBF
+    #[serde(default)]
+    pub replaces_tx_hash: Option<String>,
+    /// Replacement that superseded this transaction through RBF
+    #[serde(default)]
+    pub replaced_by_tx_hash: Option<String>,
+    /// Unconfirmed parent this transaction accelerates through CPFP
+    #[serde(default)]
+    pub parent_tx_hash: Option<String>,
+    /// Shared by the per-output rows of one batch payment
+    #[serde(default)]
+    pub batch_id: Option<String>,
+    /// Position of this row's output within a batch payment
+    #[serde(default)]
+    pub output_index: u32,
+    /// BIP329 label stored for this transaction
+    #[serde(default)]
+    pub label: Option<String>,
 }
 
 /// EVM transaction record
@@ -74,6 +104,15 @@ pub struct EvmTransaction {
     pub block_number: Option<u64>,
     pub timestamp: String,
     pub created_at: String,
+    /// Sender nonce; unknown for rows recorded before nonces were tracked
+    #[serde(default)]
+    pub nonce: Option<u64>,
+    /// Transaction this speed-up or cancel re-signed at the same nonce
+    #[serde(default)]
+    pub replaces_tx_hash: Option<String>,
+    /// Speed-up or cancel that superseded this transaction, or the one that mined instead of it
+    #[serde(default)]
+    pub replaced_by_tx_hash: Option<String>,
 }
 
 /// Request to send Bitcoin transaction
@@ -81,9 +120,67 @@ pub struct EvmTransaction {
 pub struct SendBitcoinRequest {
     pub wallet_id: String,
     pub to_address: String,
-    pub amount: f64,           // in BTC
+    pub amount_sats: u64,
     pub fee_rate: Option<f64>, // satoshis per byte, optional
+    /// Confirmation target in blocks, used to estimate the fee rate when
+    /// `fee_rate` is not given
+    #[serde(default)]
+    pub target_blocks: Option<u32>,
     pub send_all: Option<bool>,
+    /// Outpoints (`txid:vout`) to spend. When set, only these coins are used.
+    #[serde(default)]
+    pub include_outpoints: Option<Vec<String>>,
+    /// Outpoints (`txid:vout`) that must not be spent by this send
+    #[serde(default)]
+    pub exclude_outpoints: Option<Vec<String>>,
+    /// Txid of a preview of this send. When set, only the previewed
+    /// transaction is signed and broadcast.
+    #[serde(default)]
+    pub preview_txid: Option<String>,
+}
+
+/// One recipient of a batch payment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BitcoinBatchOutput {
+    pub address: String,
+    pub amount_sats: u64,
+}
+
+/// Request to pay several recipients from a single Bitcoin transaction.
+/// Outputs listed inline come first, followed by any imported from CSV.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendBitcoinBatchRequest {
+    pub wallet_id: String,
+    #[serde(default)]
+    pub outputs: Vec<BitcoinBatchOutput>,
+    pub csv: Option<String>,      // `address,amount_sats` lines
+    pub csv_path: Option<String>, // file with the same lines
+    pub fee_rate: Option<f64>,    // satoshis per byte, optional
+    /// Confirmation target in blocks, used to estimate the fee rate when
+    /// `fee_rate` is not given
+    #[serde(default)]
+    pub target_blocks: Option<u32>,
+    /// Outpoints (`txid:vout`) to spend. When set, only these coins are used.
+    #[serde(default)]
+    pub include_outpoints: Option<Vec<String>>,
+    /// Outpoints (`txid:vout`) that must not be spent by this send
+    #[serde(default)]
+    pub exclude_outpoints: Option<Vec<String>>,
+    /// Txid of a preview of this batch. When set, only the previewed
+    /// transaction is signed and broadcast.
+    #[serde(default)]
+    pub preview_txid: Option<String>,
+}
+
+/// Response for a broadcast batch payment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendBitcoinBatchResponse {
+    pub tx_hash: String,
+    pub batch_id: String,
+    pub output_count: usize,
+    pub total_sats: u64,
+    pub fee_sats: u64,
+    pub message: String,
 }
 
 /// Request to send EVM transaction
@@ -97,7 +194,84 @@ pub struct SendEvmRequest {
     pub asset_symbol: String,
     pub contract_address: Option<String>, // None for native token
     pub gas_limit: Option<u64>,
-    pub gas_price: Option<String>, // in gwei
+    /// Per-gas cap in wei: the max fee on EIP-1559 chains, the gas price elsewhere
+    pub gas_price: Option<String>,
+    /// EIP-1559 caps in wei; estimated from `speed` when unset
+    #[serde(default)]
+    pub max_fee_per_gas: Option<String>,
+    #[serde(default)]
+    pub max_priority_fee_per_gas: Option<String>,
+    #[serde(default)]
+    pub speed: EvmFeeSpeed,
+}
+
+/// Fee tier picked from recent priority fees when a send sets no explicit caps
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EvmFeeSpeed {
+    Slow,
+    #[default]
+    Normal,
+    Fast,
+}
+
+/// A BIP174 PSBT supplied to the sign or broadcast step, as base64 text or a file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BitcoinPsbtRequest {
+    pub wallet_id: String,
+    pub psbt: Option<String>,      // base64
+    pub psbt_path: Option<String>, // binary or base64 file
+    pub output_path: Option<String>,
+}
+
+/// A PSBT produced by the build or sign step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BitcoinPsbtResponse {
+    pub psbt: String, // base64
+    pub txid: String,
+    pub fee_sats: Option<u64>, // when every input amount is known
+    pub fee: Option<f64>,      // in BTC, derived from fee_sats for display
+    pub is_finalized: bool,
+    pub file_path: Option<String>,
+    pub signatures_present: Option<u32>,  // multisig wallets only
+    pub signatures_required: Option<u32>, // the wallet's multisig threshold
+}
+
+/// PSBTs of the same transaction signed by different cosigners, to be merged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BitcoinCombinePsbtRequest {
+    pub wallet_id: String,
+    pub psbts: Vec<String>, // base64
+    pub output_path: Option<String>,
+}
+
+/// A BIP21 `bitcoin:` URI decoded into a pre-filled send request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BitcoinPaymentUri {
+    pub request: SendBitcoinRequest,
+    pub amount_specified: bool, // false when the URI left the amount to the payer
+    pub label: Option<String>,
+    pub message: Option<String>,
+    pub lightning: Option<String>, // BOLT11 invoice offered alongside the address
+    /// Optional parameters this wallet does not interpret
+    pub extra_params: BTreeMap<String, String>,
+}
+
+/// Request to generate a BIP21 URI for one of a wallet's receive addresses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateBitcoinPaymentUriRequest {
+    pub wallet_id: String,
+    pub address: Option<String>, // next unused receive address when omitted
+    pub amount_sats: Option<u64>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+}
+
+/// A generated BIP21 URI and the address it pays
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BitcoinPaymentUriResponse {
+    pub uri: String,
+    pub address: String,
 }
 
 /// Response after sending a transaction
@@ -107,19 +281,101 @@ pub struct SendTransactionResponse {
     pub message: String,
 }
 
+/// EIP-1559 fee caps of one speed tier, in wei as strings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvmFeeTier {
+    pub max_fee_per_gas: String,
+    pub max_priority_fee_per_gas: String,
+}
+
 /// Response for EVM gas estimation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvmGasEstimationResponse {
     pub gas_limit: u64,
-    pub gas_price: String, // in wei as string
+    pub gas_price: String, // in wei as string; the normal tier's max fee on EIP-1559 chains
+    /// Base fee expected for the next block; `None` on chains without EIP-1559
+    #[serde(default)]
+    pub base_fee_per_gas: Option<String>,
+    #[serde(default)]
+    pub slow: Option<EvmFeeTier>,
+    #[serde(default)]
+    pub normal: Option<EvmFeeTier>,
+    #[serde(default)]
+    pub fast: Option<EvmFeeTier>,
 }
 
-/// Response for Bitcoin fee estimation
+/// Response for Bitcoin fee estimation, in sat/vB
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BitcoinFeeEstimationResponse {
-    pub fast: f32,
-    pub half_hour: f32,
-    pub hour: f32,
+    pub fast: f64,      // next block
+    pub half_hour: f64, // 3 blocks
+    pub hour: f64,      // 6 blocks
+    pub min_relay_fee_rate: f64,
+}
+
+/// How far the estimator and the current mempool agree on a fee rate
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BitcoinFeeConfidence {
+    /// The mempool clears within the target at the estimated rate
+    High,
+    /// No mempool histogram was available to check the estimate against
+    Medium,
+    /// The mempool needed a higher rate than the estimator predicted
+    Low,
+}
+
+/// Fee rate estimate for an arbitrary confirmation target, in sat/vB
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BitcoinFeeEstimate {
+    pub target_blocks: u32,
+    pub fee_rate: f64,
+    pub min_relay_fee_rate: f64,
+    /// Blocks until a transaction paying `fee_rate` is mined if nothing
+    /// better-paying arrives, from the mempool histogram
+    pub projected_blocks: Option<u32>,
+    pub confidence: BitcoinFeeConfidence,
+}
+
+/// Total fee of a send for the inputs and outputs it would actually use
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BitcoinFeePreview {
+    pub fee_rate: f64,
+    pub fee_sats: u64,
+    pub fee: f64, // in BTC, derived from fee_sats for display
+    pub input_count: usize,
+    pub output_count: usize,
+    pub estimate: Option<BitcoinFeeEstimate>, // when the rate came from target_blocks
+}
+
+/// A coin spent by a previewed send
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BitcoinPreviewInput {
+    pub outpoint: String, // txid:vout
+    pub address: Option<String>,
+    pub amount_sats: u64,
+}
+
+/// An output of a previewed send; change outputs pay back to the wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BitcoinPreviewOutput {
+    pub address: Option<String>,
+    pub amount_sats: u64,
+    pub is_change: bool,
+}
+
+/// A send built from the wallet's watch-only descriptors, before any secret
+/// is touched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BitcoinSendPreview {
+    pub txid: String, // unchanged by signing; pass back as preview_txid
+    pub inputs: Vec<BitcoinPreviewInput>,
+    pub outputs: Vec<BitcoinPreviewOutput>,
+    pub vsize: u64, // once signed, assuming maximum-size signatures
+    pub fee_sats: u64,
+    pub fee: f64,      // in BTC, derived from fee_sats for display
+    pub fee_rate: f64, // effective sat/vB, fee_sats over vsize
+    pub rbf: bool,
 }
 
 /// Request to send a raw EVM transaction (for OpenOcean swaps)
@@ -131,7 +387,7 @@ pub struct RawTransactionRequest {
     pub data: String,
     pub value: String,
     pub gas_limit: String,
-    pub gas_price: String,
+    pub gas_price: String, // per-gas cap in wei, as for `SendEvmRequest`
 }
 
 #[cfg(test)]
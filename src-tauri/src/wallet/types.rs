@@ -1,3 +1,6 @@
+use crate::wallet::bitcoin::config::BitcoinNetwork;
+use crate::wallet::bitcoin::descriptor::BitcoinKeychain;
+use crate::wallet::bitcoin::multisig::MultisigScriptType;
 use crate::wallet::state::types::FreshnessMetadata;
 use crate::wallet::sync::types::SyncOutcome;
 use serde::{Deserialize, Serialize};
@@ -15,7 +18,10 @@ pub struct WalletInfo {
     pub label: String,
     pub wallet_type: String, // "mnemonic" or "private-key"
     pub address: String,
-    pub balance: f64,
+    pub balance: f64, // derived from balance_sats for Bitcoin wallets
+    /// Bitcoin wallets only: the balance in satoshis
+    #[serde(default)]
+    pub balance_sats: Option<u64>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -152,6 +158,68 @@ impl EvmAsset {
     }
 }
 
+/// A user-defined EVM network, tracked next to the built-in chains.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EvmNetwork {
+    pub chain_id: u64,
+    /// Short identifier stored on balances and transactions, e.g. `base`.
+    pub name: String,
+    pub display_name: String,
+    pub native_symbol: String,
+    pub native_decimals: u8,
+    pub rpc_url: String,
+    pub wss_url: Option<String>,
+    /// Etherscan-compatible API used for history sync.
+    pub explorer_api_url: Option<String>,
+    pub is_testnet: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveEvmNetworkRequest {
+    pub chain_id: u64,
+    pub name: String,
+    /// Defaults to the name when absent.
+    #[serde(default)]
+    pub display_name: Option<String>,
+    pub native_symbol: String,
+    /// Defaults to 18 when absent.
+    #[serde(default)]
+    pub native_decimals: Option<u8>,
+    pub rpc_url: String,
+    #[serde(default)]
+    pub wss_url: Option<String>,
+    #[serde(default)]
+    pub explorer_api_url: Option<String>,
+    #[serde(default)]
+    pub is_testnet: bool,
+}
+
+/// An ERC-20 token the user added by contract address. Its metadata is read
+/// from the contract once, when the token is added.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvmCustomToken {
+    pub id: String,
+    pub chain_id: u64,
+    pub contract_address: String,
+    pub symbol: String,
+    pub name: String,
+    pub decimals: u8,
+    pub created_at: String,
+}
+
+impl EvmCustomToken {
+    pub fn to_asset(&self) -> EvmAsset {
+        EvmAsset::new(
+            &self.symbol,
+            &self.name,
+            self.decimals,
+            Some(&self.contract_address),
+        )
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvmAssetBalance {
     pub chain: String,
@@ -200,6 +268,259 @@ pub struct BitcoinWalletBalanceResponse {
     pub balance_state: FreshnessBackedBitcoinBalance,
 }
 
+/// One derived address of an HD Bitcoin wallet with its last known activity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BitcoinAddressInfo {
+    pub wallet_id: String,
+    pub keychain: BitcoinKeychain,
+    pub derivation_index: u32,
+    pub address: String,
+    pub balance_sats: i64,
+    pub tx_count: u64,
+    pub updated_at: String,
+}
+
+/// An unspent output of a Bitcoin wallet, merged with its local coin-control metadata.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BitcoinUtxo {
+    /// `txid:vout`
+    pub outpoint: String,
+    pub value_sats: u64,
+    pub confirmations: u32,
+    pub address: String,
+    pub label: Option<String>,
+    /// Label of the receiving address, when one is stored
+    #[serde(default)]
+    pub address_label: Option<String>,
+    pub frozen: bool,
+}
+
+/// Locally stored label and freeze flag for one outpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BitcoinUtxoMetadata {
+    pub wallet_id: String,
+    pub outpoint: String,
+    pub label: Option<String>,
+    /// BIP329 `origin` of an imported label
+    pub origin: Option<String>,
+    pub frozen: bool,
+    pub updated_at: String,
+}
+
+/// What a wallet label refers to, named as in BIP329.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BitcoinLabelType {
+    Tx,
+    Addr,
+    Pubkey,
+    Input,
+    Output,
+    Xpub,
+}
+
+impl BitcoinLabelType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Tx => "tx",
+            Self::Addr => "addr",
+            Self::Pubkey => "pubkey",
+            Self::Input => "input",
+            Self::Output => "output",
+            Self::Xpub => "xpub",
+        }
+    }
+
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "tx" => Some(Self::Tx),
+            "addr" => Some(Self::Addr),
+            "pubkey" => Some(Self::Pubkey),
+            "input" => Some(Self::Input),
+            "output" => Some(Self::Output),
+            "xpub" => Some(Self::Xpub),
+            _ => None,
+        }
+    }
+}
+
+/// A stored label. Output labels live with the coin-control metadata instead.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BitcoinLabel {
+    pub wallet_id: String,
+    pub label_type: BitcoinLabelType,
+    /// Txid, address, `txid:vout`, public key or xpub, depending on `label_type`
+    pub reference: String,
+    pub label: String,
+    pub origin: Option<String>,
+    pub updated_at: String,
+}
+
+/// One line of a BIP329 label export.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bip329Record {
+    #[serde(rename = "type")]
+    pub label_type: BitcoinLabelType,
+    #[serde(rename = "ref")]
+    pub reference: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+    /// Outputs only: `false` marks the coin frozen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spendable: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportBitcoinLabelsRequest {
+    pub wallet_id: String,
+    /// BIP329 JSONL text; `jsonl_path` reads it from a file instead
+    #[serde(default)]
+    pub jsonl: Option<String>,
+    #[serde(default)]
+    pub jsonl_path: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportBitcoinLabelsResponse {
+    pub imported: usize,
+    /// Records of unknown types or with references that do not parse
+    pub skipped: usize,
+}
+
+/// Encoding of a Bitcoin message signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BitcoinMessageFormat {
+    /// Electrum-style `signmessage`: a base64 compact recoverable signature.
+    Legacy,
+    /// BIP322 "simple": the base64 witness of the virtual `to_sign` transaction.
+    Bip322Simple,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateBitcoinMultisigWalletRequest {
+    /// Account xpubs of the other cosigners, optionally `[fingerprint/path]`-prefixed.
+    pub cosigner_keys: Vec<String>,
+    pub threshold: u32,
+    /// This device's own cosigner key; the wallet is watch-only without one.
+    #[serde(default)]
+    pub mnemonic_phrase: Option<String>,
+    #[serde(default)]
+    pub passphrase: Option<String>,
+    #[serde(default)]
+    pub wallet_label: Option<String>,
+    #[serde(default)]
+    pub script_type: Option<MultisigScriptType>,
+    #[serde(default)]
+    pub network: Option<BitcoinNetwork>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignBitcoinMessageRequest {
+    pub wallet_id: String,
+    /// Address to sign for; the wallet's primary address when absent.
+    #[serde(default)]
+    pub address: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BitcoinMessageSignature {
+    pub address: String,
+    pub message: String,
+    pub signature: String,
+    pub format: BitcoinMessageFormat,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyBitcoinMessageRequest {
+    pub address: String,
+    pub message: String,
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerifyBitcoinMessageResponse {
+    pub valid: bool,
+    /// The format the signature was checked as.
+    pub format: BitcoinMessageFormat,
+}
+
+/// Protocol spoken by a Bitcoin chain data source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BitcoinBackendKind {
+    Esplora,
+    Electrum,
+    BitcoinRpc,
+}
+
+impl BitcoinBackendKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Esplora => "esplora",
+            Self::Electrum => "electrum",
+            Self::BitcoinRpc => "bitcoin_rpc",
+        }
+    }
+
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "esplora" => Some(Self::Esplora),
+            "electrum" => Some(Self::Electrum),
+            "bitcoin_rpc" => Some(Self::BitcoinRpc),
+            _ => None,
+        }
+    }
+}
+
+/// A user-configured chain data source. Enabled backends of a network are
+/// tried in ascending priority order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BitcoinBackendConfig {
+    pub id: String,
+    pub network: BitcoinNetwork,
+    pub kind: BitcoinBackendKind,
+    pub url: String,
+    pub rpc_username: Option<String>,
+    /// Decrypted only when a backend is about to be queried, and never sent
+    /// back to the frontend.
+    #[serde(skip_serializing, default)]
+    pub rpc_password: Option<String>,
+    #[serde(default)]
+    pub has_rpc_password: bool,
+    pub priority: i64,
+    pub enabled: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveBitcoinBackendRequest {
+    /// Updates the backend with this id, or creates a new one when absent.
+    #[serde(default)]
+    pub id: Option<String>,
+    pub network: BitcoinNetwork,
+    pub kind: BitcoinBackendKind,
+    pub url: String,
+    #[serde(default)]
+    pub rpc_username: Option<String>,
+    /// Left unchanged on update when absent.
+    #[serde(default)]
+    pub rpc_password: Option<String>,
+    /// Appended after the existing backends when absent.
+    #[serde(default)]
+    pub priority: Option<i64>,
+    #[serde(default = "default_backend_enabled")]
+    pub enabled: bool,
+}
+
+fn default_backend_enabled() -> bool {
+    true
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FreshnessBackedBitcoinBalance {
     pub raw_amount: String,
@@ -145,6 +145,9 @@ const SecuritySessionProvider: React.FC<{ children: React.ReactNode }> = ({ chil
       case 'unknown_wallet':
         toast.error('Wallet was not found. Refresh and try again.');
         break;
+      case 'watch_only':
+        toast.error('This is a watch-only wallet. Sign with the device that holds its keys.');
+        break;
       case 'expired':
       case 'locked':
         setStatus('locked');
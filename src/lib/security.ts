@@ -9,9 +9,15 @@ export type SecurityError =
   | 'policy_denied'
   | 'operation_not_allowed'
   | 'unknown_wallet'
-  | 'secret_backend_unavailable';
+  | 'secret_backend_unavailable'
+  | 'watch_only';
 
-export type SignerOperation = 'send' | 'approve' | 'export_mnemonic' | 'export_private_key';
+export type SignerOperation =
+  | 'send'
+  | 'approve'
+  | 'export_mnemonic'
+  | 'export_private_key'
+  | 'sign_message';
 
 export interface LocalPasswordPolicy {
   installation_scope: 'per_installation';
@@ -61,6 +67,7 @@ const SECURITY_ERRORS: SecurityError[] = [
   'operation_not_allowed',
   'unknown_wallet',
   'secret_backend_unavailable',
+  'watch_only',
 ];
 
 export const SECURITY_STATE_EVENT = 'app-security-changed';
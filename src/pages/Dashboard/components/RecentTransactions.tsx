@@ -1,7 +1,7 @@
 import React from 'react';
 import { useNavigate } from 'react-router-dom';
 import { Card } from '@/components/ui/card';
-import { ArrowUpRight, ArrowDownLeft, ShieldCheck, Code } from 'lucide-react';
+import { ArrowUpRight, ArrowDownLeft, ShieldCheck, Code, Repeat } from 'lucide-react';
 import { shortAddress } from '@/lib/utils';
 import { UnifiedTransaction } from '../types';
 
@@ -91,6 +91,14 @@ export const RecentTransactions: React.FC<RecentTransactionsProps> = ({ transact
                     textClass: 'text-purple-500',
                     amountPrefix: '-',
                 };
+            case 'self_transfer':
+            case 'consolidation':
+                return {
+                    label: tx.tx_type === 'consolidation' ? 'Consolidate' : 'Self Transfer',
+                    icon: <Repeat className="w-3 h-3" />,
+                    textClass: 'text-muted-foreground',
+                    amountPrefix: '',
+                };
         }
     };
 
@@ -33,7 +33,7 @@ export interface BitcoinTransaction {
     id: string;
     wallet_id: string;
     tx_hash: string;
-    tx_type: 'send' | 'receive';
+    tx_type: 'send' | 'receive' | 'self_transfer' | 'consolidation';
     from_address: string;
     to_address: string;
     amount: number;
@@ -71,7 +71,7 @@ export interface EvmTransaction {
 export type UnifiedTransaction = {
     id: string;
     type: 'bitcoin' | 'evm';
-    tx_type: 'send' | 'receive' | 'approve' | 'contract';
+    tx_type: 'send' | 'receive' | 'approve' | 'contract' | 'self_transfer' | 'consolidation';
     status: 'broadcasted' | 'pending' | 'confirmed' | 'failed' | 'replaced' | 'dropped';
     tx_hash: string;
     asset_symbol: string;
@@ -4,7 +4,7 @@ import RecoveryPanel from '@/components/common/RecoveryPanel';
 import { useSecuritySession } from '@/components/common/SecuritySession';
 import { UnlockGate } from '@/components/common/UnlockGate';
 import { Card, Button, Dialog, DialogContent, DialogHeader, DialogTitle, DialogTrigger, Tabs, TabsContent, TabsList, TabsTrigger, Label, Textarea, Input, Badge } from '@/components/ui';
-import { Copy, Plus, AlertCircle, CheckCircle2, Trash2, Download, RefreshCw, Send, ExternalLink, HelpCircle } from 'lucide-react';
+import { Copy, Plus, AlertCircle, CheckCircle2, Trash2, Download, RefreshCw, Send, ExternalLink, HelpCircle, FileText } from 'lucide-react';
 import { invoke, isTauriRuntimeAvailable, TAURI_UNAVAILABLE_MESSAGE, isTauriUnavailableError } from '@/lib/tauri';
 import { parseSecurityError, securityGetBackendState } from '@/lib/security';
 import { formatFreshnessLabel, getFreshnessBadgeClass, FreshnessMetadata } from '@/lib/evm-wallet';
@@ -15,7 +15,7 @@ import { toast } from 'sonner';
 interface WalletInfo {
   id: string;
   label: string;
-  wallet_type: 'mnemonic' | 'private-key';
+  wallet_type: 'mnemonic' | 'private-key' | 'multisig' | 'watch-only';
   address: string;
   balance: number;
   created_at: string;
@@ -30,9 +30,15 @@ interface CreateWalletResponse {
 
 interface PendingMnemonicBackup {
   mnemonic: string;
+  passphrase: string;
   walletLabel: string;
 }
 
+interface ExportedMnemonic {
+  phrase: string;
+  passphrase?: string;
+}
+
 interface PriceState {
   price_usd: number | null;
   price_source: string | null;
@@ -65,18 +71,54 @@ interface ReviewedBitcoinSendIntent {
   totalChargeDisplay: string;
   sendAll: boolean;
   riskPoint: string;
+  preview: BitcoinSendPreview | null;
   request: {
     wallet_id: string;
     to_address: string;
-    amount: number;
+    amount_sats: number;
     fee_rate: number;
     send_all: boolean;
+    preview_txid?: string;
   };
   payloadFingerprint: string;
 }
 
+interface BitcoinSendPreview {
+  txid: string;
+  inputs: { outpoint: string; address: string | null; amount_sats: number }[];
+  outputs: { address: string | null; amount_sats: number; is_change: boolean }[];
+  vsize: number;
+  fee_sats: number;
+  fee: number;
+  fee_rate: number;
+  rbf: boolean;
+}
+
+interface BitcoinPsbtResponse {
+  psbt: string;
+  txid: string;
+  fee_sats: number | null;
+  fee: number | null;
+  is_finalized: boolean;
+  file_path: string | null;
+  signatures_present: number | null;
+  signatures_required: number | null;
+}
+
+type MultisigScriptType = 'p2wsh' | 'p2tr';
+
+const WALLET_TYPE_LABELS: Record<WalletInfo['wallet_type'], string> = {
+  mnemonic: 'Mnemonic',
+  'private-key': 'Private Key',
+  multisig: 'Multisig',
+  'watch-only': 'Watch-only',
+};
+
 const SATOSHIS_PER_BTC = 100_000_000n;
 
+// Preview error for wallets whose watch-only descriptors are not stored yet
+const BTC_PREVIEW_UNAVAILABLE = 'preview_unavailable';
+
 const normalizeBtcAmountToSats = (amount: string) => {
   const normalizedAmount = amount.trim();
   if (!normalizedAmount) {
@@ -115,7 +157,7 @@ const estimateBtcFeeSats = (feeRate: number) => Math.max(1, Math.trunc(feeRate))
 const buildBitcoinPayloadFingerprint = (request: ReviewedBitcoinSendIntent['request']) => [
   request.wallet_id,
   request.to_address,
-  request.amount.toString(),
+  request.amount_sats.toString(),
   request.fee_rate.toString(),
   request.send_all ? '1' : '0',
 ].join('|');
@@ -126,12 +168,23 @@ const BitcoinAssets: React.FC = () => {
   const [isDialogOpen, setIsDialogOpen] = useState(false);
   const [pendingMnemonicBackup, setPendingMnemonicBackup] = useState<PendingMnemonicBackup | null>(null);
   const [mnemonicInput, setMnemonicInput] = useState('');
+  const [passphraseInput, setPassphraseInput] = useState('');
   const [privateKeyInput, setPrivateKeyInput] = useState('');
   const [walletLabel, setWalletLabel] = useState('');
   const [isLoading, setIsLoading] = useState(false);
   const [isPersistingMnemonic, setIsPersistingMnemonic] = useState(false);
   const [mnemonicCopied, setMnemonicCopied] = useState(false);
   const [exportedSecret, setExportedSecret] = useState<string | null>(null);
+  const [exportedPassphrase, setExportedPassphrase] = useState<string | null>(null);
+  const [multisigKeysInput, setMultisigKeysInput] = useState('');
+  const [multisigThreshold, setMultisigThreshold] = useState('2');
+  const [multisigScriptType, setMultisigScriptType] = useState<MultisigScriptType>('p2wsh');
+  const [multisigMnemonicInput, setMultisigMnemonicInput] = useState('');
+  const [psbtWallet, setPsbtWallet] = useState<WalletInfo | null>(null);
+  const [psbtInput, setPsbtInput] = useState('');
+  const [psbtResult, setPsbtResult] = useState<BitcoinPsbtResponse | null>(null);
+  const [isPsbtBusy, setIsPsbtBusy] = useState(false);
+  const [psbtCopied, setPsbtCopied] = useState(false);
   const [showExportDialog, setShowExportDialog] = useState(false);
   const [exportedSecretType, setExportedSecretType] = useState<'mnemonic' | 'private-key'>('private-key');
   const [exportCopied, setExportCopied] = useState(false);
@@ -386,9 +439,11 @@ const BitcoinAssets: React.FC = () => {
 
       setPendingMnemonicBackup({
         mnemonic,
+        passphrase: passphraseInput,
         walletLabel: walletLabel || 'Bitcoin Wallet',
       });
       setMnemonicInput('');
+      setPassphraseInput('');
     } catch (error) {
       console.error('Error creating wallet:', error);
       setWalletFlowRecovery(describeWalletRecovery('create-wallet', error, { chainFamily: 'bitcoin' }));
@@ -410,11 +465,13 @@ const BitcoinAssets: React.FC = () => {
 
       const response = await invoke<CreateWalletResponse>('bitcoin_create_wallet_from_mnemonic', {
         mnemonicPhrase: mnemonicInput,
+        passphrase: passphraseInput || undefined,
         walletLabel: walletLabel || undefined,
       });
 
       await syncImportedWallet(response.wallet);
       setMnemonicInput('');
+      setPassphraseInput('');
       setWalletLabel('');
       setIsDialogOpen(false);
     } catch (error) {
@@ -453,6 +510,146 @@ const BitcoinAssets: React.FC = () => {
     }
   };
 
+  const handleCreateMultisig = async () => {
+    const cosignerKeys = multisigKeysInput.split(/[\s,]+/).filter(Boolean);
+    const localMnemonic = multisigMnemonicInput.trim();
+    if (cosignerKeys.length === 0) return;
+
+    setIsLoading(true);
+    setWalletFlowRecovery(null);
+    try {
+      if (localMnemonic) {
+        const ready = await ensureWalletProtectionReady('Set a local password before adding a multisig cosigner key on this device.');
+        if (!ready) {
+          return;
+        }
+      }
+
+      const wallet = await invoke<WalletInfo>('bitcoin_create_multisig_wallet', {
+        request: {
+          cosigner_keys: cosignerKeys,
+          threshold: Number(multisigThreshold),
+          mnemonic_phrase: localMnemonic || undefined,
+          passphrase: localMnemonic ? passphraseInput || undefined : undefined,
+          wallet_label: walletLabel || undefined,
+          script_type: multisigScriptType,
+        },
+      });
+
+      await syncImportedWallet(wallet);
+      setMultisigKeysInput('');
+      setMultisigMnemonicInput('');
+      setPassphraseInput('');
+      setWalletLabel('');
+      setIsDialogOpen(false);
+    } catch (error) {
+      console.error('Error creating multisig wallet:', error);
+      setWalletFlowRecovery(describeWalletRecovery('import-wallet', error, { chainFamily: 'bitcoin' }));
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  // One PSBT per line, as each cosigner hands back their signed copy
+  const pastedPsbts = psbtInput.split(/\s+/).filter(Boolean);
+
+  const openPsbtDialog = (wallet: WalletInfo) => {
+    setPsbtWallet(wallet);
+    setPsbtInput('');
+    setPsbtResult(null);
+    setPsbtCopied(false);
+  };
+
+  const closePsbtDialog = () => {
+    setPsbtWallet(null);
+    setPsbtInput('');
+    setPsbtResult(null);
+  };
+
+  const handlePsbtError = (error: unknown, retry: () => void) => {
+    console.error('Error handling PSBT:', error);
+    if (isTauriUnavailableError(error)) {
+      toast.error(TAURI_UNAVAILABLE_MESSAGE);
+      return;
+    }
+
+    const securityError = parseSecurityError(error);
+    if (securityError === 'locked' || securityError === 'expired' || securityError === 'reauth_required') {
+      void requestUnlock({
+        prompt: 'Re-enter your local password to sign the PSBT.',
+        reason: securityError === 'reauth_required' ? 'reauth_required' : securityError,
+        mode: 'reauth',
+        operation: 'send',
+        onUnlockSuccess: retry,
+      });
+    } else {
+      toast.error(typeof error === 'string' ? error : describeWalletRecovery('send-asset', error, { chainFamily: 'bitcoin' }).summary);
+    }
+  };
+
+  const handleSignPsbt = async () => {
+    if (!psbtWallet || pastedPsbts.length !== 1) return;
+
+    setIsPsbtBusy(true);
+    try {
+      const response = await invoke<BitcoinPsbtResponse>('bitcoin_sign_psbt', {
+        request: { wallet_id: psbtWallet.id, psbt: pastedPsbts[0] },
+      });
+      setPsbtResult(response);
+    } catch (error) {
+      handlePsbtError(error, () => void handleSignPsbt());
+    } finally {
+      setIsPsbtBusy(false);
+    }
+  };
+
+  const handleCombinePsbts = async () => {
+    if (!psbtWallet || pastedPsbts.length < 2) return;
+
+    setIsPsbtBusy(true);
+    try {
+      const response = await invoke<BitcoinPsbtResponse>('bitcoin_combine_psbts', {
+        request: { wallet_id: psbtWallet.id, psbts: pastedPsbts },
+      });
+      setPsbtResult(response);
+    } catch (error) {
+      handlePsbtError(error, () => void handleCombinePsbts());
+    } finally {
+      setIsPsbtBusy(false);
+    }
+  };
+
+  const handleBroadcastPsbt = async () => {
+    const psbt = psbtResult?.psbt ?? (pastedPsbts.length === 1 ? pastedPsbts[0] : null);
+    if (!psbtWallet || !psbt) return;
+
+    setIsPsbtBusy(true);
+    try {
+      const response = await invoke<{ tx_hash: string; message: string }>('bitcoin_broadcast_psbt', {
+        request: { wallet_id: psbtWallet.id, psbt },
+      });
+      toast.success(
+        <div className="flex flex-col gap-1">
+          <div className="font-medium">PSBT Broadcast</div>
+          <div className="text-[10px] font-mono opacity-70 break-all">{response.tx_hash}</div>
+        </div>,
+        { duration: 10000 }
+      );
+      handleRefreshBalance(psbtWallet.id);
+      closePsbtDialog();
+    } catch (error) {
+      handlePsbtError(error, () => void handleBroadcastPsbt());
+    } finally {
+      setIsPsbtBusy(false);
+    }
+  };
+
+  const handleCopyPsbt = (psbt: string) => {
+    navigator.clipboard.writeText(psbt);
+    setPsbtCopied(true);
+    setTimeout(() => setPsbtCopied(false), 2000);
+  };
+
   const handleCopyAddress = (address: string) => {
     navigator.clipboard.writeText(address);
     setAddressCopied(address);
@@ -492,6 +689,7 @@ const BitcoinAssets: React.FC = () => {
 
       const response = await invoke<CreateWalletResponse>('bitcoin_create_wallet_from_mnemonic', {
         mnemonicPhrase: pendingMnemonicBackup.mnemonic,
+        passphrase: pendingMnemonicBackup.passphrase || undefined,
         walletLabel: pendingMnemonicBackup.walletLabel || undefined,
       });
 
@@ -514,6 +712,7 @@ const BitcoinAssets: React.FC = () => {
     try {
       const secret = await invoke<string>('bitcoin_export_private_key', { walletId });
       setExportedSecret(secret);
+      setExportedPassphrase(null);
       setExportedSecretType('private-key');
       setShowExportDialog(true);
     } catch (error) {
@@ -527,8 +726,9 @@ const BitcoinAssets: React.FC = () => {
   const handleExportMnemonic = async (walletId: string) => {
     setIsLoading(true);
     try {
-      const secret = await invoke<string>('bitcoin_export_mnemonic', { walletId });
-      setExportedSecret(secret);
+      const secret = await invoke<ExportedMnemonic>('bitcoin_export_mnemonic', { walletId });
+      setExportedSecret(secret.phrase);
+      setExportedPassphrase(secret.passphrase ?? null);
       setExportedSecretType('mnemonic');
       setShowExportDialog(true);
     } catch (error) {
@@ -613,7 +813,6 @@ const BitcoinAssets: React.FC = () => {
 
     const amountSats = normalizeBtcAmountToSats(sendAmount);
     const amountBtcString = formatBtcFromSats(amountSats);
-    const amountValue = Number(amountBtcString);
     const normalizedFeeRate = Math.max(1, Math.trunc(sendFeeRate || 1));
     const estimatedFeeSats = estimateBtcFeeSats(normalizedFeeRate);
     const totalChargeSats = BigInt(amountSats) + BigInt(estimatedFeeSats);
@@ -621,7 +820,7 @@ const BitcoinAssets: React.FC = () => {
     const request = {
       wallet_id: selectedWalletForSend.id,
       to_address: recipientAddress,
-      amount: amountValue,
+      amount_sats: Number(amountSats),
       fee_rate: normalizedFeeRate,
       send_all: isSendAll,
     };
@@ -641,15 +840,47 @@ const BitcoinAssets: React.FC = () => {
       riskPoint: isSendAll
         ? 'Send-all spends the wallet balance using the reviewed fee rate, leaving no intentional remainder in this wallet.'
         : 'Bitcoin sends are irreversible once broadcast, so recipient and fee rate must match the reviewed transfer.',
+      preview: null,
       request,
       payloadFingerprint: buildBitcoinPayloadFingerprint(request),
     };
   };
 
-  const handlePrepareSendReview = () => {
+  // Replaces the rough fee guess with the transaction the backend will sign,
+  // built from the wallet's watch-only descriptors.
+  const withSendPreview = async (intent: ReviewedBitcoinSendIntent): Promise<ReviewedBitcoinSendIntent> => {
+    if (!isTauriRuntimeAvailable()) {
+      return intent;
+    }
+
+    try {
+      const preview = await invoke<BitcoinSendPreview>('bitcoin_preview_send', { request: intent.request });
+      const recipientSats = preview.outputs
+        .filter((output) => !output.is_change)
+        .reduce((sum, output) => sum + BigInt(output.amount_sats), 0n);
+      return {
+        ...intent,
+        preview,
+        feeRateDisplay: `${preview.fee_rate.toFixed(2)} sat/vB (${preview.vsize} vB)`,
+        estimatedFeeDisplay: `${formatBtcFromSats(preview.fee_sats.toString())} BTC`,
+        totalChargeDisplay: `${formatBtcFromSats((recipientSats + BigInt(preview.fee_sats)).toString())} BTC`,
+        request: { ...intent.request, preview_txid: preview.txid },
+      };
+    } catch (error) {
+      // Wallets without stored descriptors cannot be previewed yet; the
+      // refused send stores them so the next review is previewed. Any other
+      // failure stops the review.
+      if (error === BTC_PREVIEW_UNAVAILABLE) {
+        return intent;
+      }
+      throw error;
+    }
+  };
+
+  const handlePrepareSendReview = async () => {
     try {
       setSendFlowRecovery(null);
-      const reviewedIntent = buildCurrentBitcoinSendIntent();
+      const reviewedIntent = await withSendPreview(buildCurrentBitcoinSendIntent());
       setPendingSendReview(reviewedIntent);
     } catch (error) {
       const message = typeof error === 'string' ? error : error instanceof Error ? error.message : 'Unable to review BTC send';
@@ -793,10 +1024,11 @@ const BitcoinAssets: React.FC = () => {
                 <RecoveryPanel guidance={walletFlowRecovery} />
               )}
               <Tabs defaultValue="create" className="w-full">
-                <TabsList className="grid w-full grid-cols-3">
+                <TabsList className="grid w-full grid-cols-4">
                   <TabsTrigger value="create">Create New</TabsTrigger>
                   <TabsTrigger value="mnemonic">Import Mnemonic</TabsTrigger>
                   <TabsTrigger value="private-key">Import Private Key</TabsTrigger>
+                  <TabsTrigger value="multisig">Multisig</TabsTrigger>
                 </TabsList>
 
                 {/* Create New Wallet */}
@@ -813,6 +1045,19 @@ const BitcoinAssets: React.FC = () => {
                       onChange={(e) => setWalletLabel(e.target.value)}
                     />
                   </div>
+                  <div className="space-y-2">
+                    <Label htmlFor="create-passphrase">Passphrase (Optional)</Label>
+                    <Input
+                      id="create-passphrase"
+                      type="password"
+                      placeholder="Extra BIP39 passphrase to protect the new wallet"
+                      value={passphraseInput}
+                      onChange={(e) => setPassphraseInput(e.target.value)}
+                    />
+                    <p className="text-xs text-muted-foreground">
+                      The passphrase is not part of the mnemonic. Without it the backup restores a different wallet
+                    </p>
+                  </div>
                   <Button
                     onClick={handleCreateMnemonic}
                     className="w-full"
@@ -847,6 +1092,19 @@ const BitcoinAssets: React.FC = () => {
                       Words should be separated by spaces
                     </p>
                   </div>
+                  <div className="space-y-2">
+                    <Label htmlFor="mnemonic-passphrase">Passphrase (Optional)</Label>
+                    <Input
+                      id="mnemonic-passphrase"
+                      type="password"
+                      placeholder="BIP39 passphrase, if the wallet uses one"
+                      value={passphraseInput}
+                      onChange={(e) => setPassphraseInput(e.target.value)}
+                    />
+                    <p className="text-xs text-muted-foreground">
+                      A different passphrase opens a different wallet
+                    </p>
+                  </div>
                   <Button
                     onClick={handleImportMnemonic}
                     className="w-full"
@@ -889,6 +1147,91 @@ const BitcoinAssets: React.FC = () => {
                     {isLoading ? 'Importing...' : 'Import Private Key'}
                   </Button>
                 </TabsContent>
+
+                {/* Create Multisig Wallet */}
+                <TabsContent value="multisig" className="space-y-4 mt-4">
+                  <div className="space-y-2">
+                    <Label htmlFor="wallet-label-multisig">Wallet Label (Optional)</Label>
+                    <Input
+                      id="wallet-label-multisig"
+                      placeholder="e.g., Family Vault"
+                      value={walletLabel}
+                      onChange={(e) => setWalletLabel(e.target.value)}
+                    />
+                  </div>
+                  <div className="space-y-2">
+                    <Label htmlFor="multisig-keys">Cosigner Keys</Label>
+                    <Textarea
+                      id="multisig-keys"
+                      placeholder="One account xpub per line, optionally prefixed with [fingerprint/path]"
+                      value={multisigKeysInput}
+                      onChange={(e) => setMultisigKeysInput(e.target.value)}
+                      rows={4}
+                      className="font-mono text-xs"
+                    />
+                  </div>
+                  <div className="grid grid-cols-2 gap-4">
+                    <div className="space-y-2">
+                      <Label htmlFor="multisig-threshold">Signatures Required</Label>
+                      <Input
+                        id="multisig-threshold"
+                        type="number"
+                        min={1}
+                        value={multisigThreshold}
+                        onChange={(e) => setMultisigThreshold(e.target.value)}
+                      />
+                    </div>
+                    <div className="space-y-2">
+                      <Label>Script Type</Label>
+                      <div className="grid grid-cols-2 gap-2">
+                        <Button
+                          type="button"
+                          variant={multisigScriptType === 'p2wsh' ? 'default' : 'outline'}
+                          onClick={() => setMultisigScriptType('p2wsh')}
+                        >
+                          P2WSH
+                        </Button>
+                        <Button
+                          type="button"
+                          variant={multisigScriptType === 'p2tr' ? 'default' : 'outline'}
+                          onClick={() => setMultisigScriptType('p2tr')}
+                        >
+                          Taproot
+                        </Button>
+                      </div>
+                    </div>
+                  </div>
+                  <div className="space-y-2">
+                    <Label htmlFor="multisig-mnemonic">This Device's Mnemonic (Optional)</Label>
+                    <Textarea
+                      id="multisig-mnemonic"
+                      placeholder="Leave empty for a watch-only multisig wallet"
+                      value={multisigMnemonicInput}
+                      onChange={(e) => setMultisigMnemonicInput(e.target.value)}
+                      rows={3}
+                      className="font-mono text-sm"
+                    />
+                  </div>
+                  {multisigMnemonicInput.trim() && (
+                    <div className="space-y-2">
+                      <Label htmlFor="multisig-passphrase">Passphrase (Optional)</Label>
+                      <Input
+                        id="multisig-passphrase"
+                        type="password"
+                        placeholder="BIP39 passphrase, if the key uses one"
+                        value={passphraseInput}
+                        onChange={(e) => setPassphraseInput(e.target.value)}
+                      />
+                    </div>
+                  )}
+                  <Button
+                    onClick={handleCreateMultisig}
+                    className="w-full"
+                    disabled={!multisigKeysInput.trim() || !(Number(multisigThreshold) >= 1) || isLoading}
+                  >
+                    {isLoading ? 'Creating...' : 'Create Multisig Wallet'}
+                  </Button>
+                </TabsContent>
               </Tabs>
             </DialogContent>
           </Dialog>
@@ -906,6 +1249,98 @@ const BitcoinAssets: React.FC = () => {
           onConfirm={handlePersistCreatedWallet}
         />
 
+        {/* PSBT Dialog */}
+        <Dialog open={psbtWallet !== null} onOpenChange={(open) => { if (!open) closePsbtDialog(); }}>
+          <DialogContent className="sm:max-w-[600px]">
+            <DialogHeader>
+              <DialogTitle>PSBT for {psbtWallet?.label}</DialogTitle>
+            </DialogHeader>
+
+            <div className="space-y-4">
+              <div className="space-y-2">
+                <Label htmlFor="psbt-input">PSBT (base64)</Label>
+                <Textarea
+                  id="psbt-input"
+                  placeholder="Paste a PSBT to sign or broadcast, or one signed copy per line to combine them"
+                  value={psbtInput}
+                  onChange={(e) => {
+                    setPsbtInput(e.target.value);
+                    setPsbtResult(null);
+                  }}
+                  rows={5}
+                  className="font-mono text-xs"
+                />
+              </div>
+
+              <div className="grid grid-cols-3 gap-2">
+                <Button
+                  variant="outline"
+                  onClick={handleSignPsbt}
+                  disabled={pastedPsbts.length !== 1 || psbtWallet?.wallet_type === 'watch-only' || isPsbtBusy}
+                >
+                  Sign
+                </Button>
+                <Button
+                  variant="outline"
+                  onClick={handleCombinePsbts}
+                  disabled={pastedPsbts.length < 2 || isPsbtBusy}
+                >
+                  Combine
+                </Button>
+                <Button
+                  onClick={handleBroadcastPsbt}
+                  disabled={(!psbtResult && pastedPsbts.length !== 1) || isPsbtBusy}
+                >
+                  Broadcast
+                </Button>
+              </div>
+
+              {psbtResult && (
+                <div className="bg-muted rounded-lg p-4 space-y-3">
+                  <div className="flex items-center justify-between text-sm">
+                    <span className="text-muted-foreground">Signatures</span>
+                    <span className="font-mono">
+                      {psbtResult.signatures_required !== null
+                        ? `${psbtResult.signatures_present ?? 0} of ${psbtResult.signatures_required}`
+                        : psbtResult.is_finalized ? 'Complete' : 'Incomplete'}
+                    </span>
+                  </div>
+                  {psbtResult.fee !== null && (
+                    <div className="flex items-center justify-between text-sm">
+                      <span className="text-muted-foreground">Fee</span>
+                      <span className="font-mono">{psbtResult.fee.toFixed(8)} BTC</span>
+                    </div>
+                  )}
+                  <div className="flex items-center justify-between text-sm gap-4">
+                    <span className="text-muted-foreground">Transaction ID</span>
+                    <span className="font-mono text-xs break-all text-right">{psbtResult.txid}</span>
+                  </div>
+                  <Badge variant="outline" className={psbtResult.is_finalized ? 'text-green-700' : 'text-yellow-700'}>
+                    {psbtResult.is_finalized ? 'Ready to broadcast' : 'Needs more signatures'}
+                  </Badge>
+                  <p className="text-yellow-400 font-mono text-xs break-all max-h-32 overflow-y-auto">{psbtResult.psbt}</p>
+                  <button
+                    onClick={() => handleCopyPsbt(psbtResult.psbt)}
+                    className="w-full px-3 py-2 bg-muted/80 hover:bg-muted/70 text-gray-200 rounded text-sm transition-colors flex items-center justify-center gap-2"
+                  >
+                    {psbtCopied ? (
+                      <>
+                        <CheckCircle2 className="w-4 h-4" />
+                        Copied!
+                      </>
+                    ) : (
+                      <>
+                        <Copy className="w-4 h-4" />
+                        Copy PSBT
+                      </>
+                    )}
+                  </button>
+                </div>
+              )}
+            </div>
+          </DialogContent>
+        </Dialog>
+
         {/* Export Secret Dialog */}
         <Dialog open={showExportDialog} onOpenChange={setShowExportDialog}>
           <DialogContent className="sm:max-w-[600px]">
@@ -967,6 +1402,18 @@ const BitcoinAssets: React.FC = () => {
                   </div>
                 </div>
 
+                {exportedSecretType === 'mnemonic' && exportedPassphrase && (
+                  <div className="space-y-2">
+                    <Label>BIP39 Passphrase</Label>
+                    <div className="bg-muted rounded-lg p-4">
+                      <p className="text-yellow-400 font-mono text-sm break-all">{exportedPassphrase}</p>
+                      <p className="text-xs text-muted-foreground mt-2">
+                        Restoring this wallet needs both the mnemonic phrase and this passphrase.
+                      </p>
+                    </div>
+                  </div>
+                )}
+
                 {/* Close Button */}
                 <Button
                   className="w-full"
@@ -1198,6 +1645,21 @@ const BitcoinAssets: React.FC = () => {
                   <p className="text-[11px] uppercase tracking-[0.18em] text-slate-400">Total Charge</p>
                   <p className="mt-1 font-mono text-sm text-white">{pendingSendReview.totalChargeDisplay}</p>
                 </div>
+                {pendingSendReview.preview && (
+                  <div className="rounded-xl border border-white/10 bg-white/5 p-3 sm:col-span-2">
+                    <p className="text-[11px] uppercase tracking-[0.18em] text-slate-400">Transaction Preview</p>
+                    <p className="mt-1 text-xs text-slate-300">
+                      {pendingSendReview.preview.inputs.length} input(s), {pendingSendReview.preview.outputs.length} output(s)
+                      {pendingSendReview.preview.rbf ? ', replaceable (RBF)' : ', not replaceable'}
+                    </p>
+                    {pendingSendReview.preview.outputs.map((output, index) => (
+                      <p key={index} className="mt-1 break-all font-mono text-xs text-slate-300">
+                        {output.is_change ? 'Change' : 'Pay'} {formatBtcFromSats(output.amount_sats.toString())} BTC to {output.address ?? 'unknown script'}
+                      </p>
+                    ))}
+                    <p className="mt-1 break-all font-mono text-[10px] text-slate-400">txid {pendingSendReview.preview.txid}</p>
+                  </div>
+                )}
                 <div className="rounded-xl border border-white/10 bg-white/5 p-3 sm:col-span-2">
                   <p className="text-[11px] uppercase tracking-[0.18em] text-slate-400">Send-All Mode</p>
                   <p className="mt-1 text-sm font-semibold text-white">{pendingSendReview.sendAll ? 'Enabled' : 'Disabled'}</p>
@@ -1290,7 +1752,7 @@ const BitcoinAssets: React.FC = () => {
                         {wallet.label}
                       </p>
                       <Badge variant="secondary" className="text-xs">
-                        {wallet.wallet_type === 'mnemonic' ? 'Mnemonic' : 'Private Key'}
+                        {WALLET_TYPE_LABELS[wallet.wallet_type] ?? wallet.wallet_type}
                       </Badge>
                     </div>
                     <div className="flex items-center gap-2 mt-2">
@@ -1355,6 +1817,17 @@ const BitcoinAssets: React.FC = () => {
                         <span>{refreshingBalance === wallet.id ? 'Refreshing...' : 'Refresh'}</span>
                       </button>
 
+                      {/* PSBT Button */}
+                      <button
+                        onClick={() => openPsbtDialog(wallet)}
+                        className="px-2 py-1 text-xs bg-slate-100 text-slate-700 hover:bg-slate-200 rounded transition-colors flex items-center gap-1"
+                        title="Sign, combine or broadcast a PSBT"
+                        disabled={isLoading}
+                      >
+                        <FileText className="w-3 h-3" />
+                        <span>PSBT</span>
+                      </button>
+
                       {/* Export Private Key Button */}
                       <UnlockGate
                         mode="reauth"
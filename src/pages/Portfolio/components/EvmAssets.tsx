@@ -51,9 +51,15 @@ interface CreateWalletResponse {
 
 interface PendingMnemonicBackup {
   mnemonic: string;
+  passphrase: string;
   walletLabel: string;
 }
 
+interface ExportedMnemonic {
+  phrase: string;
+  passphrase?: string;
+}
+
 interface SelectedSendAsset {
   chain: string;
   chainId: number;
@@ -210,12 +216,14 @@ const EvmAssets: React.FC = () => {
   const [isDialogOpen, setIsDialogOpen] = useState(false);
   const [pendingMnemonicBackup, setPendingMnemonicBackup] = useState<PendingMnemonicBackup | null>(null);
   const [mnemonicInput, setMnemonicInput] = useState('');
+  const [passphraseInput, setPassphraseInput] = useState('');
   const [privateKeyInput, setPrivateKeyInput] = useState('');
   const [walletLabel, setWalletLabel] = useState('');
   const [isLoading, setIsLoading] = useState(false);
   const [isPersistingMnemonic, setIsPersistingMnemonic] = useState(false);
   const [mnemonicCopied, setMnemonicCopied] = useState(false);
   const [exportedSecret, setExportedSecret] = useState<string | null>(null);
+  const [exportedPassphrase, setExportedPassphrase] = useState<string | null>(null);
   const [showExportDialog, setShowExportDialog] = useState(false);
   const [exportedSecretType, setExportedSecretType] = useState<'mnemonic' | 'private-key'>('private-key');
   const [exportCopied, setExportCopied] = useState(false);
@@ -455,6 +463,7 @@ const EvmAssets: React.FC = () => {
 
       try {
         const lifecycle = await invoke<EvmTransactionLifecycleUpdate>('refresh_evm_transaction_lifecycle', {
+          walletId,
           txHash,
           chainId,
         });
@@ -646,9 +655,11 @@ const EvmAssets: React.FC = () => {
 
       setPendingMnemonicBackup({
         mnemonic,
+        passphrase: passphraseInput,
         walletLabel: walletLabel || 'EVM Wallet',
       });
       setMnemonicInput('');
+      setPassphraseInput('');
     } catch (error) {
       console.error('Error creating wallet:', error);
       setWalletFlowRecovery(describeWalletRecovery('create-wallet', error, { chainFamily: 'evm' }));
@@ -670,11 +681,13 @@ const EvmAssets: React.FC = () => {
 
       const response = await invoke<CreateWalletResponse>('evm_create_wallet_from_mnemonic', {
         mnemonicPhrase: mnemonicInput,
+        passphrase: passphraseInput || undefined,
         walletLabel: walletLabel || undefined,
       });
 
       await syncImportedWallet(response.wallet);
       setMnemonicInput('');
+      setPassphraseInput('');
       setWalletLabel('');
       setIsDialogOpen(false);
     } catch (error) {
@@ -752,6 +765,7 @@ const EvmAssets: React.FC = () => {
 
       const response = await invoke<CreateWalletResponse>('evm_create_wallet_from_mnemonic', {
         mnemonicPhrase: pendingMnemonicBackup.mnemonic,
+        passphrase: pendingMnemonicBackup.passphrase || undefined,
         walletLabel: pendingMnemonicBackup.walletLabel || undefined,
       });
 
@@ -774,6 +788,7 @@ const EvmAssets: React.FC = () => {
     try {
       const secret = await invoke<string>('evm_export_private_key', { walletId });
       setExportedSecret(secret);
+      setExportedPassphrase(null);
       setExportedSecretType('private-key');
       setShowExportDialog(true);
     } catch (error) {
@@ -787,8 +802,9 @@ const EvmAssets: React.FC = () => {
   const handleExportMnemonic = async (walletId: string) => {
     setIsLoading(true);
     try {
-      const secret = await invoke<string>('evm_export_mnemonic', { walletId });
-      setExportedSecret(secret);
+      const secret = await invoke<ExportedMnemonic>('evm_export_mnemonic', { walletId });
+      setExportedSecret(secret.phrase);
+      setExportedPassphrase(secret.passphrase ?? null);
       setExportedSecretType('mnemonic');
       setShowExportDialog(true);
     } catch (error) {
@@ -1044,6 +1060,19 @@ const EvmAssets: React.FC = () => {
                         onChange={(e) => setWalletLabel(e.target.value)}
                       />
                     </div>
+                    <div className="space-y-2">
+                      <Label htmlFor="create-passphrase">Passphrase (Optional)</Label>
+                      <Input
+                        id="create-passphrase"
+                        type="password"
+                        placeholder="Extra BIP39 passphrase to protect the new wallet"
+                        value={passphraseInput}
+                        onChange={(e) => setPassphraseInput(e.target.value)}
+                      />
+                      <p className="text-xs text-muted-foreground">
+                        The passphrase is not part of the mnemonic. Without it the backup restores a different wallet
+                      </p>
+                    </div>
                     <Button
                       onClick={handleCreateMnemonic}
                       className="w-full"
@@ -1078,6 +1107,19 @@ const EvmAssets: React.FC = () => {
                         Words should be separated by spaces
                       </p>
                     </div>
+                    <div className="space-y-2">
+                      <Label htmlFor="mnemonic-passphrase">Passphrase (Optional)</Label>
+                      <Input
+                        id="mnemonic-passphrase"
+                        type="password"
+                        placeholder="BIP39 passphrase, if the wallet uses one"
+                        value={passphraseInput}
+                        onChange={(e) => setPassphraseInput(e.target.value)}
+                      />
+                      <p className="text-xs text-muted-foreground">
+                        A different passphrase opens a different wallet
+                      </p>
+                    </div>
                     <Button
                       onClick={handleImportMnemonic}
                       className="w-full"
@@ -1199,6 +1241,18 @@ const EvmAssets: React.FC = () => {
                   </div>
                 </div>
 
+                {exportedSecretType === 'mnemonic' && exportedPassphrase && (
+                  <div className="space-y-2">
+                    <Label>BIP39 Passphrase</Label>
+                    <div className="bg-muted rounded-lg p-4">
+                      <p className="text-yellow-400 font-mono text-sm break-all">{exportedPassphrase}</p>
+                      <p className="text-xs text-muted-foreground mt-2">
+                        Restoring this wallet needs both the mnemonic phrase and this passphrase.
+                      </p>
+                    </div>
+                  </div>
+                )}
+
                 {/* Close Button */}
                 <Button
                   className="w-full"
@@ -5,7 +5,7 @@ import { Button } from '@/components/ui/button';
 import { Card, CardContent, CardDescription, CardHeader, CardTitle } from '@/components/ui/card';
 import { Tabs, TabsContent, TabsList, TabsTrigger } from '@/components/ui/tabs';
 import { Badge } from '@/components/ui/badge';
-import { ArrowUpRight, ArrowDownLeft, RefreshCw, Send, ExternalLink, ShieldCheck, Code } from 'lucide-react';
+import { ArrowUpRight, ArrowDownLeft, RefreshCw, Send, ExternalLink, ShieldCheck, Code, Repeat } from 'lucide-react';
 import { cn, shortAddress, getEvmExplorerUrl, getBitcoinExplorerUrl, openExternalLink } from '@/lib/utils';
 import { SupportedEvmHistoryChain } from '@/lib/evm-wallet';
 
@@ -13,7 +13,7 @@ interface BitcoinTransaction {
   id: string;
   wallet_id: string;
   tx_hash: string;
-  tx_type: 'send' | 'receive';
+  tx_type: 'send' | 'receive' | 'self_transfer' | 'consolidation';
   from_address: string;
   to_address: string;
   amount: number;
@@ -236,16 +236,26 @@ const Transactions: React.FC = () => {
 
   const BitcoinTransactionRow: React.FC<{ tx: BitcoinTransaction }> = ({ tx }) => {
     const isSend = tx.tx_type === 'send';
+    const isInternal = tx.tx_type === 'self_transfer' || tx.tx_type === 'consolidation';
+
+    const getLabel = () => {
+      if (isSend) return 'Send Bitcoin';
+      if (tx.tx_type === 'consolidation') return 'Consolidate Bitcoin';
+      if (isInternal) return 'Self Transfer';
+      return 'Receive Bitcoin';
+    };
 
     return (
       <div className="flex items-center justify-between p-4 border-b border-border/50 hover:bg-muted/50 transition-colors">
         <div className="flex items-center gap-4 flex-1">
           <div className={cn(
             "p-2 rounded-full",
-            isSend ? "bg-red-500/10" : "bg-green-500/10"
+            isSend ? "bg-red-500/10" : isInternal ? "bg-muted" : "bg-green-500/10"
           )}>
             {isSend ? (
               <ArrowUpRight className="w-5 h-5 text-red-500" />
+            ) : isInternal ? (
+              <Repeat className="w-5 h-5 text-muted-foreground" />
             ) : (
               <ArrowDownLeft className="w-5 h-5 text-green-500" />
             )}
@@ -254,7 +264,7 @@ const Transactions: React.FC = () => {
           <div className="flex-1">
             <div className="flex items-center gap-2 mb-1">
               <span className="font-medium text-sm">
-                {isSend ? 'Send Bitcoin' : 'Receive Bitcoin'}
+                {getLabel()}
               </span>
               <Badge variant="outline" className={cn("text-xs", getStatusColor(tx.status))}>
                 {formatStatusLabel(tx.status)}
@@ -269,9 +279,9 @@ const Transactions: React.FC = () => {
           <div className="text-right">
             <div className={cn(
               "font-semibold text-sm mb-1",
-              isSend ? "text-red-500" : "text-green-500"
+              isSend ? "text-red-500" : isInternal ? "text-muted-foreground" : "text-green-500"
             )}>
-              {isSend ? '-' : '+'}{tx.amount.toFixed(8)} BTC
+              {isSend ? '-' : isInternal ? '' : '+'}{tx.amount.toFixed(8)} BTC
             </div>
             <div className="text-xs text-muted-foreground">
               Fee: {tx.fee.toFixed(8)} BTC